@@ -0,0 +1,344 @@
+//! Liquid staking token adapters
+
+
+/// Imports
+/// ------------------------------------------------------------------------------------------------
+
+use {
+    crate::{
+        check::Check,
+        state::BondLstAdapter,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        borsh::try_from_slice_unchecked,
+        entrypoint::ProgramResult,
+        msg,
+        program::{invoke, invoke_signed},
+        program_error::ProgramError,
+        pubkey,
+        pubkey::Pubkey,
+    },
+    spl_stake_pool::state::StakePool,
+};
+
+
+/// Programs
+/// ------------------------------------------------------------------------------------------------
+
+/// The Marinade liquid staking program.
+pub const MARINADE_PROGRAM_ID: Pubkey = pubkey!("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
+
+/// The SPL single-validator stake pool program.
+pub const SINGLE_POOL_PROGRAM_ID: Pubkey = pubkey!("SVSPxpvHdN29nkVg9rPapPNDddN5DipNLRUFhyjFThE");
+
+/// The offset of `msol_mint` in Marinade's state account (after the 8 byte discriminator).
+const MARINADE_MSOL_MINT_OFFSET: usize = 8;
+
+/// The offset of `treasury_msol_account` in Marinade's state account.
+const MARINADE_TREASURY_OFFSET: usize = 104;
+
+
+/// Adapter
+/// ------------------------------------------------------------------------------------------------
+
+/// Runs the LST specific operations of a [BondLstAdapter].
+pub struct Adapter;
+
+impl Adapter {
+
+    /// Returns the id of the program that manages `adapter`'s pools.
+    pub fn program_id(adapter: BondLstAdapter) -> Pubkey {
+        match adapter {
+            BondLstAdapter::SplStakePool => spl_stake_pool::id(),
+            BondLstAdapter::Marinade => MARINADE_PROGRAM_ID,
+            BondLstAdapter::SinglePool => SINGLE_POOL_PROGRAM_ID,
+        }
+    }
+
+    /// True if `adapter`'s epoch fee is only final once [Adapter::harvest] ran in the epoch (an 
+    /// SPL stake pool pays its fee when its balance is updated).
+    pub fn requires_harvest(adapter: BondLstAdapter) -> bool {
+        adapter == BondLstAdapter::SplStakePool
+    }
+
+    /// The number of `adapter_infos` read by [Adapter::harvest] for `adapter`.
+    pub fn harvest_accounts(adapter: BondLstAdapter) -> usize {
+        match adapter {
+            BondLstAdapter::SplStakePool => 3,
+            BondLstAdapter::Marinade | BondLstAdapter::SinglePool => 0,
+        }
+    }
+
+    /// Makes the epoch fee of `pool_info` final in `fee_ata_info`.
+    ///
+    /// - [BondLstAdapter::SplStakePool] runs `UpdateStakePoolBalance`, with the pool's withdraw
+    ///     authority, validator list and reserve stake as `adapter_infos`.
+    /// - [BondLstAdapter::Marinade] mints its reward fee during its own validator cranks, so only
+    ///     the pool's mint and treasury account are checked.
+    /// - [BondLstAdapter::SinglePool] charges no fee, so only the pool's mint is checked.
+    pub fn harvest<'a>(
+        adapter: BondLstAdapter,
+        pool_program_info: &AccountInfo<'a>,
+        pool_info: &AccountInfo<'a>,
+        fee_ata_info: &AccountInfo<'a>,
+        token_mint_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        adapter_infos: &[AccountInfo<'a>],
+    ) -> ProgramResult {
+        Check::account(pool_program_info, &Self::program_id(adapter))?;
+        Check::owner(pool_info, pool_program_info.key)?;
+        match adapter {
+            BondLstAdapter::SplStakePool => {
+                let stake_pool = try_from_slice_unchecked::<StakePool>(&pool_info.data.borrow())?;
+                Check::pubkey(&stake_pool.manager_fee_account, fee_ata_info.key)?;
+                Check::pubkey(&stake_pool.pool_mint, token_mint_info.key)?;
+
+                let adapter_info_iter = &mut adapter_infos.iter();
+                let withdraw_authority_info = next_account_info(adapter_info_iter)?;
+                let validator_list_info = next_account_info(adapter_info_iter)?;
+                let reserve_stake_info = next_account_info(adapter_info_iter)?;
+                let ix = spl_stake_pool::instruction::update_stake_pool_balance(
+                    pool_program_info.key,
+                    pool_info.key,
+                    withdraw_authority_info.key,
+                    validator_list_info.key,
+                    reserve_stake_info.key,
+                    fee_ata_info.key,
+                    token_mint_info.key,
+                    token_program_info.key,
+                );
+                invoke(
+                    &ix,
+                    &[
+                        pool_info.clone(),
+                        withdraw_authority_info.clone(),
+                        validator_list_info.clone(),
+                        reserve_stake_info.clone(),
+                        fee_ata_info.clone(),
+                        token_mint_info.clone(),
+                        token_program_info.clone(),
+                        pool_program_info.clone(),
+                    ],
+                )
+            },
+            BondLstAdapter::Marinade => {
+                let data = pool_info.data.borrow();
+                Check::pubkey(
+                    &Self::read_pubkey(&data, MARINADE_MSOL_MINT_OFFSET)?,
+                    token_mint_info.key,
+                )?;
+                Check::pubkey(
+                    &Self::read_pubkey(&data, MARINADE_TREASURY_OFFSET)?,
+                    fee_ata_info.key,
+                )
+            },
+            BondLstAdapter::SinglePool => {
+                Check::pubkey(
+                    &Self::single_pool_mint(pool_program_info.key, pool_info.key),
+                    token_mint_info.key,
+                )
+            },
+        }
+    }
+
+    /// Points the epoch fee of `pool_info` at `fee_ata_info` if `manager_info`, a PDA signing 
+    /// with `seeds`, manages the pool and its fee is paid elsewhere. Returns true if the fee 
+    /// receiver was changed.
+    ///
+    /// Only [BondLstAdapter::SplStakePool] lets its manager set the fee receiver, through 
+    /// `SetManager`. Other adapters are left unchanged.
+    pub fn claim_fee_receiver<'a>(
+        adapter: BondLstAdapter,
+        pool_program_info: &AccountInfo<'a>,
+        pool_info: &AccountInfo<'a>,
+        manager_info: &AccountInfo<'a>,
+        fee_ata_info: &AccountInfo<'a>,
+        seeds: &[&[u8]],
+    ) -> Result<bool, ProgramError> {
+        Check::account(pool_program_info, &Self::program_id(adapter))?;
+        Check::owner(pool_info, pool_program_info.key)?;
+        if adapter != BondLstAdapter::SplStakePool {
+            return Ok(false);
+        }
+        let stake_pool = try_from_slice_unchecked::<StakePool>(&pool_info.data.borrow())?;
+        if stake_pool.manager_fee_account == *fee_ata_info.key 
+            || stake_pool.manager != *manager_info.key {
+            return Ok(false);
+        }
+        let ix = spl_stake_pool::instruction::set_manager(
+            pool_program_info.key,
+            pool_info.key,
+            manager_info.key,
+            manager_info.key,
+            fee_ata_info.key,
+        );
+        invoke_signed(
+            &ix,
+            &[
+                pool_info.clone(),
+                manager_info.clone(),
+                fee_ata_info.clone(),
+                pool_program_info.clone(),
+            ],
+            &[seeds],
+        )?;
+        Ok(true)
+    }
+
+    /// Redeems `amount` pool tokens of `source_info`, owned by the PDA `authority_info` (signed 
+    /// with `seeds`), for SOL paid to `lamports_to_info`.
+    ///
+    /// Only [BondLstAdapter::SplStakePool] supports it, with the pool's withdraw authority, 
+    /// reserve stake, manager fee account, the clock and stake history sysvars and the Stake 
+    /// program as `adapter_infos`.
+    pub fn withdraw_sol<'a>(
+        adapter: BondLstAdapter,
+        pool_program_info: &AccountInfo<'a>,
+        pool_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        source_info: &AccountInfo<'a>,
+        lamports_to_info: &AccountInfo<'a>,
+        token_mint_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        adapter_infos: &[AccountInfo<'a>],
+        amount: u64,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        Check::account(pool_program_info, &Self::program_id(adapter))?;
+        Check::owner(pool_info, pool_program_info.key)?;
+        match adapter {
+            BondLstAdapter::SplStakePool => {
+                let stake_pool = try_from_slice_unchecked::<StakePool>(&pool_info.data.borrow())?;
+                Check::pubkey(&stake_pool.pool_mint, token_mint_info.key)?;
+
+                let adapter_info_iter = &mut adapter_infos.iter();
+                let withdraw_authority_info = next_account_info(adapter_info_iter)?;
+                let reserve_stake_info = next_account_info(adapter_info_iter)?;
+                let manager_fee_info = next_account_info(adapter_info_iter)?;
+                let clock_info = next_account_info(adapter_info_iter)?;
+                let stake_history_info = next_account_info(adapter_info_iter)?;
+                let stake_program_info = next_account_info(adapter_info_iter)?;
+                let ix = spl_stake_pool::instruction::withdraw_sol(
+                    pool_program_info.key,
+                    pool_info.key,
+                    withdraw_authority_info.key,
+                    authority_info.key,
+                    source_info.key,
+                    reserve_stake_info.key,
+                    lamports_to_info.key,
+                    manager_fee_info.key,
+                    token_mint_info.key,
+                    token_program_info.key,
+                    amount,
+                );
+                invoke_signed(
+                    &ix,
+                    &[
+                        pool_info.clone(),
+                        withdraw_authority_info.clone(),
+                        authority_info.clone(),
+                        source_info.clone(),
+                        reserve_stake_info.clone(),
+                        lamports_to_info.clone(),
+                        manager_fee_info.clone(),
+                        token_mint_info.clone(),
+                        clock_info.clone(),
+                        stake_history_info.clone(),
+                        stake_program_info.clone(),
+                        token_program_info.clone(),
+                        pool_program_info.clone(),
+                    ],
+                    &[seeds],
+                )
+            },
+            _ => {
+                msg!("SOL withdrawals are only supported by SPL stake pools");
+                Err(ProgramError::InvalidArgument)
+            },
+        }
+    }
+
+    /// Deposits `amount` lamports of the system account `lamports_from_info` (a PDA signed with 
+    /// `seeds`) into `pool_info`, minting its pool tokens to `pool_tokens_to_info`, which also 
+    /// receives the referral fee.
+    ///
+    /// Only [BondLstAdapter::SplStakePool] supports it, with the pool's withdraw authority, 
+    /// reserve stake and manager fee account as `adapter_infos`.
+    pub fn deposit_sol<'a>(
+        adapter: BondLstAdapter,
+        pool_program_info: &AccountInfo<'a>,
+        pool_info: &AccountInfo<'a>,
+        lamports_from_info: &AccountInfo<'a>,
+        pool_tokens_to_info: &AccountInfo<'a>,
+        token_mint_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        adapter_infos: &[AccountInfo<'a>],
+        amount: u64,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        Check::account(pool_program_info, &Self::program_id(adapter))?;
+        Check::owner(pool_info, pool_program_info.key)?;
+        match adapter {
+            BondLstAdapter::SplStakePool => {
+                let stake_pool = try_from_slice_unchecked::<StakePool>(&pool_info.data.borrow())?;
+                Check::pubkey(&stake_pool.pool_mint, token_mint_info.key)?;
+
+                let adapter_info_iter = &mut adapter_infos.iter();
+                let withdraw_authority_info = next_account_info(adapter_info_iter)?;
+                let reserve_stake_info = next_account_info(adapter_info_iter)?;
+                let manager_fee_info = next_account_info(adapter_info_iter)?;
+                let ix = spl_stake_pool::instruction::deposit_sol(
+                    pool_program_info.key,
+                    pool_info.key,
+                    withdraw_authority_info.key,
+                    reserve_stake_info.key,
+                    lamports_from_info.key,
+                    pool_tokens_to_info.key,
+                    manager_fee_info.key,
+                    pool_tokens_to_info.key,
+                    token_mint_info.key,
+                    token_program_info.key,
+                    amount,
+                );
+                invoke_signed(
+                    &ix,
+                    &[
+                        pool_info.clone(),
+                        withdraw_authority_info.clone(),
+                        reserve_stake_info.clone(),
+                        lamports_from_info.clone(),
+                        pool_tokens_to_info.clone(),
+                        manager_fee_info.clone(),
+                        token_mint_info.clone(),
+                        system_program_info.clone(),
+                        token_program_info.clone(),
+                        pool_program_info.clone(),
+                    ],
+                    &[seeds],
+                )
+            },
+            _ => {
+                msg!("SOL deposits are only supported by SPL stake pools");
+                Err(ProgramError::InvalidArgument)
+            },
+        }
+    }
+
+    fn single_pool_mint(
+        pool_program_id: &Pubkey,
+        pool: &Pubkey,
+    ) -> Pubkey {
+        Pubkey::find_program_address(&[b"mint", pool.as_ref()], pool_program_id).0
+    }
+
+    fn read_pubkey(
+        data: &[u8],
+        offset: usize,
+    ) -> Result<Pubkey, ProgramError> {
+        data.get(offset..offset + 32)
+            .and_then(|bytes| Pubkey::try_from(bytes).ok())
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+}
@@ -5,9 +5,15 @@
 /// ------------------------------------------------------------------------------------------------
 
 use {
-    crate::state::{
-        BondSeed, 
-        BondAccount,
+    crate::{
+        error::BondError,
+        instruction::{Ratio, TOKEN_2022_PROGRAM_ID},
+        state::{
+            BondSeed, 
+            BondAccount,
+            BondConfig,
+            BondSquadsVault,
+        },
     },
     solana_program::{
         account_info::AccountInfo, 
@@ -15,7 +21,9 @@ use {
         rent::Rent, 
         pubkey::Pubkey, 
         msg,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
     },
+    spl_token::state::Account,
 };
 
 
@@ -27,6 +35,36 @@ pub struct Check;
 
 impl Check {
 
+    /// Check that the current instruction is a top-level instruction of `program_id` (not invoked 
+    /// by another program) in a transaction of at most `max_instructions`, whose other 
+    /// instructions all belong to `allowed_program_ids`.
+    pub fn isolated(
+        instructions_info: &AccountInfo,
+        program_id: &Pubkey,
+        max_instructions: usize,
+        allowed_program_ids: &[Pubkey],
+    ) -> Result<(), ProgramError> {
+        let current_index = usize::from(load_current_index_checked(instructions_info)?);
+        let mut index = 0;
+        while let Ok(ix) = load_instruction_at_checked(index, instructions_info) {
+            let is_allowed = if index == current_index {
+                ix.program_id == *program_id
+            } else {
+                allowed_program_ids.contains(&ix.program_id)
+            };
+            if !is_allowed {
+                msg!("Instruction {} of program {} is not allowed", index, ix.program_id);
+                return Err(BondError::TransactionNotIsolated.into());
+            }
+            index += 1;
+        }
+        if index > max_instructions {
+            msg!("Transaction has {} instructions, max {}", index, max_instructions);
+            return Err(BondError::TransactionNotIsolated.into());
+        }
+        Ok(())
+    }
+
     /// Check that `account_info` is rent exempt.
     pub fn rent_exempt(
         account_info: &AccountInfo,
@@ -103,7 +141,7 @@ impl Check {
     ) -> Result<(), ProgramError> {
         if account_info.owner.ne(owner_id) {
             msg!("Invalid Owner: expected {}, received {}", owner_id, account_info.owner);
-            Err(ProgramError::IncorrectProgramId)
+            Err(BondError::InvalidOwner.into())
         } else {
             Ok(())
         }
@@ -143,36 +181,148 @@ impl Check {
         pda_info: &AccountInfo,
         seed: BondSeed,
         bump: u8,
+    ) -> Result<(), ProgramError> {
+        let pda = seed.address(program_id, config_info.key, bump)?;
+        Self::account(
+            pda_info, 
+            &pda,
+        ).map_err(|_| BondError::InvalidSeeds.into())
+    }
+
+    /// Check that `config_info` is the config PDA derived from `authority`, `token_mint` and 
+    /// `bump`.
+    pub fn config_pda(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        authority: &Pubkey,
+        token_mint: &Pubkey,
+        bump: u8,
     ) -> Result<(), ProgramError> {
         let pda = Pubkey::create_program_address(
             &[
-                config_info.key.as_ref(),
-                seed.as_ref().as_ref(), 
+                BondSeed::Config.as_bytes(),
+                authority.as_ref(),
+                token_mint.as_ref(),
                 &[bump],
             ],
             program_id,
         )?;
         Self::account(
-            pda_info, 
+            config_info, 
             &pda,
-        )
+        ).map_err(|_| BondError::InvalidSeeds.into())
+    }
+
+    /// Check that `authority_info` is the signing authority of `config`.
+    pub fn authority(
+        config: &BondConfig,
+        authority_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        Self::signer(authority_info)?;
+        Self::account(authority_info, &config.authority)
+            .map_err(|_| BondError::InvalidAuthority)?;
+        Self::vault(&config.authority_vault, authority_info)
+    }
+
+    /// Check that `authority_info` is derived from `vault` (if any). A vault PDA can only be 
+    /// marked as a signer by the Squads program's CPI.
+    pub fn vault(
+        vault: &Option<BondSquadsVault>,
+        authority_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        match vault {
+            Some(vault) => Self::account(authority_info, &vault.address()?)
+                .map_err(|_| BondError::InvalidAuthority.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that an allocation of `space` bytes holds an account packed in `len` bytes.
+    pub fn space(
+        space: u32,
+        len: usize,
+    ) -> Result<(), ProgramError> {
+        if (space as usize) < len {
+            msg!("Space {} is below the account's size {}", space, len);
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        Ok(())
     }
 
     /// Check that `ata_info` is an associated token account derived from `pda_info` and 
-    /// `token_mint`.
+    /// `token_mint` under the token program owning it (the Token Program if it doesn't exist).
     pub fn ata(
         pda_info: &AccountInfo,
         token_mint: &Pubkey,
         ata_info: &AccountInfo,
     ) -> Result<(), ProgramError> {
-        let ata = spl_associated_token_account::get_associated_token_address(
+        let token_program = if *ata_info.owner == TOKEN_2022_PROGRAM_ID {
+            TOKEN_2022_PROGRAM_ID
+        } else {
+            spl_token::id()
+        };
+        Self::ata_with_program(pda_info, token_mint, &token_program, ata_info)
+    }
+
+    /// Check that `ata` is the associated token account of its owner for `token_mint`, derived 
+    /// under the token program owning `ata_info`.
+    pub fn owner_ata(
+        ata: &Account,
+        token_mint: &Pubkey,
+        ata_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        Self::pubkey(&ata.mint, token_mint).map_err(|_| BondError::InvalidAta)?;
+        let address = spl_associated_token_account::get_associated_token_address_with_program_id(
+            &ata.owner, 
+            token_mint,
+            ata_info.owner,
+        );
+        Self::account(
+            ata_info, 
+            &address,
+        ).map_err(|_| BondError::InvalidAta.into())
+    }
+
+    /// Check that `ata_info` is an associated token account derived from `pda_info` and 
+    /// `token_mint` under `token_program`.
+    pub fn ata_with_program(
+        pda_info: &AccountInfo,
+        token_mint: &Pubkey,
+        token_program: &Pubkey,
+        ata_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        let ata = spl_associated_token_account::get_associated_token_address_with_program_id(
             &pda_info.key, 
             token_mint,
+            token_program,
         );
         Self::account(
             ata_info, 
             &ata,
-        )
+        ).map_err(|_| BondError::InvalidAta.into())
+    }
+
+    /// Check that `token_program_info` is the Token Program or the Token-2022 Program.
+    pub fn token_program(
+        token_program_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if *token_program_info.key != spl_token::id() 
+            && *token_program_info.key != TOKEN_2022_PROGRAM_ID 
+        {
+            msg!("Unsupported token program {}", token_program_info.key);
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(())
+    }
+
+    /// Check that `token_program_info` is the Token Program or the Token-2022 Program and that it 
+    /// owns `token_mint_info`.
+    pub fn token_mint(
+        token_mint_info: &AccountInfo,
+        token_program_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        Self::token_program(token_program_info)?;
+        Self::owner(token_mint_info, token_program_info.key)
     }
 
     /// Check that `account` has been initialized.
@@ -201,7 +351,8 @@ impl Check {
         }
     }
 
-    /// Check that `account` has been initialized to the correct type.
+    /// Check that `account` has been initialized to the correct type, i.e. that its data starts 
+    /// with the type's discriminator (see [crate::state::ACCOUNT_DISCRIMINATORS]).
     pub fn valid(
         account: &impl BondAccount,
         account_info: &AccountInfo,
@@ -213,4 +364,72 @@ impl Check {
             Ok(())
         }
     }
+
+    /// Check that `config` is active (not paused or closed).
+    pub fn active(
+        config: &BondConfig,
+    ) -> Result<(), ProgramError> {
+        if !config.is_active {
+            msg!("Game is inactive");
+            return Err(BondError::GameInactive.into());
+        }
+        Ok(())
+    }
+
+    /// Check that `config` is inactive (paused or closed) before it's decommissioned.
+    pub fn inactive(
+        config: &BondConfig,
+    ) -> Result<(), ProgramError> {
+        if config.is_active {
+            msg!("Game must be paused or closed first");
+            return Err(BondError::GameActive.into());
+        }
+        Ok(())
+    }
+
+    /// Check that each of `shares` has a non-zero denominator and that they sum to at most 100%.
+    pub fn shares(
+        shares: &[Ratio],
+    ) -> Result<(), ProgramError> {
+        if shares.iter().any(|share| share.denominator == 0) {
+            msg!("Share denominators must be non-zero");
+            return Err(BondError::InvalidShares.into());
+        }
+
+        // Compare the sum over a common denominator, any overflow exceeding it.
+        let total = shares.iter().try_fold(1u128, |total, share| {
+            total.checked_mul(u128::from(share.denominator))
+        });
+        let sum = shares.iter().enumerate().try_fold(0u128, |sum, (i, share)| {
+            shares.iter().enumerate()
+                .filter(|(j, _)| *j != i)
+                .try_fold(u128::from(share.numerator), |term, (_, other)| {
+                    term.checked_mul(u128::from(other.denominator))
+                })
+                .and_then(|term| sum.checked_add(term))
+        });
+        match (sum, total) {
+            (Some(sum), Some(total)) if sum <= total => Ok(()),
+            _ => {
+                msg!("Shares sum to more than 100%");
+                Err(BondError::InvalidShares.into())
+            },
+        }
+    }
+
+    /// Check that `unix_timestamp` falls within `config`'s season.
+    pub fn in_season(
+        config: &BondConfig,
+        unix_timestamp: i64,
+    ) -> Result<(), ProgramError> {
+        if config.starts_at.map_or(false, |starts_at| unix_timestamp < starts_at) {
+            msg!("Season starts at {}", config.starts_at.unwrap_or_default());
+            Err(BondError::SeasonNotStarted.into())
+        } else if config.ends_at.map_or(false, |ends_at| unix_timestamp >= ends_at) {
+            msg!("Season ended at {}", config.ends_at.unwrap_or_default());
+            Err(BondError::SeasonEnded.into())
+        } else {
+            Ok(())
+        }
+    }
 }
\ No newline at end of file
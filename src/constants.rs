@@ -0,0 +1,290 @@
+//! Constants shared with integrators (SDKs, tests and partner programs).
+
+
+// Imports
+// -------------------------------------------------------------------------------------------------
+
+use {
+    crate::{
+        instruction::Ratio,
+        state::{MAX_JACKPOT_MINTS, MAX_LEADERBOARD_ENTRIES, MAX_NAME_LEN, MAX_URI_LEN},
+    },
+    solana_program::program_error::ProgramError,
+};
+
+
+// Seeds
+// -------------------------------------------------------------------------------------------------
+
+/// The seed of [crate::state::BondSeed::Config].
+pub const SEED_CONFIG: &str = "config";
+
+/// The seed of [crate::state::BondSeed::State].
+pub const SEED_STATE: &str = "state";
+
+/// The seed of [crate::state::BondSeed::Fee].
+pub const SEED_FEE: &str = "fee";
+
+/// The seed of [crate::state::BondSeed::Draw].
+pub const SEED_DRAW: &str = "draw";
+
+/// The seed of [crate::state::BondSeed::ExclusionList].
+pub const SEED_EXCLUSION_LIST: &str = "exclusionList";
+
+/// The seed of [crate::state::BondSeed::Equity].
+pub const SEED_EQUITY: &str = "equity";
+
+/// The seed of [crate::state::BondSeed::Treasury].
+pub const SEED_TREASURY: &str = "treasury";
+
+/// The seed of [crate::state::BondSeed::Jackpot].
+pub const SEED_JACKPOT: &str = "jackpot";
+
+/// The seed of [crate::state::BondSeed::Stake].
+pub const SEED_STAKE: &str = "stake";
+
+/// The seed of [crate::state::BondSeed::Season].
+pub const SEED_SEASON: &str = "season";
+
+/// The seed of [crate::state::BondSeed::Emitter].
+pub const SEED_EMITTER: &str = "emitter";
+
+/// The seed of [crate::state::BondSeed::Message].
+pub const SEED_MESSAGE: &str = "message";
+
+/// The seed of [crate::state::BondSeed::Participant].
+pub const SEED_PARTICIPANT: &str = "participant";
+
+/// The seed of [crate::state::BondSeed::Hold].
+pub const SEED_HOLD: &str = "hold";
+
+/// The seed of [crate::state::BondSeed::JackpotMints].
+pub const SEED_JACKPOT_MINTS: &str = "jackpotMints";
+
+/// The seed of [crate::state::BondSeed::Session].
+pub const SEED_SESSION: &str = "session";
+
+/// The seed of [crate::state::BondSeed::Receipt].
+pub const SEED_RECEIPT: &str = "receipt";
+
+/// The seed of [crate::state::BondSeed::Leaderboard].
+pub const SEED_LEADERBOARD: &str = "leaderboard";
+
+/// The seed of [crate::state::BondSeed::Stream].
+pub const SEED_STREAM: &str = "stream";
+
+/// The seed of [crate::state::BondSeed::Rebate].
+pub const SEED_REBATE: &str = "rebate";
+
+/// The seed of [crate::state::BondSeed::ExclusionValidation].
+pub const SEED_EXCLUSION_VALIDATION: &str = "exclusionValidation";
+
+/// The seed of [crate::state::BondSeed::Heartbeat].
+pub const SEED_HEARTBEAT: &str = "heartbeat";
+
+/// The seed of [crate::state::BondSeed::Snapshot].
+pub const SEED_SNAPSHOT: &str = "snapshot";
+
+/// The seed of [crate::state::BondSeed::ParticipantBuffer].
+pub const SEED_PARTICIPANT_BUFFER: &str = "participantBuffer";
+
+/// The seed of [crate::state::BondSeed::StakeVault].
+pub const SEED_STAKE_VAULT: &str = "stakeVault";
+
+
+// Account Sizes
+// -------------------------------------------------------------------------------------------------
+
+/// The size of the discriminator starting every account (see 
+/// [crate::state::ACCOUNT_DISCRIMINATORS]).
+pub const ACCOUNT_DISCRIMINATOR_SIZE: usize = 8;
+
+/// The offset of the layout version following every account's `account_type`.
+pub const ACCOUNT_VERSION_OFFSET: usize = ACCOUNT_DISCRIMINATOR_SIZE;
+
+/// The size of the fields shared by program accounts (`account_type`, `version`, `authority` and 
+/// `bump`).
+pub const ACCOUNT_HEADER_SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 1 + 32 + 1;
+
+/// The size of a [crate::state::BondConfig] with a `uri` of [MAX_URI_LEN] bytes.
+pub const CONFIG_SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 1 + 1 + 2 + 4 + 4 + 8 + 8 + 8 + 8 + 2
+    + 1 + 1 + 2 + 2 + 1 + 9 + 9 + 1 + 1 + 1 + 1 + 2 + 32 + 32 + 35 + 35 + 33 + 33 + 32 + 1 + 1
+    + 6 * 33 + MAX_NAME_LEN + 4 + MAX_URI_LEN + 1 + 33 + 1 + 9 + 33 + 1 + 1 + 1;
+
+/// The size of a [crate::state::BondState].
+pub const STATE_SIZE: usize = ACCOUNT_HEADER_SIZE + 8 + 1 + 8 + 4 + 32 + 8 + 8 + 16 + 8 + 8 + 8
+    + 32 + 8 + 8 + 4 + 8;
+
+/// The size of a [crate::state::BondShare].
+pub const SHARE_SIZE: usize = ACCOUNT_HEADER_SIZE + 4 + 4 + 8;
+
+/// The size of a [crate::state::BondFee].
+pub const FEE_SIZE: usize = ACCOUNT_HEADER_SIZE + 8;
+
+/// The size of an attested [crate::state::BondDraw] without additional jackpot prizes (add
+/// [PRIZE_SIZE] per prize).
+pub const DRAW_SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 1 + 32 + 32 + 8 + 8 + 8 + 32 + 1 + 8 + 8 
+    + 8 + 8 + 1 + 1 + 33 + 8 + 4 + 8 + 8 + 32 + 1 + 8;
+
+/// The size of a [crate::state::BondPrize].
+pub const PRIZE_SIZE: usize = 32 + 8;
+
+/// The size of an attested [crate::state::BondDraw] with a prize for each of the 
+/// [MAX_JACKPOT_MINTS].
+pub const MAX_DRAW_SIZE: usize = DRAW_SIZE + MAX_JACKPOT_MINTS * PRIZE_SIZE;
+
+/// The size of an empty [crate::state::BondExclusionList] (see [exclusion_list_size]).
+pub const EXCLUSION_LIST_SIZE: usize = ACCOUNT_HEADER_SIZE + 4 + 4 + 8 + 4;
+
+/// The size of a [crate::state::BondExclusion].
+pub const EXCLUSION_SIZE: usize = 32 + 9;
+
+/// The size of a [crate::state::BondSeasonArchive].
+pub const SEASON_ARCHIVE_SIZE: usize = ACCOUNT_HEADER_SIZE + 4 + 8 + 1 + 9 + 9 + 8 + 8 
+    + STATE_SIZE;
+
+/// The size of a [crate::state::BondParticipant].
+pub const PARTICIPANT_SIZE: usize = ACCOUNT_HEADER_SIZE + 32 + 32 + 32 + 8;
+
+/// The size of a [crate::state::BondHold].
+pub const HOLD_SIZE: usize = ACCOUNT_HEADER_SIZE + 32 + 8 + 8;
+
+/// The size of a [crate::state::BondJackpotMints] with room for [MAX_JACKPOT_MINTS] and their 
+/// reservations.
+pub const JACKPOT_MINTS_SIZE: usize = ACCOUNT_HEADER_SIZE + 4 + MAX_JACKPOT_MINTS * 32 + 4 
+    + MAX_JACKPOT_MINTS * PRIZE_SIZE;
+
+/// The size of a [crate::state::BondSession].
+pub const SESSION_SIZE: usize = ACCOUNT_HEADER_SIZE + 32 + 32 + 8;
+
+/// The size of a [crate::state::BondLeaderboard] with room for [MAX_LEADERBOARD_ENTRIES].
+pub const LEADERBOARD_SIZE: usize = ACCOUNT_HEADER_SIZE + 4 + MAX_LEADERBOARD_ENTRIES * (32 + 8);
+
+/// The size of a [crate::state::BondStream].
+pub const STREAM_SIZE: usize = ACCOUNT_HEADER_SIZE + 32 + 8 + 8 + 8 + 8;
+
+/// The size of a [crate::state::BondRebateClaim].
+pub const REBATE_CLAIM_SIZE: usize = ACCOUNT_HEADER_SIZE + 16;
+
+/// The size of a [crate::state::BondExclusionValidation].
+pub const EXCLUSION_VALIDATION_SIZE: usize = ACCOUNT_HEADER_SIZE + 32 + 8 + 8 + 8 + 4 + 4 + 1;
+
+/// The size of a [crate::state::BondHeartbeat].
+pub const HEARTBEAT_SIZE: usize = ACCOUNT_HEADER_SIZE + 8 + 8 + 8;
+
+/// The size of an empty [crate::state::BondSnapshot] (see [snapshot_size]).
+pub const SNAPSHOT_SIZE: usize = ACCOUNT_HEADER_SIZE + 8 + 8 + 8 + 8 + 8 + 4 + 4 + 32 + 1 + 33 + 4;
+
+/// The size of an empty [crate::state::BondParticipantBuffer] (see [participant_buffer_size]).
+pub const PARTICIPANT_BUFFER_SIZE: usize = ACCOUNT_HEADER_SIZE + 8 + 4 + 8 + 8 + 4;
+
+/// The size of a [crate::state::BondSnapshotEntry].
+pub const SNAPSHOT_ENTRY_SIZE: usize = 32 + 8 + 8;
+
+/// The size of a [crate::state::BondExclusionList] with room for `capacity` accounts.
+pub const fn exclusion_list_size(capacity: u32) -> usize {
+    EXCLUSION_LIST_SIZE + capacity as usize * EXCLUSION_SIZE
+}
+
+/// The size of a [crate::state::BondSnapshot] of `len` entries.
+pub const fn snapshot_size(len: usize) -> usize {
+    SNAPSHOT_SIZE + len * SNAPSHOT_ENTRY_SIZE
+}
+
+/// The size of a [crate::state::BondParticipantBuffer] of `len` entries.
+pub const fn participant_buffer_size(len: usize) -> usize {
+    PARTICIPANT_BUFFER_SIZE + len * SNAPSHOT_ENTRY_SIZE
+}
+
+
+// Transactions
+// -------------------------------------------------------------------------------------------------
+
+/// The maximum number of instructions of an isolated `Draw` transaction (the compute unit limit 
+/// and price, an attestation and the draw itself).
+pub const MAX_DRAW_TRANSACTION_INSTRUCTIONS: usize = 4;
+
+
+// Default Shares
+// -------------------------------------------------------------------------------------------------
+
+/// The default equity share of the epoch fee (10%).
+pub const DEFAULT_EQUITY_SHARE: Ratio = Ratio { numerator: 10, denominator: 100 };
+
+/// The default treasury share of the epoch fee (0%).
+pub const DEFAULT_TREASURY_SHARE: Ratio = Ratio { numerator: 0, denominator: 100 };
+
+/// The default jackpot share of the epoch fee (80%).
+pub const DEFAULT_JACKPOT_SHARE: Ratio = Ratio { numerator: 80, denominator: 100 };
+
+/// The default stake share of the epoch fee (10%).
+pub const DEFAULT_STAKE_SHARE: Ratio = Ratio { numerator: 10, denominator: 100 };
+
+
+// Decimals
+// -------------------------------------------------------------------------------------------------
+
+/// The decimals of SOL and of the liquid staking tokens supported by the program. Amounts are
+/// always in the token mint's base units, whose decimals are cached in
+/// [crate::state::BondConfig]`.decimals` and should be read from there rather than assumed.
+pub const LST_DECIMALS: u8 = 9;
+
+/// Converts an amount in base units to a UI amount with `decimals`.
+pub fn to_ui_amount(amount: u64, decimals: u8) -> f64 {
+    spl_token::amount_to_ui_amount(amount, decimals)
+}
+
+/// Converts a decimal UI amount (e.g. `"1.5"`) to base units with `decimals`, failing on 
+/// malformed input, on more fractional digits than `decimals` and on overflow.
+pub fn to_amount(ui_amount: &str, decimals: u8) -> Result<u64, ProgramError> {
+    let (whole, fraction) = ui_amount.split_once('.').unwrap_or((ui_amount, ""));
+    let is_digits = |digits: &str| digits.bytes().all(|byte| byte.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || !is_digits(whole)
+        || !is_digits(fraction)
+        || fraction.len() > usize::from(decimals)
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Pad the fraction to `decimals` digits and accumulate both parts in base units.
+    let padding = vec![b'0'; usize::from(decimals) - fraction.len()];
+    let amount = whole.bytes()
+        .chain(fraction.bytes())
+        .chain(padding)
+        .try_fold(0u128, |amount, digit| {
+            amount.checked_mul(10)?.checked_add(u128::from(digit - b'0'))
+        })
+        .ok_or(ProgramError::InvalidArgument)?;
+    u64::try_from(amount).map_err(|_| ProgramError::InvalidArgument)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_amount() {
+        assert_eq!(to_amount("1.5", LST_DECIMALS), Ok(1_500_000_000));
+        assert_eq!(to_amount("1", 6), Ok(1_000_000));
+        assert_eq!(to_amount(".25", 2), Ok(25));
+        assert_eq!(to_amount("0.000001", 6), Ok(1));
+        assert_eq!(to_amount("18446744073709551615", 0), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_to_amount_invalid() {
+        for ui_amount in ["", ".", "-1", "1.2.3", "1e9", " 1", "0.0000001"] {
+            assert_eq!(to_amount(ui_amount, 6), Err(ProgramError::InvalidArgument));
+        }
+        assert_eq!(to_amount("18446744073709551616", 0), Err(ProgramError::InvalidArgument));
+        assert_eq!(to_amount("18446744073.709551616", 9), Err(ProgramError::InvalidArgument));
+    }
+
+    #[test]
+    fn test_to_ui_amount() {
+        assert_eq!(to_ui_amount(1_500_000_000, LST_DECIMALS), 1.5);
+        assert_eq!(to_ui_amount(25, 2), 0.25);
+    }
+}
@@ -9,18 +9,25 @@ use crate::check::Check;
 /// ------------------------------------------------------------------------------------------------
 use {
     crate::{
+        error::BondError,
         state::BondSeed,
     },
     solana_program::{
         account_info::AccountInfo,
         entrypoint::ProgramResult,
-        instruction::Instruction,
+        instruction::{AccountMeta, Instruction},
+        keccak,
+        msg,
+        program_error::ProgramError,
         program::{invoke, invoke_signed}, 
+        program_pack::Pack,
         pubkey::Pubkey,
         rent::Rent, 
         system_instruction, 
+        system_program,
     },
     spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    spl_token::state::Mint,
     std::convert::AsRef,
 };
 
@@ -28,23 +35,36 @@ use {
 /// Create
 /// ------------------------------------------------------------------------------------------------
 
+/// The Anchor discriminator of the compression program's `init_empty_merkle_tree` instruction.
+const INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+
+/// The Anchor discriminator of the compression program's `append` instruction.
+const APPEND_DISCRIMINATOR: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+
+/// The instruction index of the Wormhole core bridge's `PostMessage` instruction.
+const WORMHOLE_POST_MESSAGE: u8 = 1;
+
+/// The Wormhole consistency level of finalized messages.
+const WORMHOLE_CONSISTENCY_FINALIZED: u8 = 1;
+
+/// The offset of the message fee in the Wormhole bridge account (guardian set index, last 
+/// lamports and guardian set expiration time precede it).
+const WORMHOLE_BRIDGE_FEE_OFFSET: usize = 16;
+
+/// The Anchor discriminator of the compression program's `verify_leaf` instruction.
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+/// The Anchor discriminator of the compression program's `replace_leaf` instruction.
+const REPLACE_LEAF_DISCRIMINATOR: [u8; 8] = [204, 165, 76, 100, 73, 147, 0, 128];
+
+/// The Anchor discriminator of the Switchboard program's `vrf_request_randomness` instruction.
+const VRF_REQUEST_RANDOMNESS_DISCRIMINATOR: [u8; 8] = [230, 121, 14, 164, 28, 222, 117, 118];
+
 /// Creates accounts.
 pub struct Create;
 
 impl Create {
     
-    pub fn seeds<'a: 'b, 'b>(
-        config_info: &'b AccountInfo<'a>,
-        seed: &'b [u8],
-        bump: &'b [u8],
-    ) -> [&'b [u8]; 3] {
-        return [
-            config_info.key.as_ref(),
-            seed,
-            bump,
-        ]
-    }
-
     pub fn account_ix(
         program_id: &Pubkey,
         rent: &Rent,
@@ -61,6 +81,57 @@ impl Create {
         )
     }
 
+    /// The instructions creating `account_info` for `program_id` with `space` bytes: a 
+    /// `CreateAccount`, or if the account was prefunded, a top-up to the rent exempt balance 
+    /// followed by an `Allocate` and an `Assign` (`CreateAccount` fails on funded accounts).
+    fn account_ixs(
+        program_id: &Pubkey,
+        rent: &Rent,
+        payer: &Pubkey,
+        account_info: &AccountInfo,
+        space: u32,
+    ) -> Vec<Instruction> {
+        let space = usize::try_from(space).unwrap();
+        if account_info.lamports() == 0 {
+            return vec![Self::account_ix(program_id, rent, payer, account_info.key, space)];
+        }
+        let mut ixs = Vec::new();
+        let top_up = rent.minimum_balance(space).saturating_sub(account_info.lamports());
+        if top_up > 0 {
+            ixs.push(system_instruction::transfer(payer, account_info.key, top_up));
+        }
+        ixs.push(system_instruction::allocate(account_info.key, space as u64));
+        ixs.push(system_instruction::assign(account_info.key, program_id));
+        ixs
+    }
+
+    /// Returns true if `account_info` has already been created for `program_id` with `space` bytes, 
+    /// false if it doesn't exist yet or only holds lamports (a prefunded system account without 
+    /// data), or an error if it exists with a different owner or size.
+    pub fn exists(
+        program_id: &Pubkey,
+        account_info: &AccountInfo,
+        space: u32,
+    ) -> Result<bool, ProgramError> {
+        let is_prefunded = account_info.owner.eq(&system_program::id()) 
+            && account_info.data_is_empty();
+        if account_info.lamports() == 0 || is_prefunded {
+            Ok(false)
+        } else if account_info.owner.eq(program_id) 
+            && account_info.data_len() == usize::try_from(space).unwrap() {
+            msg!("Account {} already exists", account_info.key);
+            Ok(true)
+        } else {
+            msg!(
+                "Conflicting account {}: owner {}, size {}", 
+                account_info.key,
+                account_info.owner,
+                account_info.data_len(),
+            );
+            Err(BondError::AccountConflict.into())
+        }
+    }
+
     pub fn account<'a: 'b, 'b>(
         program_id: &'b Pubkey,
         payer_info: &'b AccountInfo<'a>,
@@ -69,21 +140,27 @@ impl Create {
         rent: &'b Rent,
         space: u32,
     ) -> ProgramResult {
-        let ix = Self::account_ix(
+        if Self::exists(program_id, account_info, space)? {
+            return Ok(());
+        }
+        let ixs = Self::account_ixs(
             program_id, 
             rent, 
             payer_info.key, 
-            account_info.key,
-            space.try_into().unwrap(),
+            account_info,
+            space,
         );
-        invoke(
-            &ix, 
-            &[
-                payer_info.clone(), 
-                account_info.clone(), 
-                system_program_info.clone(),
-            ],
-        )
+        for ix in ixs.iter() {
+            invoke(
+                ix, 
+                &[
+                    payer_info.clone(), 
+                    account_info.clone(), 
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+        Ok(())
     }
 
     pub fn pda(
@@ -92,59 +169,128 @@ impl Create {
         seed: BondSeed,
         bump: u8,
     ) -> Result<Pubkey, PubkeyError> {
-        let bump = [bump];
-        let seeds = Self::seeds(
-            &config_info, 
-            &seed.as_ref().as_ref(), 
-            &bump,
-        );
-        Pubkey::create_program_address(
+        seed.address(program_id, config_info.key, bump)
+    }
+
+    pub fn pda_account<'a: 'b, 'b>(
+        program_id: &'b Pubkey,
+        config_info: &'b AccountInfo<'a>,
+        payer_info: &'b AccountInfo<'a>,
+        pda_info: &'b AccountInfo<'a>,
+        pda_seed: BondSeed,
+        pda_bump: u8,
+        system_program_info: &'b AccountInfo<'a>,
+        rent: &'b Rent,
+        space: u32,
+    ) -> ProgramResult {
+        let bump = [pda_bump];
+        let seeds = pda_seed.signer_seeds(config_info.key, &bump);
+        Self::signed_account(
+            program_id, 
+            payer_info, 
+            pda_info, 
+            system_program_info, 
+            rent, 
+            space, 
             &seeds,
-            program_id,
         )
     }
 
-    pub fn pda_account<'a: 'b, 'b>(
+    /// Creates a PDA derived from [`config`, `pda_seed`, `index`] (e.g. one account per season).
+    pub fn indexed_pda_account<'a: 'b, 'b>(
         program_id: &'b Pubkey,
         config_info: &'b AccountInfo<'a>,
         payer_info: &'b AccountInfo<'a>,
         pda_info: &'b AccountInfo<'a>,
         pda_seed: BondSeed,
+        index: &'b [u8],
         pda_bump: u8,
         system_program_info: &'b AccountInfo<'a>,
         rent: &'b Rent,
         space: u32,
     ) -> ProgramResult {
         let bump = [pda_bump];
-        let seeds = Self::seeds(
-            &config_info, 
-            &pda_seed.as_ref().as_ref(), 
+        let seeds = pda_seed.indexed_signer_seeds(config_info.key, index, &bump);
+        Self::signed_account(
+            program_id, 
+            payer_info, 
+            pda_info, 
+            system_program_info, 
+            rent, 
+            space, 
+            &seeds,
+        )
+    }
+
+    /// Creates a config PDA derived from [[BondSeed::Config], `authority`, `token_mint`].
+    pub fn config_pda_account<'a: 'b, 'b>(
+        program_id: &'b Pubkey,
+        payer_info: &'b AccountInfo<'a>,
+        config_info: &'b AccountInfo<'a>,
+        authority: &'b Pubkey,
+        token_mint: &'b Pubkey,
+        config_bump: u8,
+        system_program_info: &'b AccountInfo<'a>,
+        rent: &'b Rent,
+        space: u32,
+    ) -> ProgramResult {
+        let bump = [config_bump];
+        let seeds = [
+            BondSeed::Config.as_bytes(),
+            authority.as_ref(),
+            token_mint.as_ref(),
             &bump,
-        );
-        let pda = Pubkey::create_program_address(
+        ];
+        Self::signed_account(
+            program_id, 
+            payer_info, 
+            config_info, 
+            system_program_info, 
+            rent, 
+            space, 
             &seeds,
+        )
+    }
+
+    fn signed_account<'a: 'b, 'b>(
+        program_id: &'b Pubkey,
+        payer_info: &'b AccountInfo<'a>,
+        pda_info: &'b AccountInfo<'a>,
+        system_program_info: &'b AccountInfo<'a>,
+        rent: &'b Rent,
+        space: u32,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let pda = Pubkey::create_program_address(
+            seeds,
             program_id,
         )?;
         Check::account(
             pda_info, 
             &pda,
         )?;
-        let ix = Self::account_ix(
+        if Self::exists(program_id, pda_info, space)? {
+            return Ok(());
+        }
+        let ixs = Self::account_ixs(
             program_id, 
             &rent, 
             payer_info.key, 
-            &pda_info.key,
-            space.try_into().unwrap(),
+            pda_info,
+            space,
         );
-        invoke_signed(
-            &ix, 
-            &[
-                payer_info.clone(), 
-                pda_info.clone(), 
-                system_program_info.clone(),
-            ],
-            &[&seeds],
-        )
+        for ix in ixs.iter() {
+            invoke_signed(
+                ix, 
+                &[
+                    payer_info.clone(), 
+                    pda_info.clone(), 
+                    system_program_info.clone(),
+                ],
+                &[seeds],
+            )?;
+        }
+        Ok(())
     }
 
     pub fn ata_account<'a: 'b, 'b>(
@@ -213,8 +359,9 @@ impl Create {
         )
     }
     
+    /// Transfers `amount` tokens from `source_info`, owned by the game's PDA `authority_info` 
+    /// derived from `seed` and `bump`, to `destination_info` (Token or Token-2022 Program).
     pub fn token_transfer_checked<'a, 'b>(
-        draw_authotity_info: &'a AccountInfo<'b>,
         config_info: &'a AccountInfo<'b>,
         token_program_info: &'a AccountInfo<'b>,
         token_mint_info: &'a AccountInfo<'b>,
@@ -224,34 +371,622 @@ impl Create {
         seed: BondSeed,
         bump: u8,
         amount: u64,
+        decimals: u8,
     ) -> ProgramResult {
         let binding = [bump];
-        let seeds = Create::seeds(
-            &config_info,
-            seed.as_ref().as_ref(),
-            &binding,
+        let seeds = seed.signer_seeds(config_info.key, &binding);
+        Self::token_transfer_signed(
+            token_program_info, 
+            token_mint_info, 
+            source_info, 
+            destination_info, 
+            authority_info, 
+            amount, 
+            decimals, 
+            &seeds,
+        )
+    }
+
+    /// Creates the token mint PDA `mint_info` (signed with `seeds`) with `mint_authority_info` as 
+    /// its mint authority and no freeze authority (Token or Token-2022 Program).
+    pub fn mint_pda<'a>(
+        payer_info: &AccountInfo<'a>,
+        mint_info: &AccountInfo<'a>,
+        mint_authority_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        rent: &Rent,
+        decimals: u8,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let ix = Self::account_ix(
+            token_program_info.key, 
+            rent, 
+            payer_info.key, 
+            mint_info.key, 
+            Mint::LEN,
         );
-        let ix = spl_token::instruction::transfer_checked(
-            &token_program_info.key, 
-            &source_info.key, 
-            &token_mint_info.key, 
-            &destination_info.key, 
-            &authority_info.key, 
+        invoke_signed(
+            &ix, 
+            &[
+                payer_info.clone(), 
+                mint_info.clone(), 
+                system_program_info.clone(),
+            ], 
+            &[seeds],
+        )?;
+        let mut ix = spl_token::instruction::initialize_mint2(
+            &spl_token::id(), 
+            mint_info.key, 
+            mint_authority_info.key, 
+            None, 
+            decimals,
+        )?;
+        ix.program_id = *token_program_info.key;
+        invoke(
+            &ix, 
+            &[
+                mint_info.clone(), 
+                token_program_info.clone(),
+            ],
+        )
+    }
+
+    /// Mints `amount` tokens of the mint `mint_info` to `destination_info`, signed by the PDA 
+    /// `mint_authority_info` (with `seeds`) (Token or Token-2022 Program).
+    pub fn mint_to<'a>(
+        token_program_info: &AccountInfo<'a>,
+        mint_info: &AccountInfo<'a>,
+        destination_info: &AccountInfo<'a>,
+        mint_authority_info: &AccountInfo<'a>,
+        amount: u64,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let mut ix = spl_token::instruction::mint_to(
+            &spl_token::id(), 
+            mint_info.key, 
+            destination_info.key, 
+            mint_authority_info.key, 
+            &[], 
+            amount,
+        )?;
+        ix.program_id = *token_program_info.key;
+        invoke_signed(
+            &ix, 
+            &[
+                mint_info.clone(), 
+                destination_info.clone(), 
+                mint_authority_info.clone(), 
+                token_program_info.clone(),
+            ], 
+            &[seeds],
+        )
+    }
+
+    /// Burns `amount` tokens of the mint `mint_info` from `account_info`, signed by its owner 
+    /// `owner_info` (Token or Token-2022 Program).
+    pub fn burn<'a>(
+        token_program_info: &AccountInfo<'a>,
+        mint_info: &AccountInfo<'a>,
+        account_info: &AccountInfo<'a>,
+        owner_info: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        let mut ix = spl_token::instruction::burn(
+            &spl_token::id(), 
+            account_info.key, 
+            mint_info.key, 
+            owner_info.key, 
+            &[], 
+            amount,
+        )?;
+        ix.program_id = *token_program_info.key;
+        invoke(
+            &ix, 
+            &[
+                account_info.clone(), 
+                mint_info.clone(), 
+                owner_info.clone(), 
+                token_program_info.clone(),
+            ],
+        )
+    }
+
+    /// Closes the program account `account_info`, moving its lamports to `receiver_info`.
+    pub fn close(
+        account_info: &AccountInfo,
+        receiver_info: &AccountInfo,
+    ) -> ProgramResult {
+        let lamports = account_info.lamports();
+        **account_info.lamports.borrow_mut() = 0;
+        **receiver_info.lamports.borrow_mut() = receiver_info.lamports()
+            .checked_add(lamports)
+            .ok_or(BondError::NumericalOverflow)?;
+        account_info.data.borrow_mut().fill(0);
+        Ok(())
+    }
+
+    /// Grows the program account `account_info` to `space` bytes (zero-initialized), funding its 
+    /// rent exemption from `payer_info`.
+    pub fn realloc<'a>(
+        payer_info: &AccountInfo<'a>,
+        account_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        rent: &Rent,
+        space: u32,
+    ) -> ProgramResult {
+        let space = space as usize;
+        if space < account_info.data_len() {
+            msg!("Account size {} exceeds {}", account_info.data_len(), space);
+            return Err(ProgramError::InvalidRealloc);
+        }
+        let lamports = rent.minimum_balance(space).saturating_sub(account_info.lamports());
+        if lamports > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, account_info.key, lamports),
+                &[payer_info.clone(), account_info.clone(), system_program_info.clone()],
+            )?;
+        }
+        account_info.realloc(space, true)
+    }
+
+    /// Transfers `amount` tokens from `source_info`, owned by the PDA `authority_info` (signed 
+    /// with `seeds`), to `destination_info`. Supports the Token-2022 Program, whose instruction 
+    /// layout is shared with the Token Program.
+    pub fn token_transfer_signed<'a>(
+        token_program_info: &AccountInfo<'a>,
+        token_mint_info: &AccountInfo<'a>,
+        source_info: &AccountInfo<'a>,
+        destination_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        amount: u64,
+        decimals: u8,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let mut ix = spl_token::instruction::transfer_checked(
+            &spl_token::id(), 
+            source_info.key, 
+            token_mint_info.key, 
+            destination_info.key, 
+            authority_info.key, 
             &[],
             amount, 
-            9,
+            decimals,
         )?;
+        ix.program_id = *token_program_info.key;
         invoke_signed(
             &ix, 
             &[
-                draw_authotity_info.clone(),
                 token_program_info.clone(),
                 source_info.clone(),
                 token_mint_info.clone(),
                 destination_info.clone(),
                 authority_info.clone(),
             ], 
-            &[&seeds],
+            &[seeds],
+        )
+    }
+
+    /// Closes the token account `account_info`, owned by the PDA `authority_info` (signed with 
+    /// `seeds`), moving its lamports to `receiver_info` (Token or Token-2022 Program).
+    pub fn close_token_account<'a>(
+        token_program_info: &AccountInfo<'a>,
+        account_info: &AccountInfo<'a>,
+        receiver_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let mut ix = spl_token::instruction::close_account(
+            &spl_token::id(), 
+            account_info.key, 
+            receiver_info.key, 
+            authority_info.key, 
+            &[],
+        )?;
+        ix.program_id = *token_program_info.key;
+        invoke_signed(
+            &ix, 
+            &[
+                account_info.clone(),
+                receiver_info.clone(),
+                authority_info.clone(),
+                token_program_info.clone(),
+            ], 
+            &[seeds],
+        )
+    }
+
+    /// Unwraps the wrapped SOL account `account_info` by closing it, moving its lamports to its 
+    /// owner `wallet_info`, which must sign.
+    pub fn unwrap_sol<'a>(
+        token_program_info: &AccountInfo<'a>,
+        account_info: &AccountInfo<'a>,
+        wallet_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let ix = spl_token::instruction::close_account(
+            token_program_info.key, 
+            account_info.key, 
+            wallet_info.key, 
+            wallet_info.key, 
+            &[],
+        )?;
+        invoke(
+            &ix, 
+            &[
+                account_info.clone(),
+                wallet_info.clone(),
+                token_program_info.clone(),
+            ],
+        )
+    }
+
+    /// Creates an Address Lookup Table owned by the PDA `authority_info` (signed with `seeds`).
+    pub fn lookup_table<'a>(
+        lookup_table_program_info: &AccountInfo<'a>,
+        lookup_table_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        recent_slot: u64,
+        bump: u8,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let mut data = 0u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&recent_slot.to_le_bytes());
+        data.push(bump);
+        let ix = Instruction::new_with_bytes(
+            *lookup_table_program_info.key, 
+            &data, 
+            vec![
+                AccountMeta::new(*lookup_table_info.key, false),
+                AccountMeta::new_readonly(*authority_info.key, true),
+                AccountMeta::new(*payer_info.key, true),
+                AccountMeta::new_readonly(*system_program_info.key, false),
+            ],
+        );
+        invoke_signed(
+            &ix, 
+            &[
+                lookup_table_info.clone(),
+                authority_info.clone(),
+                payer_info.clone(),
+                system_program_info.clone(),
+                lookup_table_program_info.clone(),
+            ], 
+            &[seeds],
+        )
+    }
+
+    /// Appends `addresses` to an Address Lookup Table owned by the PDA `authority_info`.
+    pub fn extend_lookup_table<'a>(
+        lookup_table_program_info: &AccountInfo<'a>,
+        lookup_table_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        addresses: &[Pubkey],
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&(addresses.len() as u64).to_le_bytes());
+        for address in addresses {
+            data.extend_from_slice(address.as_ref());
+        }
+        let ix = Instruction::new_with_bytes(
+            *lookup_table_program_info.key, 
+            &data, 
+            vec![
+                AccountMeta::new(*lookup_table_info.key, false),
+                AccountMeta::new_readonly(*authority_info.key, true),
+                AccountMeta::new(*payer_info.key, true),
+                AccountMeta::new_readonly(*system_program_info.key, false),
+            ],
+        );
+        invoke_signed(
+            &ix, 
+            &[
+                lookup_table_info.clone(),
+                authority_info.clone(),
+                payer_info.clone(),
+                system_program_info.clone(),
+                lookup_table_program_info.clone(),
+            ], 
+            &[seeds],
+        )
+    }
+
+    /// Records `memo` through the Memo program, signed by the PDA `signer_info` (with `seeds`).
+    pub fn memo<'a>(
+        memo_program_info: &AccountInfo<'a>,
+        signer_info: &AccountInfo<'a>,
+        memo: &str,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let ix = Instruction::new_with_bytes(
+            *memo_program_info.key, 
+            memo.as_bytes(), 
+            vec![AccountMeta::new_readonly(*signer_info.key, true)],
+        );
+        invoke_signed(
+            &ix, 
+            &[
+                signer_info.clone(),
+                memo_program_info.clone(),
+            ], 
+            &[seeds],
+        )
+    }
+
+    /// Initializes an empty SPL Account Compression merkle tree owned by the PDA `authority_info`.
+    pub fn merkle_tree<'a>(
+        compression_program_info: &AccountInfo<'a>,
+        noop_program_info: &AccountInfo<'a>,
+        merkle_tree_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        max_depth: u32,
+        max_buffer_size: u32,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let mut data = INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&max_depth.to_le_bytes());
+        data.extend_from_slice(&max_buffer_size.to_le_bytes());
+        Self::compression_cpi(
+            compression_program_info, 
+            noop_program_info, 
+            merkle_tree_info, 
+            authority_info, 
+            &data, 
+            seeds,
+        )
+    }
+
+    /// Logs `leaf_data` through the noop program and appends its hash to the merkle tree.
+    pub fn append_leaf<'a>(
+        compression_program_info: &AccountInfo<'a>,
+        noop_program_info: &AccountInfo<'a>,
+        merkle_tree_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        leaf_data: &[u8],
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        invoke(
+            &Instruction::new_with_bytes(*noop_program_info.key, leaf_data, vec![]),
+            &[noop_program_info.clone()],
+        )?;
+        let mut data = APPEND_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&keccak::hash(leaf_data).to_bytes());
+        Self::compression_cpi(
+            compression_program_info, 
+            noop_program_info, 
+            merkle_tree_info, 
+            authority_info, 
+            &data, 
+            seeds,
+        )
+    }
+
+    /// Verifies that `leaf` is at `index` of the merkle tree with `root`, given the tree's proof 
+    /// nodes as `proof_infos`.
+    pub fn verify_leaf<'a>(
+        compression_program_info: &AccountInfo<'a>,
+        merkle_tree_info: &AccountInfo<'a>,
+        proof_infos: &[AccountInfo<'a>],
+        root: [u8; 32],
+        leaf: [u8; 32],
+        index: u32,
+    ) -> ProgramResult {
+        let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&root);
+        data.extend_from_slice(&leaf);
+        data.extend_from_slice(&index.to_le_bytes());
+        let mut accounts = vec![AccountMeta::new_readonly(*merkle_tree_info.key, false)];
+        accounts.extend(proof_infos.iter().map(|info| AccountMeta::new_readonly(*info.key, false)));
+        let mut account_infos = vec![merkle_tree_info.clone()];
+        account_infos.extend_from_slice(proof_infos);
+        account_infos.push(compression_program_info.clone());
+        invoke(
+            &Instruction::new_with_bytes(*compression_program_info.key, &data, accounts),
+            &account_infos,
+        )
+    }
+
+    /// Replaces `previous_leaf` at `index` of the merkle tree with `root` by `new_leaf`, given the 
+    /// tree's proof nodes as `proof_infos`, signed by the tree's PDA `authority_info`.
+    pub fn replace_leaf<'a>(
+        compression_program_info: &AccountInfo<'a>,
+        noop_program_info: &AccountInfo<'a>,
+        merkle_tree_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        proof_infos: &[AccountInfo<'a>],
+        root: [u8; 32],
+        previous_leaf: [u8; 32],
+        new_leaf: [u8; 32],
+        index: u32,
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let mut data = REPLACE_LEAF_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&root);
+        data.extend_from_slice(&previous_leaf);
+        data.extend_from_slice(&new_leaf);
+        data.extend_from_slice(&index.to_le_bytes());
+        let mut accounts = vec![
+            AccountMeta::new(*merkle_tree_info.key, false),
+            AccountMeta::new_readonly(*authority_info.key, true),
+            AccountMeta::new_readonly(*noop_program_info.key, false),
+        ];
+        accounts.extend(proof_infos.iter().map(|info| AccountMeta::new_readonly(*info.key, false)));
+        let mut account_infos = vec![
+            merkle_tree_info.clone(),
+            authority_info.clone(),
+            noop_program_info.clone(),
+        ];
+        account_infos.extend_from_slice(proof_infos);
+        account_infos.push(compression_program_info.clone());
+        invoke_signed(
+            &Instruction::new_with_bytes(*compression_program_info.key, &data, accounts),
+            &account_infos,
+            &[seeds],
+        )
+    }
+
+    /// Pays the Wormhole message fee and posts `payload` as a finalized message from the PDA 
+    /// `emitter_info` (signed with `emitter_seeds`) to the PDA `message_info` (signed with 
+    /// `message_seeds`).
+    pub fn wormhole_message<'a>(
+        wormhole_program_info: &AccountInfo<'a>,
+        bridge_info: &AccountInfo<'a>,
+        message_info: &AccountInfo<'a>,
+        emitter_info: &AccountInfo<'a>,
+        sequence_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        fee_collector_info: &AccountInfo<'a>,
+        clock_info: &AccountInfo<'a>,
+        rent_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        nonce: u32,
+        payload: Vec<u8>,
+        emitter_seeds: &[&[u8]],
+        message_seeds: &[&[u8]],
+    ) -> ProgramResult {
+
+        // The message fee is read from the bridge's config.
+        let fee = bridge_info.data.borrow()
+            .get(WORMHOLE_BRIDGE_FEE_OFFSET..WORMHOLE_BRIDGE_FEE_OFFSET + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if fee > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, fee_collector_info.key, fee),
+                &[payer_info.clone(), fee_collector_info.clone(), system_program_info.clone()],
+            )?;
+        }
+
+        let mut data = vec![WORMHOLE_POST_MESSAGE];
+        data.extend_from_slice(&nonce.to_le_bytes());
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend(payload);
+        data.push(WORMHOLE_CONSISTENCY_FINALIZED);
+        let ix = Instruction::new_with_bytes(
+            *wormhole_program_info.key, 
+            &data, 
+            vec![
+                AccountMeta::new(*bridge_info.key, false),
+                AccountMeta::new(*message_info.key, true),
+                AccountMeta::new_readonly(*emitter_info.key, true),
+                AccountMeta::new(*sequence_info.key, false),
+                AccountMeta::new(*payer_info.key, true),
+                AccountMeta::new(*fee_collector_info.key, false),
+                AccountMeta::new_readonly(*clock_info.key, false),
+                AccountMeta::new_readonly(*system_program_info.key, false),
+                AccountMeta::new_readonly(*rent_info.key, false),
+            ],
+        );
+        invoke_signed(
+            &ix, 
+            &[
+                bridge_info.clone(),
+                message_info.clone(),
+                emitter_info.clone(),
+                sequence_info.clone(),
+                payer_info.clone(),
+                fee_collector_info.clone(),
+                clock_info.clone(),
+                system_program_info.clone(),
+                rent_info.clone(),
+                wormhole_program_info.clone(),
+            ], 
+            &[emitter_seeds, message_seeds],
+        )
+    }
+
+    fn compression_cpi<'a>(
+        compression_program_info: &AccountInfo<'a>,
+        noop_program_info: &AccountInfo<'a>,
+        merkle_tree_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        data: &[u8],
+        seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let ix = Instruction::new_with_bytes(
+            *compression_program_info.key, 
+            data, 
+            vec![
+                AccountMeta::new(*merkle_tree_info.key, false),
+                AccountMeta::new_readonly(*authority_info.key, true),
+                AccountMeta::new_readonly(*noop_program_info.key, false),
+            ],
+        );
+        invoke_signed(
+            &ix, 
+            &[
+                merkle_tree_info.clone(),
+                authority_info.clone(),
+                noop_program_info.clone(),
+                compression_program_info.clone(),
+            ], 
+            &[seeds],
+        )
+    }
+
+    /// Requests randomness from the Switchboard VRF account `vrf_info`, signed by its PDA 
+    /// authority `authority_info` (with `authority_seeds`) and paid from `payer_wallet_info`.
+    pub fn vrf_request_randomness<'a>(
+        switchboard_program_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        vrf_info: &AccountInfo<'a>,
+        oracle_queue_info: &AccountInfo<'a>,
+        queue_authority_info: &AccountInfo<'a>,
+        data_buffer_info: &AccountInfo<'a>,
+        permission_info: &AccountInfo<'a>,
+        escrow_info: &AccountInfo<'a>,
+        payer_wallet_info: &AccountInfo<'a>,
+        payer_authority_info: &AccountInfo<'a>,
+        recent_blockhashes_info: &AccountInfo<'a>,
+        program_state_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        permission_bump: u8,
+        program_state_bump: u8,
+        authority_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let mut data = VRF_REQUEST_RANDOMNESS_DISCRIMINATOR.to_vec();
+        data.push(permission_bump);
+        data.push(program_state_bump);
+        let ix = Instruction::new_with_bytes(
+            *switchboard_program_info.key, 
+            &data, 
+            vec![
+                AccountMeta::new_readonly(*authority_info.key, true),
+                AccountMeta::new(*vrf_info.key, false),
+                AccountMeta::new(*oracle_queue_info.key, false),
+                AccountMeta::new_readonly(*queue_authority_info.key, false),
+                AccountMeta::new_readonly(*data_buffer_info.key, false),
+                AccountMeta::new(*permission_info.key, false),
+                AccountMeta::new(*escrow_info.key, false),
+                AccountMeta::new(*payer_wallet_info.key, false),
+                AccountMeta::new_readonly(*payer_authority_info.key, true),
+                AccountMeta::new_readonly(*recent_blockhashes_info.key, false),
+                AccountMeta::new_readonly(*program_state_info.key, false),
+                AccountMeta::new_readonly(*token_program_info.key, false),
+            ],
+        );
+        invoke_signed(
+            &ix, 
+            &[
+                authority_info.clone(),
+                vrf_info.clone(),
+                oracle_queue_info.clone(),
+                queue_authority_info.clone(),
+                data_buffer_info.clone(),
+                permission_info.clone(),
+                escrow_info.clone(),
+                payer_wallet_info.clone(),
+                payer_authority_info.clone(),
+                recent_blockhashes_info.clone(),
+                program_state_info.clone(),
+                token_program_info.clone(),
+                switchboard_program_info.clone(),
+            ], 
+            &[authority_seeds],
         )
     }
-}
\ No newline at end of file
+}
@@ -6,13 +6,14 @@
 
 use {
     crate::{
+        error::BondError,
         processor::Processor,
     },
     solana_program::{
         account_info::AccountInfo,
         entrypoint,
         entrypoint::ProgramResult,
-        msg,
+        program_error::PrintProgramError,
         pubkey::Pubkey,
     },
 };
@@ -26,7 +27,7 @@ entrypoint!(process_instruction);
 
 // The program's entry point. A solana program has one entry point and it's a convention to name it 
 // `process_instruction`.
-fn process_instruction(
+pub fn process_instruction(
     // The program's id on the solana network.
     program_id: &Pubkey,
     // The accounts used by the instruction being invoked.
@@ -35,7 +36,8 @@ fn process_instruction(
     instruction_data: &[u8],
 ) -> ProgramResult {
     if let Err(error) = Processor::process(program_id, accounts, instruction_data) {
-        msg!("[Processor] Error: {}", error); // Print the error for debugging.
+        // Print custom error codes by their [BondError] name.
+        error.print::<BondError>();
         Err(error)
     } else {
         Ok(())
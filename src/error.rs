@@ -6,7 +6,11 @@
 
 use {
     num_derive::FromPrimitive,
-    solana_program::{decode_error::DecodeError, program_error::ProgramError},
+    solana_program::{
+        decode_error::DecodeError, 
+        msg,
+        program_error::{PrintProgramError, ProgramError},
+    },
     thiserror::Error,
 };
 
@@ -16,7 +20,245 @@ use {
 
 /// Known errors returned by the Bond program.
 #[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
-pub enum BondError {}
+pub enum BondError {
+
+    /// The exclusion list has reached its `capacity`.
+    #[error("Exclusion list is full")]
+    ExclusionListFull,
+
+    /// The account is already present in the exclusion list.
+    #[error("Account is already excluded")]
+    DuplicateExclusion,
+
+    /// The draw's prize has already been paid out.
+    #[error("Prize already claimed")]
+    PrizeAlreadyClaimed,
+
+    /// An arithmetic operation overflowed.
+    #[error("Numerical overflow")]
+    NumericalOverflow,
+
+    /// The metadata URI exceeds the maximum length.
+    #[error("Metadata URI too long")]
+    UriTooLong,
+
+    /// The season has not started yet.
+    #[error("Season not started")]
+    SeasonNotStarted,
+
+    /// The season has ended.
+    #[error("Season ended")]
+    SeasonEnded,
+
+    /// An account already exists with a different owner or size.
+    #[error("Conflicting account")]
+    AccountConflict,
+
+    /// The exclusion list chunk was appended out of order.
+    #[error("Invalid chunk index")]
+    InvalidChunkIndex,
+
+    /// The Stake Pool's balance has not been updated this epoch.
+    #[error("Stale stake pool")]
+    StaleStakePool,
+
+    /// A swap returned less than the minimum amount out or spent more than the amount in.
+    #[error("Slippage exceeded")]
+    SlippageExceeded,
+
+    /// The game has a ticket ledger, so claims must prove the winner's ticket.
+    #[error("Ticket proof required")]
+    TicketProofRequired,
+
+    /// A cross-account invariant of the game does not hold.
+    #[error("Invariant violated")]
+    InvariantViolated,
+
+    /// The jackpot has reached the maximum number of additional mints.
+    #[error("Jackpot mints full")]
+    JackpotMintsFull,
+
+    /// The mint is already registered with the jackpot (or is the game's token mint).
+    #[error("Jackpot mint is already registered")]
+    DuplicateJackpotMint,
+
+    /// The session key has expired.
+    #[error("Session expired")]
+    SessionExpired,
+
+    /// The ticket wasn't held during the draw window.
+    #[error("Ineligible ticket")]
+    IneligibleTicket,
+
+    /// The winner's weight is below the game's minimum eligible weight.
+    #[error("Insufficient weight")]
+    InsufficientWeight,
+
+    /// The receiver won a draw within the game's winner cooldown.
+    #[error("Winner in cooldown")]
+    WinnerCooldown,
+
+    /// The game requires the draw's selection inputs to be attested by its operator.
+    #[error("Attestation required")]
+    AttestationRequired,
+
+    /// The attestation isn't signed by the game's attestation signer or doesn't match the draw.
+    #[error("Invalid attestation")]
+    InvalidAttestation,
+
+    /// The instruction was invoked by another program or bundled with disallowed instructions.
+    #[error("Transaction not isolated")]
+    TransactionNotIsolated,
+
+    /// The receiver is in the exclusion list.
+    #[error("Receiver is excluded")]
+    ExcludedReceiver,
+
+    /// The receiver hasn't been validated against the exclusion list for this draw.
+    #[error("Exclusion validation required")]
+    ExclusionValidationRequired,
+
+    /// `SplitShares` ran less than [crate::state::BondConfig]`.min_split_interval` slots ago (or 
+    /// already ran this epoch).
+    #[error("Split too soon")]
+    SplitTooSoon,
+
+    /// The account was written by a newer program version.
+    #[error("Invalid account version")]
+    InvalidAccountVersion,
+
+    /// The game is paused.
+    #[error("Game inactive")]
+    GameInactive,
+
+    /// A share has a zero denominator or the shares sum to more than 100%.
+    #[error("Invalid shares")]
+    InvalidShares,
+
+    /// Fewer than [crate::state::BondConfig]`.epochs_per_draw` epochs passed since the latest draw.
+    #[error("Draw too soon")]
+    DrawTooSoon,
+
+    /// The receiver's odds exceed the configured odds threshold.
+    #[error("Odds threshold exceeded")]
+    OddsThresholdExceeded,
+
+    /// An account isn't derived from the expected seeds, or a draw seed isn't the next draw id.
+    #[error("Invalid seeds")]
+    InvalidSeeds,
+
+    /// A token account isn't the expected associated token account.
+    #[error("Invalid associated token account")]
+    InvalidAta,
+
+    /// A signer isn't the expected authority.
+    #[error("Invalid authority")]
+    InvalidAuthority,
+
+    /// An account isn't owned by the expected program.
+    #[error("Invalid owner")]
+    InvalidOwner,
+
+    /// A draw's receiver weight doesn't match its token balance or exceeds the total weight.
+    #[error("Invalid weight")]
+    InvalidWeight,
+
+    /// The game's draws must be fulfilled from a Switchboard VRF result.
+    #[error("VRF required")]
+    VrfRequired,
+
+    /// The VRF account isn't the game's, has no pending request or hasn't been verified.
+    #[error("Invalid VRF")]
+    InvalidVrf,
+
+    /// The VRF result doesn't select the draw's receiver.
+    #[error("Receiver not selected")]
+    ReceiverNotSelected,
+
+    /// The draw's result isn't signed by the game's oracle.
+    #[error("Invalid oracle signature")]
+    InvalidOracleSignature,
+
+    /// The snapshot can't be written while the draw's randomness is pending.
+    #[error("Snapshot locked")]
+    SnapshotLocked,
+
+    /// The snapshot wasn't taken for the draw.
+    #[error("Invalid snapshot")]
+    InvalidSnapshot,
+
+    /// The participant buffer can't hold more entries.
+    #[error("Participant buffer full")]
+    ParticipantBufferFull,
+
+    /// The winning leaf's proof doesn't match the snapshot's merkle root.
+    #[error("Invalid snapshot proof")]
+    InvalidSnapshotProof,
+
+    /// The draw's claim window has expired.
+    #[error("Claim expired")]
+    ClaimExpired,
+
+    /// The draw's claim window hasn't expired yet.
+    #[error("Claim not expired")]
+    ClaimNotExpired,
+
+    /// The game must be paused or closed.
+    #[error("Game active")]
+    GameActive,
+
+    /// The game still holds funds or accounts that must be closed first.
+    #[error("Accounts not closed")]
+    AccountsNotClosed,
+
+    /// The token account is used by the game and can't be recovered.
+    #[error("Token account in use")]
+    TokenAccountInUse,
+
+    /// The memo exceeds the maximum length.
+    #[error("Memo too long")]
+    MemoTooLong,
+
+    /// The shares sum up to more than the split amount.
+    #[error("Shares exceed amount")]
+    SharesExceedAmount,
+
+    /// The draw's receiver is the default address.
+    #[error("Default receiver")]
+    DefaultReceiver,
+
+    /// The draw's receiver is one of the game's accounts other than the jackpot.
+    #[error("Game account receiver")]
+    GameAccountReceiver,
+
+    /// The season must be closed before the next one starts.
+    #[error("Season not closed")]
+    SeasonNotClosed,
+
+    /// A VRF request is pending and must be fulfilled first.
+    #[error("VRF request pending")]
+    VrfRequestPending,
+
+    /// The operation requires the game's draws to be selected from a snapshot.
+    #[error("Snapshot draws required")]
+    SnapshotRequired,
+
+    /// The latest draw's prize is still unclaimed or its randomness is pending.
+    #[error("Draw not settled")]
+    DrawNotSettled,
+
+    /// A swap route debited a treasury account other than the swapped one.
+    #[error("Treasury balance decreased")]
+    TreasuryBalanceDecreased,
+
+    /// The display name is not UTF-8 zero padded to `MAX_NAME_LEN` bytes.
+    #[error("Invalid name")]
+    InvalidName,
+
+    /// The prize includes additional mint prizes, which a compliance hold can't escrow.
+    #[error("Prize held")]
+    PrizeHeld,
+}
 
 impl From<BondError> for ProgramError {
     fn from(e: BondError) -> Self {
@@ -28,4 +270,39 @@ impl<T> DecodeError<T> for BondError {
     fn type_of() -> &'static str {
         "Bond Error"
     }
-}
\ No newline at end of file
+}
+
+impl PrintProgramError for BondError {
+    fn print<E>(&self)
+    where
+        E: 'static 
+            + std::error::Error 
+            + DecodeError<E> 
+            + PrintProgramError 
+            + num_traits::FromPrimitive,
+    {
+        msg!("[Processor] Error: {} ({:?})", self, self);
+    }
+}
+
+
+// Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use {super::*, num_traits::FromPrimitive};
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(ProgramError::from(BondError::ExclusionListFull), ProgramError::Custom(0));
+
+        // Every code up to the last variant decodes back to its error.
+        let last = BondError::PrizeHeld as u32;
+        for code in 0..=last {
+            let error = BondError::from_u32(code).unwrap();
+            assert_eq!(ProgramError::from(error), ProgramError::Custom(code));
+        }
+        assert_eq!(BondError::from_u32(last + 1), None);
+    }
+}
@@ -5,8 +5,27 @@
 // -------------------------------------------------------------------------------------------------
 
 use {
-    borsh::{BorshDeserialize, BorshSerialize},
-    solana_program::pubkey::Pubkey,
+    crate::state::{
+        BondLstAdapter, 
+        BondPayoutAsset, 
+        BondPayoutMode, 
+        BondRemainderPolicy, 
+        BondSeed, 
+        BondSkippedSharePolicy, 
+        BondSnapshotEntry, 
+        BondSquadsVault, 
+        BondTicket,
+        MAX_NAME_LEN,
+    },
+    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey, 
+        pubkey::Pubkey,
+        system_program, 
+        sysvar,
+    },
+    spl_associated_token_account::get_associated_token_address_with_program_id,
 };
 
 
@@ -14,19 +33,37 @@ use {
 // -------------------------------------------------------------------------------------------------
 
 /// The instructions supported by the Bond program.
-#[repr(C)]
+/// 
+/// The discriminants are stable and match the Borsh variant index that prefixes the instruction 
+/// data, so new instructions must only be appended.
+#[repr(u8)]
 #[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
 pub enum BondInstruction {
     
     /// Creates accounts for the PDAs and their corresponding ATA accounts where applicable.
     /// 
-    /// The instruction requires each account's bump seed and allocation size so that these may be 
-    /// computed off chain.
+    /// The instruction requires each account's bump seed so that it may be computed off chain, 
+    /// while the allocation sizes are computed on chain (e.g. [BondConfig]`::LEN`).
+    /// 
+    /// Accounts that already exist with the expected owner and size are skipped, so a partially 
+    /// processed deployment can be retried. Prefunded accounts (owned by the System Program, 
+    /// without data) are topped up to the rent exempt balance, allocated and assigned, and ATAs 
+    /// are created idempotently. Any other existing account fails with `AccountConflict`.
+    /// 
+    /// Where the transaction size or compute limits don't allow all accounts at once, the PDAs can 
+    /// first be created across transactions with [BondInstruction::CreateState], 
+    /// [BondInstruction::CreateShare] (once per seed) and [BondInstruction::CreateExclusionList], 
+    /// leaving only the config to `Create`.
+    /// 
+    /// `config` is either a new keypair (signer) or, if `config_bump` is provided, the PDA of 
+    /// [[BondSeed::Config], `authority`, `token_mint`] so that one game per authority and mint is 
+    /// derivable by anyone.
     /// 
     /// ## Accounts
-    /// - `[s]` `[payer]` - The fee payer.
-    /// - `[s, w]` `[config]` - The game's settings and main authority. All PDAs are generated by 
-    ///     the seeds [`config`, [BondSeed]].
+    /// - `[s, w]` `[payer]` - The fee payer (any account, independent of `authority`).
+    /// - `[s?, w]` `[config]` - The game's settings. All PDAs are generated by the seeds 
+    ///     [`config`, [BondSeed]].
+    /// - `[s]` `[authority]` - The game's main authority.
     /// - `[w]` `[state]` - The game's current state (PDA of [`config`, [BondSeed::State]]).
     /// - `[w]` `[fee]` The Stake Pool's epoch fee receiver (PDA of [`config`, [BondSeed::Fee]]).
     /// - `[w]` `[fee_ata]` The associated token address of `fee` for `token_mint`.
@@ -41,51 +78,26 @@ pub enum BondInstruction {
     /// - `[w]` `[stake]` The locked stake  (PDA of [`config`, [BondSeed::Stake]]).
     /// - `[w]` `[stake_ata]` The associated token address of `stake` for `token_mint`.
     /// - `[]` `[token_mint]`- The Stake Pool's token mint.
-    /// - `[]` `[token_program]`- The Token Program's id.
+    /// - `[]` `[token_program]`- The id of the Token or Token-2022 Program owning `token_mint`.
     /// - `[]` `[associated_token_program]`- The Associated Token Program's id.
     /// - `[]` `[system_program]`- The System Program's id.
     /// 
     /// ## Data
-    /// - `[config_space]` - The allocation size of a [BondConfig] account.
-    /// - `[state_bump]` - [BondState]'s PDA bump seed.
-    /// - `[state_space]` - The allocation size of a [BondState] account.
-    /// - `[fee_bump]` - [BondFee]'s PDA bump seed.
-    /// - `[fee_space]` - The allocation size of a [BondFee] account.
-    /// - `[exclusion_list_bump]` - [BondExclusionList]'s PDA bump seed.
-    /// - `[exclusion_list_space]` - The allocation size of a [BondExclusionList] account.
-    /// - `[equity_bump]` - Equity ([BondShare]) PDA bump seed.
-    /// - `[equity_space]` - The allocation size of a [BondShare] account.
-    /// - `[treasury_bump]` - Treasury ([BondShare]) PDA bump seed.
-    /// - `[treasury_space]` - The allocation size of a [BondShare] account.
-    /// - `[jackpot_bump]` - Jackpot ([BondShare]) PDA bump seed.
-    /// - `[jackpot_space]` - The allocation size of a [BondShare] account.
-    /// - `[stake_bump]` - Stake ([BondShare]) PDA bump seed.
-    /// - `[stake_space]` - The allocation size of a [BondShare] account.
-    Create {
-        config_space: u32,
-        state_bump: u8,
-        state_space: u32,
-        fee_bump: u8,
-        fee_space: u32,
-        exclusion_list_bump: u8,
-        exclusion_list_space: u32,
-        equity_bump: u8,
-        equity_space: u32,
-        treasury_bump: u8,
-        treasury_space: u32,
-        jackpot_bump: u8,
-        jackpot_space: u32,
-        stake_bump: u8,
-        stake_space: u32,
-    },
+    /// - `[params]` - The accounts' bump seeds and the exclusion list's capacity ([CreateParams]).
+    Create(CreateParams) = 0,
     
     /// Initializes PDAs (and ATAs if applicable) for the Bond program.
     /// 
     /// `The accounts must be created before calling Initialize`.
     /// 
+    /// `authority` and `draw_authority` may be Squads vault PDAs that sign through the Squads 
+    /// program's CPI, in which case they're validated against `authority_vault` and 
+    /// `draw_authority_vault`.
+    /// 
     /// ## Accounts
     /// - `[s]` `[payer]` - The fee payer.
-    /// - `[s, w]` `[config]` - The game's settings and main authority.
+    /// - `[s?, w]` `[config]` - The game's settings (signer unless it's a PDA).
+    /// - `[s]` `[authority]` - The game's main authority.
     /// - `[s]` `[draw_authority]` - The authority designated to run draws.
     /// - `[]` `[token_mint]` - The Stake Pool's token mint address.
     /// - `[w]` `[state]` - The game's current state ([BondState]).
@@ -95,51 +107,2369 @@ pub enum BondInstruction {
     /// - `[w]` `[treasury]` - The treasury account ([BondShare]).
     /// - `[w]` `[jackpot]` - The jackpot account ([BondShare]).
     /// - `[w]` `[stake]` - The locked stake (min balance) ([BondShare]).
-    /// - `[]` `[token_program]` - The Token Program's id.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
     /// - `[]` `[associated_token_program]` - The Associated Token Program's id.
     /// - `[]` `[system_program]` - The System Program's id.
     /// 
     /// ## Data
-    /// - `[state_bump]` - [BondState]'s PDA bump seed.
-    /// - `[fee_bump]` - [BondFee]'s PDA bump seed.
-    /// - `[exclusion_list_bump]` - [BondExclusionList]'s PDA bump seed.
-    /// - `[exclusion_list_capacity]` - The max length of the accounts list.
-    /// - `[exclusion_list_accounts]` - The list of accounts to exclude from all draws.
-    /// - `[equity_bump]` - Equity ([BondShare]) PDA bump seed.
-    /// - `[treasury_bump]` - Treasury ([BondShare]) PDA bump seed.
-    /// - `[jackpot_bump]` - Jackpot ([BondShare]) PDA bump seed.
-    /// - `[stake_bump]` - Stake ([BondShare]) PDA bump seed.
-    Initialize {
-        state_bump: u8,
-        fee_bump: u8,
-        exclusion_list_bump: u8,
-        exclusion_list_capacity: u32,
-        exclusion_list_accounts: Vec<Pubkey>,
-        equity_bump: u8,
-        treasury_bump: u8,
-        jackpot_bump: u8,
-        stake_bump: u8,
-
-        // rollover_bump: u8,
-        // treasury_bump: u8,
-        // stake_bump: u8,
-        // equity_bump: u8,
-        // draw_bump: u8,
-        // fee_bump: u8,
-        // exclusion_list_bump: u8,
-        // exclusion_list_capacity: u32,
-        // exclusion_list: Vec<Pubkey>,
-    },
+    /// - `[params]` - The accounts' bump seeds and the game's settings ([InitializeParams]). 
+    ///     Settings that are not provided use their default values.
+    Initialize(InitializeParams) = 1,
+
+    /// Creates and initializes a [BondConfig] account in a single step.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s?, w]` `[config]` - The game's settings (signer unless it's a PDA).
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[s]` `[draw_authority]` - The authority designated to run draws.
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint address.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[space]` - The allocation size of a [BondConfig] account (at least [BondConfig]`::LEN`).
+    /// - `[params]` - The game's settings ([ConfigParams]).
+    CreateAndInitializeConfig {
+        space: u32,
+        params: ConfigParams,
+    } = 2,
+
+    /// Creates and initializes the [BondState] PDA in a single step.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings (initialized).
+    /// - `[w]` `[state]` - The game's current state (PDA of [`config`, [BondSeed::State]]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondState]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondState] account (at least [BondState]`::LEN`).
+    CreateAndInitializeState {
+        bump: u8,
+        space: u32,
+    } = 3,
+
+    /// Creates and initializes the [BondFee] PDA and its ATA in a single step.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings (initialized).
+    /// - `[w]` `[fee]` - The Stake Pool's epoch fee receiver (PDA of [`config`, [BondSeed::Fee]]).
+    /// - `[w]` `[fee_ata]` - The associated token address of `fee` for `token_mint`.
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[associated_token_program]` - The Associated Token Program's id.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondFee]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondFee] account (at least [BondFee]`::LEN`).
+    CreateAndInitializeFee {
+        bump: u8,
+        space: u32,
+    } = 4,
 
+    /// Creates and initializes the [BondExclusionList] PDA in a single step.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings (initialized).
+    /// - `[w]` `[exclusion_list]` - The accounts excluded from all draws (PDA of [`config`, 
+    ///     [BondSeed::ExclusionList]]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondExclusionList]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondExclusionList] account.
+    /// - `[capacity]` - The max length of the accounts list.
+    /// - `[accounts]` - The list of accounts to exclude from all draws.
+    CreateAndInitializeExclusionList {
+        bump: u8,
+        space: u32,
+        capacity: u32,
+        accounts: Vec<Pubkey>,
+    } = 5,
+
+    /// Creates and initializes a [BondShare] PDA (equity, treasury, jackpot or stake) and its ATA 
+    /// in a single step.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings (initialized).
+    /// - `[w]` `[share]` - The share account (PDA of [`config`, `seed`]).
+    /// - `[w]` `[share_ata]` - The associated token address of `share` for `token_mint`.
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[associated_token_program]` - The Associated Token Program's id.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[seed]` - [BondSeed::Equity], [BondSeed::Treasury], [BondSeed::Jackpot] or 
+    ///     [BondSeed::Stake].
+    /// - `[bump]` - The share's PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondShare] account (at least [BondShare]`::LEN`).
+    /// - `[share]` - The share of the epoch fee (defaults to the seed's default share).
+    CreateAndInitializeShare {
+        seed: BondSeed,
+        bump: u8,
+        space: u32,
+        share: Option<Ratio>,
+    } = 6,
+
+    /// Splits the Stake Pool's epoch fee between the equity, treasury, jackpot and stake accounts.
+    /// 
+    /// Rejected with `StaleStakePool` until `UpdateStakePool` has run in the current epoch for 
+    /// SPL stake pools ([BondLstAdapter::SplStakePool]), whose epoch fee is only final once their 
+    /// balance is updated.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The draw authority, or any signer if 
+    ///     [BondConfig]`.allow_permissionless_split` is set.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[fee]` - The Stake Pool's epoch fee receiver ([BondFee]).
+    /// - `[w]` `[fee_ata]` - The associated token address of `fee` for `token_mint`.
+    /// - `[]` `[equity]` - The shareholders account ([BondShare]).
+    /// - `[w]` `[equity_ata]` - The associated token address of `equity` for `token_mint`.
+    /// - `[]` `[treasury]` - The treasury account ([BondShare]).
+    /// - `[w]` `[treasury_ata]` - The associated token address of `treasury` for `token_mint`.
+    /// - `[]` `[jackpot]` - The jackpot account ([BondShare]).
+    /// - `[w]` `[jackpot_ata]` - The associated token address of `jackpot` for `token_mint`.
+    /// - `[]` `[stake]` - The locked stake ([BondShare]).
+    /// - `[w]` `[stake_ata]` - The associated token address of `stake` for `token_mint`.
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[w]` `[state]` - The game's current state ([BondState]), recording the split's slot and 
+    ///     epoch.
+    /// - `[w]` `[authority_ata]` - A token account owned by `authority` that receives the crank 
+    ///     fee (only required for permissionless callers when the fee is non-zero).
+    /// - `[w]` `[state_ata]` - The associated token address of `state` for `token_mint`, holding 
+    ///     the ticket holders' rebates (only if [BondConfig]`.rebate_bps` is non-zero).
+    /// 
+    /// The rebate is taken from the amount after the crank fee and accrued per unit of issued 
+    /// ticket weight, to be claimed with `ClaimRebate`. Nothing is rebated before tickets are 
+    /// issued.
+    /// 
+    /// Rejected with `SplitTooSoon` until [BondConfig]`.min_split_interval` slots have passed 
+    /// since [BondState]`.last_split_slot` (see [BondState::next_split_slot]), and once a split 
+    /// already ran in the current epoch for callers other than the draw authority (or any caller 
+    /// if [BondConfig]`.split_once_per_epoch` is set).
+    /// 
+    /// The remainder of the split (rounding) is handled per [BondConfig]`.remainder_policy`, the 
+    /// retained part being recorded in [BondState]`.split_remainder`. Portions below their 
+    /// share's `min_transfer` (dust) stay in the fee account for the next split, like skipped 
+    /// portions. Fails with `InvariantViolated` unless the balance of `fee_ata` decreased by 
+    /// exactly the amounts transferred.
+    /// 
+    /// Rejected with `SharesExceedAmount` before any transfer if the shares of the amount sum up 
+    /// to more than the amount.
+    /// 
+    /// Zero amounts aren't transferred, and the ATA of a share with a zero numerator isn't read 
+    /// (any account, e.g. the share itself, may be passed in its place).
+    /// 
+    /// ## Data
+    /// - `[amount]` - The amount to split (defaults to the balance of `fee_ata`, draw authority 
+    ///     only). Permissionless callers always split the balance of `fee_ata`.
+    /// - `[destinations]` - The bitmask of shares to fund ([SPLIT_ALL] by default, draw authority 
+    ///     only). Skipped portions are handled per [BondConfig]`.skipped_share_policy`.
     SplitShares {
         amount: Option<u64>,
-    },
+        destinations: Option<u8>,
+    } = 7,
 
     /// Runs a Bond draw.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[draw_authority]` - The authority designated to run draws.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]).
+    /// - `[]` `[jackpot]` - The jackpot account ([BondShare]).
+    /// - `[w]` `[jackpot_ata]` - The associated token address of `jackpot` for `token_mint`.
+    /// - `[]` `[treasury]` - The treasury account ([BondShare]).
+    /// - `[w]` `[treasury_ata]` - The associated token address of `treasury` for `token_mint`.
+    /// - `[]` `[receiver]` - The winning account (or `jackpot` for a rollover), neither the 
+    ///     default address nor another account of the program.
+    /// - `[w]` `[receiver_ata]` - The associated token address of `receiver` (or its registered 
+    ///     `payout`) for `token_mint`, whose balance is the winner's weight unless the game has a 
+    ///     ticket ledger. It may be missing for [BondPayoutMode::Claim] games weighted by tickets 
+    ///     or snapshots, whose winners pull the prize with `ClaimPrize`.
+    /// - `[w]` `[draw]` - An uninitialized account owned by the program, created and funded by any 
+    ///     payer ([BondDraw]), or the draw tree if [BondConfig]`.draw_tree` is set.
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[slot_hashes]` - The SlotHashes sysvar.
+    /// - `[]` `[participant]` - The receiver's registration, whose `payout` owns `receiver_ata` 
+    ///     ([BondParticipant], only if [BondConfig]`.require_registration` is set).
+    /// - `[]` `[compression_program]` - The SPL Account Compression program's id (draw tree only).
+    /// - `[]` `[noop_program]` - The SPL Noop program's id (draw tree only).
+    /// - `[w]` `[leaderboard]` - The winners leaderboard ([BondConfig]`.leaderboard`, only if 
+    ///     set).
+    /// - `[]` `[instructions]` - The Instructions sysvar (only if 
+    ///     [BondConfig]`.attestation_signer`, [BondConfig]`.isolate_draws` or 
+    ///     [BondConfig]`.oracle` is set).
+    /// - `[]` `[exclusion_list]` - The accounts excluded from all draws ([BondExclusionList], only 
+    ///     if `receiver` isn't `jackpot`).
+    /// - `[]` `[validation]` - The receiver's completed validation ([BondExclusionValidation], 
+    ///     only if `receiver` isn't `jackpot` and [BondConfig]`.require_exclusion_validation` is 
+    ///     set).
+    /// - `[]` `[snapshot]` - The draw's sealed holder snapshot ([BondSnapshot], only if 
+    ///     [BondConfig]`.snapshot_draws` is set).
+    /// - `[]` `[buffer]` - The snapshot's [BondParticipantBuffer] holding the selected point 
+    ///     (only if the snapshot has buffers).
+    /// - `[w]` `[jackpot_mints]` - The jackpot's additional mints ([BondJackpotMints], only if 
+    ///     [BondConfig]`.jackpot_mints` is set, writable for escrowed draws), followed by each 
+    ///     mint's accounts:
+    ///     - `[]` `[mint]` - The additional mint.
+    ///     - `[]` `[mint_token_program]` - The id of the Token or Token-2022 Program owning 
+    ///         `mint`.
+    ///     - `[w]` `[jackpot_mint_ata]` - The associated token address of `jackpot` for `mint`.
+    ///     - `[w]` `[receiver_mint_ata]` - The associated token address for `mint` of the owner 
+    ///         of `receiver_ata` (unused by escrowed draws).
+    /// 
+    /// The additional mints' balances, excluding their unclaimed prizes, are recorded as the 
+    /// draw's `extra_prizes` and paid along with a transferred jackpot. Escrowed 
+    /// ([BondPayoutMode::Claim]) draws reserve them in [BondJackpotMints] until `ClaimPrize` or 
+    /// `ExpireClaim`, and rollovers leave them in the jackpot.
+    /// 
+    /// The operator may attest to the draw's off-chain selection inputs with an Ed25519 program 
+    /// instruction in the same transaction, signed by [BondConfig]`.attestation_signer` over 
+    /// [`config`, `draw_seed` (u64 LE), inputs...]. The message's keccak hash is recorded as the 
+    /// draw's `attestation` (required if [BondConfig]`.require_attestation` is set).
+    /// 
+    /// If [BondConfig]`.oracle` is set, the draw's result must be signed by the oracle with an 
+    /// Ed25519 program instruction in the same transaction over [`config`, `draw_seed` (u64 LE), 
+    /// `receiver`, `receiver_seed` (u64 LE)], otherwise the draw is rejected with 
+    /// `InvalidOracleSignature`.
+    /// 
+    /// If [BondConfig]`.snapshot_draws` is set, `receiver` must be the sealed [BondSnapshot]'s 
+    /// entry selected by the draw's `entropy`, held by the snapshot or one of its buffers (see 
+    /// [BondSnapshot::select] and [BondParticipantBuffer::select], `jackpot` if the snapshot has 
+    /// no weight), otherwise the draw is rejected with `ReceiverNotSelected`. The weights must 
+    /// match the selected entry and the snapshot's total weight.
+    /// Snapshots posted as a merkle root (`PostSnapshotRoot`) select the [BondSnapshotLeaf] 
+    /// proven by `proof` instead, rejected with `InvalidSnapshotProof`.
+    /// 
+    /// `receiver_seed` is mixed with the most recent slot hash into the draw's recorded 
+    /// `entropy`. As the slot hash is known to the draw authority when submitting the draw, 
+    /// snapshot draws are only run with `FulfillDraw`, whose VRF result selects the receiver 
+    /// (its first 8 bytes, u64 LE). `PreviewDraw` simulates them from the slot hash.
+    /// 
+    /// Rejected with `VrfRequired` if [BondConfig]`.require_vrf` or [BondConfig]`.snapshot_draws` 
+    /// is set (see `FulfillDraw`), and with `DrawTooSoon` until [BondConfig]`.epochs_per_draw` 
+    /// epochs have passed since [BondState]`.last_draw_epoch` (see [BondState::next_draw_epoch]).
+    /// 
+    /// Excluded receivers are rejected with `ExcludedReceiver`. Exclusion lists too large to be 
+    /// read by the draw must be checked beforehand with `ValidateReceiverChunk`.
+    /// 
+    /// If [BondConfig]`.isolate_draws` is set, the draw must be a top-level instruction of a 
+    /// transaction of at most [crate::constants::MAX_DRAW_TRANSACTION_INSTRUCTIONS], whose other 
+    /// instructions belong to the Compute Budget or Ed25519 programs, so that the selection can't 
+    /// be bundled with balance manipulations.
+    /// 
+    /// The receiver's odds (`receiver_weight / total_weight`) may not exceed the odds threshold 
+    /// ([BondConfig]`.odds_threshold_numerator` / `odds_threshold_denominator`), and 
+    /// `receiver_weight` must match the balance of `receiver_ata` unless the game has a ticket 
+    /// ledger. Both weights are recorded in the [BondDraw].
+    /// 
+    /// ## Data
+    /// - `[receiver_seed]` - The randomly generated value used to select the winner.
+    /// - `[draw_seed]` - The draw's id (the previous draw id + 1).
+    /// - `[receiver_weight]` - The receiver's weight at selection.
+    /// - `[total_weight]` - The total eligible weight at selection.
+    /// - `[proof]` - The receiver's leaf proof (only for a snapshot with a merkle root).
     Draw {
         receiver_seed: u64,
         draw_seed: u64,
-    },
+        receiver_weight: u64,
+        total_weight: u64,
+        proof: Option<SnapshotProof>,
+    } = 8,
+
+    /// Returns the deployed program's [BondVersion] via `set_return_data`.
+    /// 
+    /// ## Accounts
+    /// None.
+    GetVersion = 9,
+
+    /// Pays an unclaimed jackpot to the winner of a draw run in [BondPayoutMode::Claim] mode.
+    /// 
+    /// Games with a ticket ledger must use `ClaimPrizeWithTicket` instead. The claim is 
+    /// authorized by `receiver` or its session key alone, so any signer (e.g. a relayer) may pay 
+    /// the transaction's fees.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[receiver]` - The draw's winning account (signer unless claiming with a session, 
+    ///     writable when claiming in [BondPayoutAsset::Sol] or [BondPayoutAsset::NativeSol]).
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]).
+    /// - `[]` `[jackpot]` - The jackpot account ([BondShare]).
+    /// - `[w]` `[jackpot_ata]` - The associated token address of `jackpot` for `token_mint`.
+    /// - `[w]` `[draw]` - The winning draw ([BondDraw]).
+    /// - `[w]` `[receiver_ata]` - Any token account owned by `receiver` for `token_mint` (a 
+    ///     temporary wrapped SOL account, closed into `receiver` when claiming in 
+    ///     [BondPayoutAsset::NativeSol]).
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[s]` `[session_key]` - The receiver's session key (only if `receiver` doesn't sign, 
+    ///     which [BondPayoutAsset::NativeSol] requires).
+    /// - `[]` `[session]` - The receiver's session (PDA of [`config`, [BondSeed::Session], 
+    ///     `receiver`], only if `receiver` doesn't sign).
+    /// - `[w]` `[hold]` - The receiver's compliance hold (PDA of [`config`, [BondSeed::Hold], 
+    ///     `receiver`], only if [BondConfig]`.compliance_authority` is set). Prizes of held 
+    ///     receivers are escrowed until `ReleaseHold`.
+    /// - `[w]` `[leaderboard]` - The winners leaderboard ([BondConfig]`.leaderboard`, only if 
+    ///     set).
+    /// - `[w]` `[jackpot_mints]` - The jackpot's additional mints ([BondJackpotMints], only if 
+    ///     the draw has `extra_prizes`), followed by each prize's accounts:
+    ///     - `[]` `[mint]` - The prize's mint.
+    ///     - `[]` `[mint_token_program]` - The id of the Token or Token-2022 Program owning 
+    ///         `mint`.
+    ///     - `[w]` `[jackpot_mint_ata]` - The associated token address of `jackpot` for `mint`.
+    ///     - `[w]` `[receiver_mint_ata]` - The associated token address for `mint` of the owner 
+    ///         of `receiver_ata`.
+    /// - `[]` `[pool_program]` - The Stake Pool program's id (followed by the accounts below, 
+    ///     only if the draw's `payout_asset` is [BondPayoutAsset::Sol]).
+    /// - `[w]` `[pool]` - The Stake Pool.
+    /// - `[]` `[withdraw_authority]` - The Stake Pool's withdraw authority.
+    /// - `[w]` `[reserve_stake]` - The Stake Pool's reserve stake account.
+    /// - `[w]` `[manager_fee]` - The Stake Pool's manager fee account.
+    /// - `[]` `[clock]` - The Clock sysvar.
+    /// - `[]` `[stake_history]` - The Stake History sysvar.
+    /// - `[]` `[stake_program]` - The Stake program's id.
+    /// 
+    /// The prize is paid in the asset chosen by the winner with `SetPayoutAsset` (the Stake Pool's 
+    /// tokens by default), and the draw's `extra_prizes` in their mints. Rejected with 
+    /// `ClaimExpired` once [BondConfig]`.claim_window` has passed since the draw, and with 
+    /// `PrizeHeld` for held receivers of `extra_prizes`, until `ReleaseHold`.
+    ClaimPrize = 10,
+
+    /// Sets the game's display name and metadata URI.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// 
+    /// ## Data
+    /// - `[name]` - The display name (UTF-8, zero padded to [MAX_NAME_LEN] bytes).
+    /// - `[uri]` - The off-chain metadata URI (max [MAX_URI_LEN] bytes).
+    SetMetadata {
+        name: [u8; MAX_NAME_LEN],
+        uri: String,
+    } = 11,
+
+    /// Ends the current season, preventing any further splits or draws, and deactivates the game.
+    /// 
+    /// Rejected with `DrawNotSettled` while randomness is pending (see `RequestRandomness`) or if 
+    /// the latest draw's prize is unclaimed, until it's claimed or released with `ExpireClaim`.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]).
+    /// - `[]` `[draw]` - The latest draw ([BondDraw]), unless the game logs its draws to a tree 
+    ///     or hasn't drawn yet.
+    CloseSeason = 12,
+
+    /// Archives the current season's state and stats with its first and last draw ids, and starts 
+    /// the next season, resetting the rollover counter and reactivating the game. Draw ids 
+    /// continue from the previous season.
+    /// 
+    /// Rejected with `SeasonNotClosed` unless the season was closed with `CloseSeason`.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]).
+    /// - `[w]` `[season_archive]` - The archive of the current season (PDA of [`config`, 
+    ///     [BondSeed::Season], `season`]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondSeasonArchive]'s PDA bump seed.
+    /// - `[starts_at]` - The next season's start time.
+    /// - `[ends_at]` - The next season's end time.
+    RolloverSeason {
+        bump: u8,
+        starts_at: Option<i64>,
+        ends_at: Option<i64>,
+    } = 13,
+
+    /// Removes and then adds accounts to the exclusion list in a single instruction.
+    /// 
+    /// Fails with `DuplicateExclusion` if an added account is already excluded and with 
+    /// `ExclusionListFull` if the list's capacity is exceeded. Removing an account that isn't 
+    /// excluded has no effect.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[exclusion_list]` - The accounts excluded from all draws ([BondExclusionList]).
+    /// 
+    /// ## Data
+    /// - `[add]` - The accounts to exclude.
+    /// - `[remove]` - The accounts to make eligible again.
+    /// - `[expires_at_epoch]` - The epoch from which the added accounts are eligible again 
+    ///     (permanent exclusions if none).
+    UpdateExclusionList {
+        add: Vec<Pubkey>,
+        remove: Vec<Pubkey>,
+        expires_at_epoch: Option<u64>,
+    } = 14,
+
+    /// Appends a chunk of accounts to the exclusion list, so that lists too large for a single 
+    /// transaction can be loaded across several before the game is activated.
+    /// 
+    /// Chunks must be appended in order starting at 0. Re-sending an already appended chunk has 
+    /// no effect, so a failed upload can be resumed.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[exclusion_list]` - The accounts excluded from all draws ([BondExclusionList]).
+    /// 
+    /// ## Data
+    /// - `[chunk_index]` - The chunk's position in the upload (the list's `chunks` count).
+    /// - `[accounts]` - The accounts to exclude.
+    AppendExclusionChunk {
+        chunk_index: u32,
+        accounts: Vec<Pubkey>,
+    } = 15,
+
+    /// Runs the epoch fee crank of the game's [BondLstAdapter] so that the epoch fee is final, and 
+    /// records the epoch on [BondFee]. `SplitShares` is rejected until this has run in the 
+    /// current epoch.
+    /// 
+    /// An SPL stake pool's validator list must already be updated for the epoch.
+    /// 
+    /// ## Accounts
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[fee]` - The Stake Pool's epoch fee receiver ([BondFee]).
+    /// - `[w]` `[fee_ata]` - The associated token address of `fee` for `token_mint` (the pool's 
+    ///     fee account).
+    /// - `[w]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[pool_program]` - The adapter's program id.
+    /// - `[w]` `[pool]` - The pool's state account.
+    /// - `..` - The adapter's accounts. For [BondLstAdapter::SplStakePool]: `[]` 
+    ///     `[withdraw_authority]`, `[w]` `[validator_list]` and `[]` `[reserve_stake]`.
+    UpdateStakePool = 16,
+
+    /// Swaps part of the treasury's pool tokens into another asset held by the treasury PDA 
+    /// through a route of one of the [SWAP_PROGRAM_IDS] built off chain.
+    /// 
+    /// The treasury's output balance must grow by at least `min_amount_out` and its pool token 
+    /// balance may shrink by at most `amount_in`, otherwise the swap fails with 
+    /// `SlippageExceeded`. The treasury's lamports and any other of its token accounts passed to 
+    /// the route may not decrease, otherwise the swap fails with `TreasuryBalanceDecreased`.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[treasury]` - The treasury account ([BondShare]).
+    /// - `[w]` `[treasury_ata]` - The associated token address of `treasury` for `token_mint`.
+    /// - `[w]` `[output_ata]` - A token account owned by `treasury` for the output asset.
+    /// - `[]` `[swap_program]` - The swap program's id (one of [SWAP_PROGRAM_IDS]).
+    /// - `..` - The route's accounts, in the order expected by `route_data`.
+    /// 
+    /// ## Data
+    /// - `[amount_in]` - The maximum amount of pool tokens to swap.
+    /// - `[min_amount_out]` - The minimum amount of the output asset to receive.
+    /// - `[route_data]` - The Jupiter instruction data.
+    SwapTreasury {
+        amount_in: u64,
+        min_amount_out: u64,
+        route_data: Vec<u8>,
+    } = 17,
+
+    /// Runs whichever scheduled steps are due and does nothing otherwise, so that an automation 
+    /// network can call it repeatedly (see [crank_tick]).
+    /// 
+    /// 1. Harvests the epoch fee (`UpdateStakePool`) if it hasn't run in the current epoch.
+    /// 2. Splits the epoch fee (`SplitShares`) if `fee_ata` holds a balance, the season is open 
+    ///     and `cranker` may split.
+    /// 3. Requests the next draw's randomness (`RequestRandomness`) if the randomness accounts 
+    ///     are provided, the draw is due ([BondConfig]`.epochs_per_draw`), no request is pending, 
+    ///     its snapshot is sealed (`FinalizeSnapshot`), the season is open and `cranker` may draw 
+    ///     (the draw authority, or anyone if [BondConfig]`.allow_permissionless_draw` is set).
+    /// 
+    /// The draw is then run by `FulfillDraw`, which isn't part of the tick since its accounts 
+    /// depend on the receiver selected by the verified result.
+    /// 
+    /// ## Accounts
+    /// - `0..16` - The accounts of `SplitShares`, with `cranker` as the authority and the 
+    ///     `authority_ata` and `state_ata` always provided.
+    /// - `[]` `[pool_program]` - The adapter's program id.
+    /// - `[w]` `[pool]` - The pool's state account.
+    /// - `..` - The adapter's accounts (see `UpdateStakePool`).
+    /// - `[]` `[snapshot]` - The next draw's snapshot ([BondSnapshot]), which may not be written 
+    ///     yet (optional with the following accounts).
+    /// - `..` - The accounts of `RequestRandomness` from `vrf`, with `cranker` or the automation 
+    ///     network's wallet as `payer_authority`.
+    /// 
+    /// ## Data
+    /// - `[permission_bump]` - The bump seed of the VRF account's `permission`.
+    /// - `[program_state_bump]` - The bump seed of the Switchboard `program_state`.
+    CrankTick {
+        permission_bump: u8,
+        program_state_bump: u8,
+    } = 18,
+
+    /// Creates an Address Lookup Table holding the game's program, config, PDAs, ATAs, token mint 
+    /// and token program, and stores its address in [BondConfig]`.lookup_table`.
+    /// 
+    /// The table's authority is the `state` PDA, so it can only be extended through 
+    /// `ExtendLookupTable`.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]), the table's authority.
+    /// - `[w]` `[lookup_table]` - The new table (PDA of [`state`, `recent_slot`] under the Address 
+    ///     Lookup Table program).
+    /// - `[]` `[lookup_table_program]` - The Address Lookup Table program's id.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// - `[]` `[token_mint]` - The game's token mint, whose owner is the token program added to 
+    ///     the table.
+    /// 
+    /// ## Data
+    /// - `[recent_slot]` - A recent slot used to derive `lookup_table`.
+    /// - `[bump]` - `lookup_table`'s bump seed.
+    CreateLookupTable {
+        recent_slot: u64,
+        bump: u8,
+    } = 19,
+
+    /// Appends addresses (e.g. the pool's accounts) to the game's Address Lookup Table.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]), the table's authority.
+    /// - `[w]` `[lookup_table]` - The game's table ([BondConfig]`.lookup_table`).
+    /// - `[]` `[lookup_table_program]` - The Address Lookup Table program's id.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[addresses]` - The addresses to append.
+    ExtendLookupTable {
+        addresses: Vec<Pubkey>,
+    } = 20,
+
+    /// Initializes an SPL Account Compression merkle tree and logs all subsequent draws to it as 
+    /// leaves (the hash of the borsh serialized [BondDraw], logged in full through the noop 
+    /// program) instead of draw accounts. Not supported in [BondPayoutMode::Claim] mode.
+    /// 
+    /// `Draw` then takes the tree as its `draw` account, followed by the compression and noop 
+    /// programs.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]), the tree's authority.
+    /// - `[w]` `[draw_tree]` - An account allocated for the tree and owned by the compression 
+    ///     program.
+    /// - `[]` `[compression_program]` - The SPL Account Compression program's id.
+    /// - `[]` `[noop_program]` - The SPL Noop program's id.
+    /// 
+    /// ## Data
+    /// - `[max_depth]` - The tree's depth (the history holds up to `2^max_depth` draws).
+    /// - `[max_buffer_size]` - The tree's concurrent change buffer size.
+    CreateDrawTree {
+        max_depth: u32,
+        max_buffer_size: u32,
+    } = 21,
+
+    /// Initializes an SPL Account Compression merkle tree as the game's ticket ledger. Tickets are 
+    /// then issued as compressed [BondTicket] leaves and prize claims must prove the winner's 
+    /// ticket with `ClaimPrizeWithTicket`.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]), the tree's authority.
+    /// - `[w]` `[ticket_tree]` - An account allocated for the tree and owned by the compression 
+    ///     program.
+    /// - `[]` `[compression_program]` - The SPL Account Compression program's id.
+    /// - `[]` `[noop_program]` - The SPL Noop program's id.
+    /// 
+    /// ## Data
+    /// - `[max_depth]` - The tree's depth (the ledger holds up to `2^max_depth` tickets).
+    /// - `[max_buffer_size]` - The tree's concurrent change buffer size.
+    CreateTicketTree {
+        max_depth: u32,
+        max_buffer_size: u32,
+    } = 22,
+
+    /// Appends tickets to the game's ticket ledger, updating its root on chain, and mints each 
+    /// ticket's weight in receipt tokens to its wallet if the game has a receipt mint.
+    /// 
+    /// The tickets' `issued_at` is set to the current time, so that winners are selected by 
+    /// [BondTicket::time_weighted_weight] over the draw window rather than by spot balance, and 
+    /// their `rebate_per_weight` to the state's, so that they only earn subsequent rebates.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]), the tree's authority.
+    /// - `[w]` `[ticket_tree]` - The game's ticket ledger ([BondConfig]`.ticket_tree`).
+    /// - `[]` `[compression_program]` - The SPL Account Compression program's id.
+    /// - `[]` `[noop_program]` - The SPL Noop program's id.
+    /// - `[w]` `[receipt_mint]` - The game's receipt mint ([BondConfig]`.receipt_mint`, receipt 
+    ///     mint only).
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `receipt_mint` 
+    ///     (receipt mint only).
+    /// - `..` - A receipt token account owned by each ticket's wallet, in the order of `tickets` 
+    ///     (receipt mint only).
+    /// 
+    /// ## Data
+    /// - `[tickets]` - The tickets to issue.
+    IssueTickets {
+        tickets: Vec<BondTicket>,
+    } = 23,
+
+    /// Pays an unclaimed jackpot to the winner of a draw after verifying the winner's ticket in 
+    /// the game's ticket ledger and that it was held during the draw's epoch.
+    /// 
+    /// ## Accounts
+    /// - `0..8` - The accounts of `ClaimPrize`.
+    /// - `[s]` `[session_key]` - The receiver's session key (see `ClaimPrize`).
+    /// - `[]` `[session]` - The receiver's session (see `ClaimPrize`).
+    /// - `[w]` `[hold]` - The receiver's compliance hold (see `ClaimPrize`).
+    /// - `[w]` `[leaderboard]` - The winners leaderboard (see `ClaimPrize`).
+    /// - `..` - The accounts of the draw's `extra_prizes` (see `ClaimPrize`).
+    /// - `..` - The Stake Pool's accounts for [BondPayoutAsset::Sol] (see `ClaimPrize`).
+    /// - `[]` `[compression_program]` - The SPL Account Compression program's id.
+    /// - `[]` `[ticket_tree]` - The game's ticket ledger ([BondConfig]`.ticket_tree`).
+    /// - `..` - The ticket's proof nodes.
+    /// 
+    /// ## Data
+    /// - `[ticket_proof]` - The ticket's weight, leaf index and the ledger's root ([TicketProof]).
+    ClaimPrizeWithTicket(TicketProof) = 24,
+
+    /// Publishes a draw's result ([BondDrawMessage]) as a finalized Wormhole message, so that 
+    /// deployments on other chains can mirror results or deliver bridged prizes. Each draw can 
+    /// be published once.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer, also paying the Wormhole message fee.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[draw]` - The draw to publish ([BondDraw]).
+    /// - `[]` `[emitter]` - The game's emitter (PDA of [`config`, [BondSeed::Emitter]]).
+    /// - `[w]` `[message]` - The message account (PDA of [`config`, [BondSeed::Message], 
+    ///     `draw.id`]).
+    /// - `[w]` `[bridge]` - The Wormhole bridge config.
+    /// - `[w]` `[sequence]` - The emitter's Wormhole sequence account.
+    /// - `[w]` `[fee_collector]` - The Wormhole fee collector.
+    /// - `[]` `[clock]` - The Clock sysvar.
+    /// - `[]` `[rent]` - The Rent sysvar.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// - `[]` `[wormhole_program]` - The Wormhole core bridge's id.
+    /// 
+    /// ## Data
+    /// - `[nonce]` - The Wormhole message nonce.
+    /// - `[emitter_bump]` - `emitter`'s PDA bump seed.
+    /// - `[message_bump]` - `message`'s PDA bump seed.
+    PublishDrawMessage {
+        nonce: u32,
+        emitter_bump: u8,
+        message_bump: u8,
+    } = 25,
+
+    /// Fails with [BondError::InvariantViolated] if any cross-account invariant of the game 
+    /// doesn't hold, so that it can be sent as a canary by monitoring transactions.
+    /// 
+    /// - The shares sum up to at most 100%.
+    /// - `jackpot_ata` holds at least the prizes reserved for unclaimed draws.
+    /// - `fee_ata` holds at least the remainder retained by the latest split.
+    /// - `draw` is the latest draw (`state.draw_id`), and its prize is reserved if unclaimed.
+    /// - The receipt mint's supply doesn't exceed the issued ticket weight.
+    /// 
+    /// ## Accounts
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state (PDA of [`config`, [BondSeed::State]]).
+    /// - `[]` `[equity]` - The equity share (PDA of [`config`, [BondSeed::Equity]]).
+    /// - `[]` `[treasury]` - The treasury share (PDA of [`config`, [BondSeed::Treasury]]).
+    /// - `[]` `[jackpot]` - The jackpot share (PDA of [`config`, [BondSeed::Jackpot]]).
+    /// - `[]` `[jackpot_ata]` - The associated token address of `jackpot` for `token_mint`.
+    /// - `[]` `[stake]` - The stake share (PDA of [`config`, [BondSeed::Stake]]).
+    /// - `[]` `[fee]` - The Stake Pool's epoch fee receiver (PDA of [`config`, [BondSeed::Fee]]).
+    /// - `[]` `[fee_ata]` - The associated token address of `fee` for `token_mint`.
+    /// - `[]` `[draw]` - The latest draw ([BondDraw]), unless the game logs its draws to a tree 
+    ///     or hasn't drawn yet.
+    /// - `[]` `[receipt_mint]` - The game's receipt mint ([BondConfig]`.receipt_mint`, receipt 
+    ///     mint only).
+    AssertInvariants = 26,
+
+    /// Runs the validation and winner selection of a Bond draw without mutating any account and 
+    /// returns the would-be [BondDraw] as return data (nothing is returned if the jackpot is 
+    /// empty), for operator previews via `simulateTransaction`.
+    /// 
+    /// ## Accounts
+    /// - `..` - The accounts of `Draw`.
+    /// 
+    /// ## Data
+    /// - `[receiver_seed]` - The randomly generated value used to select the winner.
+    /// - `[draw_seed]` - The draw's id (the previous draw id + 1).
+    /// - `[receiver_weight]` - The receiver's weight at selection.
+    /// - `[total_weight]` - The total eligible weight at selection.
+    /// - `[proof]` - The receiver's leaf proof (only for a snapshot with a merkle root).
+    PreviewDraw {
+        receiver_seed: u64,
+        draw_seed: u64,
+        receiver_weight: u64,
+        total_weight: u64,
+        proof: Option<SnapshotProof>,
+    } = 27,
+
+    /// Registers a wallet as eligible to win, acknowledging the game's terms and linking the 
+    /// owner of its payout token accounts. Registrations can't be changed.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer (e.g. a relayer).
+    /// - `[s]` `[wallet]` - The wallet to register.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[participant]` - The wallet's registration (PDA of [`config`, 
+    ///     [BondSeed::Participant], `wallet`]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondParticipant]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondParticipant] account.
+    /// - `[payout]` - The owner of the token accounts that receive the wallet's prizes.
+    /// - `[terms_hash]` - The hash of the acknowledged terms (e.g. of the document at 
+    ///     [BondConfig]`.uri`).
+    RegisterParticipant {
+        bump: u8,
+        space: u32,
+        payout: Pubkey,
+        terms_hash: [u8; 32],
+    } = 28,
+
+    /// Places a compliance hold on a wallet, escrowing the prizes it claims until `ReleaseHold`.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[compliance_authority]` - The game's compliance authority, paying for the hold.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[wallet]` - The wallet to hold.
+    /// - `[w]` `[hold]` - The wallet's hold (PDA of [`config`, [BondSeed::Hold], `wallet`]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondHold]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondHold] account.
+    PlaceHold {
+        bump: u8,
+        space: u32,
+    } = 29,
+
+    /// Lifts a compliance hold, paying its escrowed prizes to the held wallet or rerouting them 
+    /// to another account, and closes the hold.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[authority]` - The game's main authority, receiving the hold's rent.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]).
+    /// - `[]` `[jackpot]` - The jackpot account ([BondShare]).
+    /// - `[w]` `[jackpot_ata]` - The associated token address of `jackpot` for `token_mint`.
+    /// - `[w]` `[hold]` - The hold to lift ([BondHold]).
+    /// - `[w]` `[destination_ata]` - A token account for `token_mint` owned by the held wallet, 
+    ///     or any token account if `reroute` is set.
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// 
+    /// ## Data
+    /// - `[reroute]` - Whether or not the escrowed prizes may be paid to another wallet.
+    ReleaseHold {
+        reroute: bool,
+    } = 30,
+
+    /// Approves an additional token mint for the jackpot (e.g. sponsor contributions in USDC) and 
+    /// creates the jackpot's associated token account for it.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[jackpot]` - The jackpot account ([BondShare]).
+    /// - `[w]` `[jackpot_mints]` - The jackpot's additional mints (PDA of [`config`, 
+    ///     [BondSeed::JackpotMints]]), created on the first registration.
+    /// - `[]` `[mint]` - The mint to register.
+    /// - `[w]` `[jackpot_mint_ata]` - The associated token address of `jackpot` for `mint`.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `mint`.
+    /// - `[]` `[associated_token_program]` - The Associated Token Program's id.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondJackpotMints]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondJackpotMints] account (with room for 
+    ///     [MAX_JACKPOT_MINTS]).
+    RegisterJackpotMint {
+        bump: u8,
+        space: u32,
+    } = 31,
+
+    /// Registers a session key that may claim prizes on a wallet's behalf until it expires, 
+    /// replacing the wallet's previous session key.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer (e.g. a relayer).
+    /// - `[s]` `[wallet]` - The wallet delegating its claims.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[session]` - The wallet's session (PDA of [`config`, [BondSeed::Session], 
+    ///     `wallet`]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondSession]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondSession] account.
+    /// - `[session_key]` - The key that may sign on behalf of `wallet`.
+    /// - `[expires_at]` - The unix timestamp from which the session key is rejected.
+    CreateSession {
+        bump: u8,
+        space: u32,
+        session_key: Pubkey,
+        expires_at: i64,
+    } = 32,
+
+    /// Creates the game's receipt mint, whose tokens are minted 1:1 with the weight of issued 
+    /// tickets (and burned by `RedeemTicket`) so that ticket positions are visible in wallets and 
+    /// composable with other protocols. The mint authority is the `state` PDA.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]), the mint authority.
+    /// - `[w]` `[receipt_mint]` - The receipt mint (PDA of [`config`, [BondSeed::Receipt]]).
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program to create the mint 
+    ///     with.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - `receipt_mint`'s PDA bump seed.
+    CreateReceiptMint {
+        bump: u8,
+    } = 33,
+
+    /// Creates the game's winners leaderboard, which `Draw` and `ClaimPrize` then update with 
+    /// each paid prize.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[leaderboard]` - The leaderboard (PDA of [`config`, [BondSeed::Leaderboard]]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondLeaderboard]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondLeaderboard] account (with room for 
+    ///     [MAX_LEADERBOARD_ENTRIES]).
+    CreateLeaderboard {
+        bump: u8,
+        space: u32,
+    } = 34,
+
+    /// Sets the smallest portion of the epoch fee that `SplitShares` transfers to a share. Smaller 
+    /// portions stay in the fee account until a later split.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[share]` - The share to update ([BondShare]).
+    /// 
+    /// ## Data
+    /// - `[min_transfer]` - The minimum transfer amount (0 transfers every portion).
+    SetMinTransfer {
+        min_transfer: u64,
+    } = 35,
+
+    /// Locks `rate * duration` treasury tokens in a stream that vests linearly to `recipient` 
+    /// (e.g. contributor compensation), revocable by the game's authority.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]), counting the open streams.
+    /// - `[]` `[treasury]` - The treasury account ([BondShare]).
+    /// - `[w]` `[treasury_ata]` - The associated token address of `treasury` for `token_mint`.
+    /// - `[w]` `[stream]` - The recipient's stream (PDA of [`config`, [BondSeed::Stream], 
+    ///     `recipient`]).
+    /// - `[w]` `[stream_ata]` - The associated token address of `stream` for `token_mint`.
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[associated_token_program]` - The Associated Token Program's id.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondStream]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondStream] account.
+    /// - `[recipient]` - The wallet the stream pays.
+    /// - `[rate]` - The amount vested per second.
+    /// - `[duration]` - The stream's duration in seconds.
+    CreateStream {
+        bump: u8,
+        space: u32,
+        recipient: Pubkey,
+        rate: u64,
+        duration: u64,
+    } = 36,
+
+    /// Withdraws a stream's vested tokens to its recipient.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[recipient]` - The stream's recipient.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[stream]` - The recipient's stream ([BondStream]).
+    /// - `[w]` `[stream_ata]` - The associated token address of `stream` for `token_mint`.
+    /// - `[w]` `[recipient_ata]` - A token account owned by `recipient` for `token_mint`.
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    WithdrawStream = 37,
+
+    /// Revokes a stream, paying its vested tokens to the recipient and returning the rest to the 
+    /// treasury, and closes the stream.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[authority]` - The game's main authority, receiving the stream's rent.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]), counting the open streams.
+    /// - `[w]` `[stream]` - The stream to revoke ([BondStream]).
+    /// - `[w]` `[stream_ata]` - The associated token address of `stream` for `token_mint`.
+    /// - `[w]` `[recipient_ata]` - A token account owned by the recipient for `token_mint`.
+    /// - `[]` `[treasury]` - The treasury account ([BondShare]).
+    /// - `[w]` `[treasury_ata]` - The associated token address of `treasury` for `token_mint`.
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    RevokeStream = 38,
+
+    /// Pays the rebates accrued by a ticket since its issue or latest claim to its wallet.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[wallet]` - The ticket's wallet, paying for the claim account.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]).
+    /// - `[w]` `[state_ata]` - The associated token address of `state` for `token_mint`.
+    /// - `[w]` `[wallet_ata]` - A token account owned by `wallet` for `token_mint`.
+    /// - `[w]` `[rebate_claim]` - The ticket's claim (PDA of [`config`, [BondSeed::Rebate], 
+    ///     [BondRebateClaim::seed_index] of the ticket's leaf and index]).
+    /// - `[]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// - `[]` `[compression_program]` - The SPL Account Compression program's id.
+    /// - `[]` `[ticket_tree]` - The game's ticket ledger ([BondConfig]`.ticket_tree`).
+    /// - `..` - The ticket's proof nodes.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondRebateClaim]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondRebateClaim] account.
+    /// - `[ticket_proof]` - The ticket's proof ([TicketProof]).
+    ClaimRebate {
+        bump: u8,
+        space: u32,
+        ticket_proof: TicketProof,
+    } = 39,
+
+    /// Chooses the asset an escrowed prize is claimed in ([BondPayoutMode::Claim]).
+    /// 
+    /// [BondPayoutAsset::Sol] requires an SPL stake pool and [BondPayoutAsset::NativeSol] a 
+    /// wrapped SOL token mint. Prizes paid by `Draw` itself are always paid in tokens.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[receiver]` - The draw's winning account.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[draw]` - The winning draw ([BondDraw]).
+    /// 
+    /// ## Data
+    /// - `[payout_asset]` - The asset to claim the prize in.
+    SetPayoutAsset {
+        payout_asset: BondPayoutAsset,
+    } = 40,
+
+    /// Compacts the exclusion list by removing its expired entries. Expired entries are already 
+    /// eligible, so anyone may prune them.
+    /// 
+    /// ## Accounts
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[exclusion_list]` - The accounts excluded from all draws ([BondExclusionList]).
+    PruneExpired = 41,
+
+    /// Checks the next draw's receiver against a chunk of the exclusion list, recording the 
+    /// progress in a validation account. Lists too large to be read by a single instruction are 
+    /// validated across several instructions, which `Draw` requires to be complete if 
+    /// [BondConfig]`.require_exclusion_validation` is set. The validation restarts whenever the 
+    /// receiver, the draw, the epoch or the list's revision changes.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[draw_authority]` - The authority designated to run draws, paying for the 
+    ///     validation account.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]).
+    /// - `[]` `[exclusion_list]` - The accounts excluded from all draws ([BondExclusionList]).
+    /// - `[w]` `[validation]` - The validation's progress (PDA of [`config`, 
+    ///     [BondSeed::ExclusionValidation]]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondExclusionValidation]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondExclusionValidation] account.
+    /// - `[receiver]` - The account to validate.
+    /// - `[count]` - The maximum number of entries to check.
+    ValidateReceiverChunk {
+        bump: u8,
+        space: u32,
+        receiver: Pubkey,
+        count: u32,
+    } = 42,
+
+    /// Replaces a share's associated token account after a token mint or token program 
+    /// migration: sweeps the old account's balance, closes it and creates the share's associated 
+    /// token account for [BondConfig]`.token_mint` under the new token program.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[authority]` - The game's main authority, paying for the new account and 
+    ///     receiving the old account's rent.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[share]` - The share account ([BondShare]).
+    /// - `[w]` `[old_ata]` - The associated token address of `share` for `old_mint` under 
+    ///     `old_token_program`.
+    /// - `[]` `[old_mint]` - The previous token mint.
+    /// - `[]` `[old_token_program]` - The previous token program's id.
+    /// - `[w]` `[destination]` - A token account for `old_mint` receiving the old balance.
+    /// - `[w]` `[new_ata]` - The associated token address of `share` for `new_mint` under 
+    ///     `new_token_program`.
+    /// - `[]` `[new_mint]` - The game's token mint ([BondConfig]`.token_mint`).
+    /// - `[]` `[new_token_program]` - The id of the Token or Token-2022 Program owning `new_mint`.
+    /// - `[]` `[associated_token_program]` - The Associated Token Account program's id.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[seed]` - The share's seed.
+    RotateShareAta {
+        seed: BondSeed,
+    } = 43,
+
+    /// Upgrades a program account written with an older [crate::state::ACCOUNT_LAYOUT_VERSION] 
+    /// to the current layout, reallocating it to `space` bytes. Fields appended since its version 
+    /// are read as zeroed defaults. Accounts that are up to date are left unchanged. Accounts 
+    /// written before [crate::state::DISCRIMINATOR_LAYOUT_VERSION] are shifted behind their 
+    /// 8-byte discriminator, so `space` must exceed their size by at least 7 bytes.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[authority]` - The game's main authority, funding the additional rent.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[account]` - The game's account to migrate (may be `config`).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[space]` - The allocation size of the account in the current layout.
+    Migrate {
+        space: u32,
+    } = 44,
+
+    /// Records the keeper's latest runs in the game's heartbeat, created on the first call.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[draw_authority]` - The authority designated to run draws, paying for the 
+    ///     heartbeat account.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[heartbeat]` - The keeper's heartbeat (PDA of [`config`, 
+    ///     [BondSeed::Heartbeat]]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondHeartbeat]'s PDA bump seed.
+    /// - `[space]` - The allocation size of a [BondHeartbeat] account.
+    /// - `[runs]` - The bitmask of the runs completed by the keeper ([HEARTBEAT_HARVEST], 
+    ///     [HEARTBEAT_SPLIT], [HEARTBEAT_DRAW]), recorded at the current slot.
+    Heartbeat {
+        bump: u8,
+        space: u32,
+        runs: u8,
+    } = 45,
+
+    /// Pauses the game, rejecting every instruction that mutates it with `GameInactive` until 
+    /// `Resume`. The exemptions are:
+    /// - `Create*`, `Initialize` and `CreateAndInitialize*`, which set up the game's accounts.
+    /// - `ClaimPrize`, `ExpireClaim`, `RedeemTicket`, `ClaimRebate` and `WithdrawStream`, so 
+    ///     prizes and streams stay payable and unclaimed prizes return to the pool.
+    /// - `RegisterParticipant`, `CreateSession` and `SetPayoutAsset`, the participants' own 
+    ///     settings.
+    /// - `PlaceHold` and `ReleaseHold`, the compliance holds.
+    /// - `Migrate` and `RecoverTokens`.
+    /// - `GetVersion`, `PublishDrawMessage` and `AssertInvariants`, which don't mutate the game.
+    /// - `RolloverSeason`, `CloseLottoShare` and `CloseLotto`, which require an inactive game.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    Pause = 46,
+
+    /// Resumes a paused game.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    Resume = 47,
+
+    /// Updates the shares of the epoch fee, rejected with `InvalidShares` if they sum to more 
+    /// than 100%. The remainder stays in the fee account.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[equity]` - The shareholders account ([BondShare]).
+    /// - `[w]` `[treasury]` - The treasury account ([BondShare]).
+    /// - `[w]` `[jackpot]` - The jackpot account ([BondShare]).
+    /// - `[w]` `[stake]` - The locked stake ([BondShare]).
+    /// 
+    /// ## Data
+    /// - `[equity]` - The equity share.
+    /// - `[treasury]` - The treasury share.
+    /// - `[jackpot]` - The jackpot share.
+    /// - `[stake]` - The stake share.
+    SetShares {
+        equity: Ratio,
+        treasury: Ratio,
+        jackpot: Ratio,
+        stake: Ratio,
+    } = 48,
+
+    /// Adds accounts to the exclusion list, skipping accounts that are already excluded.
+    /// 
+    /// Fails with `ExclusionListFull` if the list's capacity is exceeded.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[exclusion_list]` - The accounts excluded from all draws ([BondExclusionList]).
+    /// 
+    /// ## Data
+    /// - `[accounts]` - The accounts to exclude.
+    /// - `[expires_at_epoch]` - The epoch from which the added accounts are eligible again 
+    ///     (permanent exclusions if none).
+    AddExcludedAccounts {
+        accounts: Vec<Pubkey>,
+        expires_at_epoch: Option<u64>,
+    } = 49,
+
+    /// Removes accounts from the exclusion list, skipping accounts that aren't excluded.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[exclusion_list]` - The accounts excluded from all draws ([BondExclusionList]).
+    /// 
+    /// ## Data
+    /// - `[accounts]` - The accounts to make eligible again.
+    RemoveExcludedAccounts {
+        accounts: Vec<Pubkey>,
+    } = 50,
+
+    /// Grows the exclusion list to `capacity` accounts, reallocating it and topping up its rent 
+    /// from `payer` (by at most 10 KiB per instruction).
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[exclusion_list]` - The accounts excluded from all draws ([BondExclusionList]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[capacity]` - The new maximum number of accounts (above the current capacity).
+    GrowExclusionList {
+        capacity: u32,
+    } = 51,
+
+    /// Requests the randomness of the next draw from a Switchboard VRF account whose authority 
+    /// is `state`, recording the request in [BondState] until `FulfillDraw` consumes its result.
+    /// 
+    /// Rejected with `VrfRequestPending` while a previous request is pending, unless the oracle 
+    /// hasn't verified it within 9,000 slots. Games must have [BondConfig]`.snapshot_draws` set 
+    /// (`SnapshotRequired`).
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[draw_authority]` - The authority designated to run draws.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]), the VRF account's authority.
+    /// - `[w]` `[vrf]` - The Switchboard VRF account.
+    /// - `[w]` `[oracle_queue]` - The VRF account's oracle queue.
+    /// - `[]` `[queue_authority]` - The oracle queue's authority.
+    /// - `[]` `[data_buffer]` - The oracle queue's data buffer.
+    /// - `[w]` `[permission]` - The VRF account's permission on the oracle queue.
+    /// - `[w]` `[escrow]` - The VRF account's escrow.
+    /// - `[w]` `[payer_wallet]` - The wrapped SOL token account paying the request.
+    /// - `[s]` `[payer_authority]` - The owner of `payer_wallet`.
+    /// - `[]` `[recent_blockhashes]` - The RecentBlockhashes sysvar.
+    /// - `[]` `[program_state]` - The Switchboard program state.
+    /// - `[]` `[token_program]` - The Token Program's id.
+    /// - `[]` `[switchboard_program]` - The Switchboard program's id.
+    /// 
+    /// ## Data
+    /// - `[permission_bump]` - The bump seed of `permission`.
+    /// - `[program_state_bump]` - The bump seed of `program_state`.
+    RequestRandomness {
+        permission_bump: u8,
+        program_state_bump: u8,
+    } = 52,
+
+    /// Runs a Bond draw from the verified result of the pending `RequestRandomness`. The first 
+    /// 8 bytes of the VRF result (u64 LE) are the draw's `receiver_seed`, which selects the 
+    /// receiver from the draw's sealed [BondSnapshot] (see `Draw`). Rollovers (`receiver` is 
+    /// `jackpot`) are only accepted without any eligible weight.
+    /// 
+    /// Games must have [BondConfig]`.snapshot_draws` set (`SnapshotRequired`), so that the 
+    /// receiver is selected on chain rather than by the caller.
+    /// 
+    /// If [BondConfig]`.allow_permissionless_draw` is set for a game with 
+    /// [BondConfig]`.snapshot_draws`, any signer may run the draw in place of `draw_authority` 
+    /// once it's due, and is paid [BondConfig]`.crank_fee_bps` of the jackpot.
+    /// 
+    /// ## Accounts
+    /// - `[]` `[vrf]` - The Switchboard VRF account recorded by `RequestRandomness`.
+    /// - `..` - The accounts of `Draw`.
+    /// - `[w]` `[draw_authority_ata]` - A token account owned by `draw_authority` that receives 
+    ///     the crank fee (only required for permissionless callers when the fee is non-zero).
+    /// 
+    /// ## Data
+    /// - `[draw_seed]` - The draw's id (the previous draw id + 1).
+    /// - `[receiver_weight]` - The receiver's weight at selection.
+    /// - `[total_weight]` - The total eligible weight at selection.
+    /// - `[proof]` - The receiver's leaf proof (only for a snapshot with a merkle root).
+    FulfillDraw {
+        draw_seed: u64,
+        receiver_weight: u64,
+        total_weight: u64,
+        proof: Option<SnapshotProof>,
+    } = 53,
+
+    /// Appends holder weights to the snapshot of the next draw, creating it on the first write 
+    /// and reallocating it from `draw_authority` (by at most 10 KiB per instruction). Each 
+    /// holder's balance is weighted by the portion of the snapshot's draw window (from the start 
+    /// of the epoch in which the snapshot was created until its creation) during which it was 
+    /// held, so that balances acquired just before a draw barely count. Rejected with 
+    /// `InsufficientWeight` if a holder's balance is below the snapshot's `min_balance` 
+    /// ([BondConfig]`.min_eligible_weight` when the snapshot was created), with `SnapshotLocked` 
+    /// while randomness is pending (see `RequestRandomness`) or once the snapshot is sealed, and 
+    /// with `InvalidSnapshot` if the snapshot's entries are held by buffers (see 
+    /// `WriteParticipants`).
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[draw_authority]` - The authority designated to run draws, paying for the 
+    ///     snapshot.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]).
+    /// - `[w]` `[snapshot]` - The next draw's snapshot (PDA of [`config`, [BondSeed::Snapshot], 
+    ///     draw id (u64 LE)]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondSnapshot]'s PDA bump seed.
+    /// - `[entries]` - The holders, their balances and the timestamps since which they have held 
+    ///     them, appended with their time-weighted weights over the snapshot's draw window.
+    WriteSnapshot {
+        bump: u8,
+        entries: Vec<BondSnapshotEntry>,
+    } = 54,
+
+    /// Appends holder weights to a [BondParticipantBuffer] of the next draw's snapshot, for 
+    /// snapshots too large to be held by a single account. Entries are appended to the latest 
+    /// buffer (`index` is the snapshot's `buffers - 1`) or open the next one (`index` is 
+    /// `buffers`), creating the snapshot on the first write. Rejected like `WriteSnapshot`, and 
+    /// with `ParticipantBufferFull` past [crate::state::MAX_PARTICIPANT_BUFFER_ENTRIES] entries.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[draw_authority]` - The authority designated to run draws, paying for the 
+    ///     accounts.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]).
+    /// - `[w]` `[snapshot]` - The next draw's snapshot (PDA of [`config`, [BondSeed::Snapshot], 
+    ///     draw id (u64 LE)]).
+    /// - `[w]` `[buffer]` - The snapshot's buffer (PDA of [`config`, 
+    ///     [BondSeed::ParticipantBuffer], draw id (u64 LE) + `index` (u32 LE)]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[snapshot_bump]` - [BondSnapshot]'s PDA bump seed.
+    /// - `[bump]` - [BondParticipantBuffer]'s PDA bump seed.
+    /// - `[index]` - The buffer's index in the snapshot.
+    /// - `[entries]` - The holders, their balances and the timestamps since which they have held 
+    ///     them, appended with their time-weighted weights over the snapshot's draw window.
+    WriteParticipants {
+        snapshot_bump: u8,
+        bump: u8,
+        index: u32,
+        entries: Vec<BondSnapshotEntry>,
+    } = 55,
+
+    /// Seals the next draw's snapshot, freezing its entries, `total_weight` and `hash` for the 
+    /// draw. Draws only select from sealed snapshots.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[draw_authority]` - The authority designated to run draws.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]).
+    /// - `[w]` `[snapshot]` - The next draw's snapshot ([BondSnapshot]).
+    FinalizeSnapshot = 56,
+
+    /// Posts the merkle root of the next draw's [BondSnapshotLeaf] leaves instead of writing its 
+    /// entries, creating and sealing the snapshot. Draws then prove the receiver's leaf, that its 
+    /// weight is its time-weighted balance over the snapshot's draw window and that its weight 
+    /// range holds the selected point (see [SnapshotProof]). Leaves whose balance is below the 
+    /// snapshot's `min_balance` are rejected by the draw with `InsufficientWeight`, and must be 
+    /// left out of the tree.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[draw_authority]` - The authority designated to run draws, paying for the 
+    ///     snapshot.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]).
+    /// - `[w]` `[snapshot]` - The next draw's snapshot (PDA of [`config`, [BondSeed::Snapshot], 
+    ///     draw id (u64 LE)]).
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[bump]` - [BondSnapshot]'s PDA bump seed.
+    /// - `[root]` - The merkle root of the leaves.
+    /// - `[leaf_count]` - The number of leaves.
+    /// - `[total_weight]` - The sum of the leaves' (time-weighted) weights.
+    PostSnapshotRoot {
+        bump: u8,
+        root: [u8; 32],
+        leaf_count: u32,
+        total_weight: u64,
+    } = 57,
+
+    /// Releases the escrowed prize of a draw unclaimed within [BondConfig]`.claim_window` back 
+    /// into the jackpot and marks the draw as expired. Can be run by anyone.
+    /// 
+    /// ## Accounts
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]).
+    /// - `[w]` `[draw]` - The unclaimed draw ([BondDraw]).
+    /// - `[w]` `[jackpot_mints]` - The jackpot's additional mints ([BondJackpotMints], only if 
+    ///     the draw has `extra_prizes`, whose reservations are released too).
+    ExpireClaim = 58,
+
+    /// Decommissions one of the game's fee, equity, treasury, jackpot or stake accounts: drains 
+    /// its token account to `destination`, then closes the token account and the PDA, returning 
+    /// their rent to `authority`. The first step of a `CloseLotto`.
+    /// 
+    /// The game must be paused or closed, and no `RequestRandomness` may be pending. The jackpot 
+    /// can't be closed while it holds unclaimed prizes ([BondState]`.reserved`), and its 
+    /// additional mints are drained in the same instruction. The treasury can't be closed while 
+    /// streams are open ([BondState]`.open_streams`, see `RevokeStream`), and holds should be 
+    /// settled beforehand, as they're released to the treasury.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[authority]` - The game's main authority, receiving the rent.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[state]` - The game's current state ([BondState]).
+    /// - `[w]` `[share]` - The account to close (PDA of [`config`, `seed`]).
+    /// - `[w]` `[share_ata]` - The associated token address of `share` for `token_mint`.
+    /// - `[w]` `[destination]` - A token account for `token_mint` receiving the balance.
+    /// - `[]` `[token_mint]` - The game's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[jackpot_mints]` - The jackpot's additional mints ([BondJackpotMints], only if 
+    ///     `seed` is [BondSeed::Jackpot] and [BondConfig]`.jackpot_mints` is set), followed by 
+    ///     each mint's accounts:
+    ///     - `[]` `[mint]` - The additional mint.
+    ///     - `[]` `[mint_token_program]` - The id of the Token or Token-2022 Program owning 
+    ///         `mint`.
+    ///     - `[w]` `[jackpot_mint_ata]` - The associated token address of `jackpot` for `mint`.
+    ///     - `[w]` `[destination_mint_ata]` - A token account for `mint` receiving the balance.
+    /// 
+    /// ## Data
+    /// - `[seed]` - [BondSeed::Fee], [BondSeed::Equity], [BondSeed::Treasury], 
+    ///     [BondSeed::Jackpot] or [BondSeed::Stake].
+    CloseLottoShare {
+        seed: BondSeed,
+    } = 59,
+
+    /// Decommissions the game once every `CloseLottoShare` has run: drains the state's token 
+    /// account (the ticket holders' unclaimed rebates) to `destination`, then closes it, the 
+    /// accounts referenced by the config (jackpot mints, leaderboard and heartbeat), the 
+    /// exclusion list, the state and the config, returning their rent to `authority`.
+    /// 
+    /// Rejected with `AccountsNotClosed` while any of the fee, equity, treasury, jackpot or stake 
+    /// accounts still exists, prizes are unclaimed or streams are open, so that no funds are 
+    /// stranded. Per-draw and per-wallet accounts (draws, snapshots, registrations, sessions) 
+    /// are left to their owners.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[authority]` - The game's main authority, receiving the rent.
+    /// - `[w]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]).
+    /// - `[w]` `[exclusion_list]` - The accounts excluded from all draws ([BondExclusionList]).
+    /// - `[]` `[fee]` - The closed fee account (PDA of [`config`, [BondSeed::Fee]]).
+    /// - `[]` `[equity]` - The closed equity account (PDA of [`config`, [BondSeed::Equity]]).
+    /// - `[]` `[treasury]` - The closed treasury account (PDA of [`config`, 
+    ///     [BondSeed::Treasury]]).
+    /// - `[]` `[jackpot]` - The closed jackpot account (PDA of [`config`, [BondSeed::Jackpot]]).
+    /// - `[]` `[stake]` - The closed stake account (PDA of [`config`, [BondSeed::Stake]]).
+    /// - `[w]` `[state_ata]` - The associated token address of `state` for `token_mint` 
+    ///     (drained and closed if it exists).
+    /// - `[w]` `[destination]` - A token account for `token_mint` receiving its balance.
+    /// - `[]` `[token_mint]` - The game's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[w]` `[jackpot_mints]` - The jackpot's additional mints ([BondConfig]`.jackpot_mints`, 
+    ///     only if set).
+    /// - `[w]` `[leaderboard]` - The winners leaderboard ([BondConfig]`.leaderboard`, only if 
+    ///     set).
+    /// - `[w]` `[heartbeat]` - The keeper's heartbeat (PDA of [`config`, [BondSeed::Heartbeat]], 
+    ///     closed if it exists).
+    CloseLotto = 60,
+
+    /// Sweeps a stray token account owned by one of the game's fee, equity, treasury, jackpot or 
+    /// stake accounts (e.g. tokens sent to the PDA itself or an ATA of another mint) into the 
+    /// fee's associated token account for its mint, created if needed, and closes it.
+    /// 
+    /// The share's token account for the game's mint and the jackpot's additional mints are in 
+    /// use and rejected with `TokenAccountInUse`.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[authority]` - The game's main authority, paying for `fee_ata` and receiving 
+    ///     the stray account's rent.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[owner]` - The stray account's owner (PDA of [`config`, `seed`]).
+    /// - `[w]` `[stray]` - The token account to recover, owned by `owner`.
+    /// - `[]` `[mint]` - The mint of `stray`.
+    /// - `[]` `[fee]` - The Stake Pool's epoch fee receiver ([BondFee]).
+    /// - `[w]` `[fee_ata]` - The associated token address of `fee` for `mint`.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `mint`.
+    /// - `[]` `[associated_token_program]` - The Associated Token Program's id.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// - `[]` `[jackpot_mints]` - The jackpot's additional mints ([BondJackpotMints], only if 
+    ///     `seed` is [BondSeed::Jackpot] and [BondConfig]`.jackpot_mints` is set).
+    /// 
+    /// ## Data
+    /// - `[seed]` - [BondSeed::Fee], [BondSeed::Equity], [BondSeed::Treasury], 
+    ///     [BondSeed::Jackpot] or [BondSeed::Stake].
+    RecoverTokens {
+        seed: BondSeed,
+    } = 61,
+
+    /// Withdraws `amount` tokens from the equity account to `destination`.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[equity_authority]` - [BondConfig]`.equity_authority`, or the game's main 
+    ///     authority if none.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[equity]` - The shareholders account ([BondShare]).
+    /// - `[w]` `[equity_ata]` - The associated token address of `equity` for `token_mint`.
+    /// - `[w]` `[destination]` - A token account for `token_mint`.
+    /// - `[]` `[token_mint]` - The game's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// 
+    /// ## Data
+    /// - `[amount]` - The amount to withdraw.
+    WithdrawEquity {
+        amount: u64,
+    } = 62,
+
+    /// Spends `amount` tokens from the treasury to `destination`, recording `memo` through the 
+    /// [MEMO_PROGRAM_ID] signed by the treasury so that the outflow is documented on chain.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[]` `[treasury]` - The treasury account ([BondShare]).
+    /// - `[w]` `[treasury_ata]` - The associated token address of `treasury` for `token_mint`.
+    /// - `[w]` `[destination]` - A token account for `token_mint`.
+    /// - `[]` `[token_mint]` - The game's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[memo_program]` - The Memo program's id.
+    /// 
+    /// ## Data
+    /// - `[amount]` - The amount to spend.
+    /// - `[memo]` - The purpose of the spend (max [MAX_MEMO_LEN] bytes).
+    SpendTreasury {
+        amount: u64,
+        memo: String,
+    } = 63,
+
+    /// Compounds SOL into the game's stake. The stake's pool tokens already accrue the pool's 
+    /// staking yield, so this deposits the SOL held by the stake vault above its rent exemption 
+    /// (SOL sent to the game's stake) into the pool, minting pool tokens to `stake_ata`. The stake 
+    /// account's lamports above rent exemption are then swept into the vault, for the next run. 
+    /// Can be run by anyone.
+    /// 
+    /// Only supported by [crate::state::BondLstAdapter::SplStakePool].
+    /// 
+    /// ## Accounts
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[stake]` - The locked stake ([BondShare]).
+    /// - `[w]` `[stake_ata]` - The associated token address of `stake` for `token_mint`.
+    /// - `[w]` `[stake_vault]` - The stake's SOL vault (PDA of [`config`, 
+    ///     [BondSeed::StakeVault]]).
+    /// - `[]` `[pool_program]` - The Stake Pool program's id.
+    /// - `[w]` `[pool]` - The Stake Pool.
+    /// - `[w]` `[token_mint]` - The Stake Pool's token mint.
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[system_program]` - The System Program's id.
+    /// - `[]` `[withdraw_authority]` - The Stake Pool's withdraw authority.
+    /// - `[w]` `[reserve_stake]` - The Stake Pool's reserve stake.
+    /// - `[w]` `[manager_fee]` - The Stake Pool's manager fee account.
+    /// 
+    /// ## Data
+    /// - `[vault_bump]` - The stake vault's PDA bump seed.
+    CompoundStake {
+        vault_bump: u8,
+    } = 64,
+
+    /// Harvests the pool's epoch fee into the game's fee account fully on chain: if `fee` is the 
+    /// pool's manager and the fee is paid to another account, first points the pool's fee at 
+    /// `fee_ata`, then runs `UpdateStakePool`. Can be run by anyone.
+    /// 
+    /// ## Accounts
+    /// The accounts of `UpdateStakePool`.
+    HarvestFees = 65,
+
+    /// Redeems a ticket: removes it from the game's ticket ledger (its leaf is replaced by an 
+    /// empty leaf) and burns its weight in receipt tokens from its wallet if the game has a 
+    /// receipt mint. The ticket's unclaimed rebates are forfeited, so `ClaimRebate` should be run 
+    /// first.
+    /// 
+    /// ## Accounts
+    /// - `[s]` `[wallet]` - The ticket's wallet.
+    /// - `[]` `[config]` - The game's settings ([BondConfig]).
+    /// - `[w]` `[state]` - The game's current state ([BondState]), the tree's authority.
+    /// - `[w]` `[ticket_tree]` - The game's ticket ledger ([BondConfig]`.ticket_tree`).
+    /// - `[]` `[compression_program]` - The SPL Account Compression program's id.
+    /// - `[]` `[noop_program]` - The SPL Noop program's id.
+    /// - `[w]` `[receipt_mint]` - The game's receipt mint ([BondConfig]`.receipt_mint`, receipt 
+    ///     mint only).
+    /// - `[w]` `[receipt_ata]` - A receipt token account owned by `wallet` (receipt mint only).
+    /// - `[]` `[token_program]` - The id of the Token or Token-2022 Program owning `receipt_mint` 
+    ///     (receipt mint only).
+    /// - `..` - The ticket's proof nodes.
+    /// 
+    /// ## Data
+    /// - `[ticket_proof]` - The ticket's proof ([TicketProof]).
+    RedeemTicket(TicketProof) = 66,
+
+    /// Creates the [BondState] PDA for `Initialize`, so that `Create`'s accounts may be split 
+    /// across transactions. An account that already exists is skipped, as with `Create`.
+    /// 
+    /// `config` is validated as in `Create`: a new keypair (signer) or, if `config_bump` is 
+    /// provided, the PDA of [[BondSeed::Config], `authority`, `token_mint`].
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s?]` `[config]` - The game's settings.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[state]` - The game's current state (PDA of [`config`, [BondSeed::State]]).
+    /// - `[]` `[token_mint]`- The Stake Pool's token mint.
+    /// - `[]` `[system_program]`- The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[config_bump]` - [BondConfig]'s PDA bump seed, or none if `config` is a keypair.
+    /// - `[bump]` - [BondState]'s PDA bump seed.
+    CreateState {
+        config_bump: Option<u8>,
+        bump: u8,
+    } = 67,
+
+    /// Creates the [BondFee] PDA or a [BondShare] PDA (equity, treasury, jackpot or stake) and 
+    /// its ATA for `Initialize`, as in `CreateState`.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s?]` `[config]` - The game's settings.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[share]` - The fee or share account (PDA of [`config`, `seed`]).
+    /// - `[w]` `[share_ata]` - The associated token address of `share` for `token_mint`.
+    /// - `[]` `[token_mint]`- The Stake Pool's token mint.
+    /// - `[]` `[token_program]`- The id of the Token or Token-2022 Program owning `token_mint`.
+    /// - `[]` `[associated_token_program]`- The Associated Token Program's id.
+    /// - `[]` `[system_program]`- The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[config_bump]` - [BondConfig]'s PDA bump seed, or none if `config` is a keypair.
+    /// - `[seed]` - [BondSeed::Fee], [BondSeed::Equity], [BondSeed::Treasury], 
+    ///     [BondSeed::Jackpot] or [BondSeed::Stake].
+    /// - `[bump]` - The account's PDA bump seed.
+    CreateShare {
+        config_bump: Option<u8>,
+        seed: BondSeed,
+        bump: u8,
+    } = 68,
+
+    /// Creates the [BondExclusionList] PDA for `Initialize`, as in `CreateState`.
+    /// 
+    /// ## Accounts
+    /// - `[s, w]` `[payer]` - The fee payer.
+    /// - `[s?]` `[config]` - The game's settings.
+    /// - `[s]` `[authority]` - The game's main authority.
+    /// - `[w]` `[exclusion_list]` - The accounts excluded from all draws (PDA of [`config`, 
+    ///     [BondSeed::ExclusionList]]).
+    /// - `[]` `[token_mint]`- The Stake Pool's token mint.
+    /// - `[]` `[system_program]`- The System Program's id.
+    /// 
+    /// ## Data
+    /// - `[config_bump]` - [BondConfig]'s PDA bump seed, or none if `config` is a keypair.
+    /// - `[bump]` - [BondExclusionList]'s PDA bump seed.
+    /// - `[capacity]` - The max length of the exclusion list, which its account is sized for.
+    CreateExclusionList {
+        config_bump: Option<u8>,
+        bump: u8,
+        capacity: u32,
+    } = 69,
+}
+
+
+// Programs
+// -------------------------------------------------------------------------------------------------
+
+/// The Address Lookup Table program used by [BondInstruction::CreateLookupTable].
+pub const LOOKUP_TABLE_PROGRAM_ID: Pubkey = pubkey!("AddressLookupTab1e1111111111111111111111111");
+
+/// The SPL Account Compression program used by [BondInstruction::CreateDrawTree].
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey = 
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// The SPL Noop program used to log compressed leaves.
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// The Wormhole core bridge used by [BondInstruction::PublishDrawMessage].
+pub const WORMHOLE_PROGRAM_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+/// The Token-2022 Program, supported alongside the Token Program.
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// The Jupiter aggregator program used by [BondInstruction::SwapTreasury].
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVhwV4");
+
+/// The programs whose routes [BondInstruction::SwapTreasury] may run with the treasury's 
+/// signature.
+pub const SWAP_PROGRAM_IDS: [Pubkey; 1] = [JUPITER_PROGRAM_ID];
+
+/// The Switchboard V2 program used by [BondInstruction::RequestRandomness].
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = pubkey!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+
+/// The SPL Memo program used by [BondInstruction::SpendTreasury].
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+
+// Parameters
+// -------------------------------------------------------------------------------------------------
+
+/// The default minimum number of epochs between draws.
+pub const DEFAULT_EPOCHS_PER_DRAW: u8 = 0;
+
+/// The default maximum number of consecutive rollovers.
+pub const DEFAULT_MAX_ROLLOVER: u8 = 3;
+
+/// The default maximum odds of a single account (10%).
+pub const DEFAULT_ODDS_THRESHOLD: Ratio = Ratio { numerator: 10, denominator: 100 };
+
+/// The default shares of the epoch fee (see [crate::constants]).
+pub use crate::constants::{
+    DEFAULT_EQUITY_SHARE, 
+    DEFAULT_JACKPOT_SHARE, 
+    DEFAULT_STAKE_SHARE, 
+    DEFAULT_TREASURY_SHARE,
+};
+
+/// The maximum length in bytes of a `SpendTreasury` memo.
+pub const MAX_MEMO_LEN: usize = 200;
+
+/// The `SplitShares` destination bit of the equity share.
+pub const SPLIT_EQUITY: u8 = 1 << 0;
+
+/// The `SplitShares` destination bit of the treasury share.
+pub const SPLIT_TREASURY: u8 = 1 << 1;
+
+/// The `SplitShares` destination bit of the jackpot share.
+pub const SPLIT_JACKPOT: u8 = 1 << 2;
+
+/// The `SplitShares` destination bit of the stake share.
+pub const SPLIT_STAKE: u8 = 1 << 3;
+
+/// All `SplitShares` destinations.
+pub const SPLIT_ALL: u8 = SPLIT_EQUITY | SPLIT_TREASURY | SPLIT_JACKPOT | SPLIT_STAKE;
+
+/// The `Heartbeat` bit of a Stake Pool update.
+pub const HEARTBEAT_HARVEST: u8 = 1 << 0;
+
+/// The `Heartbeat` bit of a `SplitShares`.
+pub const HEARTBEAT_SPLIT: u8 = 1 << 1;
+
+/// The `Heartbeat` bit of a `Draw`.
+pub const HEARTBEAT_DRAW: u8 = 1 << 2;
+
+/// A percentage (0-100) expressed by `(numerator/denominator)*100`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Ratio {
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+/// The data of [BondInstruction::Create].
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct CreateParams {
+
+    /// [BondConfig]'s PDA bump seed, or none to create `config` as a keypair account.
+    pub config_bump: Option<u8>,
+
+    /// [BondState]'s PDA bump seed.
+    pub state_bump: u8,
+
+    /// [BondFee]'s PDA bump seed.
+    pub fee_bump: u8,
+
+    /// [BondExclusionList]'s PDA bump seed.
+    pub exclusion_list_bump: u8,
+
+    /// The max length of the exclusion list, which its account is sized for.
+    pub exclusion_list_capacity: u32,
+
+    /// Equity ([BondShare]) PDA bump seed.
+    pub equity_bump: u8,
+
+    /// Treasury ([BondShare]) PDA bump seed.
+    pub treasury_bump: u8,
+
+    /// Jackpot ([BondShare]) PDA bump seed.
+    pub jackpot_bump: u8,
+
+    /// Stake ([BondShare]) PDA bump seed.
+    pub stake_bump: u8,
+}
+
+/// The data of [BondInstruction::Initialize]. Optional settings fall back to their defaults.
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct InitializeParams {
+
+    /// [BondState]'s PDA bump seed.
+    pub state_bump: u8,
+
+    /// [BondFee]'s PDA bump seed.
+    pub fee_bump: u8,
+
+    /// [BondExclusionList]'s PDA bump seed.
+    pub exclusion_list_bump: u8,
+
+    /// The max length of the exclusion list.
+    pub exclusion_list_capacity: u32,
+
+    /// The list of accounts to exclude from all draws.
+    pub exclusion_list_accounts: Vec<Pubkey>,
+
+    /// Equity ([BondShare]) PDA bump seed.
+    pub equity_bump: u8,
+
+    /// Treasury ([BondShare]) PDA bump seed.
+    pub treasury_bump: u8,
+
+    /// Jackpot ([BondShare]) PDA bump seed.
+    pub jackpot_bump: u8,
+
+    /// Stake ([BondShare]) PDA bump seed.
+    pub stake_bump: u8,
+
+    /// The equity share of the epoch fee ([DEFAULT_EQUITY_SHARE]).
+    pub equity_share: Option<Ratio>,
+
+    /// The treasury share of the epoch fee ([DEFAULT_TREASURY_SHARE]).
+    pub treasury_share: Option<Ratio>,
+
+    /// The jackpot share of the epoch fee ([DEFAULT_JACKPOT_SHARE]).
+    pub jackpot_share: Option<Ratio>,
+
+    /// The stake share of the epoch fee ([DEFAULT_STAKE_SHARE]).
+    pub stake_share: Option<Ratio>,
+
+    /// The game's settings.
+    pub config: ConfigParams,
+}
+
+/// The settings of a new [BondConfig]. Settings that are not provided use their default values.
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct ConfigParams {
+
+    /// [BondConfig]'s PDA bump seed, or none if `config` is a keypair account.
+    pub bump: Option<u8>,
+
+    /// The minimum number of epochs between draws ([DEFAULT_EPOCHS_PER_DRAW]).
+    pub epochs_per_draw: Option<u8>,
+
+    /// The maximum number of consecutive rollovers ([DEFAULT_MAX_ROLLOVER]).
+    pub max_rollover: Option<u8>,
+
+    /// The maximum odds of a single account ([DEFAULT_ODDS_THRESHOLD]).
+    pub odds_threshold: Option<Ratio>,
+
+    /// The minimum jackpot paid out by a draw (0).
+    pub min_draw_amount: Option<u64>,
+
+    /// The minimum weight of an eligible winner (0).
+    pub min_eligible_weight: Option<u64>,
+
+    /// The number of draws following a win during which the winner can't win again (0).
+    pub winner_cooldown: Option<u64>,
+
+    /// The minimum number of slots between two `SplitShares` (0).
+    pub min_split_interval: Option<u64>,
+
+    /// The fee paid to permissionless callers in basis points (0).
+    pub crank_fee_bps: Option<u16>,
+
+    /// Whether or not any account may run `SplitShares` (false).
+    pub allow_permissionless_split: Option<bool>,
+
+    /// How the jackpot is paid to a draw's winner ([BondPayoutMode::Transfer]).
+    pub payout_mode: Option<BondPayoutMode>,
+
+    /// The portion of a rolled over jackpot moved to the treasury in basis points (0).
+    pub rollover_retention_bps: Option<u16>,
+
+    /// The portion of each split rebated to ticket holders in basis points (0).
+    pub rebate_bps: Option<u16>,
+
+    /// The season's start time (unbounded).
+    pub starts_at: Option<i64>,
+
+    /// The season's end time (unbounded).
+    pub ends_at: Option<i64>,
+
+    /// The liquid staking program that issues the token mint ([BondLstAdapter::SplStakePool]).
+    pub lst_adapter: Option<BondLstAdapter>,
+
+    /// What happens to the portions of shares skipped by `SplitShares` 
+    /// ([BondSkippedSharePolicy::Retain]).
+    pub skipped_share_policy: Option<BondSkippedSharePolicy>,
+
+    /// The Squads vault that `authority` is derived from (none for a keypair).
+    pub authority_vault: Option<BondSquadsVault>,
+
+    /// The Squads vault that `draw_authority` is derived from (none for a keypair).
+    pub draw_authority_vault: Option<BondSquadsVault>,
+
+    /// Whether or not winners must have registered with `RegisterParticipant` (false).
+    pub require_registration: Option<bool>,
+
+    /// The account authorized to place compliance holds on wallets (none).
+    pub compliance_authority: Option<Pubkey>,
+
+    /// The operator key attesting to the draws' selection inputs (none).
+    pub attestation_signer: Option<Pubkey>,
+
+    /// Whether or not draws must be attested by `attestation_signer` (false).
+    pub require_attestation: Option<bool>,
+
+    /// Whether or not draws must be isolated in their transaction (false).
+    pub isolate_draws: Option<bool>,
+
+    /// Whether or not draws require a completed `ValidateReceiverChunk` validation (false).
+    pub require_exclusion_validation: Option<bool>,
+
+    /// Whether or not draws must be fulfilled from a Switchboard VRF result, which requires 
+    /// `snapshot_draws` (false).
+    pub require_vrf: Option<bool>,
+
+    /// The oracle key whose signature of each draw's result is required (none).
+    pub oracle: Option<Pubkey>,
+
+    /// Whether or not draws select their winner on chain from a [BondSnapshot], which requires 
+    /// them to be fulfilled from a VRF result (false).
+    pub snapshot_draws: Option<bool>,
+
+    /// The number of seconds during which escrowed prizes may be claimed (never expire).
+    pub claim_window: Option<i64>,
+
+    /// The account authorized to withdraw from the equity account (`authority`).
+    pub equity_authority: Option<Pubkey>,
+
+    /// What happens to the remainder of each `SplitShares` amount 
+    /// ([BondRemainderPolicy::Retain]).
+    pub remainder_policy: Option<BondRemainderPolicy>,
+
+    /// Whether or not any account may run `FulfillDraw` for snapshot draws (false).
+    pub allow_permissionless_draw: Option<bool>,
+
+    /// Whether or not the draw authority is limited to one `SplitShares` per epoch (false).
+    pub split_once_per_epoch: Option<bool>,
+}
+
+
+/// A proof that the winner holds a [BondTicket] in the game's ticket ledger.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct TicketProof {
+
+    /// The ledger's root the proof was built against.
+    pub root: [u8; 32],
+
+    /// The ticket's weight.
+    pub weight: u64,
+
+    /// The ticket's issue timestamp.
+    pub issued_at: i64,
+
+    /// The ticket's rebate checkpoint.
+    pub rebate_per_weight: u128,
+
+    /// The ticket's leaf index.
+    pub index: u32,
+}
+
+
+/// A proof that a draw's receiver holds the selected [BondSnapshotLeaf] of a snapshot's merkle 
+/// root (the leaf's wallet is `receiver` and its weight, the time-weighted `balance`, is 
+/// `receiver_weight`).
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct SnapshotProof {
+
+    /// The leaf's index.
+    pub index: u32,
+
+    /// The leaf's balance.
+    pub balance: u64,
+
+    /// The timestamp since which the leaf's wallet has held `balance`.
+    pub held_since: i64,
+
+    /// The total weight of the preceding leaves.
+    pub offset: u64,
+
+    /// The sibling hashes of the leaf's path, from the leaf up.
+    pub proof: Vec<[u8; 32]>,
+}
+
+
+// Return Data
+// -------------------------------------------------------------------------------------------------
+
+/// The data returned by [BondInstruction::GetVersion].
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondVersion {
+
+    /// The program's crate version.
+    pub program_version: String,
+
+    /// The version of the program's account layouts ([crate::state::ACCOUNT_LAYOUT_VERSION]).
+    pub layout_version: u8,
+}
+
+
+// Compute Budget
+// -------------------------------------------------------------------------------------------------
+
+/// The Compute Budget program.
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// The compute unit limit of [BondInstruction::SplitShares] (five token transfers).
+pub const COMPUTE_UNITS_SPLIT_SHARES: u32 = 60_000;
+
+/// The compute unit limit of [BondInstruction::Draw] (up to two token transfers).
+pub const COMPUTE_UNITS_DRAW: u32 = 50_000;
+
+/// The compute unit limit of [BondInstruction::ClaimPrize].
+pub const COMPUTE_UNITS_CLAIM_PRIZE: u32 = 40_000;
+
+/// The compute unit limit of [BondInstruction::UpdateStakePool] (the pool's balance update).
+pub const COMPUTE_UNITS_UPDATE_STAKE_POOL: u32 = 80_000;
+
+/// The compute unit limit of [BondInstruction::RequestRandomness] (the Switchboard request).
+pub const COMPUTE_UNITS_REQUEST_RANDOMNESS: u32 = 100_000;
+
+/// The compute unit limit of [BondInstruction::CrankTick] (a harvest, a split and a randomness 
+/// request).
+pub const COMPUTE_UNITS_CRANK_TICK: u32 = COMPUTE_UNITS_UPDATE_STAKE_POOL 
+    + COMPUTE_UNITS_SPLIT_SHARES 
+    + COMPUTE_UNITS_REQUEST_RANDOMNESS;
+
+/// The compute unit limit of [BondInstruction::SwapTreasury] (a multi-hop route).
+pub const COMPUTE_UNITS_SWAP_TREASURY: u32 = 400_000;
+
+/// Creates a Compute Budget `SetComputeUnitLimit` instruction.
+pub fn set_compute_unit_limit(
+    units: u32,
+) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+}
+
+/// Creates a Compute Budget `SetComputeUnitPrice` instruction (priority fee per compute unit).
+pub fn set_compute_unit_price(
+    micro_lamports: u64,
+) -> Instruction {
+    let mut data = vec![3];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+}
+
+/// Prepends the Compute Budget instructions to `instructions`. The unit limit is the sum of 
+/// `units` (e.g. [COMPUTE_UNITS_DRAW]) and the price is skipped if `micro_lamports` is none.
+pub fn with_compute_budget(
+    instructions: Vec<Instruction>,
+    units: &[u32],
+    micro_lamports: Option<u64>,
+) -> Vec<Instruction> {
+    let mut budgeted = vec![set_compute_unit_limit(units.iter().sum())];
+    if let Some(micro_lamports) = micro_lamports {
+        budgeted.push(set_compute_unit_price(micro_lamports));
+    }
+    budgeted.extend(instructions);
+    budgeted
+}
+
+
+// Addresses
+// -------------------------------------------------------------------------------------------------
+
+/// Every address derived from a game's config and token mint, with the PDAs' bump seeds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BondAddresses {
+
+    /// The game's current state and its bump.
+    pub state: (Pubkey, u8),
+
+    /// The Stake Pool's epoch fee, its bump and its token account.
+    pub fee: (Pubkey, u8),
+    pub fee_ata: Pubkey,
+
+    /// The exclusion list and its bump.
+    pub exclusion_list: (Pubkey, u8),
+
+    /// The creator's share, its bump and its token account.
+    pub equity: (Pubkey, u8),
+    pub equity_ata: Pubkey,
+
+    /// The treasury, its bump and its token account.
+    pub treasury: (Pubkey, u8),
+    pub treasury_ata: Pubkey,
+
+    /// The jackpot, its bump and its token account.
+    pub jackpot: (Pubkey, u8),
+    pub jackpot_ata: Pubkey,
+
+    /// The game's stake, its bump and its token account.
+    pub stake: (Pubkey, u8),
+    pub stake_ata: Pubkey,
+
+    /// The game's Wormhole emitter and its bump.
+    pub emitter: (Pubkey, u8),
+}
+
+impl BondAddresses {
+
+    /// Derives all the addresses of the game at `config` playing with `token_mint`, owned by 
+    /// `token_program` (the Token or Token-2022 Program).
+    pub fn derive(
+        program_id: &Pubkey,
+        config: &Pubkey,
+        token_mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Self {
+        let pda = |seed: BondSeed| seed.derive(program_id, config);
+        let ata = |(owner, _): (Pubkey, u8)| {
+            get_associated_token_address_with_program_id(&owner, token_mint, token_program)
+        };
+        let fee = pda(BondSeed::Fee);
+        let equity = pda(BondSeed::Equity);
+        let treasury = pda(BondSeed::Treasury);
+        let jackpot = pda(BondSeed::Jackpot);
+        let stake = pda(BondSeed::Stake);
+        Self {
+            state: pda(BondSeed::State),
+            fee,
+            fee_ata: ata(fee),
+            exclusion_list: pda(BondSeed::ExclusionList),
+            equity,
+            equity_ata: ata(equity),
+            treasury,
+            treasury_ata: ata(treasury),
+            jackpot,
+            jackpot_ata: ata(jackpot),
+            stake,
+            stake_ata: ata(stake),
+            emitter: pda(BondSeed::Emitter),
+        }
+    }
+}
+
+
+// Builders
+// -------------------------------------------------------------------------------------------------
+
+/// Returns the address of the PDA derived from `config` and `seed`.
+pub fn find_pda(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    seed: BondSeed,
+) -> Pubkey {
+    seed.derive(program_id, config).0
+}
+
+/// Creates a [BondInstruction::Create] instruction with the bump seeds of the game's PDAs. 
+/// `config` is a new keypair, or the PDA of [[BondSeed::Config], `authority`, `token_mint`] if 
+/// `config_bump` is provided. `token_program` is the Token or Token-2022 Program owning 
+/// `token_mint`, as for the other builders.
+pub fn create(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    config_bump: Option<u8>,
+    exclusion_list_capacity: u32,
+) -> Instruction {
+    let addresses = BondAddresses::derive(program_id, config, token_mint, token_program);
+    let params = CreateParams {
+        config_bump,
+        state_bump: addresses.state.1,
+        fee_bump: addresses.fee.1,
+        exclusion_list_bump: addresses.exclusion_list.1,
+        exclusion_list_capacity,
+        equity_bump: addresses.equity.1,
+        treasury_bump: addresses.treasury.1,
+        jackpot_bump: addresses.jackpot.1,
+        stake_bump: addresses.stake.1,
+    };
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*config, config_bump.is_none()),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(addresses.state.0, false),
+            AccountMeta::new(addresses.fee.0, false),
+            AccountMeta::new(addresses.fee_ata, false),
+            AccountMeta::new(addresses.exclusion_list.0, false),
+            AccountMeta::new(addresses.equity.0, false),
+            AccountMeta::new(addresses.equity_ata, false),
+            AccountMeta::new(addresses.treasury.0, false),
+            AccountMeta::new(addresses.treasury_ata, false),
+            AccountMeta::new(addresses.jackpot.0, false),
+            AccountMeta::new(addresses.jackpot_ata, false),
+            AccountMeta::new(addresses.stake.0, false),
+            AccountMeta::new(addresses.stake_ata, false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::Create(params).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates a [BondInstruction::Initialize] instruction, filling in the bump seeds of the game's 
+/// PDAs in `params`. `config` signs unless `params.config.bump` is provided.
+pub fn initialize(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    draw_authority: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    params: InitializeParams,
+) -> Instruction {
+    let addresses = BondAddresses::derive(program_id, config, token_mint, token_program);
+    let config_signer = params.config.bump.is_none();
+    let params = InitializeParams {
+        state_bump: addresses.state.1,
+        fee_bump: addresses.fee.1,
+        exclusion_list_bump: addresses.exclusion_list.1,
+        equity_bump: addresses.equity.1,
+        treasury_bump: addresses.treasury.1,
+        jackpot_bump: addresses.jackpot.1,
+        stake_bump: addresses.stake.1,
+        ..params
+    };
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*config, config_signer),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*draw_authority, true),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new(addresses.state.0, false),
+            AccountMeta::new(addresses.fee.0, false),
+            AccountMeta::new(addresses.exclusion_list.0, false),
+            AccountMeta::new(addresses.equity.0, false),
+            AccountMeta::new(addresses.treasury.0, false),
+            AccountMeta::new(addresses.jackpot.0, false),
+            AccountMeta::new(addresses.stake.0, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::Initialize(params).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates a [BondInstruction::SplitShares] instruction. `authority_ata` receives the crank fee of 
+/// a permissionless caller (required if the fee is non-zero), and `with_rebate` passes the state's 
+/// token account for games with a non-zero [BondConfig]`.rebate_bps`.
+pub fn split_shares(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    config: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    authority_ata: Option<&Pubkey>,
+    with_rebate: bool,
+    amount: Option<u64>,
+    destinations: Option<u8>,
+) -> Instruction {
+    let addresses = BondAddresses::derive(program_id, config, token_mint, token_program);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(addresses.fee.0, false),
+        AccountMeta::new(addresses.fee_ata, false),
+        AccountMeta::new_readonly(addresses.equity.0, false),
+        AccountMeta::new(addresses.equity_ata, false),
+        AccountMeta::new_readonly(addresses.treasury.0, false),
+        AccountMeta::new(addresses.treasury_ata, false),
+        AccountMeta::new_readonly(addresses.jackpot.0, false),
+        AccountMeta::new(addresses.jackpot_ata, false),
+        AccountMeta::new_readonly(addresses.stake.0, false),
+        AccountMeta::new(addresses.stake_ata, false),
+        AccountMeta::new_readonly(*token_mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new(addresses.state.0, false),
+    ];
+    if let Some(authority_ata) = authority_ata {
+        accounts.push(AccountMeta::new(*authority_ata, false));
+    }
+    if with_rebate {
+        let state_ata = get_associated_token_address_with_program_id(
+            &addresses.state.0, 
+            token_mint, 
+            token_program,
+        );
+        accounts.push(AccountMeta::new(state_ata, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: BondInstruction::SplitShares {
+            amount,
+            destinations,
+        }.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates a [BondInstruction::Draw] instruction paying `receiver_ata`. `draw` is the new 
+/// [BondDraw] account (or the draw tree), and `remaining_accounts` are the accounts the game's 
+/// settings require after the SlotHashes sysvar, in the order listed by 
+/// [BondInstruction::Draw] (e.g. `participant`, `exclusion_list`, `snapshot`).
+pub fn draw(
+    program_id: &Pubkey,
+    draw_authority: &Pubkey,
+    config: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    receiver: &Pubkey,
+    receiver_ata: &Pubkey,
+    draw: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+    receiver_seed: u64,
+    draw_seed: u64,
+    receiver_weight: u64,
+    total_weight: u64,
+    proof: Option<SnapshotProof>,
+) -> Instruction {
+    let addresses = BondAddresses::derive(program_id, config, token_mint, token_program);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*draw_authority, true),
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(addresses.state.0, false),
+        AccountMeta::new_readonly(addresses.jackpot.0, false),
+        AccountMeta::new(addresses.jackpot_ata, false),
+        AccountMeta::new_readonly(addresses.treasury.0, false),
+        AccountMeta::new(addresses.treasury_ata, false),
+        AccountMeta::new_readonly(*receiver, false),
+        AccountMeta::new(*receiver_ata, false),
+        AccountMeta::new(*draw, false),
+        AccountMeta::new_readonly(*token_mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+    ];
+    accounts.extend_from_slice(remaining_accounts);
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: BondInstruction::Draw {
+            receiver_seed,
+            draw_seed,
+            receiver_weight,
+            total_weight,
+            proof,
+        }.try_to_vec().unwrap(),
+    }
+}
 
-    Test,
-}
\ No newline at end of file
+/// Creates a [BondInstruction::CrankTick] instruction to register with an automation network 
+/// (e.g. as a scheduled thread's instruction). `cranker` is the automation's signer, 
+/// `adapter_accounts` are the accounts of the game's [BondLstAdapter] and `randomness_accounts` 
+/// are the next draw's snapshot followed by the accounts of `RequestRandomness` from `vrf` (empty 
+/// to skip the randomness request).
+pub fn crank_tick(
+    program_id: &Pubkey,
+    cranker: &Pubkey,
+    config: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    pool_program: &Pubkey,
+    pool: &Pubkey,
+    adapter_accounts: &[AccountMeta],
+    randomness_accounts: &[AccountMeta],
+    permission_bump: u8,
+    program_state_bump: u8,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*cranker, true),
+        AccountMeta::new_readonly(*config, false),
+    ];
+    for seed in [
+        BondSeed::Fee, 
+        BondSeed::Equity, 
+        BondSeed::Treasury, 
+        BondSeed::Jackpot, 
+        BondSeed::Stake,
+    ] {
+        let pda = find_pda(program_id, config, seed);
+        accounts.push(AccountMeta::new(pda, false));
+        let ata = get_associated_token_address_with_program_id(&pda, token_mint, token_program);
+        accounts.push(AccountMeta::new(ata, false));
+    }
+    let state = find_pda(program_id, config, BondSeed::State);
+    let ata = |owner: &Pubkey| {
+        get_associated_token_address_with_program_id(owner, token_mint, token_program)
+    };
+    accounts.extend([
+        AccountMeta::new(*token_mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new(state, false),
+        AccountMeta::new(ata(cranker), false),
+        AccountMeta::new(ata(&state), false),
+        AccountMeta::new_readonly(*pool_program, false),
+        AccountMeta::new(*pool, false),
+    ]);
+    accounts.extend_from_slice(adapter_accounts);
+    accounts.extend_from_slice(randomness_accounts);
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: BondInstruction::CrankTick {
+            permission_bump,
+            program_state_bump,
+        }.try_to_vec().unwrap(),
+    }
+}
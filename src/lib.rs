@@ -4,8 +4,10 @@
 // The deployed program id.
 solana_program::declare_id!("98iqnEfLWpWK69Yn7YAPnWvkCSZAUgkXeS4tciGjccHQ");
 
+pub mod adapter;
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod check;
+pub mod constants;
 pub mod create;
 pub mod entrypoint;
 pub mod error;
@@ -5,12 +5,24 @@
 // -------------------------------------------------------------------------------------------------
 
 use solana_program::program_pack::Pack;
-use spl_token::state::Account;
+use spl_token::state::{Account, Mint};
 use {
     crate::{
-        instruction::BondInstruction,
+        adapter::Adapter,
+        error::BondError,
+        instruction::*,
         state::*,
         check::Check,
+        constants::{
+            exclusion_list_size, 
+            participant_buffer_size, 
+            snapshot_size, 
+            ACCOUNT_DISCRIMINATOR_SIZE, 
+            ACCOUNT_VERSION_OFFSET, 
+            CONFIG_SIZE, 
+            MAX_DRAW_TRANSACTION_INSTRUCTIONS, 
+            SEASON_ARCHIVE_SIZE,
+        },
         create::Create,
     },
     borsh::{BorshDeserialize, BorshSerialize},
@@ -18,12 +30,17 @@ use {
         account_info::{next_account_info, AccountInfo},
         borsh::try_from_slice_unchecked,
         clock::Clock,
+        ed25519_program,
         entrypoint::ProgramResult,
+        keccak,
         msg,
+        instruction::{AccountMeta, Instruction},
+        program::{invoke_signed, set_return_data},
         program_error::ProgramError,
         pubkey::Pubkey,
         rent::Rent, 
-        sysvar::Sysvar, 
+        sysvar,
+        sysvar::{instructions::load_instruction_at_checked, Sysvar}, 
         system_program,
     },
 };
@@ -32,10 +49,58 @@ use {
 // Processor
 // -------------------------------------------------------------------------------------------------
 
+/// The number of bytes added to the start of an account written with a 1-byte `account_type` by 
+/// its 8-byte discriminator.
+const LEGACY_DISCRIMINATOR_GROWTH: usize = ACCOUNT_DISCRIMINATOR_SIZE - 1;
+
+/// The number of Stake Pool accounts following the pool in a SOL payout (see 
+/// [Adapter::withdraw_sol]).
+const SOL_WITHDRAW_ADAPTER_ACCOUNTS: usize = 6;
+
+/// The Anchor discriminator of Switchboard's `VrfAccountData` accounts.
+const VRF_ACCOUNT_DISCRIMINATOR: [u8; 8] = [101, 35, 62, 239, 103, 151, 6, 18];
+
+/// The offset of a VRF account's `status` (after the discriminator).
+const VRF_STATUS_OFFSET: usize = 8;
+
+/// The offset of a VRF account's `authority` (after `status` and the u128 `counter`).
+const VRF_AUTHORITY_OFFSET: usize = 25;
+
+/// The offset of `current_round.request_slot` from the end of a VRF account (followed by the 
+/// round's `request_timestamp`, `result`, `num_verified` and 256 reserved bytes, and the account's 
+/// 1024 reserved bytes).
+const VRF_REQUEST_SLOT_END_OFFSET: usize = 8 + 8 + 32 + 4 + 256 + 1024;
+
+/// The offset of `current_round.result` from the end of a VRF account.
+const VRF_RESULT_END_OFFSET: usize = 32 + 4 + 256 + 1024;
+
+/// The VRF statuses of verified results (`StatusVerified` and `StatusCallbackSuccess`).
+const VRF_VERIFIED_STATUSES: [u8; 2] = [3, 4];
+
+/// The number of slots after which an unfulfilled VRF request may be replaced (about an hour).
+const VRF_REQUEST_EXPIRY_SLOTS: u64 = 9_000;
+
+/// The offset of a Token-2022 mint's extensions (after the base mint padded to the size of a 
+/// token account, and the account type).
+const MINT_EXTENSIONS_OFFSET: usize = Account::LEN + 1;
+
+/// The Token-2022 extension type of `TransferFeeConfig`.
+const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+
+/// The offset of `older_transfer_fee` in `TransferFeeConfig` (after both authorities and the 
+/// withheld amount), followed by `newer_transfer_fee`.
+const OLDER_TRANSFER_FEE_OFFSET: usize = 32 + 32 + 8;
+
+/// The size of a Token-2022 `TransferFee` (`epoch`, `maximum_fee` and `transfer_fee_basis_points`).
+const TRANSFER_FEE_SIZE: usize = 8 + 8 + 2;
+
 pub struct Processor;
 
 impl Processor {
 
+    /// Processes an instruction. Each processor that mutates a game rejects it with 
+    /// `GameInactive` while its config is inactive, except for the exemptions listed in 
+    /// [BondInstruction::Pause].
     pub fn process(
         program_id: &Pubkey, 
         accounts: &[AccountInfo], 
@@ -44,84 +109,110 @@ impl Processor {
         msg!("Process Instruction...");
         let instruction = BondInstruction::try_from_slice(instruction_data)?;
         msg!("Process Instruction Data...");
+
         match instruction {
-            BondInstruction::Create { 
-                config_space, 
-                state_bump, 
-                state_space, 
-                fee_bump, 
-                fee_space, 
-                exclusion_list_bump, 
-                exclusion_list_space, 
-                equity_bump, 
-                equity_space, 
-                treasury_bump, 
-                treasury_space, 
-                jackpot_bump, 
-                jackpot_space, 
-                stake_bump, 
-                stake_space 
-            } => {
+            BondInstruction::Create(params) => {
                 msg!("Instruction: Create accounts");
                 Self::process_create(
                     program_id, 
                     accounts, 
-                    config_space, 
-                    state_bump, 
-                    state_space, 
-                    fee_bump, 
-                    fee_space, 
-                    exclusion_list_bump, 
-                    exclusion_list_space, 
-                    equity_bump, 
-                    equity_space, 
-                    treasury_bump, 
-                    treasury_space, 
-                    jackpot_bump, 
-                    jackpot_space, 
-                    stake_bump, 
-                    stake_space, 
-                )
-            },
-            BondInstruction::Initialize { 
-                state_bump, 
-                fee_bump, 
-                exclusion_list_bump, 
-                exclusion_list_capacity, 
-                exclusion_list_accounts, 
-                equity_bump, 
-                treasury_bump, 
-                jackpot_bump, 
-                stake_bump, 
-            } => {
+                    params,
+                )
+            },
+            BondInstruction::Initialize(params) => {
                 msg!("Instruction: Initialize accounts");
                 Self::process_initialize(
                     program_id, 
                     accounts, 
-                    state_bump,
-                    fee_bump,
-                    exclusion_list_bump,
-                    exclusion_list_capacity,
+                    params,
+                )
+            },
+            BondInstruction::CreateAndInitializeConfig {
+                space,
+                params,
+            } => {
+                msg!("Instruction: Create and Initialize Config");
+                Self::process_create_and_initialize_config(
+                    program_id, 
+                    accounts, 
+                    space,
+                    params,
+                )
+            },
+            BondInstruction::CreateAndInitializeState {
+                bump,
+                space,
+            } => {
+                msg!("Instruction: Create and Initialize State");
+                Self::process_create_and_initialize_state(
+                    program_id, 
+                    accounts, 
+                    bump,
+                    space,
+                )
+            },
+            BondInstruction::CreateAndInitializeFee {
+                bump,
+                space,
+            } => {
+                msg!("Instruction: Create and Initialize Fee");
+                Self::process_create_and_initialize_fee(
+                    program_id, 
+                    accounts, 
+                    bump,
+                    space,
+                )
+            },
+            BondInstruction::CreateAndInitializeExclusionList {
+                bump,
+                space,
+                capacity,
+                accounts: exclusion_list_accounts,
+            } => {
+                msg!("Instruction: Create and Initialize Exclusion List");
+                Self::process_create_and_initialize_exclusion_list(
+                    program_id, 
+                    accounts, 
+                    bump,
+                    space,
+                    capacity,
                     exclusion_list_accounts,
-                    equity_bump,
-                    treasury_bump,
-                    jackpot_bump,
-                    stake_bump,
+                )
+            },
+            BondInstruction::CreateAndInitializeShare {
+                seed,
+                bump,
+                space,
+                share,
+            } => {
+                msg!("Instruction: Create and Initialize Share");
+                Self::process_create_and_initialize_share(
+                    program_id, 
+                    accounts, 
+                    seed,
+                    bump,
+                    space,
+                    share,
                 )
             },
             BondInstruction::SplitShares {
                 amount,
+                destinations,
             } => {
                 msg!("Instruction: Split Shares");
                 Self::process_split_shares(
                     program_id, 
                     accounts, 
                     amount,
+                    destinations,
                 )
             },
             BondInstruction::Draw {
                 receiver_seed,
                 draw_seed,
+                receiver_weight,
+                total_weight,
+                proof,
             } => {
                 msg!("Instruction: Draw");
                 Self::process_draw(
@@ -129,512 +220,6900 @@ impl Processor {
                     accounts, 
                     receiver_seed,
                     draw_seed,
+                    receiver_weight,
+                    total_weight,
+                    proof,
+                    false,
+                    None,
                 )
             },
-            BondInstruction::Test => {
-                Self::process_test(program_id, accounts)
-            }
-        }
-    }
-
-    fn process_test(
-        program_id: &Pubkey,
-        accounts: &[AccountInfo],
-    ) -> ProgramResult {
-        Ok(())
-    }
-
-    /// Create accounts.
-    fn process_create(
-        program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        config_space: u32, 
-        state_bump: u8, 
-        state_space: u32, 
-        fee_bump: u8, 
-        fee_space: u32, 
-        exclusion_list_bump: u8, 
-        exclusion_list_space: u32, 
-        equity_bump: u8, 
-        equity_space: u32, 
-        treasury_bump: u8, 
-        treasury_space: u32, 
-        jackpot_bump: u8, 
-        jackpot_space: u32, 
-        stake_bump: u8, 
-        stake_space: u32, 
-    ) -> ProgramResult {
-
-        // Unpack accounts.
-        let account_info_iter = &mut accounts.iter();
-        let payer_info = next_account_info(account_info_iter)?;
-        let config_info = next_account_info(account_info_iter)?; 
-        let state_info = next_account_info(account_info_iter)?;
-        let fee_info = next_account_info(account_info_iter)?; 
-        let fee_ata_info = next_account_info(account_info_iter)?; 
-        let exclusion_list_info = next_account_info(account_info_iter)?; 
-        let equity_info = next_account_info(account_info_iter)?; 
-        let equity_ata_info = next_account_info(account_info_iter)?; 
-        let treasury_info = next_account_info(account_info_iter)?; 
-        let treasury_ata_info = next_account_info(account_info_iter)?; 
-        let jackpot_info = next_account_info(account_info_iter)?; 
-        let jackpot_ata_info = next_account_info(account_info_iter)?; 
-        let stake_info = next_account_info(account_info_iter)?; 
-        let stake_ata_info = next_account_info(account_info_iter)?; 
-        let token_mint_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-        let associated_token_program_info = next_account_info(account_info_iter)?;
-        let system_program_info = next_account_info(account_info_iter)?;
-
-        // Validate accounts.
-        Check::signer(payer_info)?;
-        Check::signer_and_writable(config_info)?;
-        Check::writable(state_info)?;
-        Check::writable(fee_info)?;
-        Check::writable(fee_ata_info)?;
-        Check::writable(exclusion_list_info)?;
-        Check::writable(equity_info)?;
-        Check::writable(equity_ata_info)?;
-        Check::writable(treasury_info)?;
-        Check::writable(treasury_ata_info)?;
-        Check::writable(jackpot_info)?;
-        Check::writable(jackpot_ata_info)?;
-        Check::writable(stake_info)?;
-        Check::writable(stake_ata_info)?;
-        Check::owner(token_mint_info, &spl_token::id())?;
-        Check::account(token_program_info, &spl_token::id())?;
-        Check::account(associated_token_program_info, &spl_associated_token_account::id())?;
-        Check::account(system_program_info, &system_program::id())?;
-
-        // Rent.
-        let rent = Rent::get()?;
-
-        // Config Account.
-        Create::account(
-            program_id, 
-            payer_info, 
-            config_info, 
-            system_program_info, 
-            &rent,
-            config_space,
-        )?;
-
-        // State PDA Account.
-        Create::pda_account(
-            program_id, 
-            config_info, 
-            payer_info, 
-            &state_info,
-            BondSeed::State,
-            state_bump,
-            system_program_info,
-            &rent,
-            state_space,
-        )?;
-
-        // Fee PDA + ATA Accounts.
-        Create::pda_and_ata_accounts(
-            program_id, 
-            config_info, 
-            payer_info, 
-            fee_info, 
-            BondSeed::Fee, 
-            fee_bump, 
-            fee_ata_info, 
-            token_mint_info, 
-            token_program_info, 
-            associated_token_program_info, 
-            system_program_info, 
-            &rent,
-            fee_space,
-        )?;
-
-        // Exclusion List PDA Account.
-        Create::pda_account(
-            program_id, 
-            config_info, 
-            payer_info, 
-            exclusion_list_info, 
-            BondSeed::ExclusionList, 
-            exclusion_list_bump, 
-            system_program_info,
-            &rent,
-            exclusion_list_space
-        )?;
-
-        // Equity PDA + ATA Accounts.
-        Create::pda_and_ata_accounts(
-            program_id, 
-            config_info, 
-            payer_info, 
-            equity_info, 
-            BondSeed::Equity, 
-            equity_bump, 
-            equity_ata_info, 
-            token_mint_info, 
-            token_program_info, 
-            associated_token_program_info, 
-            system_program_info, 
-            &rent,
-            equity_space,
-        )?;
-
-        // Treasury PDA + ATA Accounts.
-        Create::pda_and_ata_accounts(
-            program_id, 
-            config_info, 
-            payer_info, 
-            treasury_info, 
-            BondSeed::Treasury, 
-            treasury_bump, 
-            treasury_ata_info, 
-            token_mint_info, 
-            token_program_info, 
-            associated_token_program_info, 
-            system_program_info, 
-            &rent,
-            treasury_space,
-        )?;
-
-        // Jackpot PDA + ATA Accounts.
-        Create::pda_and_ata_accounts(
-            program_id, 
-            config_info, 
-            payer_info, 
-            jackpot_info, 
-            BondSeed::Jackpot, 
-            jackpot_bump, 
-            jackpot_ata_info, 
-            token_mint_info, 
-            token_program_info, 
-            associated_token_program_info, 
-            system_program_info, 
-            &rent,
-            jackpot_space,
-        )?;
-
-        // Stake PDA + ATA Accounts.
-        Create::pda_and_ata_accounts(
-            program_id, 
-            config_info, 
-            payer_info, 
-            stake_info, 
-            BondSeed::Stake, 
-            stake_bump, 
-            stake_ata_info, 
-            token_mint_info, 
-            token_program_info, 
-            associated_token_program_info, 
-            system_program_info, 
-            &rent,
-            stake_space,
-        )
-    }
-
-    fn check_initialize_account(
-        program_id: &Pubkey,
-        account: &impl BondAccount,
-        account_info: &AccountInfo,
-        rent: &Rent,
-    ) -> Result<(), ProgramError> {
-        // Check::uninitialized(account, account_info)?;
-        Check::writable(account_info)?; 
-        Check::rent_exempt(account_info, rent)?;
-        Check::owner(account_info, program_id)?;
-        Ok(())
-    }
-
-    fn check_initialize_pda_account(
-        program_id: &Pubkey,
-        config_info: &AccountInfo,
-        pda: &impl BondAccount,
-        pda_info: &AccountInfo,
-        seed: BondSeed,
-        bump: u8,
-        rent: &Rent,
-    ) -> Result<(), ProgramError> {
-        Check::pda(program_id, config_info, pda_info, seed, bump)?;
-        Self::check_initialize_account(program_id, pda, pda_info, rent)
-    }
-
-    fn initialize_share(
-        program_id: &Pubkey,
-        config_info: &AccountInfo,
-        account_info: &AccountInfo,
-        authority: Pubkey,
-        numerator: u32,
-        denominator: u32,
-        seed: BondSeed,
-        bump: u8,
-        rent: &Rent,
-    ) -> ProgramResult {
-        let account = try_from_slice_unchecked::<BondShare>(
-            &account_info.data.borrow(),
-        )?;
-        Self::check_initialize_pda_account(
-            program_id, 
-            config_info, 
-            &account,
-            account_info, 
-            seed, 
-            bump, 
-            &rent,
-        )?;
-        BondShare::new(
-            authority, 
-            bump, 
+            BondInstruction::GetVersion => {
+                msg!("Instruction: Get Version");
+                Self::process_get_version()
+            },
+            BondInstruction::ClaimPrize => {
+                msg!("Instruction: Claim Prize");
+                Self::process_claim_prize(
+                    program_id, 
+                    accounts,
+                    None,
+                )
+            },
+            BondInstruction::SetMetadata {
+                name,
+                uri,
+            } => {
+                msg!("Instruction: Set Metadata");
+                Self::process_set_metadata(
+                    program_id, 
+                    accounts,
+                    name,
+                    uri,
+                )
+            },
+            BondInstruction::CloseSeason => {
+                msg!("Instruction: Close Season");
+                Self::process_close_season(
+                    program_id, 
+                    accounts,
+                )
+            },
+            BondInstruction::RolloverSeason {
+                bump,
+                starts_at,
+                ends_at,
+            } => {
+                msg!("Instruction: Rollover Season");
+                Self::process_rollover_season(
+                    program_id, 
+                    accounts,
+                    bump,
+                    starts_at,
+                    ends_at,
+                )
+            },
+            BondInstruction::UpdateExclusionList {
+                add,
+                remove,
+                expires_at_epoch,
+            } => {
+                msg!("Instruction: Update Exclusion List");
+                Self::process_update_exclusion_list(
+                    program_id, 
+                    accounts,
+                    add,
+                    remove,
+                    expires_at_epoch,
+                )
+            },
+            BondInstruction::AppendExclusionChunk {
+                chunk_index,
+                accounts: exclusion_list_accounts,
+            } => {
+                msg!("Instruction: Append Exclusion Chunk");
+                Self::process_append_exclusion_chunk(
+                    program_id, 
+                    accounts,
+                    chunk_index,
+                    exclusion_list_accounts,
+                )
+            },
+            BondInstruction::UpdateStakePool => {
+                msg!("Instruction: Update Stake Pool");
+                Self::process_update_stake_pool(
+                    program_id, 
+                    accounts,
+                )
+            },
+            BondInstruction::SwapTreasury {
+                amount_in,
+                min_amount_out,
+                route_data,
+            } => {
+                msg!("Instruction: Swap Treasury");
+                Self::process_swap_treasury(
+                    program_id, 
+                    accounts,
+                    amount_in,
+                    min_amount_out,
+                    route_data,
+                )
+            },
+            BondInstruction::CrankTick {
+                permission_bump,
+                program_state_bump,
+            } => {
+                msg!("Instruction: Crank Tick");
+                Self::process_crank_tick(
+                    program_id, 
+                    accounts,
+                    permission_bump,
+                    program_state_bump,
+                )
+            },
+            BondInstruction::CreateLookupTable {
+                recent_slot,
+                bump,
+            } => {
+                msg!("Instruction: Create Lookup Table");
+                Self::process_create_lookup_table(
+                    program_id, 
+                    accounts,
+                    recent_slot,
+                    bump,
+                )
+            },
+            BondInstruction::ExtendLookupTable {
+                addresses,
+            } => {
+                msg!("Instruction: Extend Lookup Table");
+                Self::process_extend_lookup_table(
+                    program_id, 
+                    accounts,
+                    addresses,
+                )
+            },
+            BondInstruction::CreateDrawTree {
+                max_depth,
+                max_buffer_size,
+            } => {
+                msg!("Instruction: Create Draw Tree");
+                Self::process_create_draw_tree(
+                    program_id, 
+                    accounts,
+                    max_depth,
+                    max_buffer_size,
+                )
+            },
+            BondInstruction::CreateTicketTree {
+                max_depth,
+                max_buffer_size,
+            } => {
+                msg!("Instruction: Create Ticket Tree");
+                Self::process_create_ticket_tree(
+                    program_id, 
+                    accounts,
+                    max_depth,
+                    max_buffer_size,
+                )
+            },
+            BondInstruction::IssueTickets {
+                tickets,
+            } => {
+                msg!("Instruction: Issue Tickets");
+                Self::process_issue_tickets(
+                    program_id, 
+                    accounts,
+                    tickets,
+                )
+            },
+            BondInstruction::ClaimPrizeWithTicket(ticket_proof) => {
+                msg!("Instruction: Claim Prize With Ticket");
+                Self::process_claim_prize(
+                    program_id, 
+                    accounts,
+                    Some(ticket_proof),
+                )
+            },
+            BondInstruction::PublishDrawMessage {
+                nonce,
+                emitter_bump,
+                message_bump,
+            } => {
+                msg!("Instruction: Publish Draw Message");
+                Self::process_publish_draw_message(
+                    program_id, 
+                    accounts,
+                    nonce,
+                    emitter_bump,
+                    message_bump,
+                )
+            },
+            BondInstruction::PreviewDraw {
+                receiver_seed,
+                draw_seed,
+                receiver_weight,
+                total_weight,
+                proof,
+            } => {
+                msg!("Instruction: Preview Draw");
+                Self::process_draw(
+                    program_id, 
+                    accounts, 
+                    receiver_seed,
+                    draw_seed,
+                    receiver_weight,
+                    total_weight,
+                    proof,
+                    true,
+                    None,
+                )
+            },
+            BondInstruction::RegisterParticipant {
+                bump,
+                space,
+                payout,
+                terms_hash,
+            } => {
+                msg!("Instruction: Register Participant");
+                Self::process_register_participant(
+                    program_id, 
+                    accounts,
+                    bump,
+                    space,
+                    payout,
+                    terms_hash,
+                )
+            },
+            BondInstruction::PlaceHold {
+                bump,
+                space,
+            } => {
+                msg!("Instruction: Place Hold");
+                Self::process_place_hold(
+                    program_id, 
+                    accounts,
+                    bump,
+                    space,
+                )
+            },
+            BondInstruction::ReleaseHold {
+                reroute,
+            } => {
+                msg!("Instruction: Release Hold");
+                Self::process_release_hold(
+                    program_id, 
+                    accounts,
+                    reroute,
+                )
+            },
+            BondInstruction::RegisterJackpotMint {
+                bump,
+                space,
+            } => {
+                msg!("Instruction: Register Jackpot Mint");
+                Self::process_register_jackpot_mint(
+                    program_id, 
+                    accounts,
+                    bump,
+                    space,
+                )
+            },
+            BondInstruction::CreateSession {
+                bump,
+                space,
+                session_key,
+                expires_at,
+            } => {
+                msg!("Instruction: Create Session");
+                Self::process_create_session(
+                    program_id, 
+                    accounts,
+                    bump,
+                    space,
+                    session_key,
+                    expires_at,
+                )
+            },
+            BondInstruction::CreateReceiptMint {
+                bump,
+            } => {
+                msg!("Instruction: Create Receipt Mint");
+                Self::process_create_receipt_mint(
+                    program_id, 
+                    accounts,
+                    bump,
+                )
+            },
+            BondInstruction::CreateLeaderboard {
+                bump,
+                space,
+            } => {
+                msg!("Instruction: Create Leaderboard");
+                Self::process_create_leaderboard(
+                    program_id, 
+                    accounts,
+                    bump,
+                    space,
+                )
+            },
+            BondInstruction::SetMinTransfer {
+                min_transfer,
+            } => {
+                msg!("Instruction: Set Min Transfer");
+                Self::process_set_min_transfer(
+                    program_id, 
+                    accounts,
+                    min_transfer,
+                )
+            },
+            BondInstruction::CreateStream {
+                bump,
+                space,
+                recipient,
+                rate,
+                duration,
+            } => {
+                msg!("Instruction: Create Stream");
+                Self::process_create_stream(
+                    program_id, 
+                    accounts,
+                    bump,
+                    space,
+                    recipient,
+                    rate,
+                    duration,
+                )
+            },
+            BondInstruction::WithdrawStream => {
+                msg!("Instruction: Withdraw Stream");
+                Self::process_withdraw_stream(
+                    program_id, 
+                    accounts,
+                )
+            },
+            BondInstruction::RevokeStream => {
+                msg!("Instruction: Revoke Stream");
+                Self::process_revoke_stream(
+                    program_id, 
+                    accounts,
+                )
+            },
+            BondInstruction::ClaimRebate {
+                bump,
+                space,
+                ticket_proof,
+            } => {
+                msg!("Instruction: Claim Rebate");
+                Self::process_claim_rebate(
+                    program_id, 
+                    accounts,
+                    bump,
+                    space,
+                    ticket_proof,
+                )
+            },
+            BondInstruction::SetPayoutAsset {
+                payout_asset,
+            } => {
+                msg!("Instruction: Set Payout Asset");
+                Self::process_set_payout_asset(
+                    program_id, 
+                    accounts,
+                    payout_asset,
+                )
+            },
+            BondInstruction::PruneExpired => {
+                msg!("Instruction: Prune Expired");
+                Self::process_prune_expired(
+                    program_id, 
+                    accounts,
+                )
+            },
+            BondInstruction::ValidateReceiverChunk {
+                bump,
+                space,
+                receiver,
+                count,
+            } => {
+                msg!("Instruction: Validate Receiver Chunk");
+                Self::process_validate_receiver_chunk(
+                    program_id, 
+                    accounts,
+                    bump,
+                    space,
+                    receiver,
+                    count,
+                )
+            },
+            BondInstruction::RotateShareAta {
+                seed,
+            } => {
+                msg!("Instruction: Rotate Share ATA");
+                Self::process_rotate_share_ata(
+                    program_id, 
+                    accounts,
+                    seed,
+                )
+            },
+            BondInstruction::Migrate {
+                space,
+            } => {
+                msg!("Instruction: Migrate");
+                Self::process_migrate(
+                    program_id, 
+                    accounts,
+                    space,
+                )
+            },
+            BondInstruction::Heartbeat {
+                bump,
+                space,
+                runs,
+            } => {
+                msg!("Instruction: Heartbeat");
+                Self::process_heartbeat(
+                    program_id, 
+                    accounts,
+                    bump,
+                    space,
+                    runs,
+                )
+            },
+            BondInstruction::Pause => {
+                msg!("Instruction: Pause");
+                Self::process_set_active(
+                    program_id, 
+                    accounts,
+                    false,
+                )
+            },
+            BondInstruction::Resume => {
+                msg!("Instruction: Resume");
+                Self::process_set_active(
+                    program_id, 
+                    accounts,
+                    true,
+                )
+            },
+            BondInstruction::SetShares {
+                equity,
+                treasury,
+                jackpot,
+                stake,
+            } => {
+                msg!("Instruction: Set Shares");
+                Self::process_set_shares(
+                    program_id, 
+                    accounts,
+                    [equity, treasury, jackpot, stake],
+                )
+            },
+            BondInstruction::AddExcludedAccounts {
+                accounts: exclusion_list_accounts,
+                expires_at_epoch,
+            } => {
+                msg!("Instruction: Add Excluded Accounts");
+                Self::process_add_excluded_accounts(
+                    program_id, 
+                    accounts,
+                    exclusion_list_accounts,
+                    expires_at_epoch,
+                )
+            },
+            BondInstruction::RemoveExcludedAccounts {
+                accounts: exclusion_list_accounts,
+            } => {
+                msg!("Instruction: Remove Excluded Accounts");
+                Self::process_remove_excluded_accounts(
+                    program_id, 
+                    accounts,
+                    exclusion_list_accounts,
+                )
+            },
+            BondInstruction::GrowExclusionList {
+                capacity,
+            } => {
+                msg!("Instruction: Grow Exclusion List");
+                Self::process_grow_exclusion_list(
+                    program_id, 
+                    accounts,
+                    capacity,
+                )
+            },
+            BondInstruction::RequestRandomness {
+                permission_bump,
+                program_state_bump,
+            } => {
+                msg!("Instruction: Request Randomness");
+                Self::process_request_randomness(
+                    program_id, 
+                    accounts,
+                    permission_bump,
+                    program_state_bump,
+                )
+            },
+            BondInstruction::FulfillDraw {
+                draw_seed,
+                receiver_weight,
+                total_weight,
+                proof,
+            } => {
+                msg!("Instruction: Fulfill Draw");
+                Self::process_fulfill_draw(
+                    program_id, 
+                    accounts,
+                    draw_seed,
+                    receiver_weight,
+                    total_weight,
+                    proof,
+                )
+            },
+            BondInstruction::WriteSnapshot {
+                bump,
+                entries,
+            } => {
+                msg!("Instruction: Write Snapshot");
+                Self::process_write_snapshot(
+                    program_id, 
+                    accounts,
+                    bump,
+                    entries,
+                )
+            },
+            BondInstruction::WriteParticipants {
+                snapshot_bump,
+                bump,
+                index,
+                entries,
+            } => {
+                msg!("Instruction: Write Participants");
+                Self::process_write_participants(
+                    program_id, 
+                    accounts,
+                    snapshot_bump,
+                    bump,
+                    index,
+                    entries,
+                )
+            },
+            BondInstruction::FinalizeSnapshot => {
+                msg!("Instruction: Finalize Snapshot");
+                Self::process_finalize_snapshot(
+                    program_id, 
+                    accounts,
+                )
+            },
+            BondInstruction::PostSnapshotRoot {
+                bump,
+                root,
+                leaf_count,
+                total_weight,
+            } => {
+                msg!("Instruction: Post Snapshot Root");
+                Self::process_post_snapshot_root(
+                    program_id, 
+                    accounts,
+                    bump,
+                    root,
+                    leaf_count,
+                    total_weight,
+                )
+            },
+            BondInstruction::ExpireClaim => {
+                msg!("Instruction: Expire Claim");
+                Self::process_expire_claim(
+                    program_id, 
+                    accounts,
+                )
+            },
+            BondInstruction::CloseLottoShare {
+                seed,
+            } => {
+                msg!("Instruction: Close Lotto Share");
+                Self::process_close_lotto_share(
+                    program_id, 
+                    accounts,
+                    seed,
+                )
+            },
+            BondInstruction::CloseLotto => {
+                msg!("Instruction: Close Lotto");
+                Self::process_close_lotto(
+                    program_id, 
+                    accounts,
+                )
+            },
+            BondInstruction::RecoverTokens {
+                seed,
+            } => {
+                msg!("Instruction: Recover Tokens");
+                Self::process_recover_tokens(
+                    program_id, 
+                    accounts,
+                    seed,
+                )
+            },
+            BondInstruction::WithdrawEquity {
+                amount,
+            } => {
+                msg!("Instruction: Withdraw Equity");
+                Self::process_withdraw_equity(
+                    program_id, 
+                    accounts,
+                    amount,
+                )
+            },
+            BondInstruction::SpendTreasury {
+                amount,
+                memo,
+            } => {
+                msg!("Instruction: Spend Treasury");
+                Self::process_spend_treasury(
+                    program_id, 
+                    accounts,
+                    amount,
+                    memo,
+                )
+            },
+            BondInstruction::CompoundStake {
+                vault_bump,
+            } => {
+                msg!("Instruction: Compound Stake");
+                Self::process_compound_stake(
+                    program_id, 
+                    accounts,
+                    vault_bump,
+                )
+            },
+            BondInstruction::HarvestFees => {
+                msg!("Instruction: Harvest Fees");
+                Self::process_harvest_fees(
+                    program_id, 
+                    accounts,
+                )
+            },
+            BondInstruction::RedeemTicket(ticket_proof) => {
+                msg!("Instruction: Redeem Ticket");
+                Self::process_redeem_ticket(
+                    program_id, 
+                    accounts,
+                    ticket_proof,
+                )
+            },
+            BondInstruction::CreateState {
+                config_bump,
+                bump,
+            } => {
+                msg!("Instruction: Create State");
+                Self::process_create_state(
+                    program_id, 
+                    accounts, 
+                    config_bump,
+                    bump,
+                )
+            },
+            BondInstruction::CreateShare {
+                config_bump,
+                seed,
+                bump,
+            } => {
+                msg!("Instruction: Create Share");
+                Self::process_create_share(
+                    program_id, 
+                    accounts, 
+                    config_bump,
+                    seed,
+                    bump,
+                )
+            },
+            BondInstruction::CreateExclusionList {
+                config_bump,
+                bump,
+                capacity,
+            } => {
+                msg!("Instruction: Create Exclusion List");
+                Self::process_create_exclusion_list(
+                    program_id, 
+                    accounts, 
+                    config_bump,
+                    bump,
+                    capacity,
+                )
+            },
+            BondInstruction::AssertInvariants => {
+                msg!("Instruction: Assert Invariants");
+                Self::process_assert_invariants(
+                    program_id, 
+                    accounts,
+                )
+            },
+        }
+    }
+
+    fn process_get_version() -> ProgramResult {
+        let version = BondVersion {
+            program_version: env!("CARGO_PKG_VERSION").to_string(),
+            layout_version: ACCOUNT_LAYOUT_VERSION,
+        };
+        msg!("Version {} (layout {})", version.program_version, version.layout_version);
+        set_return_data(&version.try_to_vec()?);
+        Ok(())
+    }
+
+    /// Create accounts.
+    fn process_create(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        params: CreateParams,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?; 
+        let authority_info = next_account_info(account_info_iter)?; 
+        let state_info = next_account_info(account_info_iter)?;
+        let fee_info = next_account_info(account_info_iter)?; 
+        let fee_ata_info = next_account_info(account_info_iter)?; 
+        let exclusion_list_info = next_account_info(account_info_iter)?; 
+        let equity_info = next_account_info(account_info_iter)?; 
+        let equity_ata_info = next_account_info(account_info_iter)?; 
+        let treasury_info = next_account_info(account_info_iter)?; 
+        let treasury_ata_info = next_account_info(account_info_iter)?; 
+        let jackpot_info = next_account_info(account_info_iter)?; 
+        let jackpot_ata_info = next_account_info(account_info_iter)?; 
+        let stake_info = next_account_info(account_info_iter)?; 
+        let stake_ata_info = next_account_info(account_info_iter)?; 
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let associated_token_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        // Validate accounts.
+        Check::signer_and_writable(payer_info)?;
+        Check::writable(config_info)?;
+        Check::signer(authority_info)?;
+        Check::writable(state_info)?;
+        Check::writable(fee_info)?;
+        Check::writable(fee_ata_info)?;
+        Check::writable(exclusion_list_info)?;
+        Check::writable(equity_info)?;
+        Check::writable(equity_ata_info)?;
+        Check::writable(treasury_info)?;
+        Check::writable(treasury_ata_info)?;
+        Check::writable(jackpot_info)?;
+        Check::writable(jackpot_ata_info)?;
+        Check::writable(stake_info)?;
+        Check::writable(stake_ata_info)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        Check::account(associated_token_program_info, &spl_associated_token_account::id())?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Rent.
+        let rent = Rent::get()?;
+
+        // Config Account.
+        Self::create_config(
+            program_id, 
+            payer_info, 
+            config_info, 
+            authority_info, 
+            token_mint_info, 
+            params.config_bump, 
+            system_program_info, 
+            &rent,
+            BondConfig::LEN as u32,
+        )?;
+
+        // State PDA Account.
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            &state_info,
+            BondSeed::State,
+            params.state_bump,
+            system_program_info,
+            &rent,
+            BondState::LEN as u32,
+        )?;
+
+        // Fee PDA + ATA Accounts.
+        Create::pda_and_ata_accounts(
+            program_id, 
+            config_info, 
+            payer_info, 
+            fee_info, 
+            BondSeed::Fee, 
+            params.fee_bump, 
+            fee_ata_info, 
+            token_mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info, 
+            &rent,
+            BondFee::LEN as u32,
+        )?;
+
+        // Exclusion List PDA Account.
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            exclusion_list_info, 
+            BondSeed::ExclusionList, 
+            params.exclusion_list_bump, 
+            system_program_info,
+            &rent,
+            BondExclusionList::space(params.exclusion_list_capacity) as u32
+        )?;
+
+        // Equity PDA + ATA Accounts.
+        Create::pda_and_ata_accounts(
+            program_id, 
+            config_info, 
+            payer_info, 
+            equity_info, 
+            BondSeed::Equity, 
+            params.equity_bump, 
+            equity_ata_info, 
+            token_mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info, 
+            &rent,
+            BondShare::LEN as u32,
+        )?;
+
+        // Treasury PDA + ATA Accounts.
+        Create::pda_and_ata_accounts(
+            program_id, 
+            config_info, 
+            payer_info, 
+            treasury_info, 
+            BondSeed::Treasury, 
+            params.treasury_bump, 
+            treasury_ata_info, 
+            token_mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info, 
+            &rent,
+            BondShare::LEN as u32,
+        )?;
+
+        // Jackpot PDA + ATA Accounts.
+        Create::pda_and_ata_accounts(
+            program_id, 
+            config_info, 
+            payer_info, 
+            jackpot_info, 
+            BondSeed::Jackpot, 
+            params.jackpot_bump, 
+            jackpot_ata_info, 
+            token_mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info, 
+            &rent,
+            BondShare::LEN as u32,
+        )?;
+
+        // Stake PDA + ATA Accounts.
+        Create::pda_and_ata_accounts(
+            program_id, 
+            config_info, 
+            payer_info, 
+            stake_info, 
+            BondSeed::Stake, 
+            params.stake_bump, 
+            stake_ata_info, 
+            token_mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info, 
+            &rent,
+            BondShare::LEN as u32,
+        )
+    }
+
+    /// Checks the accounts shared by `CreateState`, `CreateShare` and `CreateExclusionList`: 
+    /// `config` is a signing keypair or, if `config_bump` is provided, the config PDA of 
+    /// `authority` and `token_mint`, as in `Create`.
+    fn check_create_accounts(
+        program_id: &Pubkey,
+        payer_info: &AccountInfo,
+        config_info: &AccountInfo,
+        authority_info: &AccountInfo,
+        token_mint_info: &AccountInfo,
+        config_bump: Option<u8>,
+        system_program_info: &AccountInfo,
+    ) -> ProgramResult {
+        Check::signer_and_writable(payer_info)?;
+        Check::signer(authority_info)?;
+        match config_bump {
+            Some(bump) => Check::config_pda(
+                program_id, 
+                config_info, 
+                authority_info.key, 
+                token_mint_info.key, 
+                bump,
+            )?,
+            None => Check::signer(config_info)?,
+        }
+        Check::account(system_program_info, &system_program::id())
+    }
+
+    fn process_create_state(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        config_bump: Option<u8>,
+        bump: u8,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let state_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Self::check_create_accounts(
+            program_id, 
+            payer_info, 
+            config_info, 
+            authority_info, 
+            token_mint_info, 
+            config_bump, 
+            system_program_info,
+        )?;
+        Check::writable(state_info)?;
+
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            state_info, 
+            BondSeed::State, 
+            bump, 
+            system_program_info, 
+            &Rent::get()?,
+            BondState::LEN as u32,
+        )
+    }
+
+    fn process_create_share(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        config_bump: Option<u8>,
+        seed: BondSeed,
+        bump: u8,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let share_info = next_account_info(account_info_iter)?;
+        let share_ata_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let associated_token_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Self::check_create_accounts(
+            program_id, 
+            payer_info, 
+            config_info, 
+            authority_info, 
+            token_mint_info, 
+            config_bump, 
+            system_program_info,
+        )?;
+        Check::writable(share_info)?;
+        Check::writable(share_ata_info)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        Check::account(associated_token_program_info, &spl_associated_token_account::id())?;
+        let space = match seed {
+            BondSeed::Fee => BondFee::LEN,
+            _ => {
+                Self::default_share(&seed)?;
+                BondShare::LEN
+            },
+        };
+
+        Create::pda_and_ata_accounts(
+            program_id, 
+            config_info, 
+            payer_info, 
+            share_info, 
+            seed, 
+            bump, 
+            share_ata_info, 
+            token_mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info, 
+            &Rent::get()?,
+            space as u32,
+        )
+    }
+
+    fn process_create_exclusion_list(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        config_bump: Option<u8>,
+        bump: u8,
+        capacity: u32,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Self::check_create_accounts(
+            program_id, 
+            payer_info, 
+            config_info, 
+            authority_info, 
+            token_mint_info, 
+            config_bump, 
+            system_program_info,
+        )?;
+        Check::writable(exclusion_list_info)?;
+
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            exclusion_list_info, 
+            BondSeed::ExclusionList, 
+            bump, 
+            system_program_info, 
+            &Rent::get()?,
+            BondExclusionList::space(capacity) as u32,
+        )
+    }
+
+    /// Creates the config as a keypair account, or as a PDA of the authority and token mint if 
+    /// `bump` is provided.
+    fn create_config<'a>(
+        program_id: &Pubkey,
+        payer_info: &AccountInfo<'a>,
+        config_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        token_mint_info: &AccountInfo<'a>,
+        bump: Option<u8>,
+        system_program_info: &AccountInfo<'a>,
+        rent: &Rent,
+        space: u32,
+    ) -> ProgramResult {
+        match bump {
+            Some(bump) => Create::config_pda_account(
+                program_id, 
+                payer_info, 
+                config_info, 
+                authority_info.key, 
+                token_mint_info.key, 
+                bump, 
+                system_program_info, 
+                rent, 
+                space,
+            ),
+            None => {
+                Check::signer(config_info)?;
+                Create::account(
+                    program_id, 
+                    payer_info, 
+                    config_info, 
+                    system_program_info, 
+                    rent,
+                    space,
+                )
+            },
+        }
+    }
+
+    fn check_initialize_account(
+        program_id: &Pubkey,
+        account: &impl BondAccount,
+        account_info: &AccountInfo,
+        rent: &Rent,
+    ) -> Result<(), ProgramError> {
+        // Check::uninitialized(account, account_info)?;
+        Check::writable(account_info)?; 
+        Check::rent_exempt(account_info, rent)?;
+        Check::owner(account_info, program_id)?;
+        Ok(())
+    }
+
+    fn check_initialize_pda_account(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        pda: &impl BondAccount,
+        pda_info: &AccountInfo,
+        seed: BondSeed,
+        bump: u8,
+        rent: &Rent,
+    ) -> Result<(), ProgramError> {
+        Check::pda(program_id, config_info, pda_info, seed, bump)?;
+        Self::check_initialize_account(program_id, pda, pda_info, rent)
+    }
+
+    fn initialize_config(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        authority_info: &AccountInfo,
+        draw_authority_info: &AccountInfo,
+        token_mint_info: &AccountInfo,
+        params: ConfigParams,
+        rent: &Rent,
+    ) -> ProgramResult {
+        Check::writable(config_info)?;
+        match params.bump {
+            Some(bump) => Check::config_pda(
+                program_id, 
+                config_info, 
+                authority_info.key, 
+                token_mint_info.key, 
+                bump,
+            )?,
+            None => Check::signer(config_info)?,
+        }
+        Check::signer(authority_info)?;
+        Check::signer(draw_authority_info)?;
+        Check::vault(&params.authority_vault, authority_info)?;
+        Check::vault(&params.draw_authority_vault, draw_authority_info)?;
+        if *token_mint_info.owner != TOKEN_2022_PROGRAM_ID {
+            Check::owner(token_mint_info, &spl_token::id())?;
+        }
+        if params.require_vrf.unwrap_or_default() && !params.snapshot_draws.unwrap_or_default() {
+            msg!("VRF draws require snapshot draws");
+            return Err(BondError::SnapshotRequired.into());
+        }
+        let token_mint = Mint::unpack_from_slice(&token_mint_info.data.borrow())?;
+        let config = BondConfig::unpack_from_slice(
+            &config_info.data.borrow(),
+        )?;
+        Self::check_initialize_account(
+            program_id, 
+            &config, 
+            config_info,
+            &rent,
+        )?;
+        let config = BondConfig::new(
+            *authority_info.key,
+            *draw_authority_info.key, 
+            *token_mint_info.key,
+            token_mint.decimals,
+            params,
+        );
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    fn initialize_state(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        state_info: &AccountInfo,
+        bump: u8,
+        rent: &Rent,
+    ) -> ProgramResult {
+        let state = BondState::unpack_from_slice(
+            &state_info.data.borrow(),
+        )?;
+        Self::check_initialize_pda_account(
+            program_id, 
+            config_info, 
+            &state,
+            state_info, 
+            BondSeed::State, 
+            bump, 
+            &rent,
+        )?;
+        let state = BondState::new(
+            *config_info.key,
+            bump,
+            0,
+            0,
+            0,
+            1,
+        );
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    fn initialize_fee(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        fee_info: &AccountInfo,
+        bump: u8,
+        rent: &Rent,
+    ) -> ProgramResult {
+        let fee = BondFee::unpack_from_slice(
+            &fee_info.data.borrow(),
+        )?;
+        Self::check_initialize_pda_account(
+            program_id, 
+            config_info, 
+            &fee,
+            fee_info, 
+            BondSeed::Fee, 
+            bump, 
+            &rent,
+        )?;
+        let fee = BondFee::new(
+            *config_info.key,
+            bump,
+        );
+        pack_account(&fee, &mut fee_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    fn initialize_exclusion_list(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        exclusion_list_info: &AccountInfo,
+        bump: u8,
+        capacity: u32,
+        accounts: Vec<Pubkey>,
+        rent: &Rent,
+    ) -> ProgramResult {
+        let exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
+            &exclusion_list_info.data.borrow(),
+        )?;
+        Self::check_initialize_pda_account(
+            program_id, 
+            config_info, 
+            &exclusion_list,
+            exclusion_list_info, 
+            BondSeed::ExclusionList, 
+            bump, 
+            &rent,
+        )?;
+        BondExclusionList::new(
+            *config_info.key,
+            bump,
+            capacity,
+            accounts,
+        ).serialize(
+            &mut &mut exclusion_list_info.data.borrow_mut()[..],
+        )?;
+        Ok(())
+    }
+
+    fn initialize_share(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        account_info: &AccountInfo,
+        authority: Pubkey,
+        numerator: u32,
+        denominator: u32,
+        seed: BondSeed,
+        bump: u8,
+        rent: &Rent,
+    ) -> ProgramResult {
+        let account = BondShare::unpack_from_slice(
+            &account_info.data.borrow(),
+        )?;
+        Self::check_initialize_pda_account(
+            program_id, 
+            config_info, 
+            &account,
+            account_info, 
+            seed, 
+            bump, 
+            &rent,
+        )?;
+        let share = BondShare::new(
+            authority, 
+            bump, 
             numerator,
             denominator,
+        );
+        pack_account(&share, &mut account_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    /// The default share of the epoch fee for a [BondShare] `seed`.
+    fn default_share(
+        seed: &BondSeed,
+    ) -> Result<Ratio, ProgramError> {
+        match seed {
+            BondSeed::Equity => Ok(DEFAULT_EQUITY_SHARE),
+            BondSeed::Treasury => Ok(DEFAULT_TREASURY_SHARE),
+            BondSeed::Jackpot => Ok(DEFAULT_JACKPOT_SHARE),
+            BondSeed::Stake => Ok(DEFAULT_STAKE_SHARE),
+            _ => {
+                msg!("Invalid share seed {}", seed.as_ref());
+                Err(BondError::InvalidSeeds.into())
+            },
+        }
+    }
+
+    /// Initialize accounts.
+    fn process_initialize(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        params: InitializeParams,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let state_info = next_account_info(account_info_iter)?;
+        let fee_info = next_account_info(account_info_iter)?;
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let equity_info = next_account_info(account_info_iter)?;
+        let treasury_info = next_account_info(account_info_iter)?;
+        let jackpot_info = next_account_info(account_info_iter)?;
+        let stake_info = next_account_info(account_info_iter)?;
+
+        // Initial validation.
+        Check::signer(payer_info)?;
+
+        // The default PDA account authority.
+        let authority = *config_info.key;
+
+        // Rent.
+        let rent = Rent::get()?;
+
+        // Config Account.
+        msg!("Initialize Config");
+        Self::initialize_config(
+            program_id, 
+            config_info, 
+            authority_info, 
+            draw_authority_info, 
+            token_mint_info, 
+            params.config, 
+            &rent,
+        )?;
+
+        // State PDA Account.
+        msg!("Initialize State");
+        Self::initialize_state(
+            program_id, 
+            config_info, 
+            state_info, 
+            params.state_bump, 
+            &rent,
+        )?;
+
+        // Fee PDA Account.
+        msg!("Initialize Fee");
+        Self::initialize_fee(
+            program_id, 
+            config_info, 
+            fee_info, 
+            params.fee_bump, 
+            &rent,
+        )?;
+
+        // Exclusion List PDA Account.
+        msg!("Initialize Exclusion List");
+        Self::initialize_exclusion_list(
+            program_id, 
+            config_info, 
+            exclusion_list_info, 
+            params.exclusion_list_bump, 
+            params.exclusion_list_capacity, 
+            params.exclusion_list_accounts, 
+            &rent,
+        )?;
+
+        // Equity PDA Account.
+        msg!("Initialize Equity");
+        let equity_share = params.equity_share.unwrap_or(DEFAULT_EQUITY_SHARE);
+        Self::initialize_share(
+            program_id, 
+            config_info, 
+            equity_info, 
+            authority, 
+            equity_share.numerator,
+            equity_share.denominator,
+            BondSeed::Equity, 
+            params.equity_bump, 
+            &rent,
+        )?;
+
+        // Treasury PDA Account.
+        msg!("Initialize Treasury");
+        let treasury_share = params.treasury_share.unwrap_or(DEFAULT_TREASURY_SHARE);
+        Self::initialize_share(
+            program_id, 
+            config_info, 
+            treasury_info, 
+            authority, 
+            treasury_share.numerator,
+            treasury_share.denominator,
+            BondSeed::Treasury, 
+            params.treasury_bump, 
+            &rent,
+        )?;
+
+        // Jackpot PDA Account.
+        msg!("Initialize Jackpot");
+        let jackpot_share = params.jackpot_share.unwrap_or(DEFAULT_JACKPOT_SHARE);
+        Self::initialize_share(
+            program_id, 
+            config_info, 
+            jackpot_info, 
+            authority, 
+            jackpot_share.numerator,
+            jackpot_share.denominator,
+            BondSeed::Jackpot, 
+            params.jackpot_bump, 
+            &rent,
+        )?;
+
+        // Stake PDA Account.
+        msg!("Initialize Stake");
+        let stake_share = params.stake_share.unwrap_or(DEFAULT_STAKE_SHARE);
+        Self::initialize_share(
+            program_id, 
+            config_info, 
+            stake_info, 
+            authority, 
+            stake_share.numerator,
+            stake_share.denominator,
+            BondSeed::Stake, 
+            params.stake_bump, 
+            &rent,
+        )?;
+
+        msg!("Initialize Bond Complete!");
+        Ok(())
+    }
+
+    /// Validates the accounts shared by the `CreateAndInitialize*` instructions and returns the 
+    /// game's initialized config.
+    fn check_create_and_initialize_accounts(
+        program_id: &Pubkey,
+        payer_info: &AccountInfo,
+        authority_info: &AccountInfo,
+        config_info: &AccountInfo,
+        system_program_info: &AccountInfo,
+    ) -> Result<BondConfig, ProgramError> {
+        Check::signer_and_writable(payer_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::account(system_program_info, &system_program::id())?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Ok(config)
+    }
+
+    fn process_create_and_initialize_config(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        space: u32,
+        params: ConfigParams,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+        Check::account(system_program_info, &system_program::id())?;
+        Check::space(space, BondConfig::LEN)?;
+
+        let rent = Rent::get()?;
+        Self::create_config(
+            program_id, 
+            payer_info, 
+            config_info, 
+            authority_info, 
+            token_mint_info, 
+            params.bump, 
+            system_program_info, 
+            &rent,
+            space,
+        )?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::uninitialized(&config, config_info)?;
+        Self::initialize_config(
+            program_id, 
+            config_info, 
+            authority_info, 
+            draw_authority_info, 
+            token_mint_info, 
+            params, 
+            &rent,
+        )
+    }
+
+    fn process_create_and_initialize_state(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let state_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Self::check_create_and_initialize_accounts(
+            program_id, 
+            payer_info, 
+            authority_info, 
+            config_info, 
+            system_program_info,
+        )?;
+        Check::space(space, BondState::LEN)?;
+
+        let rent = Rent::get()?;
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            state_info, 
+            BondSeed::State, 
+            bump, 
+            system_program_info, 
+            &rent, 
+            space,
+        )?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::uninitialized(&state, state_info)?;
+        Self::initialize_state(
+            program_id, 
+            config_info, 
+            state_info, 
+            bump, 
+            &rent,
+        )
+    }
+
+    fn process_create_and_initialize_fee(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let fee_info = next_account_info(account_info_iter)?;
+        let fee_ata_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let associated_token_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let config = Self::check_create_and_initialize_accounts(
+            program_id, 
+            payer_info, 
+            authority_info, 
+            config_info, 
+            system_program_info,
+        )?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        Check::account(associated_token_program_info, &spl_associated_token_account::id())?;
+        Check::space(space, BondFee::LEN)?;
+
+        let rent = Rent::get()?;
+        Create::pda_and_ata_accounts(
+            program_id, 
+            config_info, 
+            payer_info, 
+            fee_info, 
+            BondSeed::Fee, 
+            bump, 
+            fee_ata_info, 
+            token_mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info, 
+            &rent,
+            space,
+        )?;
+        let fee = BondFee::unpack_from_slice(&fee_info.data.borrow())?;
+        Check::uninitialized(&fee, fee_info)?;
+        Self::initialize_fee(
+            program_id, 
+            config_info, 
+            fee_info, 
+            bump, 
+            &rent,
+        )
+    }
+
+    fn process_create_and_initialize_exclusion_list(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+        capacity: u32,
+        exclusion_list_accounts: Vec<Pubkey>,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Self::check_create_and_initialize_accounts(
+            program_id, 
+            payer_info, 
+            authority_info, 
+            config_info, 
+            system_program_info,
+        )?;
+
+        let rent = Rent::get()?;
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            exclusion_list_info, 
+            BondSeed::ExclusionList, 
+            bump, 
+            system_program_info, 
+            &rent, 
+            space,
+        )?;
+        let exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
+            &exclusion_list_info.data.borrow(),
+        )?;
+        Check::uninitialized(&exclusion_list, exclusion_list_info)?;
+        Self::initialize_exclusion_list(
+            program_id, 
+            config_info, 
+            exclusion_list_info, 
+            bump, 
+            capacity, 
+            exclusion_list_accounts, 
+            &rent,
+        )
+    }
+
+    fn process_create_and_initialize_share(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seed: BondSeed,
+        bump: u8,
+        space: u32,
+        share: Option<Ratio>,
+    ) -> ProgramResult {
+
+        // Unpack accounts.
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let share_info = next_account_info(account_info_iter)?;
+        let share_ata_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let associated_token_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let config = Self::check_create_and_initialize_accounts(
+            program_id, 
+            payer_info, 
+            authority_info, 
+            config_info, 
+            system_program_info,
+        )?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        Check::account(associated_token_program_info, &spl_associated_token_account::id())?;
+        let share = share.unwrap_or(Self::default_share(&seed)?);
+        Check::space(space, BondShare::LEN)?;
+
+        let rent = Rent::get()?;
+        Create::pda_and_ata_accounts(
+            program_id, 
+            config_info, 
+            payer_info, 
+            share_info, 
+            seed.clone(), 
+            bump, 
+            share_ata_info, 
+            token_mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info, 
+            &rent,
+            space,
+        )?;
+        let account = BondShare::unpack_from_slice(&share_info.data.borrow())?;
+        Check::uninitialized(&account, share_info)?;
+        Self::initialize_share(
+            program_id, 
+            config_info, 
+            share_info, 
+            *config_info.key, 
+            share.numerator, 
+            share.denominator, 
+            seed, 
+            bump, 
+            &rent,
+        )
+    }
+
+    fn check_draw_account(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        draw_authority_info: &AccountInfo,
+        config: &BondConfig,
+    ) -> Result<(), ProgramError> {
+        Check::owner(config_info, program_id)?;
+        Check::signer(draw_authority_info)?;
+        Check::account(draw_authority_info, &config.draw_authority)
+            .map_err(|_| BondError::InvalidAuthority)?;
+        Check::vault(&config.draw_authority_vault, draw_authority_info)?;
+        Check::valid(config, config_info)
+    }
+
+    /// Checks the caller of a draw, returning whether it's the draw authority. Any signer may 
+    /// fulfill the VRF draws of games allowing permissionless draws.
+    fn check_draw_caller(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        caller_info: &AccountInfo,
+        config: &BondConfig,
+        is_vrf: bool,
+    ) -> Result<bool, ProgramError> {
+        let is_draw_authority = caller_info.key.eq(&config.draw_authority);
+        if is_vrf && !is_draw_authority && config.is_permissionless_draw() {
+            Check::signer(caller_info)?;
+            Check::owner(config_info, program_id)?;
+            Check::valid(config, config_info)?;
+        } else {
+            Self::check_draw_account(program_id, config_info, caller_info, config)?;
+        }
+        Ok(is_draw_authority)
+    }
+
+    fn check_draw_pda_account(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        share_info: &AccountInfo,
+        share: &impl BondProgramAccount,
+        share_ata_info: &AccountInfo,
+        share_ata: &Account,
+    ) -> Result<(), ProgramError> {
+        Check::account(config_info, &share.authority())?;
+        Check::owner(&share_info, program_id)?;
+        Check::valid(share, share_info)?;
+        Check::pubkey(&share_ata.owner, &share_info.key)
+    }
+
+    /// The ATA of a share without a numerator isn't read, as nothing is transferred to it.
+    fn check_split_share_account(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        share_info: &AccountInfo,
+        share: &BondShare,
+        share_ata_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if share.numerator == 0 {
+            Check::account(config_info, &share.authority)?;
+            Check::owner(share_info, program_id)?;
+            return Check::valid(share, share_info)
+        }
+        let share_ata = Account::unpack_from_slice(&share_ata_info.data.borrow())?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            share_info, 
+            share, 
+            share_ata_info,
+            &share_ata,
+        )
+    }
+
+    fn process_split_shares(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: Option<u64>,
+        destinations: Option<u8>,
+    ) -> ProgramResult {
+
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        let is_draw_authority = authority_info.key.eq(&config.draw_authority);
+        if config.allow_permissionless_split {
+            Check::signer(authority_info)?;
+            Check::owner(config_info, program_id)?;
+            Check::valid(&config, config_info)?;
+        } else {
+            Self::check_draw_account(program_id, config_info, authority_info, &config)?;
+        }
+        Check::active(&config)?;
+        Check::in_season(&config, Clock::get()?.unix_timestamp)?;
+
+        let fee_info = next_account_info(account_info_iter)?;
+        let fee = BondFee::unpack_from_slice(&fee_info.data.borrow())?;
+        let fee_ata_info = next_account_info(account_info_iter)?;
+        let fee_ata = Account::unpack_from_slice(&fee_ata_info.data.borrow())?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            fee_info, 
+            &fee, 
+            fee_ata_info,
+            &fee_ata,
+        )?;
+        let is_harvested = fee.last_update_epoch == Clock::get()?.epoch;
+        if Adapter::requires_harvest(config.lst_adapter) && !is_harvested {
+            msg!("Stake Pool last updated through Bond in epoch {}", fee.last_update_epoch);
+            return Err(BondError::StaleStakePool.into());
+        }
+        
+        let equity_info = next_account_info(account_info_iter)?;
+        let equity = BondShare::unpack_from_slice(&equity_info.data.borrow())?;
+        let equity_ata_info = next_account_info(account_info_iter)?;
+        Self::check_split_share_account(
+            program_id, 
+            config_info, 
+            equity_info, 
+            &equity, 
+            equity_ata_info,
+        )?;
+
+        let treasury_info = next_account_info(account_info_iter)?;
+        let treasury = BondShare::unpack_from_slice(&treasury_info.data.borrow())?;
+        let treasury_ata_info = next_account_info(account_info_iter)?;
+        Self::check_split_share_account(
+            program_id, 
+            config_info, 
+            treasury_info, 
+            &treasury, 
+            treasury_ata_info,
+        )?;
+
+        let jackpot_info = next_account_info(account_info_iter)?;
+        let jackpot = BondShare::unpack_from_slice(&jackpot_info.data.borrow())?;
+        let jackpot_ata_info = next_account_info(account_info_iter)?;
+        Self::check_split_share_account(
+            program_id, 
+            config_info, 
+            jackpot_info, 
+            &jackpot, 
+            jackpot_ata_info,
+        )?;
+
+        let stake_info = next_account_info(account_info_iter)?;       
+        let stake = BondShare::unpack_from_slice(&stake_info.data.borrow())?; 
+        let stake_ata_info = next_account_info(account_info_iter)?;
+        Self::check_split_share_account(
+            program_id, 
+            config_info, 
+            stake_info, 
+            &stake, 
+            stake_ata_info,
+        )?;
+
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        // Keepers space splits by the configured interval.
+        let clock = Clock::get()?;
+        let next_split_slot = state.next_split_slot(config.min_split_interval);
+        if clock.slot < next_split_slot {
+            msg!("Next split at slot {}", next_split_slot);
+            return Err(BondError::SplitTooSoon.into());
+        }
+
+        // Permissionless keepers (and optionally the draw authority) split at most once per epoch.
+        let is_epoch_limited = !is_draw_authority || config.split_once_per_epoch;
+        if is_epoch_limited && state.is_split_in_epoch(clock.epoch) {
+            msg!("Already split in epoch {}", clock.epoch);
+            return Err(BondError::SplitTooSoon.into());
+        }
+        state.record_split(clock.slot, clock.epoch);
+
+        // Permissionless keepers split the fee account's full balance.
+        let amount: u64 = match amount {
+            Some(amount) if is_draw_authority => amount,
+            _ => fee_ata.amount,
+        };
+
+        // Pay the crank fee to a permissionless caller.
+        let crank_fee = if is_draw_authority { 0 } else { config.crank_fee(amount) };
+        let authority_ata_info = if crank_fee > 0 {
+            let authority_ata_info = next_account_info(account_info_iter)?;
+            let authority_ata = Account::unpack_from_slice(&authority_ata_info.data.borrow())?;
+            Check::pubkey(&authority_ata.owner, authority_info.key)?;
+            Some(authority_ata_info)
+        } else {
+            None
+        };
+        let amount = amount - crank_fee;
+
+        // Rebate a slice to the ticket holders.
+        let (state_ata_info, rebate) = if config.rebate_bps > 0 {
+            let state_ata_info = next_account_info(account_info_iter)?;
+            Check::ata(state_info, &config.token_mint, state_ata_info)?;
+            let rebate = if state.ticket_weight > 0 { config.rebate(amount) } else { 0 };
+            (Some(state_ata_info), rebate)
+        } else {
+            (None, 0)
+        };
+        let amount = amount - rebate;
+
+        // Only the draw authority may skip shares.
+        let destinations = destinations.unwrap_or(SPLIT_ALL);
+        if destinations != SPLIT_ALL && !is_draw_authority {
+            msg!("Only the draw authority may select destinations");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let mut portions = [
+            (SPLIT_EQUITY, equity_ata_info, &equity, equity.try_share(amount)?),
+            (SPLIT_TREASURY, treasury_ata_info, &treasury, treasury.try_share(amount)?),
+            (SPLIT_JACKPOT, jackpot_ata_info, &jackpot, jackpot.try_share(amount)?),
+            (SPLIT_STAKE, stake_ata_info, &stake, stake.try_share(amount)?),
+        ];
+
+        // Misconfigured shares fail the split before any transfer.
+        let shares = portions.iter().try_fold(0u64, |shares, portion| {
+            shares.checked_add(portion.3).ok_or(BondError::NumericalOverflow)
+        })?;
+        if shares > amount {
+            msg!("Shares sum up to {} of {}", shares, amount);
+            return Err(BondError::SharesExceedAmount.into());
+        }
+
+        let (funded, skipped) = portions.iter().fold((0u64, 0u64), |(funded, skipped), portion| {
+            if destinations & portion.0 == 0 {
+                (funded, skipped + portion.3)
+            } else {
+                (funded + portion.3, skipped)
+            }
+        });
+        if config.skipped_share_policy == BondSkippedSharePolicy::Reallocate && funded > 0 {
+            for (destination, _, _, portion) in portions.iter_mut() {
+                if destinations & *destination != 0 {
+                    let reallocated = u128::from(skipped) * u128::from(*portion) 
+                        / u128::from(funded);
+                    *portion += u64::try_from(reallocated)
+                        .map_err(|_| BondError::NumericalOverflow)?;
+                }
+            }
+        }
+
+        // Fee -> Caller
+        if let Some(authority_ata_info) = authority_ata_info {
+            Create::token_transfer_checked(
+                config_info,
+                token_program_info,
+                token_mint_info,
+                fee_ata_info,
+                authority_ata_info,
+                fee_info,
+                BondSeed::Fee,
+                fee.bump,
+                crank_fee,
+                config.decimals,
+            )?;
+        }
+
+        // Fee -> State
+        if let Some(state_ata_info) = state_ata_info.filter(|_| rebate > 0) {
+            state.accrue_rebate(rebate - Self::transfer_fee(token_mint_info, rebate)?)?;
+            Create::token_transfer_checked(
+                config_info,
+                token_program_info,
+                token_mint_info,
+                fee_ata_info,
+                state_ata_info,
+                fee_info,
+                BondSeed::Fee,
+                fee.bump,
+                rebate,
+                config.decimals,
+            )?;
+        }
+
+        // Fee -> Shares (dust below a share's minimum stays in the fee account for its next split)
+        let mut transferred: u64 = 0;
+        let mut dust: u64 = 0;
+        for (destination, share_ata_info, share, portion) in portions {
+            if destinations & destination == 0 || portion == 0 {
+                continue;
+            }
+            if portion < share.min_transfer {
+                dust += portion;
+                continue;
+            }
+            Create::token_transfer_checked(
+                config_info,
+                token_program_info,
+                token_mint_info,
+                fee_ata_info,
+                share_ata_info,
+                fee_info,
+                BondSeed::Fee,
+                fee.bump,
+                portion,
+                config.decimals,
+            )?;
+            transferred += portion;
+        }
+
+        // The remainder excludes the skipped portions and the dust left for the next split.
+        let retained = if config.skipped_share_policy == BondSkippedSharePolicy::Reallocate 
+            && funded > 0 { 0 } else { skipped };
+        let remainder = amount
+            .checked_sub(transferred)
+            .and_then(|remainder| remainder.checked_sub(retained))
+            .and_then(|remainder| remainder.checked_sub(dust))
+            .ok_or(BondError::NumericalOverflow)?;
+        let routed = if remainder > 0 
+            && config.remainder_policy == BondRemainderPolicy::Jackpot 
+            && destinations & SPLIT_JACKPOT != 0 
+            && jackpot.numerator > 0 
+        {
+            // Fee -> Jackpot
+            Create::token_transfer_checked(
+                config_info,
+                token_program_info,
+                token_mint_info,
+                fee_ata_info,
+                jackpot_ata_info,
+                fee_info,
+                BondSeed::Fee,
+                fee.bump,
+                remainder,
+                config.decimals,
+            )?;
+            remainder
+        } else {
+            0
+        };
+        state.split_remainder = remainder - routed;
+        if state.split_remainder > 0 {
+            msg!("Split remainder {} retained in the fee account", state.split_remainder);
+        }
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        // The fee account must have released exactly the split's transfers.
+        let released = crank_fee + rebate + transferred + routed;
+        let fee_ata_amount = Account::unpack_from_slice(&fee_ata_info.data.borrow())?.amount;
+        if fee_ata.amount.checked_sub(fee_ata_amount) != Some(released) {
+            msg!("Fee account released {} of {}", fee_ata.amount - fee_ata_amount, released);
+            return Err(BondError::InvariantViolated.into());
+        }
+        Ok(())
+    }
+
+    fn process_draw(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        receiver_seed: u64,
+        draw_seed: u64,
+        receiver_weight: u64,
+        total_weight: u64,
+        proof: Option<SnapshotProof>,
+        simulate: bool,
+        vrf_result: Option<[u8; 32]>,
+    ) -> ProgramResult {
+        let is_vrf = vrf_result.is_some();
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        let is_draw_authority = Self::check_draw_caller(
+            program_id, 
+            config_info, 
+            draw_authority_info, 
+            &config, 
+            is_vrf,
+        )?;
+        Check::active(&config)?;
+        Check::in_season(&config, Clock::get()?.unix_timestamp)?;
+
+        // The winners of VRF games are only selected by `FulfillDraw`.
+        if config.require_vrf && !is_vrf && !simulate {
+            return Err(BondError::VrfRequired.into());
+        }
+
+        // Snapshot draws select their winner on chain, and the slot hash mixed into `Draw`'s 
+        // entropy is known to (and grindable by) the draw authority when it submits the draw.
+        if config.snapshot_draws && !is_vrf && !simulate {
+            msg!("Snapshot draws must be fulfilled from a VRF result");
+            return Err(BondError::VrfRequired.into());
+        }
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow_mut())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+        
+        let jackpot_info = next_account_info(account_info_iter)?;
+        let jackpot = BondShare::unpack_from_slice(&jackpot_info.data.borrow())?;
+        let jackpot_ata_info = next_account_info(account_info_iter)?;
+        let jackpot_ata = Account::unpack_from_slice(&jackpot_ata_info.data.borrow())?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            jackpot_info, 
+            &jackpot, 
+            jackpot_ata_info,
+            &jackpot_ata,
+        )?;
+
+        let treasury_info = next_account_info(account_info_iter)?;
+        let treasury = BondShare::unpack_from_slice(&treasury_info.data.borrow())?;
+        let treasury_ata_info = next_account_info(account_info_iter)?;
+        let treasury_ata = Account::unpack_from_slice(&treasury_ata_info.data.borrow())?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            treasury_info, 
+            &treasury, 
+            treasury_ata_info,
+            &treasury_ata,
+        )?;
+        Check::pda(program_id, config_info, treasury_info, BondSeed::Treasury, treasury.bump)?;
+
+        let receiver_info = next_account_info(account_info_iter)?;
+        let receiver_ata_info = next_account_info(account_info_iter)?;
+
+        // Only the jackpot PDA may receive a draw among the game's accounts (a rollover), which 
+        // all belong to the program.
+        if receiver_info.key != jackpot_info.key {
+            if *receiver_info.key == Pubkey::default() {
+                return Err(BondError::DefaultReceiver.into());
+            }
+            if receiver_info.owner == program_id || receiver_info.key == config_info.key {
+                msg!("Receiver {} is a game account", receiver_info.key);
+                return Err(BondError::GameAccountReceiver.into());
+            }
+        }
+
+        // Escrowed prizes are pulled by the winner to any of its token accounts with `ClaimPrize`, 
+        // so the receiver's token account may be missing unless the prize is pushed to it or 
+        // weighted by its balance.
+        let is_pull_only = config.payout_mode == BondPayoutMode::Claim 
+            && (config.ticket_tree.is_some() || config.snapshot_draws);
+        let receiver_ata = match Account::unpack_from_slice(&receiver_ata_info.data.borrow()) {
+            Ok(receiver_ata) => Some(receiver_ata),
+            Err(_) if is_pull_only => None,
+            Err(error) => return Err(error),
+        };
+
+        let draw_info = next_account_info(account_info_iter)?;
+        match config.draw_tree {
+            Some(draw_tree) => {
+                Check::writable(draw_info)?;
+                Check::account(draw_info, &draw_tree)?;
+            },
+            None => {
+                let draw = BondDraw::unpack_from_slice(&draw_info.data.borrow())?;
+                Check::uninitialized(&draw, draw_info)?;
+                Check::owner(draw_info, program_id)?;
+            },
+        }
+
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        let slot_hashes_info = next_account_info(account_info_iter)?;
+        Check::account(slot_hashes_info, &sysvar::slot_hashes::id())?;
+
+        // Winners must be registered and are paid to their linked payout owner.
+        if config.require_registration && receiver_info.key != jackpot_info.key {
+            let participant_info = next_account_info(account_info_iter)?;
+            let participant = try_from_slice_unchecked::<BondParticipant>(
+                &participant_info.data.borrow(),
+            )?;
+            Check::owner(participant_info, program_id)?;
+            Check::valid(&participant, participant_info)?;
+            Check::account(config_info, &participant.authority)?;
+            Check::account(receiver_info, &participant.wallet)?;
+            if let Some(receiver_ata) = &receiver_ata {
+                Check::pubkey(&receiver_ata.owner, &participant.payout)?;
+            }
+        } else if let Some(receiver_ata) = &receiver_ata {
+            Check::pubkey(&receiver_ata.owner, receiver_info.key)?;
+        }
+
+        // The prize is paid to the payout owner's associated token account for the game's mint.
+        if let Some(receiver_ata) = &receiver_ata {
+            Check::owner_ata(receiver_ata, &config.token_mint, receiver_ata_info)?;
+        }
+
+        let tree_program_infos = match config.draw_tree {
+            Some(_) => {
+                let compression_program_info = next_account_info(account_info_iter)?;
+                let noop_program_info = next_account_info(account_info_iter)?;
+                Check::account(compression_program_info, &ACCOUNT_COMPRESSION_PROGRAM_ID)?;
+                Check::account(noop_program_info, &NOOP_PROGRAM_ID)?;
+                Some((compression_program_info, noop_program_info))
+            },
+            None => None,
+        };
+
+        let leaderboard_info = Self::next_leaderboard_info(&config, account_info_iter)?;
+
+        let needs_instructions = config.attestation_signer.is_some() 
+            || config.isolate_draws 
+            || config.oracle.is_some();
+        let instructions_info = if needs_instructions {
+            let instructions_info = next_account_info(account_info_iter)?;
+            Check::account(instructions_info, &sysvar::instructions::id())?;
+            Some(instructions_info)
+        } else {
+            None
+        };
+
+        // The draw can't be bundled with arbitrary instructions that could manipulate balances.
+        if let (true, Some(instructions_info)) = (config.isolate_draws, instructions_info) {
+            Check::isolated(
+                instructions_info, 
+                program_id, 
+                MAX_DRAW_TRANSACTION_INSTRUCTIONS, 
+                &[COMPUTE_BUDGET_PROGRAM_ID, ed25519_program::id()],
+            )?;
+        }
+
+        // The operator's attestation of the selection inputs.
+        let attestation = match (config.attestation_signer, instructions_info) {
+            (Some(attestation_signer), Some(instructions_info)) => {
+                let mut prefix = config_info.key.to_bytes().to_vec();
+                prefix.extend_from_slice(&draw_seed.to_le_bytes());
+                Self::find_attestation(instructions_info, &attestation_signer, &prefix)?
+            },
+            _ => None,
+        };
+        if config.require_attestation && attestation.is_none() {
+            return Err(BondError::AttestationRequired.into());
+        }
+
+        // The oracle's signature of the draw's result.
+        if let (Some(oracle), Some(instructions_info)) = (config.oracle, instructions_info) {
+            let mut message = config_info.key.to_bytes().to_vec();
+            message.extend_from_slice(&draw_seed.to_le_bytes());
+            message.extend_from_slice(receiver_info.key.as_ref());
+            message.extend_from_slice(&receiver_seed.to_le_bytes());
+            let signature = Self::find_attestation(instructions_info, &oracle, &message)
+                .map_err(|_| BondError::InvalidOracleSignature)?;
+            if signature != Some(keccak::hash(&message).to_bytes()) {
+                msg!("Draw {} isn't signed by the oracle {}", draw_seed, oracle);
+                return Err(BondError::InvalidOracleSignature.into());
+            }
+        }
+
+        // The exclusion list and the receiver's validation against it (for large lists).
+        let exclusion_infos = if receiver_info.key != jackpot_info.key {
+            let exclusion_list_info = next_account_info(account_info_iter)?;
+            let validation_info = if config.require_exclusion_validation {
+                Some(next_account_info(account_info_iter)?)
+            } else {
+                None
+            };
+            Some((exclusion_list_info, validation_info))
+        } else {
+            None
+        };
+
+        // The draw's snapshot and the buffer holding the selected point (if the snapshot has any).
+        let snapshot_infos = if config.snapshot_draws {
+            let snapshot_info = next_account_info(account_info_iter)?;
+            let snapshot = try_from_slice_unchecked::<BondSnapshot>(&snapshot_info.data.borrow())?;
+            Check::owner(snapshot_info, program_id)?;
+            Check::valid(&snapshot, snapshot_info)?;
+            Check::account(config_info, &snapshot.authority)?;
+            let buffer_info = if snapshot.buffers > 0 {
+                Some(next_account_info(account_info_iter)?)
+            } else {
+                None
+            };
+            Some((snapshot, buffer_info))
+        } else {
+            None
+        };
+
+        // The additional jackpot mints' [mint, mint_token_program, jackpot_mint_ata, 
+        // receiver_mint_ata] accounts, each mint being paid through its own token program.
+        let mut jackpot_mint_infos = Vec::new();
+        let jackpot_mints = match config.jackpot_mints {
+            Some(jackpot_mints) => {
+                let jackpot_mints_info = next_account_info(account_info_iter)?;
+                Check::account(jackpot_mints_info, &jackpot_mints)?;
+                let jackpot_mints = try_from_slice_unchecked::<BondJackpotMints>(
+                    &jackpot_mints_info.data.borrow(),
+                )?;
+                Some((jackpot_mints_info, jackpot_mints))
+            },
+            None => None,
+        };
+        if let Some((_, jackpot_mints)) = &jackpot_mints {
+            for mint in jackpot_mints.mints.iter().copied() {
+                let mint_info = next_account_info(account_info_iter)?;
+                Check::account(mint_info, &mint)?;
+                let mint_token_program_info = next_account_info(account_info_iter)?;
+                Check::token_mint(mint_info, mint_token_program_info)?;
+                let jackpot_mint_ata_info = next_account_info(account_info_iter)?;
+                Check::ata_with_program(
+                    jackpot_info, 
+                    &mint, 
+                    mint_token_program_info.key, 
+                    jackpot_mint_ata_info,
+                )?;
+                let receiver_mint_ata_info = next_account_info(account_info_iter)?;
+                let receiver_mint_ata = Account::unpack_from_slice(
+                    &receiver_mint_ata_info.data.borrow(),
+                )?;
+                if let Some(receiver_ata) = &receiver_ata {
+                    Check::pubkey(&receiver_mint_ata.owner, &receiver_ata.owner)?;
+                    Check::owner_ata(&receiver_mint_ata, &mint, receiver_mint_ata_info)?;
+                }
+                jackpot_mint_infos.push((
+                    mint_info, 
+                    mint_token_program_info, 
+                    jackpot_mint_ata_info, 
+                    receiver_mint_ata_info,
+                ));
+            }
+        }
+
+        // Exclude prizes that are waiting to be claimed.
+        let amount = jackpot_ata.amount.saturating_sub(state.reserved);
+        if amount == 0 {
+            return Ok(())
+        }
+
+        // Pay the crank fee to a permissionless caller.
+        let crank_fee = if is_draw_authority { 0 } else { config.crank_fee(amount) };
+        let draw_authority_ata_info = if crank_fee > 0 {
+            let draw_authority_ata_info = next_account_info(account_info_iter)?;
+            let draw_authority_ata = Account::unpack_from_slice(
+                &draw_authority_ata_info.data.borrow(),
+            )?;
+            Check::pubkey(&draw_authority_ata.owner, draw_authority_info.key)?;
+            Some(draw_authority_ata_info)
+        } else {
+            None
+        };
+        let amount = amount - crank_fee;
+
+        let epoch = Clock::get()?;
+        let id = state.draw_id + 1;
+
+        if draw_seed != id {
+            return Err(BondError::InvalidSeeds.into());
+        }
+
+        // Draws are spaced by the configured number of epochs.
+        let next_draw_epoch = state.next_draw_epoch(config.epochs_per_draw);
+        if epoch.epoch < next_draw_epoch {
+            msg!("Next draw in epoch {}", next_draw_epoch);
+            return Err(BondError::DrawTooSoon.into());
+        }
+
+        // On chain selection uses the VRF result. Otherwise `receiver_seed` mixed with the most 
+        // recent slot hash is only recorded, as the draw authority selected the receiver.
+        let entropy = match vrf_result {
+            Some(vrf_result) => vrf_result,
+            None => Self::mix_slot_hash(slot_hashes_info, receiver_seed)?,
+        };
+
+        // The receiver and the weights are selected on chain from the draw's sealed snapshot.
+        if let Some((snapshot, buffer_info)) = snapshot_infos {
+            if snapshot.draw_id != id || !snapshot.is_sealed {
+                msg!("Snapshot of draw {} isn't sealed for draw {}", snapshot.draw_id, id);
+                return Err(BondError::InvalidSnapshot.into());
+            }
+            let entry = match (snapshot.point(&entropy), buffer_info, proof) {
+                (Some(point), _, Some(proof)) if snapshot.root.is_some() => {
+                    let leaf = BondSnapshotLeaf {
+                        wallet: *receiver_info.key,
+                        balance: proof.balance,
+                        held_since: proof.held_since,
+                        weight: snapshot.time_weighted(proof.balance, proof.held_since),
+                        offset: proof.offset,
+                    };
+                    if !snapshot.verify(&leaf, proof.index, &proof.proof) {
+                        return Err(BondError::InvalidSnapshotProof.into());
+                    }
+                    if !snapshot.is_eligible(leaf.balance) {
+                        msg!("Leaf balance {} is below {}", leaf.balance, snapshot.min_balance);
+                        return Err(BondError::InsufficientWeight.into());
+                    }
+                    if !leaf.holds(point) {
+                        msg!("Leaf {} doesn't hold point {}", proof.index, point);
+                        return Err(BondError::ReceiverNotSelected.into());
+                    }
+                    Some(BondSnapshotEntry { 
+                        wallet: leaf.wallet, 
+                        weight: leaf.weight, 
+                        held_since: leaf.held_since,
+                    })
+                },
+                (Some(_), _, None) if snapshot.root.is_some() => {
+                    msg!("A snapshot proof is required");
+                    return Err(BondError::InvalidSnapshotProof.into());
+                },
+                (Some(point), Some(buffer_info), _) => {
+                    let buffer = try_from_slice_unchecked::<BondParticipantBuffer>(
+                        &buffer_info.data.borrow(),
+                    )?;
+                    Check::owner(buffer_info, program_id)?;
+                    Check::valid(&buffer, buffer_info)?;
+                    Check::account(config_info, &buffer.authority)?;
+                    if buffer.draw_id != id {
+                        return Err(BondError::InvalidSnapshot.into());
+                    }
+                    let entry = buffer.select(point).cloned();
+                    if entry.is_none() {
+                        msg!("Buffer {} doesn't hold point {}", buffer.index, point);
+                        return Err(BondError::ReceiverNotSelected.into());
+                    }
+                    entry
+                },
+                _ => snapshot.select(&entropy).cloned(),
+            };
+            let (selected, selected_weight) = match entry {
+                Some(entry) => (entry.wallet, entry.weight),
+                None => (*jackpot_info.key, 0),
+            };
+            if receiver_info.key != &selected {
+                msg!("Receiver {} isn't selected, {} is", receiver_info.key, selected);
+                return Err(BondError::ReceiverNotSelected.into());
+            }
+            if receiver_weight != selected_weight || total_weight != snapshot.total_weight {
+                return Err(BondError::InvalidWeight.into());
+            }
+        }
+
+        // A jackpot below the minimum draw amount rolls over without counting towards the rollover 
+        // limit.
+        let is_below_min = amount < config.min_draw_amount;
+        let receiver = if is_below_min { jackpot_info.key } else { receiver_info.key };
+        let is_rollover = receiver.eq(jackpot_info.key);
+        let rollover = if is_below_min { 
+            state.rollover 
+        } else if is_rollover { 
+            state.rollover + 1 
+        } else { 
+            0 
+        };
+
+        // Winners are weighted by their token balance (or their snapshot weight, whose balance 
+        // floor is enforced by the snapshot), unless proven by a ticket on claim.
+        let is_weighed = !is_rollover && config.ticket_tree.is_none();
+        let is_balance_weight = is_weighed && !config.snapshot_draws;
+        let balance = receiver_ata.as_ref().map_or(0, |receiver_ata| receiver_ata.amount);
+        if is_balance_weight && !config.is_eligible(balance) {
+            msg!("Receiver balance {} is below {}", balance, config.min_eligible_weight);
+            return Err(BondError::InsufficientWeight.into());
+        }
+
+        // No single winner may have better odds than the threshold.
+        if !is_rollover {
+            if is_balance_weight && receiver_weight != balance {
+                msg!("Receiver weight {} doesn't match {}", receiver_weight, balance);
+                return Err(BondError::InvalidWeight.into());
+            }
+            if receiver_weight > total_weight {
+                msg!("Receiver weight {} exceeds the total {}", receiver_weight, total_weight);
+                return Err(BondError::InvalidWeight.into());
+            }
+            if config.exceeds_odds_threshold(receiver_weight, total_weight) {
+                msg!("Receiver weight {} out of {}", receiver_weight, total_weight);
+                return Err(BondError::OddsThresholdExceeded.into());
+            }
+        }
+
+        // The latest winner sits out the cooldown's draws.
+        let is_cooling_down = receiver == &state.last_winner 
+            && id > state.last_win_draw_id
+            && id - state.last_win_draw_id <= config.winner_cooldown;
+        if !is_rollover && is_cooling_down {
+            msg!("Receiver won draw {}", state.last_win_draw_id);
+            return Err(BondError::WinnerCooldown.into());
+        }
+
+        // Excluded accounts can't win. Large lists are checked ahead of the draw with 
+        // `ValidateReceiverChunk`, whose validation must be complete for the current list during 
+        // this epoch.
+        match (is_rollover, exclusion_infos) {
+            (false, Some((exclusion_list_info, Some(validation_info)))) => {
+                let header = Self::unpack_exclusion_list_header(
+                    program_id, 
+                    config_info, 
+                    exclusion_list_info,
+                )?;
+                let validation = try_from_slice_unchecked::<BondExclusionValidation>(
+                    &validation_info.data.borrow(),
+                )?;
+                Check::owner(validation_info, program_id)?;
+                Check::pda(
+                    program_id, 
+                    config_info, 
+                    validation_info, 
+                    BondSeed::ExclusionValidation, 
+                    validation.bump,
+                )?;
+                if !validation.is_complete 
+                    || !validation.is_for(receiver, id, epoch.epoch, header.revision) 
+                {
+                    return Err(BondError::ExclusionValidationRequired.into());
+                }
+            },
+            (false, Some((exclusion_list_info, None))) => {
+                let exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
+                    &exclusion_list_info.data.borrow(),
+                )?;
+                Check::owner(exclusion_list_info, program_id)?;
+                Check::valid(&exclusion_list, exclusion_list_info)?;
+                Check::pda(
+                    program_id, 
+                    config_info, 
+                    exclusion_list_info, 
+                    BondSeed::ExclusionList, 
+                    exclusion_list.bump,
+                )?;
+                if exclusion_list.is_excluded(receiver, epoch.epoch) {
+                    msg!("Receiver {} is excluded", receiver);
+                    return Err(BondError::ExcludedReceiver.into());
+                }
+            },
+            _ => {},
+        }
+
+        // Hold the jackpot in escrow until the winner claims it.
+        let is_escrowed = !is_rollover && config.payout_mode == BondPayoutMode::Claim;
+
+        // Skim a portion of a rolled over jackpot into the treasury.
+        let retained = if is_rollover { config.rollover_retention(amount) } else { 0 };
+
+        // Pay the additional mints' balances (excluding their unclaimed prizes) along with the 
+        // jackpot, or escrow them with it.
+        let mut extra_prizes = Vec::new();
+        if let (false, Some((_, jackpot_mints))) = (is_rollover, &jackpot_mints) {
+            for (mint_info, _, jackpot_mint_ata_info, _) in jackpot_mint_infos.iter() {
+                let jackpot_mint_ata = Account::unpack_from_slice(
+                    &jackpot_mint_ata_info.data.borrow(),
+                )?;
+                let amount = jackpot_mint_ata.amount
+                    .saturating_sub(jackpot_mints.reserved(mint_info.key));
+                if amount > 0 {
+                    extra_prizes.push(BondPrize {
+                        mint: *mint_info.key,
+                        amount,
+                    });
+                }
+            }
+        }
+
+        let mut draw = BondDraw::new(
+            draw_authority_info.key.clone(),
+            config_info.key.clone(),
+            id,
+            amount,
+            receiver_seed,
+            receiver.clone(),
+            rollover,
+            epoch.slot,
+            epoch.epoch_start_timestamp,
+            epoch.epoch,
+            epoch.unix_timestamp,
+            !is_escrowed,
+            retained,
+            extra_prizes.clone(),
+        );
+        draw.attestation = attestation;
+        draw.entropy = entropy;
+        if !is_rollover && !is_escrowed {
+            draw.transfer_fee = Self::transfer_fee(token_mint_info, amount)?;
+        }
+        if !is_rollover {
+            draw.receiver_weight = receiver_weight;
+            draw.total_weight = total_weight;
+        }
+        if simulate {
+            set_return_data(&draw.try_to_vec()?);
+            return Ok(())
+        }
+
+        if let Some((compression_program_info, noop_program_info)) = tree_program_infos {
+            // Log the draw as a leaf of the draw history tree.
+            let state_bump = [state.bump];
+            let seeds = BondSeed::State.signer_seeds(config_info.key, &state_bump);
+            Create::append_leaf(
+                compression_program_info, 
+                noop_program_info, 
+                draw_info, 
+                state_info, 
+                &draw.try_to_vec()?, 
+                &seeds,
+            )?;
+        } else {
+            pack_account(&draw, &mut draw_info.data.borrow_mut())?;
+        }
+
+        state.draw_id = id;
+        state.rollover = rollover;
+        state.last_draw_epoch = epoch.epoch;
+        if !is_rollover {
+            state.last_winner = *receiver;
+            state.last_win_draw_id = id;
+        }
+        if is_escrowed {
+            state.reserved += amount;
+        }
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        if let (true, Some((jackpot_mints_info, mut jackpot_mints))) = (
+            is_escrowed && !extra_prizes.is_empty(), 
+            jackpot_mints,
+        ) {
+            Check::writable(jackpot_mints_info)?;
+            for prize in extra_prizes.iter() {
+                jackpot_mints.reserve(prize)?;
+            }
+            jackpot_mints.serialize(&mut &mut jackpot_mints_info.data.borrow_mut()[..])?;
+        }
+
+        if let Some(draw_authority_ata_info) = draw_authority_ata_info {
+            // Jackpot -> Caller
+            Create::token_transfer_checked(
+                config_info,
+                token_program_info,
+                token_mint_info,
+                jackpot_ata_info,
+                draw_authority_ata_info,
+                jackpot_info,
+                BondSeed::Jackpot,
+                jackpot.bump,
+                crank_fee,
+                config.decimals,
+            )?;
+        }
+
+        if !is_rollover && !is_escrowed {
+            Self::record_winner(leaderboard_info, receiver_info.key, amount - draw.transfer_fee)?;
+
+            // Jackpot -> Winner!
+            Create::token_transfer_checked(
+                config_info,
+                token_program_info,
+                token_mint_info,
+                jackpot_ata_info,
+                receiver_ata_info,
+                jackpot_info,
+                BondSeed::Jackpot,
+                jackpot.bump,
+                amount,
+                config.decimals,
+            )?;
+        }
+
+        for (
+            mint_info, 
+            mint_token_program_info, 
+            jackpot_mint_ata_info, 
+            receiver_mint_ata_info,
+        ) in jackpot_mint_infos {
+            let prize = extra_prizes.iter().find(|prize| prize.mint == *mint_info.key);
+            if let (false, Some(prize)) = (is_escrowed, prize) {
+                // Jackpot -> Winner (additional mint)
+                Create::token_transfer_checked(
+                    config_info,
+                    mint_token_program_info,
+                    mint_info,
+                    jackpot_mint_ata_info,
+                    receiver_mint_ata_info,
+                    jackpot_info,
+                    BondSeed::Jackpot,
+                    jackpot.bump,
+                    prize.amount,
+                    Mint::unpack_from_slice(&mint_info.data.borrow())?.decimals,
+                )?;
+            }
+        }
+
+        if retained > 0 {
+            // Jackpot -> Treasury
+            Create::token_transfer_checked(
+                config_info,
+                token_program_info,
+                token_mint_info,
+                jackpot_ata_info,
+                treasury_ata_info,
+                jackpot_info,
+                BondSeed::Jackpot,
+                jackpot.bump,
+                retained,
+                config.decimals,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Hashes `seed` with the most recent entry (slot and hash) of the SlotHashes sysvar.
+    fn mix_slot_hash(slot_hashes_info: &AccountInfo, seed: u64) -> Result<[u8; 32], ProgramError> {
+        let data = slot_hashes_info.data.borrow();
+        let slot_hash = data.get(8..8 + 8 + 32).ok_or(ProgramError::InvalidAccountData)?;
+        Ok(keccak::hashv(&[&seed.to_le_bytes(), slot_hash]).to_bytes())
+    }
+
+    fn process_claim_prize(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        ticket_proof: Option<TicketProof>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let receiver_info = next_account_info(account_info_iter)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+
+        let jackpot_info = next_account_info(account_info_iter)?;
+        let jackpot = BondShare::unpack_from_slice(&jackpot_info.data.borrow())?;
+        let jackpot_ata_info = next_account_info(account_info_iter)?;
+        let jackpot_ata = Account::unpack_from_slice(&jackpot_ata_info.data.borrow())?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            jackpot_info, 
+            &jackpot, 
+            jackpot_ata_info,
+            &jackpot_ata,
+        )?;
+
+        let draw_info = next_account_info(account_info_iter)?;
+        let mut draw = BondDraw::unpack_from_slice(&draw_info.data.borrow())?;
+        Check::owner(draw_info, program_id)?;
+        Check::valid(&draw, draw_info)?;
+        Check::account(config_info, &draw.config)?;
+        Check::account(receiver_info, &draw.receiver)?;
+        if draw.is_claimed {
+            return Err(BondError::PrizeAlreadyClaimed.into());
+        }
+        if draw.is_claim_expired(config.claim_window, Clock::get()?.unix_timestamp) {
+            return Err(BondError::ClaimExpired.into());
+        }
+
+        let receiver_ata_info = next_account_info(account_info_iter)?;
+        let receiver_ata = Account::unpack_from_slice(&receiver_ata_info.data.borrow())?;
+        Check::pubkey(&receiver_ata.owner, receiver_info.key)?;
+
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+
+        // The claim is authorized by the receiver or its session key.
+        if !receiver_info.is_signer {
+            let session_key_info = next_account_info(account_info_iter)?;
+            Check::signer(session_key_info)?;
+            let session_info = next_account_info(account_info_iter)?;
+            let session = try_from_slice_unchecked::<BondSession>(&session_info.data.borrow())?;
+            Check::owner(session_info, program_id)?;
+            Check::valid(&session, session_info)?;
+            Check::account(config_info, &session.authority)?;
+            Check::account(receiver_info, &session.wallet)?;
+            Check::account(session_key_info, &session.session_key)?;
+            if Clock::get()?.unix_timestamp >= session.expires_at {
+                return Err(BondError::SessionExpired.into());
+            }
+        }
+
+        // Escrow the prize of held receivers until the hold is released.
+        let hold = match config.compliance_authority {
+            Some(_) => {
+                let hold_info = next_account_info(account_info_iter)?;
+                let (hold_key, _) = BondSeed::Hold.derive_indexed(
+                    program_id, 
+                    config_info.key, 
+                    receiver_info.key.as_ref(),
+                );
+                Check::account(hold_info, &hold_key)?;
+                let hold = try_from_slice_unchecked::<BondHold>(&hold_info.data.borrow())
+                    .unwrap_or_default();
+                if hold_info.owner == program_id && hold.is_valid() {
+                    Check::writable(hold_info)?;
+                    Some((hold_info, hold))
+                } else {
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let leaderboard_info = Self::next_leaderboard_info(&config, account_info_iter)?;
+
+        // The additional mints' prizes reserved by the draw, with each prize's [mint, 
+        // mint_token_program, jackpot_mint_ata, receiver_mint_ata] accounts.
+        let mut prize_infos = Vec::new();
+        let jackpot_mints = if draw.extra_prizes.is_empty() {
+            None
+        } else {
+            if hold.is_some() {
+                msg!("Additional mint prizes can't be escrowed by a hold");
+                return Err(BondError::PrizeHeld.into());
+            }
+            let jackpot_mints_info = next_account_info(account_info_iter)?;
+            Check::writable(jackpot_mints_info)?;
+            Check::account(
+                jackpot_mints_info, 
+                &config.jackpot_mints.ok_or(ProgramError::InvalidAccountData)?,
+            )?;
+            let jackpot_mints = try_from_slice_unchecked::<BondJackpotMints>(
+                &jackpot_mints_info.data.borrow(),
+            )?;
+            for prize in draw.extra_prizes.iter() {
+                let mint_info = next_account_info(account_info_iter)?;
+                Check::account(mint_info, &prize.mint)?;
+                let mint_token_program_info = next_account_info(account_info_iter)?;
+                Check::token_mint(mint_info, mint_token_program_info)?;
+                let jackpot_mint_ata_info = next_account_info(account_info_iter)?;
+                Check::ata_with_program(
+                    jackpot_info, 
+                    &prize.mint, 
+                    mint_token_program_info.key, 
+                    jackpot_mint_ata_info,
+                )?;
+                let receiver_mint_ata_info = next_account_info(account_info_iter)?;
+                let receiver_mint_ata = Account::unpack_from_slice(
+                    &receiver_mint_ata_info.data.borrow(),
+                )?;
+                Check::pubkey(&receiver_mint_ata.owner, &receiver_ata.owner)?;
+                Check::owner_ata(&receiver_mint_ata, &prize.mint, receiver_mint_ata_info)?;
+                prize_infos.push((
+                    prize, 
+                    mint_info, 
+                    mint_token_program_info, 
+                    jackpot_mint_ata_info, 
+                    receiver_mint_ata_info,
+                ));
+            }
+            Some((jackpot_mints_info, jackpot_mints))
+        };
+
+        // Redeem the prize for SOL if the winner chose to.
+        let sol_infos = match draw.payout_asset {
+            BondPayoutAsset::Sol => {
+                Check::writable(receiver_info)?;
+                let pool_program_info = next_account_info(account_info_iter)?;
+                let pool_info = next_account_info(account_info_iter)?;
+                let adapter_infos = account_info_iter
+                    .as_slice()
+                    .get(..SOL_WITHDRAW_ADAPTER_ACCOUNTS)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                account_info_iter.nth(SOL_WITHDRAW_ADAPTER_ACCOUNTS - 1);
+                Some((pool_program_info, pool_info, adapter_infos))
+            },
+            BondPayoutAsset::NativeSol => {
+                // Closing the temporary wrapped SOL account requires its owner's signature.
+                Check::signer(receiver_info)?;
+                Check::writable(receiver_info)?;
+                None
+            },
+            BondPayoutAsset::Token => None,
+        };
+
+        // Prove that the winner holds a ticket in the game's ticket ledger.
+        match (config.ticket_tree, ticket_proof) {
+            (Some(ticket_tree), Some(ticket_proof)) => {
+                let compression_program_info = next_account_info(account_info_iter)?;
+                let ticket_tree_info = next_account_info(account_info_iter)?;
+                Check::account(compression_program_info, &ACCOUNT_COMPRESSION_PROGRAM_ID)?;
+                Check::account(ticket_tree_info, &ticket_tree)?;
+                let ticket = BondTicket {
+                    wallet: *receiver_info.key,
+                    weight: ticket_proof.weight,
+                    issued_at: ticket_proof.issued_at,
+                    rebate_per_weight: ticket_proof.rebate_per_weight,
+                };
+                let weight = ticket.time_weighted_weight(
+                    draw.epoch_start_timestamp, 
+                    draw.unix_timestamp,
+                );
+                if weight == 0 {
+                    msg!("Ticket issued at {} after the draw window", ticket.issued_at);
+                    return Err(BondError::IneligibleTicket.into());
+                }
+                if !config.is_eligible(ticket.weight) {
+                    msg!("Ticket weight {} is below {}", ticket.weight, config.min_eligible_weight);
+                    return Err(BondError::InsufficientWeight.into());
+                }
+                Create::verify_leaf(
+                    compression_program_info, 
+                    ticket_tree_info, 
+                    account_info_iter.as_slice(), 
+                    ticket_proof.root, 
+                    ticket.leaf()?, 
+                    ticket_proof.index,
+                )?;
+            },
+            (Some(_), None) => {
+                msg!("A ticket proof is required");
+                return Err(BondError::TicketProofRequired.into());
+            },
+            (None, Some(_)) => {
+                msg!("The game has no ticket ledger");
+                return Err(ProgramError::InvalidArgument);
+            },
+            (None, None) => {},
+        }
+
+        draw.is_claimed = true;
+        if hold.is_none() && sol_infos.is_none() {
+            draw.transfer_fee = Self::transfer_fee(token_mint_info, draw.amount)?;
+        }
+        pack_account(&draw, &mut draw_info.data.borrow_mut())?;
+
+        if let Some((hold_info, mut hold)) = hold {
+            // The prize stays reserved in the jackpot.
+            hold.amount = hold.amount
+                .checked_add(draw.amount)
+                .ok_or(BondError::NumericalOverflow)?;
+            hold.serialize(&mut &mut hold_info.data.borrow_mut()[..])?;
+            msg!("Prize held: {}", draw.amount);
+            return Ok(())
+        }
+
+        state.reserved = state.reserved
+            .checked_sub(draw.amount)
+            .ok_or(BondError::NumericalOverflow)?;
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        Self::record_winner(leaderboard_info, receiver_info.key, draw.amount - draw.transfer_fee)?;
+
+        if let Some((jackpot_mints_info, mut jackpot_mints)) = jackpot_mints {
+            for prize in draw.extra_prizes.iter() {
+                jackpot_mints.release(prize)?;
+            }
+            jackpot_mints.serialize(&mut &mut jackpot_mints_info.data.borrow_mut()[..])?;
+        }
+        for (
+            prize, 
+            mint_info, 
+            mint_token_program_info, 
+            jackpot_mint_ata_info, 
+            receiver_mint_ata_info,
+        ) in prize_infos {
+            // Jackpot -> Winner (additional mint)
+            Create::token_transfer_checked(
+                config_info,
+                mint_token_program_info,
+                mint_info,
+                jackpot_mint_ata_info,
+                receiver_mint_ata_info,
+                jackpot_info,
+                BondSeed::Jackpot,
+                jackpot.bump,
+                prize.amount,
+                Mint::unpack_from_slice(&mint_info.data.borrow())?.decimals,
+            )?;
+        }
+
+        if let Some((pool_program_info, pool_info, adapter_infos)) = sol_infos {
+            // Jackpot -> SOL -> Winner!
+            let jackpot_bump = [jackpot.bump];
+            let seeds = BondSeed::Jackpot.signer_seeds(config_info.key, &jackpot_bump);
+            return Adapter::withdraw_sol(
+                config.lst_adapter, 
+                pool_program_info, 
+                pool_info, 
+                jackpot_info, 
+                jackpot_ata_info, 
+                receiver_info, 
+                token_mint_info, 
+                token_program_info, 
+                adapter_infos, 
+                draw.amount, 
+                &seeds,
+            )
+        }
+
+        // Jackpot -> Winner!
+        Create::token_transfer_checked(
+            config_info,
+            token_program_info,
+            token_mint_info,
+            jackpot_ata_info,
+            receiver_ata_info,
+            jackpot_info,
+            BondSeed::Jackpot,
+            jackpot.bump,
+            draw.amount,
+            config.decimals,
+        )?;
+
+        // Wrapped SOL -> Winner's wallet
+        if draw.payout_asset == BondPayoutAsset::NativeSol {
+            Create::unwrap_sol(token_program_info, receiver_ata_info, receiver_info)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the keccak hash of the message of the transaction's Ed25519 program instruction 
+    /// signed by `signer`, if any. The message must start with `prefix`, binding it to the draw.
+    fn find_attestation(
+        instructions_info: &AccountInfo,
+        signer: &Pubkey,
+        prefix: &[u8],
+    ) -> Result<Option<[u8; 32]>, ProgramError> {
+        let mut index = 0;
+        while let Ok(ix) = load_instruction_at_checked(index, instructions_info) {
+            index += 1;
+            if ix.program_id != ed25519_program::id() {
+                continue;
+            }
+            // A single signature whose offsets point into the instruction's own data.
+            let data = &ix.data;
+            let offset = |at: usize| -> Result<usize, ProgramError> {
+                data.get(at..at + 2)
+                    .map(|bytes| usize::from(u16::from_le_bytes([bytes[0], bytes[1]])))
+                    .ok_or(BondError::InvalidAttestation.into())
+            };
+            if data.first() != Some(&1) {
+                continue;
+            }
+            let is_self_contained = [4, 8, 14]
+                .iter()
+                .map(|at| offset(*at))
+                .collect::<Result<Vec<_>, _>>()?
+                .iter()
+                .all(|index| *index == usize::from(u16::MAX));
+            let public_key_offset = offset(6)?;
+            let message_offset = offset(10)?;
+            let message_size = offset(12)?;
+            let public_key = data
+                .get(public_key_offset..public_key_offset + 32)
+                .ok_or(BondError::InvalidAttestation)?;
+            if !is_self_contained || public_key != signer.as_ref() {
+                continue;
+            }
+            let message = data
+                .get(message_offset..message_offset + message_size)
+                .ok_or(BondError::InvalidAttestation)?;
+            if !message.starts_with(prefix) {
+                msg!("Attestation is not bound to this draw");
+                return Err(BondError::InvalidAttestation.into());
+            }
+            return Ok(Some(keccak::hash(message).to_bytes()));
+        }
+        Ok(None)
+    }
+
+    /// Returns the game's leaderboard account if it has one.
+    fn next_leaderboard_info<'a, 'b>(
+        config: &BondConfig,
+        account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    ) -> Result<Option<&'a AccountInfo<'b>>, ProgramError> {
+        match config.leaderboard {
+            Some(leaderboard) => {
+                let leaderboard_info = next_account_info(account_info_iter)?;
+                Check::writable(leaderboard_info)?;
+                Check::account(leaderboard_info, &leaderboard)?;
+                Ok(Some(leaderboard_info))
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the fee withheld by `token_mint_info`'s Token-2022 `TransferFeeConfig` extension 
+    /// when transferring `amount` in the current epoch (0 without the extension).
+    fn transfer_fee(
+        token_mint_info: &AccountInfo,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        if *token_mint_info.owner != TOKEN_2022_PROGRAM_ID {
+            return Ok(0);
+        }
+        let data = token_mint_info.data.borrow();
+        let mut offset = MINT_EXTENSIONS_OFFSET;
+        while let Some(header) = data.get(offset..offset + 4) {
+            let extension_type = u16::from_le_bytes([header[0], header[1]]);
+            let len = usize::from(u16::from_le_bytes([header[2], header[3]]));
+            offset += 4;
+            if extension_type != TRANSFER_FEE_CONFIG_EXTENSION {
+                offset += len;
+                continue;
+            }
+
+            // The newer fee applies from its epoch on.
+            let older = OLDER_TRANSFER_FEE_OFFSET;
+            let newer = older + TRANSFER_FEE_SIZE;
+            let config = data.get(offset..offset + len).ok_or(ProgramError::InvalidAccountData)?;
+            let read_u64 = |at: usize| -> Result<u64, ProgramError> {
+                config.get(at..at + 8)
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .map(u64::from_le_bytes)
+                    .ok_or(ProgramError::InvalidAccountData)
+            };
+            let fee = if Clock::get()?.epoch >= read_u64(newer)? { newer } else { older };
+            let maximum_fee = read_u64(fee + 8)?;
+            let basis_points = config.get(fee + 16..fee + 18)
+                .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+                .ok_or(ProgramError::InvalidAccountData)?;
+
+            // The fee is rounded up and capped at `maximum_fee`.
+            let fee = (u128::from(amount) * u128::from(basis_points)).div_ceil(10_000);
+            return Ok(u64::try_from(fee).unwrap_or(u64::MAX).min(maximum_fee));
+        }
+        Ok(0)
+    }
+
+    /// Adds a paid prize to the game's leaderboard (if any).
+    fn record_winner(
+        leaderboard_info: Option<&AccountInfo>,
+        wallet: &Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        if let Some(leaderboard_info) = leaderboard_info {
+            let mut leaderboard = try_from_slice_unchecked::<BondLeaderboard>(
+                &leaderboard_info.data.borrow(),
+            )?;
+            leaderboard.record(*wallet, amount)?;
+            leaderboard.serialize(&mut &mut leaderboard_info.data.borrow_mut()[..])?;
+        }
+        Ok(())
+    }
+
+    fn process_set_metadata(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        name: [u8; MAX_NAME_LEN],
+        uri: String,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let mut config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        if uri.len() > MAX_URI_LEN {
+            return Err(BondError::UriTooLong.into());
+        }
+
+        // The name is UTF-8 followed only by zero padding.
+        let name_len = name.iter().rposition(|byte| *byte != 0).map_or(0, |index| index + 1);
+        if name[..name_len].contains(&0) || std::str::from_utf8(&name[..name_len]).is_err() {
+            return Err(BondError::InvalidName.into());
+        }
+
+        config.name = name;
+        config.uri = uri;
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    fn process_close_season(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let mut config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+
+        // The season's last draw must be settled: paid, rolled over or released by `ExpireClaim`.
+        if state.vrf_request_slot != 0 {
+            msg!("Randomness requested at slot {} is pending", state.vrf_request_slot);
+            return Err(BondError::DrawNotSettled.into());
+        }
+        if config.draw_tree.is_none() && state.draw_id > 0 {
+            let draw_info = next_account_info(account_info_iter)?;
+            let draw = BondDraw::unpack_from_slice(&draw_info.data.borrow())?;
+            Check::owner(draw_info, program_id)?;
+            Check::valid(&draw, draw_info)?;
+            Check::account(config_info, &draw.config)?;
+            if draw.id != state.draw_id {
+                msg!("Latest draw {} but state is at {}", draw.id, state.draw_id);
+                return Err(ProgramError::InvalidArgument);
+            }
+            if !draw.is_claimed && !draw.is_expired {
+                msg!("Draw {} is unclaimed", draw.id);
+                return Err(BondError::DrawNotSettled.into());
+            }
+        }
+
+        // End the season now unless it has already ended.
+        let now = Clock::get()?.unix_timestamp;
+        config.ends_at = Some(config.ends_at.map_or(now, |ends_at| ends_at.min(now)));
+        config.is_active = false;
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+
+        msg!("Season closed after draw {}", state.draw_id);
+        Ok(())
+    }
+
+    fn process_rollover_season(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        starts_at: Option<i64>,
+        ends_at: Option<i64>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let mut config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+
+        let season_archive_info = next_account_info(account_info_iter)?;
+        Check::writable(season_archive_info)?;
+
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // The season must have been closed (see `CloseSeason`), not merely paused, so this is 
+        // exempt from the active check (see `Pause`).
+        let now = Clock::get()?.unix_timestamp;
+        if config.is_active || !config.ends_at.map_or(false, |ends_at| ends_at <= now) {
+            msg!("Season {} must be closed first", state.season);
+            return Err(BondError::SeasonNotClosed.into());
+        }
+
+        // Season Archive PDA Account.
+        let season = state.season.to_le_bytes();
+        Create::indexed_pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            season_archive_info, 
+            BondSeed::Season, 
+            &season, 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            SEASON_ARCHIVE_SIZE as u32,
+        )?;
+        BondSeasonArchive::new(
+            *config_info.key,
+            bump,
+            &state,
+            config.starts_at,
+            config.ends_at,
+            now,
+        ).serialize(
+            &mut &mut season_archive_info.data.borrow_mut()[..],
+        )?;
+
+        // Start the next season. Draw ids keep increasing across seasons, since the accounts 
+        // derived from them (snapshots, buffers, draws and messages) outlive their season.
+        state.season += 1;
+        state.rollover = 0;
+        state.season_start_draw_id = state.draw_id;
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        config.starts_at = starts_at;
+        config.ends_at = ends_at;
+        config.is_active = true;
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+
+        msg!("Season {} started", state.season);
+        Ok(())
+    }
+
+    fn process_update_exclusion_list(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        add: Vec<Pubkey>,
+        remove: Vec<Pubkey>,
+        expires_at_epoch: Option<u64>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let mut exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
+            &exclusion_list_info.data.borrow(),
+        )?;
+        Check::writable(exclusion_list_info)?;
+        Check::owner(exclusion_list_info, program_id)?;
+        Check::valid(&exclusion_list, exclusion_list_info)?;
+        Check::account(config_info, &exclusion_list.authority)?;
+
+        for account in remove.iter() {
+            exclusion_list.remove(account);
+        }
+        for account in add.into_iter() {
+            exclusion_list.add(account, expires_at_epoch)?;
+        }
+
+        exclusion_list.serialize(&mut &mut exclusion_list_info.data.borrow_mut()[..])?;
+        msg!("Exclusion list size {}/{}", exclusion_list.accounts.len(), exclusion_list.capacity);
+        Ok(())
+    }
+
+    fn process_append_exclusion_chunk(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        chunk_index: u32,
+        exclusion_list_accounts: Vec<Pubkey>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let mut exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
+            &exclusion_list_info.data.borrow(),
+        )?;
+        Check::writable(exclusion_list_info)?;
+        Check::owner(exclusion_list_info, program_id)?;
+        Check::valid(&exclusion_list, exclusion_list_info)?;
+        Check::account(config_info, &exclusion_list.authority)?;
+
+        // Skip chunks that have already been appended.
+        if chunk_index < exclusion_list.chunks {
+            msg!("Chunk {} already appended", chunk_index);
+            return Ok(());
+        }
+        if chunk_index > exclusion_list.chunks {
+            msg!("Expected chunk {}, received {}", exclusion_list.chunks, chunk_index);
+            return Err(BondError::InvalidChunkIndex.into());
+        }
+
+        for account in exclusion_list_accounts.into_iter() {
+            exclusion_list.add(account, None)?;
+        }
+        exclusion_list.chunks += 1;
+
+        exclusion_list.serialize(&mut &mut exclusion_list_info.data.borrow_mut()[..])?;
+        msg!("Exclusion list size {}/{}", exclusion_list.accounts.len(), exclusion_list.capacity);
+        Ok(())
+    }
+
+    fn process_prune_expired(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let mut exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
+            &exclusion_list_info.data.borrow(),
+        )?;
+        Check::writable(exclusion_list_info)?;
+        Check::owner(exclusion_list_info, program_id)?;
+        Check::valid(&exclusion_list, exclusion_list_info)?;
+        Check::account(config_info, &exclusion_list.authority)?;
+        Check::active(&config)?;
+
+        let pruned = exclusion_list.prune_expired(Clock::get()?.epoch);
+        exclusion_list.serialize(&mut &mut exclusion_list_info.data.borrow_mut()[..])?;
+        msg!("Pruned {} expired exclusions", pruned);
+        Ok(())
+    }
+
+    fn process_update_stake_pool(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::active(&config)?;
+
+        let fee_info = next_account_info(account_info_iter)?;
+        let mut fee = BondFee::unpack_from_slice(&fee_info.data.borrow())?;
+        let fee_ata_info = next_account_info(account_info_iter)?;
+        let fee_ata = Account::unpack_from_slice(&fee_ata_info.data.borrow())?;
+        Check::writable(fee_info)?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            fee_info, 
+            &fee, 
+            fee_ata_info,
+            &fee_ata,
+        )?;
+
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let pool_program_info = next_account_info(account_info_iter)?;
+        let pool_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+
+        // Only the pool paying its epoch fee to this game may be cranked.
+        Adapter::harvest(
+            config.lst_adapter, 
+            pool_program_info, 
+            pool_info, 
+            fee_ata_info, 
+            token_mint_info, 
+            token_program_info, 
+            account_info_iter.as_slice(),
+        )?;
+
+        fee.last_update_epoch = Clock::get()?.epoch;
+        pack_account(&fee, &mut fee_info.data.borrow_mut())?;
+        msg!("Epoch fee harvested for epoch {}", fee.last_update_epoch);
+        Ok(())
+    }
+
+    fn process_swap_treasury(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount_in: u64,
+        min_amount_out: u64,
+        route_data: Vec<u8>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let treasury_info = next_account_info(account_info_iter)?;
+        let treasury = BondShare::unpack_from_slice(&treasury_info.data.borrow())?;
+        let treasury_ata_info = next_account_info(account_info_iter)?;
+        let treasury_ata = Account::unpack_from_slice(&treasury_ata_info.data.borrow())?;
+        Check::writable(treasury_ata_info)?;
+        Check::pda(program_id, config_info, treasury_info, BondSeed::Treasury, treasury.bump)?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            treasury_info, 
+            &treasury, 
+            treasury_ata_info,
+            &treasury_ata,
+        )?;
+        Check::pubkey(&treasury_ata.mint, &config.token_mint)?;
+
+        let output_ata_info = next_account_info(account_info_iter)?;
+        let output_ata = Account::unpack_from_slice(&output_ata_info.data.borrow())?;
+        Check::writable(output_ata_info)?;
+        Check::pubkey(&output_ata.owner, treasury_info.key)?;
+        if output_ata.mint.eq(&config.token_mint) {
+            msg!("Output asset must differ from {}", config.token_mint);
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let swap_program_info = next_account_info(account_info_iter)?;
+        if !SWAP_PROGRAM_IDS.contains(swap_program_info.key) {
+            msg!("Unsupported swap program {}", swap_program_info.key);
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if amount_in > treasury_ata.amount {
+            msg!("Insufficient treasury balance {}", treasury_ata.amount);
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Forward the route's accounts, signing for the treasury PDA, after recording the 
+        // balances of the treasury's other token accounts among them.
+        let route_infos: Vec<AccountInfo> = account_info_iter.cloned().collect();
+        let treasury_lamports = treasury_info.lamports();
+        let treasury_balances: Vec<u64> = route_infos
+            .iter()
+            .map(|info| Self::treasury_balance(treasury_info, info))
+            .collect();
+        let ix = Instruction {
+            program_id: *swap_program_info.key,
+            accounts: route_infos.iter().map(|info| AccountMeta {
+                pubkey: *info.key,
+                is_signer: info.is_signer || info.key.eq(treasury_info.key),
+                is_writable: info.is_writable,
+            }).collect(),
+            data: route_data,
+        };
+        let bump = [treasury.bump];
+        let seeds = BondSeed::Treasury.signer_seeds(config_info.key, &bump);
+        invoke_signed(
+            &ix, 
+            &[route_infos.as_slice(), &[swap_program_info.clone()]].concat(), 
+            &[&seeds],
+        )?;
+
+        // The route may only debit the swapped token account.
+        if treasury_info.lamports() < treasury_lamports {
+            msg!("Treasury lamports decreased from {}", treasury_lamports);
+            return Err(BondError::TreasuryBalanceDecreased.into());
+        }
+        for (info, before) in route_infos.iter().zip(treasury_balances) {
+            let is_swapped = info.key == treasury_ata_info.key || info.key == output_ata_info.key;
+            let after = Self::treasury_balance(treasury_info, info);
+            if !is_swapped && after < before {
+                msg!("Treasury account {} decreased from {} to {}", info.key, before, after);
+                return Err(BondError::TreasuryBalanceDecreased.into());
+            }
+        }
+
+        // Enforce the slippage limits on the actual balance changes.
+        let spent = treasury_ata.amount.saturating_sub(
+            Account::unpack_from_slice(&treasury_ata_info.data.borrow())?.amount,
+        );
+        let received = Account::unpack_from_slice(&output_ata_info.data.borrow())?.amount
+            .saturating_sub(output_ata.amount);
+        if spent > amount_in || received < min_amount_out {
+            msg!(
+                "Swapped {} for {} (max in {}, min out {})", 
+                spent, 
+                received, 
+                amount_in, 
+                min_amount_out,
+            );
+            return Err(BondError::SlippageExceeded.into());
+        }
+
+        msg!("Swapped {} for {}", spent, received);
+        Ok(())
+    }
+
+    /// The balance of `info` if it's a token account owned by `treasury_info` (0 otherwise, e.g. 
+    /// once closed).
+    fn treasury_balance(treasury_info: &AccountInfo, info: &AccountInfo) -> u64 {
+        if *info.owner != spl_token::id() && *info.owner != TOKEN_2022_PROGRAM_ID {
+            return 0;
+        }
+        Account::unpack_from_slice(&info.data.borrow())
+            .ok()
+            .filter(|account| account.owner == *treasury_info.key)
+            .map_or(0, |account| account.amount)
+    }
+
+    /// The number of `SplitShares` accounts at the start of a `CrankTick`.
+    const CRANK_TICK_SPLIT_ACCOUNTS: usize = 17;
+
+    fn process_crank_tick(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        permission_bump: u8,
+        program_state_bump: u8,
+    ) -> ProgramResult {
+        if accounts.len() < Self::CRANK_TICK_SPLIT_ACCOUNTS + 2 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (split_infos, harvest_infos) = accounts.split_at(Self::CRANK_TICK_SPLIT_ACCOUNTS);
+
+        // Unpack accounts...
+        let cranker_info = &split_infos[0];
+        let config_info = &split_infos[1];
+        let fee_info = &split_infos[2];
+        let fee_ata_info = &split_infos[3];
+        let token_mint_info = &split_infos[12];
+        let token_program_info = &split_infos[13];
+        let state_info = &split_infos[14];
+        Check::signer(cranker_info)?;
+
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::active(&config)?;
+
+        let clock = Clock::get()?;
+
+        // 1. Harvest.
+        let fee = BondFee::unpack_from_slice(&fee_info.data.borrow())?;
+        if fee.last_update_epoch != clock.epoch {
+            msg!("Harvest");
+            let update_infos: Vec<AccountInfo> = [
+                config_info, 
+                fee_info, 
+                fee_ata_info, 
+                token_mint_info, 
+                token_program_info,
+            ].into_iter().chain(harvest_infos.iter()).cloned().collect();
+            Self::process_update_stake_pool(program_id, &update_infos)?;
+        }
+
+        // The randomness request's [snapshot, vrf, ..] accounts follow the adapter's (if any).
+        let randomness_infos = harvest_infos
+            .get(2 + Adapter::harvest_accounts(config.lst_adapter)..)
+            .unwrap_or_default();
+
+        // 2. Split.
+        let fee_ata = Account::unpack_from_slice(&fee_ata_info.data.borrow())?;
+        let is_draw_authority = cranker_info.key.eq(&config.draw_authority);
+
+        // Splits that aren't due yet are skipped rather than failing the tick.
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        let is_epoch_limited = !is_draw_authority || config.split_once_per_epoch;
+        let is_due = clock.slot >= state.next_split_slot(config.min_split_interval)
+            && !(is_epoch_limited && state.is_split_in_epoch(clock.epoch));
+        let is_in_season = Check::in_season(&config, clock.unix_timestamp).is_ok();
+        let can_split = (config.allow_permissionless_split || is_draw_authority)
+            && is_due
+            && is_in_season;
+        if fee_ata.amount > 0 && can_split {
+            msg!("Split");
+
+            // Only pass the optional accounts `SplitShares` expects.
+            let (required_infos, optional_infos) = split_infos.split_at(
+                Self::CRANK_TICK_SPLIT_ACCOUNTS - 2,
+            );
+            let authority_ata_info = &optional_infos[0];
+            let state_ata_info = &optional_infos[1];
+            let mut split_infos = required_infos.to_vec();
+            if !is_draw_authority && config.crank_fee(fee_ata.amount) > 0 {
+                split_infos.push(authority_ata_info.clone());
+            }
+            if config.rebate_bps > 0 {
+                split_infos.push(state_ata_info.clone());
+            }
+            Self::process_split_shares(program_id, &split_infos, None, None)?;
+        }
+
+        // 3. Request the next draw's randomness once its snapshot is sealed. The draw itself 
+        // (`FulfillDraw`) needs the accounts of the receiver selected by the result.
+        let randomness_infos = randomness_infos
+            .split_first()
+            .filter(|(snapshot_info, _)| !snapshot_info.data_is_empty());
+        if let Some((snapshot_info, vrf_infos)) = randomness_infos {
+            let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+            Check::owner(state_info, program_id)?;
+            Check::valid(&state, state_info)?;
+            Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+            let snapshot = try_from_slice_unchecked::<BondSnapshot>(&snapshot_info.data.borrow())?;
+            Check::owner(snapshot_info, program_id)?;
+            Check::valid(&snapshot, snapshot_info)?;
+            Check::account(config_info, &snapshot.authority)?;
+
+            let is_draw_due = clock.epoch >= state.next_draw_epoch(config.epochs_per_draw)
+                && state.vrf_request_slot == 0
+                && snapshot.draw_id == state.draw_id + 1
+                && snapshot.is_sealed;
+            let can_request = config.snapshot_draws
+                && (config.is_permissionless_draw() || is_draw_authority)
+                && is_draw_due
+                && is_in_season;
+            if can_request {
+                msg!("Request randomness");
+                Self::request_randomness(
+                    config_info, 
+                    state_info, 
+                    &mut state, 
+                    vrf_infos, 
+                    permission_bump, 
+                    program_state_bump,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_create_lookup_table(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        recent_slot: u64,
+        bump: u8,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let mut config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+        if let Some(lookup_table) = config.lookup_table {
+            msg!("Lookup table {} already exists", lookup_table);
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let lookup_table_info = next_account_info(account_info_iter)?;
+        let lookup_table_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::writable(lookup_table_info)?;
+        Check::account(lookup_table_program_info, &LOOKUP_TABLE_PROGRAM_ID)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // The token program is the one owning the game's mint.
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program = *token_mint_info.owner;
+        if token_program != spl_token::id() && token_program != TOKEN_2022_PROGRAM_ID {
+            msg!("Unsupported token program {}", token_program);
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let state_bump = [state.bump];
+        let seeds = BondSeed::State.signer_seeds(config_info.key, &state_bump);
+        Create::lookup_table(
+            lookup_table_program_info, 
+            lookup_table_info, 
+            state_info, 
+            payer_info, 
+            system_program_info, 
+            recent_slot, 
+            bump, 
+            &seeds,
+        )?;
+
+        // The game's program, config, PDAs and ATAs.
+        let mut addresses = vec![
+            *program_id,
+            *config_info.key,
+            config.token_mint,
+            token_program,
+        ];
+        for seed in [BondSeed::State, BondSeed::ExclusionList] {
+            addresses.push(find_pda(program_id, config_info.key, seed));
+        }
+        for seed in [
+            BondSeed::Fee, 
+            BondSeed::Equity, 
+            BondSeed::Treasury, 
+            BondSeed::Jackpot, 
+            BondSeed::Stake,
+        ] {
+            let pda = find_pda(program_id, config_info.key, seed);
+            addresses.push(pda);
+            addresses.push(
+                spl_associated_token_account::get_associated_token_address_with_program_id(
+                    &pda, 
+                    &config.token_mint,
+                    &token_program,
+                ),
+            );
+        }
+        Create::extend_lookup_table(
+            lookup_table_program_info, 
+            lookup_table_info, 
+            state_info, 
+            payer_info, 
+            system_program_info, 
+            &addresses, 
+            &seeds,
+        )?;
+
+        config.lookup_table = Some(*lookup_table_info.key);
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+        msg!("Lookup table {} created", lookup_table_info.key);
+        Ok(())
+    }
+
+    fn process_extend_lookup_table(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        addresses: Vec<Pubkey>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let lookup_table_info = next_account_info(account_info_iter)?;
+        let lookup_table_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::writable(lookup_table_info)?;
+        Check::account(lookup_table_info, &config.lookup_table.unwrap_or_default())?;
+        Check::account(lookup_table_program_info, &LOOKUP_TABLE_PROGRAM_ID)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        let state_bump = [state.bump];
+        let seeds = BondSeed::State.signer_seeds(config_info.key, &state_bump);
+        Create::extend_lookup_table(
+            lookup_table_program_info, 
+            lookup_table_info, 
+            state_info, 
+            payer_info, 
+            system_program_info, 
+            &addresses, 
+            &seeds,
+        )
+    }
+
+    fn process_create_draw_tree(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> ProgramResult {
+        let mut config = Self::create_tree(program_id, accounts, max_depth, max_buffer_size)?;
+        Check::active(&config)?;
+        if let Some(draw_tree) = config.draw_tree {
+            msg!("Draw tree {} already exists", draw_tree);
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Claims read the draw account, so escrowed prizes require full draw accounts.
+        if config.payout_mode == BondPayoutMode::Claim {
+            msg!("Compressed draws are not supported in claim mode");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let config_info = &accounts[1];
+        let draw_tree_info = &accounts[3];
+        config.draw_tree = Some(*draw_tree_info.key);
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+        msg!("Draw tree {} created", draw_tree_info.key);
+        Ok(())
+    }
+
+    fn process_create_ticket_tree(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> ProgramResult {
+        let mut config = Self::create_tree(program_id, accounts, max_depth, max_buffer_size)?;
+        Check::active(&config)?;
+        if let Some(ticket_tree) = config.ticket_tree {
+            msg!("Ticket tree {} already exists", ticket_tree);
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let config_info = &accounts[1];
+        let ticket_tree_info = &accounts[3];
+        config.ticket_tree = Some(*ticket_tree_info.key);
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+        msg!("Ticket tree {} created", ticket_tree_info.key);
+        Ok(())
+    }
+
+    /// Initializes a merkle tree owned by the `state` PDA for the `CreateDrawTree` and 
+    /// `CreateTicketTree` instructions and returns the game's config.
+    fn create_tree(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<BondConfig, ProgramError> {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let tree_info = next_account_info(account_info_iter)?;
+        let compression_program_info = next_account_info(account_info_iter)?;
+        let noop_program_info = next_account_info(account_info_iter)?;
+        Check::writable(tree_info)?;
+        Check::owner(tree_info, &ACCOUNT_COMPRESSION_PROGRAM_ID)?;
+        Check::account(compression_program_info, &ACCOUNT_COMPRESSION_PROGRAM_ID)?;
+        Check::account(noop_program_info, &NOOP_PROGRAM_ID)?;
+
+        let state_bump = [state.bump];
+        let seeds = BondSeed::State.signer_seeds(config_info.key, &state_bump);
+        Create::merkle_tree(
+            compression_program_info, 
+            noop_program_info, 
+            tree_info, 
+            state_info, 
+            max_depth, 
+            max_buffer_size, 
+            &seeds,
+        )?;
+        Ok(config)
+    }
+
+    fn process_issue_tickets(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        mut tickets: Vec<BondTicket>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let ticket_tree_info = next_account_info(account_info_iter)?;
+        let compression_program_info = next_account_info(account_info_iter)?;
+        let noop_program_info = next_account_info(account_info_iter)?;
+        Check::writable(ticket_tree_info)?;
+        Check::account(ticket_tree_info, &config.ticket_tree.unwrap_or_default())?;
+        Check::account(compression_program_info, &ACCOUNT_COMPRESSION_PROGRAM_ID)?;
+        Check::account(noop_program_info, &NOOP_PROGRAM_ID)?;
+
+        let state_bump = [state.bump];
+        let seeds = BondSeed::State.signer_seeds(config_info.key, &state_bump);
+        let issued_at = Clock::get()?.unix_timestamp;
+        for ticket in tickets.iter_mut() {
+            ticket.issued_at = issued_at;
+            ticket.rebate_per_weight = state.rebate_per_weight;
+            state.ticket_weight = state.ticket_weight
+                .checked_add(ticket.weight)
+                .ok_or(BondError::NumericalOverflow)?;
+            Create::append_leaf(
+                compression_program_info, 
+                noop_program_info, 
+                ticket_tree_info, 
+                state_info, 
+                &ticket.try_to_vec()?, 
+                &seeds,
+            )?;
+        }
+
+        // Receipts are minted 1:1 with each ticket's weight.
+        if let Some(receipt_mint) = config.receipt_mint {
+            let receipt_mint_info = next_account_info(account_info_iter)?;
+            let token_program_info = next_account_info(account_info_iter)?;
+            Check::account(receipt_mint_info, &receipt_mint)?;
+            Check::token_mint(receipt_mint_info, token_program_info)?;
+            for ticket in tickets.iter() {
+                let receipt_ata_info = next_account_info(account_info_iter)?;
+                let receipt_ata = Account::unpack_from_slice(&receipt_ata_info.data.borrow())?;
+                Check::pubkey(&receipt_ata.owner, &ticket.wallet)?;
+                Create::mint_to(
+                    token_program_info, 
+                    receipt_mint_info, 
+                    receipt_ata_info, 
+                    state_info, 
+                    ticket.weight, 
+                    &seeds,
+                )?;
+            }
+        }
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+        msg!("Issued {} tickets", tickets.len());
+        Ok(())
+    }
+
+    fn process_publish_draw_message(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        nonce: u32,
+        emitter_bump: u8,
+        message_bump: u8,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let draw_info = next_account_info(account_info_iter)?;
+        let draw = BondDraw::unpack_from_slice(&draw_info.data.borrow())?;
+        Check::owner(draw_info, program_id)?;
+        Check::valid(&draw, draw_info)?;
+        Check::account(config_info, &draw.config)?;
+
+        let emitter_info = next_account_info(account_info_iter)?;
+        Check::pda(program_id, config_info, emitter_info, BondSeed::Emitter, emitter_bump)?;
+
+        let message_info = next_account_info(account_info_iter)?;
+        let draw_id = draw.id.to_le_bytes();
+        let message_bump = [message_bump];
+        let message_seeds = BondSeed::Message.indexed_signer_seeds(
+            config_info.key, 
+            &draw_id, 
+            &message_bump,
+        );
+        Check::account(
+            message_info, 
+            &Pubkey::create_program_address(&message_seeds, program_id)?,
+        )?;
+
+        let bridge_info = next_account_info(account_info_iter)?;
+        let sequence_info = next_account_info(account_info_iter)?;
+        let fee_collector_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let wormhole_program_info = next_account_info(account_info_iter)?;
+        Check::owner(bridge_info, &WORMHOLE_PROGRAM_ID)?;
+        Check::account(clock_info, &sysvar::clock::id())?;
+        Check::account(rent_info, &sysvar::rent::id())?;
+        Check::account(system_program_info, &system_program::id())?;
+        Check::account(wormhole_program_info, &WORMHOLE_PROGRAM_ID)?;
+
+        let emitter_bump = [emitter_bump];
+        let emitter_seeds = BondSeed::Emitter.signer_seeds(config_info.key, &emitter_bump);
+        Create::wormhole_message(
+            wormhole_program_info, 
+            bridge_info, 
+            message_info, 
+            emitter_info, 
+            sequence_info, 
+            payer_info, 
+            fee_collector_info, 
+            clock_info, 
+            rent_info, 
+            system_program_info, 
+            nonce, 
+            BondDrawMessage::from(&draw).try_to_vec()?, 
+            &emitter_seeds, 
+            &message_seeds,
+        )?;
+
+        msg!("Published draw {}", draw.id);
+        Ok(())
+    }
+
+    fn process_assert_invariants(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        // The shares can't distribute more than the epoch fee.
+        let (mut numerator, mut denominator) = (0u128, 1u128);
+        for seed in [BondSeed::Equity, BondSeed::Treasury, BondSeed::Jackpot, BondSeed::Stake] {
+            let share_info = next_account_info(account_info_iter)?;
+            let share = BondShare::unpack_from_slice(&share_info.data.borrow())?;
+            Check::owner(share_info, program_id)?;
+            Check::valid(&share, share_info)?;
+            if share.denominator == 0 {
+                msg!("Share {} has no denominator", seed.as_ref());
+                return Err(BondError::InvariantViolated.into());
+            }
+            let is_jackpot = seed == BondSeed::Jackpot;
+            Check::pda(program_id, config_info, share_info, seed, share.bump)?;
+            numerator = numerator
+                .checked_mul(u128::from(share.denominator))
+                .and_then(|n| n.checked_add(u128::from(share.numerator) * denominator))
+                .ok_or(BondError::NumericalOverflow)?;
+            denominator = denominator
+                .checked_mul(u128::from(share.denominator))
+                .ok_or(BondError::NumericalOverflow)?;
+            if is_jackpot {
+                let jackpot_ata_info = next_account_info(account_info_iter)?;
+                let jackpot_ata = Account::unpack_from_slice(&jackpot_ata_info.data.borrow())?;
+                Check::ata(share_info, &config.token_mint, jackpot_ata_info)?;
+
+                // The jackpot must cover every prize waiting to be claimed.
+                if jackpot_ata.amount < state.reserved {
+                    msg!("Jackpot holds {} but reserves {}", jackpot_ata.amount, state.reserved);
+                    return Err(BondError::InvariantViolated.into());
+                }
+            }
+        }
+        if numerator > denominator {
+            msg!("Shares sum up to {}/{}", numerator, denominator);
+            return Err(BondError::InvariantViolated.into());
+        }
+
+        // The fee account must hold the remainder retained by the latest split.
+        let fee_info = next_account_info(account_info_iter)?;
+        let fee = BondFee::unpack_from_slice(&fee_info.data.borrow())?;
+        Check::owner(fee_info, program_id)?;
+        Check::valid(&fee, fee_info)?;
+        Check::pda(program_id, config_info, fee_info, BondSeed::Fee, fee.bump)?;
+        let fee_ata_info = next_account_info(account_info_iter)?;
+        let fee_ata = Account::unpack_from_slice(&fee_ata_info.data.borrow())?;
+        Check::ata(fee_info, &config.token_mint, fee_ata_info)?;
+        if fee_ata.amount < state.split_remainder {
+            msg!("Fee holds {} but retains {}", fee_ata.amount, state.split_remainder);
+            return Err(BondError::InvariantViolated.into());
+        }
+
+        // The latest draw must be the state's draw id, and its unclaimed prize must be reserved.
+        if config.draw_tree.is_none() && state.draw_id > 0 {
+            let draw_info = next_account_info(account_info_iter)?;
+            let draw = BondDraw::unpack_from_slice(&draw_info.data.borrow())?;
+            Check::owner(draw_info, program_id)?;
+            Check::valid(&draw, draw_info)?;
+            Check::account(config_info, &draw.config)?;
+            if draw.id != state.draw_id {
+                msg!("Latest draw {} but state is at {}", draw.id, state.draw_id);
+                return Err(BondError::InvariantViolated.into());
+            }
+            let is_unclaimed = !draw.is_claimed && !draw.is_expired;
+            if is_unclaimed && draw.amount > state.reserved {
+                msg!("Draw {} pays {} but {} is reserved", draw.id, draw.amount, state.reserved);
+                return Err(BondError::InvariantViolated.into());
+            }
+        }
+
+        // Receipts are only minted for issued ticket weight.
+        if let Some(receipt_mint) = config.receipt_mint {
+            let receipt_mint_info = next_account_info(account_info_iter)?;
+            Check::account(receipt_mint_info, &receipt_mint)?;
+            let supply = Mint::unpack_from_slice(&receipt_mint_info.data.borrow())?.supply;
+            if supply > state.ticket_weight {
+                msg!("Receipt supply {} exceeds ticket weight {}", supply, state.ticket_weight);
+                return Err(BondError::InvariantViolated.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_register_participant(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+        payout: Pubkey,
+        terms_hash: [u8; 32],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let wallet_info = next_account_info(account_info_iter)?;
+        Check::signer(wallet_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let participant_info = next_account_info(account_info_iter)?;
+        Check::writable(participant_info)?;
+
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Participant PDA Account.
+        Create::indexed_pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            participant_info, 
+            BondSeed::Participant, 
+            wallet_info.key.as_ref(), 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            space,
+        )?;
+        let participant = try_from_slice_unchecked::<BondParticipant>(
+            &participant_info.data.borrow(),
+        )?;
+        Check::uninitialized(&participant, participant_info)?;
+        BondParticipant::new(
+            *config_info.key,
+            bump,
+            *wallet_info.key,
+            payout,
+            terms_hash,
+            Clock::get()?.unix_timestamp,
+        ).serialize(
+            &mut &mut participant_info.data.borrow_mut()[..],
+        )?;
+
+        msg!("Registered {}", wallet_info.key);
+        Ok(())
+    }
+
+    fn process_place_hold(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let compliance_authority_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(compliance_authority_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::account(
+            compliance_authority_info, 
+            &config.compliance_authority.ok_or(ProgramError::InvalidAccountData)?,
+        )?;
+
+        let wallet_info = next_account_info(account_info_iter)?;
+        let hold_info = next_account_info(account_info_iter)?;
+        Check::writable(hold_info)?;
+
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Hold PDA Account.
+        Create::indexed_pda_account(
+            program_id, 
+            config_info, 
+            compliance_authority_info, 
+            hold_info, 
+            BondSeed::Hold, 
+            wallet_info.key.as_ref(), 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            space,
+        )?;
+        let hold = try_from_slice_unchecked::<BondHold>(&hold_info.data.borrow())?;
+        Check::uninitialized(&hold, hold_info)?;
+        BondHold::new(
+            *config_info.key,
+            bump,
+            *wallet_info.key,
+            Clock::get()?.unix_timestamp,
+        ).serialize(
+            &mut &mut hold_info.data.borrow_mut()[..],
+        )?;
+
+        msg!("Held {}", wallet_info.key);
+        Ok(())
+    }
+
+    fn process_release_hold(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        reroute: bool,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        Check::writable(authority_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+
+        let jackpot_info = next_account_info(account_info_iter)?;
+        let jackpot = BondShare::unpack_from_slice(&jackpot_info.data.borrow())?;
+        let jackpot_ata_info = next_account_info(account_info_iter)?;
+        let jackpot_ata = Account::unpack_from_slice(&jackpot_ata_info.data.borrow())?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            jackpot_info, 
+            &jackpot, 
+            jackpot_ata_info,
+            &jackpot_ata,
+        )?;
+        Check::pda(program_id, config_info, jackpot_info, BondSeed::Jackpot, jackpot.bump)?;
+
+        let hold_info = next_account_info(account_info_iter)?;
+        let hold = try_from_slice_unchecked::<BondHold>(&hold_info.data.borrow())?;
+        Check::writable(hold_info)?;
+        Check::owner(hold_info, program_id)?;
+        Check::valid(&hold, hold_info)?;
+        Check::account(config_info, &hold.authority)?;
+
+        let destination_ata_info = next_account_info(account_info_iter)?;
+        let destination_ata = Account::unpack_from_slice(&destination_ata_info.data.borrow())?;
+        if !reroute {
+            Check::pubkey(&destination_ata.owner, &hold.wallet)?;
+        }
+
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+
+        state.reserved = state.reserved
+            .checked_sub(hold.amount)
+            .ok_or(BondError::NumericalOverflow)?;
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        if hold.amount > 0 {
+            // Jackpot -> Destination
+            Create::token_transfer_checked(
+                config_info,
+                token_program_info,
+                token_mint_info,
+                jackpot_ata_info,
+                destination_ata_info,
+                jackpot_info,
+                BondSeed::Jackpot,
+                jackpot.bump,
+                hold.amount,
+                config.decimals,
+            )?;
+        }
+        Create::close(hold_info, authority_info)?;
+
+        msg!("Released {} ({})", hold.wallet, hold.amount);
+        Ok(())
+    }
+
+    fn process_register_jackpot_mint(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let mut config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let jackpot_info = next_account_info(account_info_iter)?;
+        let jackpot = BondShare::unpack_from_slice(&jackpot_info.data.borrow())?;
+        Check::owner(jackpot_info, program_id)?;
+        Check::valid(&jackpot, jackpot_info)?;
+        Check::pda(program_id, config_info, jackpot_info, BondSeed::Jackpot, jackpot.bump)?;
+
+        let jackpot_mints_info = next_account_info(account_info_iter)?;
+        Check::writable(jackpot_mints_info)?;
+        Check::pda(program_id, config_info, jackpot_mints_info, BondSeed::JackpotMints, bump)?;
+
+        let mint_info = next_account_info(account_info_iter)?;
+        Mint::unpack_from_slice(&mint_info.data.borrow())?;
+        if mint_info.key == &config.token_mint {
+            return Err(BondError::DuplicateJackpotMint.into());
+        }
+
+        let jackpot_mint_ata_info = next_account_info(account_info_iter)?;
+
+        // The mint may belong to either token program, independently of the game's mint.
+        let token_program_info = next_account_info(account_info_iter)?;
+        let associated_token_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(mint_info, token_program_info)?;
+        Check::ata_with_program(
+            jackpot_info, 
+            mint_info.key, 
+            token_program_info.key, 
+            jackpot_mint_ata_info,
+        )?;
+        Check::account(associated_token_program_info, &spl_associated_token_account::id())?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Jackpot Mints PDA Account (created on the first registration).
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            jackpot_mints_info, 
+            BondSeed::JackpotMints, 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            space,
+        )?;
+        let mut jackpot_mints = try_from_slice_unchecked::<BondJackpotMints>(
+            &jackpot_mints_info.data.borrow(),
+        )?;
+        if !jackpot_mints.is_initialized() {
+            jackpot_mints = BondJackpotMints::new(*config_info.key, bump);
+        }
+        Check::valid(&jackpot_mints, jackpot_mints_info)?;
+        jackpot_mints.add(*mint_info.key)?;
+        jackpot_mints.serialize(&mut &mut jackpot_mints_info.data.borrow_mut()[..])?;
+
+        // Jackpot ATA for the mint.
+        Create::ata_account(
+            payer_info, 
+            jackpot_mint_ata_info, 
+            jackpot_info, 
+            mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info,
+        )?;
+
+        config.jackpot_mints = Some(*jackpot_mints_info.key);
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+
+        msg!("Jackpot mint {} registered", mint_info.key);
+        Ok(())
+    }
+
+    fn process_create_session(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+        session_key: Pubkey,
+        expires_at: i64,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let wallet_info = next_account_info(account_info_iter)?;
+        Check::signer(wallet_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let session_info = next_account_info(account_info_iter)?;
+        Check::writable(session_info)?;
+
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Session PDA Account (reused by later sessions).
+        Create::indexed_pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            session_info, 
+            BondSeed::Session, 
+            wallet_info.key.as_ref(), 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            space,
+        )?;
+        BondSession::new(
+            *config_info.key,
+            bump,
+            *wallet_info.key,
+            session_key,
+            expires_at,
+        ).serialize(
+            &mut &mut session_info.data.borrow_mut()[..],
+        )?;
+
+        msg!("Session {} created for {}", session_key, wallet_info.key);
+        Ok(())
+    }
+
+    fn process_create_receipt_mint(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let mut config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+        if let Some(receipt_mint) = config.receipt_mint {
+            msg!("Receipt mint {} already exists", receipt_mint);
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let receipt_mint_info = next_account_info(account_info_iter)?;
+        Check::writable(receipt_mint_info)?;
+        Check::pda(program_id, config_info, receipt_mint_info, BondSeed::Receipt, bump)?;
+
+        let token_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::token_program(token_program_info)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Receipt Mint PDA Account.
+        let receipt_bump = [bump];
+        let seeds = BondSeed::Receipt.signer_seeds(config_info.key, &receipt_bump);
+        Create::mint_pda(
+            payer_info, 
+            receipt_mint_info, 
+            state_info, 
+            token_program_info, 
+            system_program_info, 
+            &Rent::get()?, 
+            config.decimals, 
+            &seeds,
+        )?;
+
+        config.receipt_mint = Some(*receipt_mint_info.key);
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+
+        msg!("Receipt mint {} created", receipt_mint_info.key);
+        Ok(())
+    }
+
+    fn process_create_leaderboard(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let mut config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let leaderboard_info = next_account_info(account_info_iter)?;
+        Check::writable(leaderboard_info)?;
+
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Leaderboard PDA Account.
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            leaderboard_info, 
+            BondSeed::Leaderboard, 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            space,
+        )?;
+        let leaderboard = try_from_slice_unchecked::<BondLeaderboard>(
+            &leaderboard_info.data.borrow(),
+        )?;
+        Check::uninitialized(&leaderboard, leaderboard_info)?;
+        BondLeaderboard::new(
+            *config_info.key,
+            bump,
+        ).serialize(
+            &mut &mut leaderboard_info.data.borrow_mut()[..],
+        )?;
+
+        config.leaderboard = Some(*leaderboard_info.key);
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+
+        msg!("Leaderboard {} created", leaderboard_info.key);
+        Ok(())
+    }
+
+    fn process_set_min_transfer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        min_transfer: u64,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let share_info = next_account_info(account_info_iter)?;
+        let mut share = BondShare::unpack_from_slice(&share_info.data.borrow())?;
+        Check::writable(share_info)?;
+        Check::owner(share_info, program_id)?;
+        Check::valid(&share, share_info)?;
+        Check::account(config_info, &share.authority)?;
+
+        share.min_transfer = min_transfer;
+        pack_account(&share, &mut share_info.data.borrow_mut())?;
+
+        msg!("Share {} minimum transfer set to {}", share_info.key, min_transfer);
+        Ok(())
+    }
+
+    fn process_create_stream(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+        recipient: Pubkey,
+        rate: u64,
+        duration: u64,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+
+        let treasury_info = next_account_info(account_info_iter)?;
+        let treasury = BondShare::unpack_from_slice(&treasury_info.data.borrow())?;
+        let treasury_ata_info = next_account_info(account_info_iter)?;
+        let treasury_ata = Account::unpack_from_slice(&treasury_ata_info.data.borrow())?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            treasury_info, 
+            &treasury, 
+            treasury_ata_info,
+            &treasury_ata,
+        )?;
+        Check::pda(program_id, config_info, treasury_info, BondSeed::Treasury, treasury.bump)?;
+
+        let stream_info = next_account_info(account_info_iter)?;
+        let stream_ata_info = next_account_info(account_info_iter)?;
+        Check::writable(stream_info)?;
+        Check::ata(stream_info, &config.token_mint, stream_ata_info)?;
+
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        let associated_token_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Stream PDA Account.
+        Create::indexed_pda_account(
+            program_id, 
+            config_info, 
+            payer_info, 
+            stream_info, 
+            BondSeed::Stream, 
+            recipient.as_ref(), 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            space,
+        )?;
+        let stream = try_from_slice_unchecked::<BondStream>(&stream_info.data.borrow())?;
+        Check::uninitialized(&stream, stream_info)?;
+        let stream = BondStream::new(
+            *config_info.key,
+            bump,
+            recipient,
+            rate,
+            Clock::get()?.unix_timestamp,
+            duration,
+        );
+        stream.serialize(&mut &mut stream_info.data.borrow_mut()[..])?;
+        Create::ata_account(
+            payer_info, 
+            stream_ata_info, 
+            stream_info, 
+            token_mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info,
+        )?;
+
+        // Treasury -> Stream
+        let total = stream.total().ok_or(BondError::NumericalOverflow)?;
+        Create::token_transfer_checked(
+            config_info,
+            token_program_info,
+            token_mint_info,
+            treasury_ata_info,
+            stream_ata_info,
+            treasury_info,
+            BondSeed::Treasury,
+            treasury.bump,
+            total,
+            config.decimals,
+        )?;
+
+        // Streams must be revoked before the treasury is closed by `CloseLottoShare`.
+        state.open_streams = state.open_streams
+            .checked_add(1)
+            .ok_or(BondError::NumericalOverflow)?;
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        msg!("Stream of {} created for {}", total, recipient);
+        Ok(())
+    }
+
+    fn process_withdraw_stream(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let recipient_info = next_account_info(account_info_iter)?;
+        Check::signer(recipient_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let stream_info = next_account_info(account_info_iter)?;
+        let mut stream = try_from_slice_unchecked::<BondStream>(&stream_info.data.borrow())?;
+        Check::writable(stream_info)?;
+        Check::owner(stream_info, program_id)?;
+        Check::valid(&stream, stream_info)?;
+        Check::account(config_info, &stream.authority)?;
+        Check::account(recipient_info, &stream.recipient)?;
+
+        let stream_ata_info = next_account_info(account_info_iter)?;
+        Check::ata(stream_info, &config.token_mint, stream_ata_info)?;
+        let recipient_ata_info = next_account_info(account_info_iter)?;
+        let recipient_ata = Account::unpack_from_slice(&recipient_ata_info.data.borrow())?;
+        Check::pubkey(&recipient_ata.owner, recipient_info.key)?;
+
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+
+        let amount = stream
+            .withdrawable(Clock::get()?.unix_timestamp)
+            .ok_or(BondError::NumericalOverflow)?;
+        stream.withdrawn += amount;
+        stream.serialize(&mut &mut stream_info.data.borrow_mut()[..])?;
+
+        // Stream -> Recipient
+        let stream_bump = [stream.bump];
+        let seeds = BondSeed::Stream.indexed_signer_seeds(
+            config_info.key, 
+            stream.recipient.as_ref(), 
+            &stream_bump,
+        );
+        Create::token_transfer_signed(
+            token_program_info, 
+            token_mint_info, 
+            stream_ata_info, 
+            recipient_ata_info, 
+            stream_info, 
+            amount, 
+            config.decimals, 
+            &seeds,
+        )?;
+
+        msg!("Withdrew {} from stream", amount);
+        Ok(())
+    }
+
+    fn process_revoke_stream(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        Check::writable(authority_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+
+        let stream_info = next_account_info(account_info_iter)?;
+        let stream = try_from_slice_unchecked::<BondStream>(&stream_info.data.borrow())?;
+        Check::writable(stream_info)?;
+        Check::owner(stream_info, program_id)?;
+        Check::valid(&stream, stream_info)?;
+        Check::account(config_info, &stream.authority)?;
+
+        let stream_ata_info = next_account_info(account_info_iter)?;
+        let stream_ata = Account::unpack_from_slice(&stream_ata_info.data.borrow())?;
+        Check::ata(stream_info, &config.token_mint, stream_ata_info)?;
+        let recipient_ata_info = next_account_info(account_info_iter)?;
+        let recipient_ata = Account::unpack_from_slice(&recipient_ata_info.data.borrow())?;
+        Check::pubkey(&recipient_ata.owner, &stream.recipient)?;
+
+        let treasury_info = next_account_info(account_info_iter)?;
+        let treasury = BondShare::unpack_from_slice(&treasury_info.data.borrow())?;
+        let treasury_ata_info = next_account_info(account_info_iter)?;
+        let treasury_ata = Account::unpack_from_slice(&treasury_ata_info.data.borrow())?;
+        Self::check_draw_pda_account(
+            program_id, 
+            config_info, 
+            treasury_info, 
+            &treasury, 
+            treasury_ata_info,
+            &treasury_ata,
+        )?;
+        Check::pda(program_id, config_info, treasury_info, BondSeed::Treasury, treasury.bump)?;
+
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+
+        let vested = stream
+            .withdrawable(Clock::get()?.unix_timestamp)
+            .ok_or(BondError::NumericalOverflow)?
+            .min(stream_ata.amount);
+        let stream_bump = [stream.bump];
+        let seeds = BondSeed::Stream.indexed_signer_seeds(
+            config_info.key, 
+            stream.recipient.as_ref(), 
+            &stream_bump,
+        );
+
+        // Stream -> Recipient
+        Create::token_transfer_signed(
+            token_program_info, 
+            token_mint_info, 
+            stream_ata_info, 
+            recipient_ata_info, 
+            stream_info, 
+            vested, 
+            config.decimals, 
+            &seeds,
+        )?;
+
+        // Stream -> Treasury
+        Create::token_transfer_signed(
+            token_program_info, 
+            token_mint_info, 
+            stream_ata_info, 
+            treasury_ata_info, 
+            stream_info, 
+            stream_ata.amount - vested, 
+            config.decimals, 
+            &seeds,
+        )?;
+
+        Create::close_token_account(
+            token_program_info, 
+            stream_ata_info, 
+            authority_info, 
+            stream_info, 
+            &seeds,
+        )?;
+        Create::close(stream_info, authority_info)?;
+        state.open_streams = state.open_streams.saturating_sub(1);
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        msg!("Stream revoked: {} vested, {} returned", vested, stream_ata.amount - vested);
+        Ok(())
+    }
+
+    fn process_claim_rebate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+        ticket_proof: TicketProof,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let wallet_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(wallet_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let state_ata_info = next_account_info(account_info_iter)?;
+        Check::ata(state_info, &config.token_mint, state_ata_info)?;
+        let wallet_ata_info = next_account_info(account_info_iter)?;
+        let wallet_ata = Account::unpack_from_slice(&wallet_ata_info.data.borrow())?;
+        Check::pubkey(&wallet_ata.owner, wallet_info.key)?;
+
+        let rebate_claim_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Prove the ticket.
+        let compression_program_info = next_account_info(account_info_iter)?;
+        let ticket_tree_info = next_account_info(account_info_iter)?;
+        Check::account(compression_program_info, &ACCOUNT_COMPRESSION_PROGRAM_ID)?;
+        Check::account(ticket_tree_info, &config.ticket_tree.unwrap_or_default())?;
+        let ticket = BondTicket {
+            wallet: *wallet_info.key,
+            weight: ticket_proof.weight,
+            issued_at: ticket_proof.issued_at,
+            rebate_per_weight: ticket_proof.rebate_per_weight,
+        };
+        let leaf = ticket.leaf()?;
+        Create::verify_leaf(
+            compression_program_info, 
+            ticket_tree_info, 
+            account_info_iter.as_slice(), 
+            ticket_proof.root, 
+            leaf, 
+            ticket_proof.index,
+        )?;
+
+        // Rebate Claim PDA Account (created on the ticket's first claim).
+        Create::indexed_pda_account(
+            program_id, 
+            config_info, 
+            wallet_info, 
+            rebate_claim_info, 
+            BondSeed::Rebate, 
+            &BondRebateClaim::seed_index(&leaf, ticket_proof.index), 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            space,
+        )?;
+        let rebate_claim = try_from_slice_unchecked::<BondRebateClaim>(
+            &rebate_claim_info.data.borrow(),
+        )?;
+        let checkpoint = if rebate_claim.is_initialized() {
+            Check::valid(&rebate_claim, rebate_claim_info)?;
+            Check::account(config_info, &rebate_claim.authority)?;
+            rebate_claim.rebate_per_weight
+        } else {
+            ticket.rebate_per_weight
+        };
+        let amount = ticket
+            .rebate(state.rebate_per_weight, checkpoint)
+            .ok_or(BondError::NumericalOverflow)?;
+        BondRebateClaim::new(
+            *config_info.key,
+            bump,
+            state.rebate_per_weight,
         ).serialize(
-            &mut &mut account_info.data.borrow_mut()[..],
+            &mut &mut rebate_claim_info.data.borrow_mut()[..],
+        )?;
+
+        // State -> Wallet
+        Create::token_transfer_checked(
+            config_info,
+            token_program_info,
+            token_mint_info,
+            state_ata_info,
+            wallet_ata_info,
+            state_info,
+            BondSeed::State,
+            state.bump,
+            amount,
+            config.decimals,
+        )?;
+
+        msg!("Rebate claimed: {}", amount);
+        Ok(())
+    }
+
+    fn process_redeem_ticket(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        ticket_proof: TicketProof,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let wallet_info = next_account_info(account_info_iter)?;
+        Check::signer(wallet_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let ticket_tree_info = next_account_info(account_info_iter)?;
+        let compression_program_info = next_account_info(account_info_iter)?;
+        let noop_program_info = next_account_info(account_info_iter)?;
+        Check::writable(ticket_tree_info)?;
+        Check::account(ticket_tree_info, &config.ticket_tree.unwrap_or_default())?;
+        Check::account(compression_program_info, &ACCOUNT_COMPRESSION_PROGRAM_ID)?;
+        Check::account(noop_program_info, &NOOP_PROGRAM_ID)?;
+
+        let receipt_infos = match config.receipt_mint {
+            Some(receipt_mint) => {
+                let receipt_mint_info = next_account_info(account_info_iter)?;
+                let receipt_ata_info = next_account_info(account_info_iter)?;
+                let token_program_info = next_account_info(account_info_iter)?;
+                Check::account(receipt_mint_info, &receipt_mint)?;
+                Check::token_mint(receipt_mint_info, token_program_info)?;
+                Some((receipt_mint_info, receipt_ata_info, token_program_info))
+            },
+            None => None,
+        };
+
+        // Remove the ticket from the ledger, which fails unless it's held by the wallet.
+        let ticket = BondTicket {
+            wallet: *wallet_info.key,
+            weight: ticket_proof.weight,
+            issued_at: ticket_proof.issued_at,
+            rebate_per_weight: ticket_proof.rebate_per_weight,
+        };
+        let state_bump = [state.bump];
+        let seeds = BondSeed::State.signer_seeds(config_info.key, &state_bump);
+        Create::replace_leaf(
+            compression_program_info, 
+            noop_program_info, 
+            ticket_tree_info, 
+            state_info, 
+            account_info_iter.as_slice(), 
+            ticket_proof.root, 
+            ticket.leaf()?, 
+            [0; 32], 
+            ticket_proof.index, 
+            &seeds,
+        )?;
+        state.ticket_weight = state.ticket_weight
+            .checked_sub(ticket.weight)
+            .ok_or(BondError::NumericalOverflow)?;
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        // Receipts are burned 1:1 with the ticket's weight.
+        if let Some((receipt_mint_info, receipt_ata_info, token_program_info)) = receipt_infos {
+            Create::burn(
+                token_program_info, 
+                receipt_mint_info, 
+                receipt_ata_info, 
+                wallet_info, 
+                ticket.weight,
+            )?;
+        }
+
+        msg!("Ticket {} redeemed: {}", ticket_proof.index, ticket.weight);
+        Ok(())
+    }
+
+    fn process_set_payout_asset(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        payout_asset: BondPayoutAsset,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let receiver_info = next_account_info(account_info_iter)?;
+        Check::signer(receiver_info)?;
+
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let draw_info = next_account_info(account_info_iter)?;
+        let mut draw = BondDraw::unpack_from_slice(&draw_info.data.borrow())?;
+        Check::writable(draw_info)?;
+        Check::owner(draw_info, program_id)?;
+        Check::valid(&draw, draw_info)?;
+        Check::account(config_info, &draw.config)?;
+        Check::account(receiver_info, &draw.receiver)?;
+        if draw.is_claimed {
+            return Err(BondError::PrizeAlreadyClaimed.into());
+        }
+        if draw.is_claim_expired(config.claim_window, Clock::get()?.unix_timestamp) {
+            return Err(BondError::ClaimExpired.into());
+        }
+        let is_sol_supported = config.lst_adapter == BondLstAdapter::SplStakePool;
+        if payout_asset == BondPayoutAsset::Sol && !is_sol_supported {
+            msg!("SOL payouts are only supported by SPL stake pools");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let is_native_mint = config.token_mint == spl_token::native_mint::id();
+        if payout_asset == BondPayoutAsset::NativeSol && !is_native_mint {
+            msg!("Unwrapped payouts are only supported by wrapped SOL games");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        draw.payout_asset = payout_asset;
+        pack_account(&draw, &mut draw_info.data.borrow_mut())?;
+        msg!("Payout asset: {:?}", payout_asset);
+        Ok(())
+    }
+
+    fn unpack_exclusion_list_header(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        exclusion_list_info: &AccountInfo,
+    ) -> Result<BondExclusionListHeader, ProgramError> {
+        let header = try_from_slice_unchecked::<BondExclusionListHeader>(
+            &exclusion_list_info.data.borrow(),
+        )?;
+        Check::owner(exclusion_list_info, program_id)?;
+        Check::valid(&header, exclusion_list_info)?;
+        Check::account(config_info, &header.authority)?;
+        Check::pda(
+            program_id, 
+            config_info, 
+            exclusion_list_info, 
+            BondSeed::ExclusionList, 
+            header.bump,
+        )?;
+        Ok(header)
+    }
+
+    fn process_validate_receiver_chunk(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+        receiver: Pubkey,
+        count: u32,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Self::check_draw_account(program_id, config_info, draw_authority_info, &config)?;
+        Check::active(&config)?;
+        Check::writable(draw_authority_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let header = Self::unpack_exclusion_list_header(
+            program_id, 
+            config_info, 
+            exclusion_list_info,
+        )?;
+
+        let validation_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Validation PDA Account (created on the first validation).
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            draw_authority_info, 
+            validation_info, 
+            BondSeed::ExclusionValidation, 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            space,
+        )?;
+        let mut validation = try_from_slice_unchecked::<BondExclusionValidation>(
+            &validation_info.data.borrow(),
+        )?;
+        if validation.is_initialized() {
+            Check::valid(&validation, validation_info)?;
+            Check::account(config_info, &validation.authority)?;
+        }
+
+        // Restart the validation if the receiver, the draw, the epoch or the list changed.
+        let epoch = Clock::get()?.epoch;
+        let draw_id = state.draw_id + 1;
+        if !validation.is_for(&receiver, draw_id, epoch, header.revision) {
+            validation = BondExclusionValidation::new(
+                *config_info.key,
+                bump,
+                receiver,
+                draw_id,
+                epoch,
+                header.revision,
+            );
+        }
+
+        // Check the next `count` entries, resuming at the recorded byte offset since entries vary 
+        // in size.
+        {
+            let data = exclusion_list_info.data.borrow();
+            let mut entries = data
+                .get(validation.offset as usize..)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            let end = header.len.min(validation.index.saturating_add(count));
+            while validation.index < end {
+                let excluded = BondExclusion::deserialize(&mut entries)?;
+                if excluded.account == receiver && !excluded.is_expired(epoch) {
+                    msg!("Receiver excluded at index {}", validation.index);
+                    return Err(BondError::ExcludedReceiver.into());
+                }
+                validation.index += 1;
+            }
+            validation.offset = (data.len() - entries.len()) as u32;
+        }
+        validation.is_complete = validation.index == header.len;
+
+        validation.serialize(&mut &mut validation_info.data.borrow_mut()[..])?;
+        msg!("Validated {}/{} exclusions", validation.index, header.len);
+        Ok(())
+    }
+
+    fn process_rotate_share_ata(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seed: BondSeed,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(authority_info)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        Self::default_share(&seed)?;
+        let share_info = next_account_info(account_info_iter)?;
+        let share = BondShare::unpack_from_slice(&share_info.data.borrow())?;
+        Check::owner(share_info, program_id)?;
+        Check::valid(&share, share_info)?;
+        Check::pda(program_id, config_info, share_info, seed.clone(), share.bump)?;
+
+        let old_ata_info = next_account_info(account_info_iter)?;
+        let old_mint_info = next_account_info(account_info_iter)?;
+        let old_token_program_info = next_account_info(account_info_iter)?;
+        Check::token_program(old_token_program_info)?;
+        Check::owner(old_mint_info, old_token_program_info.key)?;
+        Check::ata_with_program(
+            share_info, 
+            old_mint_info.key, 
+            old_token_program_info.key, 
+            old_ata_info,
+        )?;
+        let destination_info = next_account_info(account_info_iter)?;
+
+        let new_ata_info = next_account_info(account_info_iter)?;
+        let new_mint_info = next_account_info(account_info_iter)?;
+        Check::account(new_mint_info, &config.token_mint)?;
+        let new_token_program_info = next_account_info(account_info_iter)?;
+        Check::token_program(new_token_program_info)?;
+        Check::owner(new_mint_info, new_token_program_info.key)?;
+        Check::ata_with_program(
+            share_info, 
+            new_mint_info.key, 
+            new_token_program_info.key, 
+            new_ata_info,
+        )?;
+        if old_ata_info.key == new_ata_info.key {
+            msg!("Share ATA is already derived from the current mint and token program");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let associated_token_program_info = next_account_info(account_info_iter)?;
+        Check::account(associated_token_program_info, &spl_associated_token_account::id())?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        let share_bump = [share.bump];
+        let seeds = seed.signer_seeds(config_info.key, &share_bump);
+
+        // Old ATA -> Destination
+        Check::owner(old_ata_info, old_token_program_info.key)?;
+        let old_ata = Account::unpack_from_slice(&old_ata_info.data.borrow())?;
+        if old_ata.amount > 0 {
+            let old_mint = Mint::unpack_from_slice(&old_mint_info.data.borrow())?;
+            Create::token_transfer_signed(
+                old_token_program_info,
+                old_mint_info,
+                old_ata_info,
+                destination_info,
+                share_info,
+                old_ata.amount,
+                old_mint.decimals,
+                &seeds,
+            )?;
+        }
+        Create::close_token_account(
+            old_token_program_info, 
+            old_ata_info, 
+            authority_info, 
+            share_info, 
+            &seeds,
+        )?;
+
+        // Share ATA Account
+        Create::ata_account(
+            authority_info, 
+            new_ata_info, 
+            share_info, 
+            new_mint_info, 
+            new_token_program_info, 
+            associated_token_program_info, 
+            system_program_info,
+        )?;
+
+        msg!("Share ATA rotated to {}", new_ata_info.key);
+        Ok(())
+    }
+
+    fn process_migrate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        space: u32,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(authority_info)?;
+
+        // The config may itself be pending migration, so it's read behind its discriminator and 
+        // its appended fields are zero-padded.
+        let config_info = next_account_info(account_info_iter)?;
+        let mut config_data = config_info.data.borrow().to_vec();
+        if BondAccountType::from_legacy(&config_data).is_some() {
+            let len = config_data.len();
+            config_data.resize(len + LEGACY_DISCRIMINATOR_GROWTH, 0);
+            Self::upgrade_legacy_layout(&mut config_data, len, BondAccountType::Config);
+        }
+        config_data.resize(config_data.len().max(CONFIG_SIZE), 0);
+        let config = BondConfig::unpack_from_slice(&config_data)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+
+        let account_info = next_account_info(account_info_iter)?;
+        Check::writable(account_info)?;
+        Check::owner(account_info, program_id)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Accounts written with a 1-byte `account_type` are shifted behind their discriminator.
+        let legacy_type = BondAccountType::from_legacy(&account_info.data.borrow());
+        if let Some(account_type) = legacy_type {
+            let len = account_info.data_len();
+            Check::space(space, len + LEGACY_DISCRIMINATOR_GROWTH)?;
+            Create::realloc(
+                authority_info, 
+                account_info, 
+                system_program_info, 
+                &Rent::get()?, 
+                space,
+            )?;
+            Self::upgrade_legacy_layout(&mut account_info.data.borrow_mut(), len, account_type);
+        }
+
+        let version = *account_info.data.borrow()
+            .get(ACCOUNT_VERSION_OFFSET)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if version > ACCOUNT_LAYOUT_VERSION {
+            msg!("Account version {} is newer than {}", version, ACCOUNT_LAYOUT_VERSION);
+            return Err(BondError::InvalidAccountVersion.into());
+        }
+        if version == ACCOUNT_LAYOUT_VERSION {
+            msg!("Account is up to date");
+            return Ok(());
+        }
+
+        // Grow the account and read it with the current layout.
+        Create::realloc(authority_info, account_info, system_program_info, &Rent::get()?, space)?;
+        account_info.data.borrow_mut()[ACCOUNT_VERSION_OFFSET] = ACCOUNT_LAYOUT_VERSION;
+        let account_type = BondAccountType::deserialize(&mut &account_info.data.borrow()[..])?;
+        match account_type {
+            BondAccountType::Config => {
+                Check::account(account_info, config_info.key)?;
+                Self::migrate_account::<BondConfig>(account_info, |_| Ok(()))?
+            },
+            BondAccountType::Draw => {
+                Self::migrate_account::<BondDraw>(account_info, |draw| {
+                    Check::account(config_info, &draw.config)
+                })?
+            },
+            BondAccountType::State => {
+                Self::migrate_program_account::<BondState>(config_info, account_info)?
+            },
+            BondAccountType::Share => {
+                Self::migrate_program_account::<BondShare>(config_info, account_info)?
+            },
+            BondAccountType::Fee => {
+                Self::migrate_program_account::<BondFee>(config_info, account_info)?
+            },
+            BondAccountType::ExclusionList => {
+                Self::migrate_program_account::<BondExclusionList>(config_info, account_info)?
+            },
+            BondAccountType::SeasonArchive => {
+                Self::migrate_program_account::<BondSeasonArchive>(config_info, account_info)?
+            },
+            BondAccountType::Participant => {
+                Self::migrate_program_account::<BondParticipant>(config_info, account_info)?
+            },
+            BondAccountType::Hold => {
+                Self::migrate_program_account::<BondHold>(config_info, account_info)?
+            },
+            BondAccountType::JackpotMints => {
+                Self::migrate_program_account::<BondJackpotMints>(config_info, account_info)?
+            },
+            BondAccountType::Session => {
+                Self::migrate_program_account::<BondSession>(config_info, account_info)?
+            },
+            BondAccountType::Leaderboard => {
+                Self::migrate_program_account::<BondLeaderboard>(config_info, account_info)?
+            },
+            BondAccountType::Stream => {
+                Self::migrate_program_account::<BondStream>(config_info, account_info)?
+            },
+            BondAccountType::RebateClaim => {
+                Self::migrate_program_account::<BondRebateClaim>(config_info, account_info)?
+            },
+            BondAccountType::ExclusionValidation => {
+                Self::migrate_program_account::<BondExclusionValidation>(
+                    config_info, 
+                    account_info,
+                )?
+            },
+            BondAccountType::Heartbeat => {
+                Self::migrate_program_account::<BondHeartbeat>(config_info, account_info)?
+            },
+            BondAccountType::Snapshot => {
+                Self::migrate_program_account::<BondSnapshot>(config_info, account_info)?
+            },
+            BondAccountType::ParticipantBuffer => {
+                Self::migrate_program_account::<BondParticipantBuffer>(config_info, account_info)?
+            },
+            BondAccountType::Uninitialized => return Err(ProgramError::UninitializedAccount),
+        }
+
+        msg!("Account migrated from version {} to {}", version, ACCOUNT_LAYOUT_VERSION);
+        Ok(())
+    }
+
+    /// Rewrites the first `len` bytes of `data`, an account written with a 1-byte `account_type`, 
+    /// behind the 8-byte discriminator of `account_type`.
+    fn upgrade_legacy_layout(data: &mut [u8], len: usize, account_type: BondAccountType) {
+        data.copy_within(1..len, ACCOUNT_DISCRIMINATOR_SIZE);
+        data[..ACCOUNT_DISCRIMINATOR_SIZE].copy_from_slice(&account_type.discriminator());
+    }
+
+    fn migrate_program_account<T>(
+        config_info: &AccountInfo,
+        account_info: &AccountInfo,
+    ) -> ProgramResult 
+    where
+        T: BondProgramAccount + BorshDeserialize + BorshSerialize,
+    {
+        Self::migrate_account::<T>(account_info, |account| {
+            Check::account(config_info, &account.authority())
+        })
+    }
+
+    fn migrate_account<T>(
+        account_info: &AccountInfo,
+        check: impl FnOnce(&T) -> ProgramResult,
+    ) -> ProgramResult 
+    where
+        T: BondAccount + BorshDeserialize + BorshSerialize,
+    {
+        let account = try_from_slice_unchecked::<T>(&account_info.data.borrow())?;
+        Check::valid(&account, account_info)?;
+        check(&account)?;
+        account.serialize(&mut &mut account_info.data.borrow_mut()[..])?;
+        Ok(())
+    }
+
+    fn process_heartbeat(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        space: u32,
+        runs: u8,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Self::check_draw_account(program_id, config_info, draw_authority_info, &config)?;
+        Check::active(&config)?;
+        Check::writable(draw_authority_info)?;
+
+        let heartbeat_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        // Heartbeat PDA Account (created on the first heartbeat).
+        Create::pda_account(
+            program_id, 
+            config_info, 
+            draw_authority_info, 
+            heartbeat_info, 
+            BondSeed::Heartbeat, 
+            bump, 
+            system_program_info, 
+            &Rent::get()?, 
+            space,
+        )?;
+        let mut heartbeat = try_from_slice_unchecked::<BondHeartbeat>(
+            &heartbeat_info.data.borrow(),
+        )?;
+        if heartbeat.is_initialized() {
+            Check::valid(&heartbeat, heartbeat_info)?;
+            Check::account(config_info, &heartbeat.authority)?;
+        } else {
+            heartbeat = BondHeartbeat::new(*config_info.key, bump);
+        }
+
+        let slot = Clock::get()?.slot;
+        if runs & HEARTBEAT_HARVEST != 0 {
+            heartbeat.last_harvest_slot = slot;
+        }
+        if runs & HEARTBEAT_SPLIT != 0 {
+            heartbeat.last_split_slot = slot;
+        }
+        if runs & HEARTBEAT_DRAW != 0 {
+            heartbeat.last_draw_slot = slot;
+        }
+
+        heartbeat.serialize(&mut &mut heartbeat_info.data.borrow_mut()[..])?;
+        Ok(())
+    }
+
+    fn process_set_active(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        is_active: bool,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let mut config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+
+        config.is_active = is_active;
+        pack_account(&config, &mut config_info.data.borrow_mut())?;
+
+        msg!("Game {}", if is_active { "resumed" } else { "paused" });
+        Ok(())
+    }
+
+    fn process_set_shares(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        shares: [Ratio; 4],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+        Check::shares(&shares)?;
+
+        let seeds = [BondSeed::Equity, BondSeed::Treasury, BondSeed::Jackpot, BondSeed::Stake];
+        for (seed, ratio) in seeds.into_iter().zip(shares) {
+            let share_info = next_account_info(account_info_iter)?;
+            let mut share = BondShare::unpack_from_slice(&share_info.data.borrow())?;
+            Check::writable(share_info)?;
+            Check::owner(share_info, program_id)?;
+            Check::valid(&share, share_info)?;
+            Check::account(config_info, &share.authority)?;
+            Check::pda(program_id, config_info, share_info, seed, share.bump)?;
+
+            share.numerator = ratio.numerator;
+            share.denominator = ratio.denominator;
+            pack_account(&share, &mut share_info.data.borrow_mut())?;
+            msg!("Share {} set to {}/{}", share_info.key, ratio.numerator, ratio.denominator);
+        }
+        Ok(())
+    }
+
+    fn process_add_excluded_accounts(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        exclusion_list_accounts: Vec<Pubkey>,
+        expires_at_epoch: Option<u64>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let mut exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
+            &exclusion_list_info.data.borrow(),
+        )?;
+        Check::writable(exclusion_list_info)?;
+        Check::owner(exclusion_list_info, program_id)?;
+        Check::valid(&exclusion_list, exclusion_list_info)?;
+        Check::account(config_info, &exclusion_list.authority)?;
+
+        // Already excluded (or repeated) accounts are skipped.
+        let mut added = 0;
+        for account in exclusion_list_accounts.into_iter() {
+            if exclusion_list.contains(&account) {
+                continue;
+            }
+            exclusion_list.add(account, expires_at_epoch)?;
+            added += 1;
+        }
+
+        exclusion_list.serialize(&mut &mut exclusion_list_info.data.borrow_mut()[..])?;
+        msg!(
+            "Added {} exclusions, size {}/{}", 
+            added, 
+            exclusion_list.accounts.len(), 
+            exclusion_list.capacity,
+        );
+        Ok(())
+    }
+
+    fn process_remove_excluded_accounts(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        exclusion_list_accounts: Vec<Pubkey>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let mut exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
+            &exclusion_list_info.data.borrow(),
+        )?;
+        Check::writable(exclusion_list_info)?;
+        Check::owner(exclusion_list_info, program_id)?;
+        Check::valid(&exclusion_list, exclusion_list_info)?;
+        Check::account(config_info, &exclusion_list.authority)?;
+
+        let removed = exclusion_list_accounts
+            .iter()
+            .filter(|account| exclusion_list.remove(account))
+            .count();
+
+        exclusion_list.serialize(&mut &mut exclusion_list_info.data.borrow_mut()[..])?;
+        msg!(
+            "Removed {} exclusions, size {}/{}", 
+            removed, 
+            exclusion_list.accounts.len(), 
+            exclusion_list.capacity,
+        );
+        Ok(())
+    }
+
+    fn process_grow_exclusion_list(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        capacity: u32,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(payer_info)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
+
+        let exclusion_list_info = next_account_info(account_info_iter)?;
+        let mut exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
+            &exclusion_list_info.data.borrow(),
+        )?;
+        Check::writable(exclusion_list_info)?;
+        Check::owner(exclusion_list_info, program_id)?;
+        Check::valid(&exclusion_list, exclusion_list_info)?;
+        Check::account(config_info, &exclusion_list.authority)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        if capacity <= exclusion_list.capacity {
+            msg!("Capacity {} doesn't exceed {}", capacity, exclusion_list.capacity);
+            return Err(ProgramError::InvalidArgument);
+        }
+        let space = exclusion_list_size(capacity).max(exclusion_list_info.data_len());
+        Create::realloc(
+            payer_info, 
+            exclusion_list_info, 
+            system_program_info, 
+            &Rent::get()?, 
+            u32::try_from(space).map_err(|_| BondError::NumericalOverflow)?,
+        )?;
+
+        exclusion_list.capacity = capacity;
+        exclusion_list.serialize(&mut &mut exclusion_list_info.data.borrow_mut()[..])?;
+        msg!("Exclusion list capacity {}", capacity);
+        Ok(())
+    }
+
+    fn process_request_randomness(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        permission_bump: u8,
+        program_state_bump: u8,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Self::check_draw_account(program_id, config_info, draw_authority_info, &config)?;
+        Check::active(&config)?;
+        Self::check_vrf_snapshot(&config)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        Self::request_randomness(
+            config_info, 
+            state_info, 
+            &mut state, 
+            account_info_iter.as_slice(), 
+            permission_bump, 
+            program_state_bump,
+        )
+    }
+
+    /// Requests the randomness of the next draw with `vrf_infos` (the accounts of 
+    /// `RequestRandomness` from `vrf`), recording the request in `state`.
+    fn request_randomness<'a>(
+        config_info: &AccountInfo<'a>,
+        state_info: &AccountInfo<'a>,
+        state: &mut BondState,
+        vrf_infos: &[AccountInfo<'a>],
+        permission_bump: u8,
+        program_state_bump: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut vrf_infos.iter();
+        let vrf_info = next_account_info(account_info_iter)?;
+        let (status, request_slot, _) = Self::unpack_vrf(vrf_info, state_info)?;
+
+        // A pending request must be fulfilled before the next one, so that its result can't be 
+        // discarded. Only requests left unverified by the oracle expire.
+        let clock = Clock::get()?;
+        if state.vrf_request_slot != 0 {
+            Check::account(vrf_info, &state.vrf)?;
+            let is_verified = request_slot == state.vrf_request_slot 
+                && VRF_VERIFIED_STATUSES.contains(&status);
+            let expiry_slot = state.vrf_request_slot.saturating_add(VRF_REQUEST_EXPIRY_SLOTS);
+            if is_verified || clock.slot < expiry_slot {
+                msg!("Randomness requested at slot {} is pending", state.vrf_request_slot);
+                return Err(BondError::VrfRequestPending.into());
+            }
+        }
+
+        let oracle_queue_info = next_account_info(account_info_iter)?;
+        let queue_authority_info = next_account_info(account_info_iter)?;
+        let data_buffer_info = next_account_info(account_info_iter)?;
+        let permission_info = next_account_info(account_info_iter)?;
+        let escrow_info = next_account_info(account_info_iter)?;
+        let payer_wallet_info = next_account_info(account_info_iter)?;
+        let payer_authority_info = next_account_info(account_info_iter)?;
+        let recent_blockhashes_info = next_account_info(account_info_iter)?;
+        let program_state_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let switchboard_program_info = next_account_info(account_info_iter)?;
+        Check::account(switchboard_program_info, &SWITCHBOARD_PROGRAM_ID)?;
+
+        let state_bump = [state.bump];
+        let state_seeds = BondSeed::State.signer_seeds(config_info.key, &state_bump);
+        Create::vrf_request_randomness(
+            switchboard_program_info, 
+            state_info, 
+            vrf_info, 
+            oracle_queue_info, 
+            queue_authority_info, 
+            data_buffer_info, 
+            permission_info, 
+            escrow_info, 
+            payer_wallet_info, 
+            payer_authority_info, 
+            recent_blockhashes_info, 
+            program_state_info, 
+            token_program_info, 
+            permission_bump, 
+            program_state_bump, 
+            &state_seeds,
+        )?;
+
+        state.vrf = *vrf_info.key;
+        state.vrf_request_slot = clock.slot;
+        pack_account(state, &mut state_info.data.borrow_mut())?;
+        msg!("Requested randomness for draw {}", state.draw_id + 1);
+        Ok(())
+    }
+
+    /// Checks that `vrf_info` is a Switchboard VRF account of `authority_info` and returns its 
+    /// `status`, `current_round.request_slot` and `current_round.result`.
+    fn unpack_vrf(
+        vrf_info: &AccountInfo,
+        authority_info: &AccountInfo,
+    ) -> Result<(u8, u64, [u8; 32]), ProgramError> {
+        Check::owner(vrf_info, &SWITCHBOARD_PROGRAM_ID)?;
+        let data = vrf_info.data.borrow();
+        if data.len() < VRF_AUTHORITY_OFFSET + 32 + VRF_REQUEST_SLOT_END_OFFSET 
+            || data[..8] != VRF_ACCOUNT_DISCRIMINATOR 
+        {
+            return Err(BondError::InvalidVrf.into());
+        }
+        let authority = Pubkey::new_from_array(
+            data[VRF_AUTHORITY_OFFSET..VRF_AUTHORITY_OFFSET + 32].try_into().unwrap(),
+        );
+        Check::pubkey(&authority, authority_info.key)?;
+        let request_slot_offset = data.len() - VRF_REQUEST_SLOT_END_OFFSET;
+        let request_slot = u64::from_le_bytes(
+            data[request_slot_offset..request_slot_offset + 8].try_into().unwrap(),
+        );
+        let result_offset = data.len() - VRF_RESULT_END_OFFSET;
+        let result = data[result_offset..result_offset + 32].try_into().unwrap();
+        Ok((data[VRF_STATUS_OFFSET], request_slot, result))
+    }
+
+    /// Check that the VRF draws of `config` select their receiver on chain from a snapshot, since 
+    /// the caller could otherwise pick the receiver regardless of the result.
+    fn check_vrf_snapshot(config: &BondConfig) -> ProgramResult {
+        if !config.snapshot_draws {
+            msg!("VRF draws require snapshot draws");
+            return Err(BondError::SnapshotRequired.into());
+        }
+        Ok(())
+    }
+
+    fn process_fulfill_draw(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        draw_seed: u64,
+        receiver_weight: u64,
+        total_weight: u64,
+        proof: Option<SnapshotProof>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let vrf_info = next_account_info(account_info_iter)?;
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Self::check_draw_caller(program_id, config_info, draw_authority_info, &config, true)?;
+        Check::active(&config)?;
+        Self::check_vrf_snapshot(&config)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        // The result must be verified for the pending request, which it consumes.
+        Check::account(vrf_info, &state.vrf)?;
+        let (status, request_slot, result) = Self::unpack_vrf(vrf_info, state_info)?;
+        let is_pending = state.vrf_request_slot != 0 && request_slot == state.vrf_request_slot;
+        if !is_pending || !VRF_VERIFIED_STATUSES.contains(&status) {
+            return Err(BondError::InvalidVrf.into());
+        }
+        state.vrf_request_slot = 0;
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        // The result selects the receiver from the draw's sealed snapshot (see `process_draw`).
+        let receiver_seed = u64::from_le_bytes(result[..8].try_into().unwrap());
+        Self::process_draw(
+            program_id, 
+            &accounts[1..], 
+            receiver_seed, 
+            draw_seed, 
+            receiver_weight, 
+            total_weight, 
+            proof, 
+            false, 
+            Some(result),
+        )
+    }
+
+    /// Creates the next draw's snapshot on the first write and returns it, failing if it can't be 
+    /// written anymore.
+    fn write_snapshot<'a>(
+        program_id: &Pubkey,
+        config_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        config: &BondConfig,
+        state: &BondState,
+        snapshot_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        rent: &Rent,
+        bump: u8,
+        space: usize,
+    ) -> Result<BondSnapshot, ProgramError> {
+
+        // The snapshot is frozen once the draw's randomness has been requested.
+        if state.vrf_request_slot != 0 {
+            return Err(BondError::SnapshotLocked.into());
+        }
+
+        // Snapshot PDA Account (created on the first write, then grown by its writers).
+        let draw_id = state.draw_id + 1;
+        if snapshot_info.data_is_empty() {
+            Create::indexed_pda_account(
+                program_id, 
+                config_info, 
+                payer_info, 
+                snapshot_info, 
+                BondSeed::Snapshot, 
+                &draw_id.to_le_bytes(), 
+                bump, 
+                system_program_info, 
+                rent, 
+                space as u32,
+            )?;
+        }
+        Check::owner(snapshot_info, program_id)?;
+        let snapshot = try_from_slice_unchecked::<BondSnapshot>(&snapshot_info.data.borrow())?;
+        if !snapshot.is_initialized() {
+            let clock = Clock::get()?;
+            return Ok(BondSnapshot::new(
+                *config_info.key, 
+                bump, 
+                draw_id, 
+                clock.epoch_start_timestamp, 
+                clock.unix_timestamp, 
+                config.min_eligible_weight,
+            ));
+        }
+        Check::valid(&snapshot, snapshot_info)?;
+        Check::account(config_info, &snapshot.authority)?;
+        if snapshot.draw_id != draw_id {
+            return Err(BondError::InvalidSnapshot.into());
+        }
+        if snapshot.is_sealed {
+            return Err(BondError::SnapshotLocked.into());
+        }
+        Ok(snapshot)
+    }
+
+    /// Checks that the balance of each of `entries` is eligible for the snapshot and returns the 
+    /// entries with their time-weighted weights.
+    fn weigh_entries(
+        snapshot: &BondSnapshot,
+        entries: Vec<BondSnapshotEntry>,
+    ) -> Result<Vec<BondSnapshotEntry>, ProgramError> {
+        if let Some(entry) = entries.iter().find(|entry| !snapshot.is_eligible(entry.weight)) {
+            msg!("Balance {} of {} is below {}", entry.weight, entry.wallet, snapshot.min_balance);
+            return Err(BondError::InsufficientWeight.into());
+        }
+        Ok(snapshot.time_weight(entries))
+    }
+
+    fn process_write_snapshot(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        entries: Vec<BondSnapshotEntry>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Self::check_draw_account(program_id, config_info, draw_authority_info, &config)?;
+        Check::active(&config)?;
+        Check::writable(draw_authority_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let snapshot_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        let rent = Rent::get()?;
+        let mut snapshot = Self::write_snapshot(
+            program_id, 
+            config_info, 
+            draw_authority_info, 
+            &config, 
+            &state, 
+            snapshot_info, 
+            system_program_info, 
+            &rent, 
+            bump, 
+            snapshot_size(entries.len()),
+        )?;
+        if snapshot.buffers > 0 {
+            msg!("Snapshot entries are held by {} buffers", snapshot.buffers);
+            return Err(BondError::InvalidSnapshot.into());
+        }
+
+        let entries = Self::weigh_entries(&snapshot, entries)?;
+        snapshot.append(entries)?;
+        Create::realloc(
+            draw_authority_info, 
+            snapshot_info, 
+            system_program_info, 
+            &rent, 
+            snapshot_size(snapshot.entries.len()) as u32,
+        )?;
+        snapshot.serialize(&mut &mut snapshot_info.data.borrow_mut()[..])?;
+        msg!("Snapshot of draw {}: {} entries", snapshot.draw_id, snapshot.entries.len());
+        Ok(())
+    }
+
+    fn process_write_participants(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        snapshot_bump: u8,
+        bump: u8,
+        index: u32,
+        entries: Vec<BondSnapshotEntry>,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Self::check_draw_account(program_id, config_info, draw_authority_info, &config)?;
+        Check::active(&config)?;
+        Check::writable(draw_authority_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let snapshot_info = next_account_info(account_info_iter)?;
+        let buffer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        let rent = Rent::get()?;
+        let mut snapshot = Self::write_snapshot(
+            program_id, 
+            config_info, 
+            draw_authority_info, 
+            &config, 
+            &state, 
+            snapshot_info, 
+            system_program_info, 
+            &rent, 
+            snapshot_bump, 
+            snapshot_size(0),
+        )?;
+        if !snapshot.entries.is_empty() {
+            msg!("Snapshot entries are held by the snapshot");
+            return Err(BondError::InvalidSnapshot.into());
+        }
+
+        // Entries are appended to the latest buffer or open the next one, so that each buffer 
+        // holds a contiguous range of the snapshot's weight.
+        let is_next = index == snapshot.buffers;
+        if !is_next && index.checked_add(1) != Some(snapshot.buffers) {
+            msg!("Buffer {} isn't the latest or next of {}", index, snapshot.buffers);
+            return Err(BondError::InvalidSnapshot.into());
+        }
+
+        // Participant Buffer PDA Account (created when opened).
+        let mut buffer = if is_next {
+            let seed_index = BondParticipantBuffer::seed_index(snapshot.draw_id, index);
+            Create::indexed_pda_account(
+                program_id, 
+                config_info, 
+                draw_authority_info, 
+                buffer_info, 
+                BondSeed::ParticipantBuffer, 
+                &seed_index, 
+                bump, 
+                system_program_info, 
+                &rent, 
+                participant_buffer_size(entries.len()) as u32,
+            )?;
+            BondParticipantBuffer::new(
+                *config_info.key, 
+                bump, 
+                snapshot.draw_id, 
+                index, 
+                snapshot.total_weight,
+            )
+        } else {
+            let buffer = try_from_slice_unchecked::<BondParticipantBuffer>(
+                &buffer_info.data.borrow(),
+            )?;
+            Check::owner(buffer_info, program_id)?;
+            Check::valid(&buffer, buffer_info)?;
+            Check::account(config_info, &buffer.authority)?;
+            if buffer.draw_id != snapshot.draw_id || buffer.index != index {
+                return Err(BondError::InvalidSnapshot.into());
+            }
+            buffer
+        };
+
+        let entries = Self::weigh_entries(&snapshot, entries)?;
+        snapshot.record(&entries)?;
+        buffer.append(entries)?;
+        if is_next {
+            snapshot.buffers += 1;
+        }
+        Create::realloc(
+            draw_authority_info, 
+            buffer_info, 
+            system_program_info, 
+            &rent, 
+            participant_buffer_size(buffer.entries.len()) as u32,
         )?;
+        buffer.serialize(&mut &mut buffer_info.data.borrow_mut()[..])?;
+        snapshot.serialize(&mut &mut snapshot_info.data.borrow_mut()[..])?;
+        msg!("Buffer {} of draw {}: {} entries", index, snapshot.draw_id, buffer.entries.len());
         Ok(())
     }
 
-    /// Initialize accounts.
-    fn process_initialize(
+    fn process_finalize_snapshot(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        state_bump: u8,
-        fee_bump: u8,
-        exclusion_list_bump: u8,
-        exclusion_list_capacity: u32,
-        exclusion_list_accounts: Vec<Pubkey>,
-        equity_bump: u8,
-        treasury_bump: u8,
-        jackpot_bump: u8,
-        stake_bump: u8,
     ) -> ProgramResult {
 
-        // Unpack accounts.
+        // Unpack accounts...
         let account_info_iter = &mut accounts.iter();
-        let payer_info = next_account_info(account_info_iter)?;
-        let config_info = next_account_info(account_info_iter)?;
         let draw_authority_info = next_account_info(account_info_iter)?;
-        let token_mint_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Self::check_draw_account(program_id, config_info, draw_authority_info, &config)?;
+        Check::active(&config)?;
+
         let state_info = next_account_info(account_info_iter)?;
-        let fee_info = next_account_info(account_info_iter)?;
-        let exclusion_list_info = next_account_info(account_info_iter)?;
-        let equity_info = next_account_info(account_info_iter)?;
-        let treasury_info = next_account_info(account_info_iter)?;
-        let jackpot_info = next_account_info(account_info_iter)?;
-        let stake_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
 
-        // Initial validation.
-        Check::signer(payer_info)?;
-        Check::signer_and_writable(config_info)?;
-        Check::signer(draw_authority_info)?;
-        Check::owner(token_mint_info, &spl_token::id())?;
+        let snapshot_info = next_account_info(account_info_iter)?;
+        let mut snapshot = try_from_slice_unchecked::<BondSnapshot>(&snapshot_info.data.borrow())?;
+        Check::writable(snapshot_info)?;
+        Check::owner(snapshot_info, program_id)?;
+        Check::valid(&snapshot, snapshot_info)?;
+        Check::account(config_info, &snapshot.authority)?;
+        if snapshot.draw_id != state.draw_id + 1 {
+            msg!("Snapshot of draw {} isn't for draw {}", snapshot.draw_id, state.draw_id + 1);
+            return Err(BondError::InvalidSnapshot.into());
+        }
+        if snapshot.is_sealed {
+            return Err(BondError::SnapshotLocked.into());
+        }
 
-        // The default PDA account authority.
-        let authority = *config_info.key;
+        snapshot.is_sealed = true;
+        snapshot.serialize(&mut &mut snapshot_info.data.borrow_mut()[..])?;
+        msg!(
+            "Snapshot of draw {} sealed: weight {}, hash {:?}", 
+            snapshot.draw_id, 
+            snapshot.total_weight, 
+            snapshot.hash,
+        );
+        Ok(())
+    }
 
-        // Rent.
-        let rent = Rent::get()?;
+    fn process_post_snapshot_root(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bump: u8,
+        root: [u8; 32],
+        leaf_count: u32,
+        total_weight: u64,
+    ) -> ProgramResult {
 
-        // Config Account.
-        msg!("Initialize Config");
-        let config = try_from_slice_unchecked::<BondConfig>(
-            &config_info.data.borrow(),
-        )?;
-        Self::check_initialize_account(
-            program_id, 
-            &config, 
-            config_info,
-            &rent,
-        )?;
-        BondConfig::new(
-            true,
-            0, 
-            3,
-            10, 
-            100, 
-            *draw_authority_info.key, 
-            *token_mint_info.key,
-        ).serialize(
-            &mut &mut config_info.data.borrow_mut()[..],
-        )?;
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let draw_authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Self::check_draw_account(program_id, config_info, draw_authority_info, &config)?;
+        Check::active(&config)?;
+        Check::writable(draw_authority_info)?;
 
-        // State PDA Account.
-        msg!("Initialize State");
-        let state = try_from_slice_unchecked::<BondState>(
-            &state_info.data.borrow(),
-        )?;
-        Self::check_initialize_pda_account(
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+
+        let snapshot_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        let mut snapshot = Self::write_snapshot(
             program_id, 
             config_info, 
-            &state,
-            state_info, 
-            BondSeed::State, 
-            state_bump, 
-            &rent,
-        )?;
-        BondState::new(
-            authority,
-            state_bump,
-            0,
-            0,
-        ).serialize(
-            &mut &mut state_info.data.borrow_mut()[..],
+            draw_authority_info, 
+            &config, 
+            &state, 
+            snapshot_info, 
+            system_program_info, 
+            &Rent::get()?, 
+            bump, 
+            snapshot_size(0),
         )?;
+        if !snapshot.entries.is_empty() || snapshot.buffers > 0 {
+            msg!("Snapshot entries were already written");
+            return Err(BondError::InvalidSnapshot.into());
+        }
 
-        // Fee PDA Account.
-        msg!("Initialize Fee");
-        let fee = try_from_slice_unchecked::<BondFee>(
-            &fee_info.data.borrow(),
+        snapshot.root = Some(root);
+        snapshot.leaf_count = leaf_count;
+        snapshot.total_weight = total_weight;
+        snapshot.hash = root;
+        snapshot.is_sealed = true;
+        snapshot.serialize(&mut &mut snapshot_info.data.borrow_mut()[..])?;
+        msg!(
+            "Snapshot of draw {} sealed: {} leaves, weight {}", 
+            snapshot.draw_id, 
+            leaf_count, 
+            total_weight,
+        );
+        Ok(())
+    }
+
+    fn process_expire_claim(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let mut state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+
+        let draw_info = next_account_info(account_info_iter)?;
+        let mut draw = BondDraw::unpack_from_slice(&draw_info.data.borrow())?;
+        Check::writable(draw_info)?;
+        Check::owner(draw_info, program_id)?;
+        Check::valid(&draw, draw_info)?;
+        Check::account(config_info, &draw.config)?;
+        if draw.is_claimed || draw.is_expired {
+            return Err(BondError::PrizeAlreadyClaimed.into());
+        }
+        if !draw.is_claim_expired(config.claim_window, Clock::get()?.unix_timestamp) {
+            return Err(BondError::ClaimNotExpired.into());
+        }
+
+        // The prize is held by the jackpot, so releasing its reservation returns it to the pot.
+        state.reserved = state.reserved
+            .checked_sub(draw.amount)
+            .ok_or(BondError::NumericalOverflow)?;
+        pack_account(&state, &mut state_info.data.borrow_mut())?;
+
+        // So are the additional mints' prizes.
+        if !draw.extra_prizes.is_empty() {
+            let jackpot_mints_info = next_account_info(account_info_iter)?;
+            Check::writable(jackpot_mints_info)?;
+            Check::account(
+                jackpot_mints_info, 
+                &config.jackpot_mints.ok_or(ProgramError::InvalidAccountData)?,
+            )?;
+            let mut jackpot_mints = try_from_slice_unchecked::<BondJackpotMints>(
+                &jackpot_mints_info.data.borrow(),
+            )?;
+            for prize in draw.extra_prizes.iter() {
+                jackpot_mints.release(prize)?;
+            }
+            jackpot_mints.serialize(&mut &mut jackpot_mints_info.data.borrow_mut()[..])?;
+        }
+
+        draw.is_expired = true;
+        pack_account(&draw, &mut draw_info.data.borrow_mut())?;
+        msg!("Prize of draw {} expired: {}", draw.id, draw.amount);
+        Ok(())
+    }
+
+    fn process_close_lotto_share(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seed: BondSeed,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(authority_info)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::inactive(&config)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+        if state.vrf_request_slot != 0 {
+            msg!("Randomness request pending since slot {}", state.vrf_request_slot);
+            return Err(BondError::AccountsNotClosed.into());
+        }
+
+        let share_info = next_account_info(account_info_iter)?;
+        Check::writable(share_info)?;
+        let bump = Self::unpack_share_bump(program_id, config_info, share_info, &seed)?;
+
+        let share_ata_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        Check::ata_with_program(
+            share_info, 
+            token_mint_info.key, 
+            token_program_info.key, 
+            share_ata_info,
         )?;
-        Self::check_initialize_pda_account(
-            program_id, 
+
+        // Revoked streams return their unvested tokens to the treasury.
+        if seed == BondSeed::Treasury && state.open_streams > 0 {
+            msg!("{} streams must be revoked first", state.open_streams);
+            return Err(BondError::AccountsNotClosed.into());
+        }
+
+        // The jackpot holds the escrowed prizes and the additional mints' balances.
+        let mut jackpot_mint_infos = Vec::new();
+        if seed == BondSeed::Jackpot {
+            if state.reserved > 0 {
+                msg!("{} reserved for unclaimed prizes", state.reserved);
+                return Err(BondError::AccountsNotClosed.into());
+            }
+            if let Some(jackpot_mints) = config.jackpot_mints {
+                let jackpot_mints_info = next_account_info(account_info_iter)?;
+                Check::account(jackpot_mints_info, &jackpot_mints)?;
+                let jackpot_mints = try_from_slice_unchecked::<BondJackpotMints>(
+                    &jackpot_mints_info.data.borrow(),
+                )?;
+                if jackpot_mints.is_reserved() {
+                    msg!("Additional mints reserved for unclaimed prizes");
+                    return Err(BondError::AccountsNotClosed.into());
+                }
+                for mint in jackpot_mints.mints {
+                    let mint_info = next_account_info(account_info_iter)?;
+                    Check::account(mint_info, &mint)?;
+                    let mint_token_program_info = next_account_info(account_info_iter)?;
+                    Check::token_mint(mint_info, mint_token_program_info)?;
+                    let jackpot_mint_ata_info = next_account_info(account_info_iter)?;
+                    Check::ata_with_program(
+                        share_info, 
+                        &mint, 
+                        mint_token_program_info.key, 
+                        jackpot_mint_ata_info,
+                    )?;
+                    let destination_mint_ata_info = next_account_info(account_info_iter)?;
+                    jackpot_mint_infos.push((
+                        mint_info, 
+                        mint_token_program_info, 
+                        jackpot_mint_ata_info, 
+                        destination_mint_ata_info,
+                    ));
+                }
+            }
+        }
+
+        // Share -> Destination
+        Self::close_share_ata(
             config_info, 
-            &fee,
-            fee_info, 
-            BondSeed::Fee, 
-            fee_bump, 
-            &rent,
-        )?;
-        BondFee::new(
-            authority,
-            fee_bump,
-        ).serialize(
-            &mut &mut fee_info.data.borrow_mut()[..],
+            token_program_info, 
+            token_mint_info, 
+            share_ata_info, 
+            destination_info, 
+            authority_info, 
+            share_info, 
+            seed.clone(), 
+            bump,
         )?;
+        for (
+            mint_info, 
+            mint_token_program_info, 
+            jackpot_mint_ata_info, 
+            destination_mint_ata_info,
+        ) in jackpot_mint_infos {
+            // Jackpot -> Destination (additional mint)
+            Self::close_share_ata(
+                config_info, 
+                mint_token_program_info, 
+                mint_info, 
+                jackpot_mint_ata_info, 
+                destination_mint_ata_info, 
+                authority_info, 
+                share_info, 
+                seed.clone(), 
+                bump,
+            )?;
+        }
+        Create::close(share_info, authority_info)?;
 
-        // Exclusion List PDA Account.
-        msg!("Initialize Exclusion List");
+        msg!("Closed the {} account", seed.as_ref());
+        Ok(())
+    }
+
+    /// Returns the bump seed of the fee or share account `share_info` derived from `seed`. The fee 
+    /// receiver shares the header of the share accounts but not their layout.
+    fn unpack_share_bump(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+        share_info: &AccountInfo,
+        seed: &BondSeed,
+    ) -> Result<u8, ProgramError> {
+        Check::owner(share_info, program_id)?;
+        let bump = if *seed == BondSeed::Fee {
+            let fee = BondFee::unpack_from_slice(&share_info.data.borrow())?;
+            Check::valid(&fee, share_info)?;
+            Check::account(config_info, &fee.authority)?;
+            fee.bump
+        } else {
+            Self::default_share(seed)?;
+            let share = BondShare::unpack_from_slice(&share_info.data.borrow())?;
+            Check::valid(&share, share_info)?;
+            Check::account(config_info, &share.authority)?;
+            share.bump
+        };
+        Check::pda(program_id, config_info, share_info, seed.clone(), bump)?;
+        Ok(bump)
+    }
+
+    /// Transfers the balance of `ata_info`, owned by the PDA `share_info`, to `destination_info` 
+    /// and closes it, moving its lamports to `receiver_info`.
+    fn close_share_ata<'a>(
+        config_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        mint_info: &AccountInfo<'a>,
+        ata_info: &AccountInfo<'a>,
+        destination_info: &AccountInfo<'a>,
+        receiver_info: &AccountInfo<'a>,
+        share_info: &AccountInfo<'a>,
+        seed: BondSeed,
+        bump: u8,
+    ) -> ProgramResult {
+        let ata = Account::unpack_from_slice(&ata_info.data.borrow())?;
+        if ata.amount > 0 {
+            Create::token_transfer_checked(
+                config_info, 
+                token_program_info, 
+                mint_info, 
+                ata_info, 
+                destination_info, 
+                share_info, 
+                seed.clone(), 
+                bump, 
+                ata.amount, 
+                Mint::unpack_from_slice(&mint_info.data.borrow())?.decimals,
+            )?;
+        }
+        let share_bump = [bump];
+        Create::close_token_account(
+            token_program_info, 
+            ata_info, 
+            receiver_info, 
+            share_info, 
+            &seed.signer_seeds(config_info.key, &share_bump),
+        )
+    }
+
+    fn process_close_lotto(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(authority_info)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::writable(config_info)?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::inactive(&config)?;
+
+        let state_info = next_account_info(account_info_iter)?;
+        let state = BondState::unpack_from_slice(&state_info.data.borrow())?;
+        Check::writable(state_info)?;
+        Check::owner(state_info, program_id)?;
+        Check::valid(&state, state_info)?;
+        Check::account(config_info, &state.authority)?;
+        Check::pda(program_id, config_info, state_info, BondSeed::State, state.bump)?;
+        if state.reserved > 0 || state.vrf_request_slot != 0 || state.open_streams > 0 {
+            msg!("Unclaimed prizes, pending randomness request or open streams");
+            return Err(BondError::AccountsNotClosed.into());
+        }
+
+        let exclusion_list_info = next_account_info(account_info_iter)?;
         let exclusion_list = try_from_slice_unchecked::<BondExclusionList>(
             &exclusion_list_info.data.borrow(),
         )?;
-        Self::check_initialize_pda_account(
+        Check::writable(exclusion_list_info)?;
+        Check::owner(exclusion_list_info, program_id)?;
+        Check::valid(&exclusion_list, exclusion_list_info)?;
+        Check::account(config_info, &exclusion_list.authority)?;
+        Check::pda(
             program_id, 
             config_info, 
-            &exclusion_list,
             exclusion_list_info, 
             BondSeed::ExclusionList, 
-            exclusion_list_bump, 
-            &rent,
-        )?;
-        BondExclusionList::new(
-            authority,
-            exclusion_list_bump,
-            exclusion_list_capacity,
-            exclusion_list_accounts,
-        ).serialize(
-            &mut &mut exclusion_list_info.data.borrow_mut()[..],
+            exclusion_list.bump,
         )?;
 
-        // Equity PDA Account.
-        msg!("Initialize Equity");
-        Self::initialize_share(
-            program_id, 
-            config_info, 
-            equity_info, 
-            authority, 
-            10,
-            100, 
+        // Every account holding funds must have been drained and closed by `CloseLottoShare`.
+        for seed in [
+            BondSeed::Fee, 
             BondSeed::Equity, 
-            equity_bump, 
-            &rent,
-        )?;
-
-        // Treasury PDA Account.
-        msg!("Initialize Treasury");
-        Self::initialize_share(
-            program_id, 
-            config_info, 
-            treasury_info, 
-            authority, 
-            0, 
-            0,
             BondSeed::Treasury, 
-            treasury_bump, 
-            &rent,
-        )?;
-
-        // Jackpot PDA Account.
-        msg!("Initialize Jackpot");
-        Self::initialize_share(
-            program_id, 
-            config_info, 
-            jackpot_info, 
-            authority, 
-            80, 
-            100,
             BondSeed::Jackpot, 
-            jackpot_bump, 
-            &rent,
-        )?;
+            BondSeed::Stake,
+        ] {
+            let share_info = next_account_info(account_info_iter)?;
+            Check::account(share_info, &seed.derive(program_id, config_info.key).0)?;
+            if share_info.lamports() > 0 {
+                msg!("The {} account must be closed first", seed.as_ref());
+                return Err(BondError::AccountsNotClosed.into());
+            }
+        }
 
-        // Stake PDA Account.
-        msg!("Initialize Stake");
-        Self::initialize_share(
-            program_id, 
-            config_info, 
-            stake_info, 
-            authority, 
-            10, 
-            100, 
-            BondSeed::Stake, 
-            stake_bump, 
-            &rent,
+        let state_ata_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        Check::ata_with_program(
+            state_info, 
+            token_mint_info.key, 
+            token_program_info.key, 
+            state_ata_info,
         )?;
 
-        msg!("Initialize Bond Complete!");
+        // Accounts referenced by the config are closed with it.
+        let mut rent_infos = Vec::new();
+        for address in [config.jackpot_mints, config.leaderboard].into_iter().flatten() {
+            let account_info = next_account_info(account_info_iter)?;
+            Check::writable(account_info)?;
+            Check::owner(account_info, program_id)?;
+            Check::account(account_info, &address)?;
+            rent_infos.push(account_info);
+        }
+        let heartbeat_info = next_account_info(account_info_iter)?;
+        Check::account(heartbeat_info, &BondSeed::Heartbeat.derive(program_id, config_info.key).0)?;
+        if heartbeat_info.lamports() > 0 {
+            Check::writable(heartbeat_info)?;
+            Check::owner(heartbeat_info, program_id)?;
+            rent_infos.push(heartbeat_info);
+        }
+
+        // State -> Destination (the ticket holders' unclaimed rebates)
+        if !state_ata_info.data_is_empty() {
+            Self::close_share_ata(
+                config_info, 
+                token_program_info, 
+                token_mint_info, 
+                state_ata_info, 
+                destination_info, 
+                authority_info, 
+                state_info, 
+                BondSeed::State, 
+                state.bump,
+            )?;
+        }
+        for account_info in rent_infos {
+            Create::close(account_info, authority_info)?;
+        }
+        Create::close(exclusion_list_info, authority_info)?;
+        Create::close(state_info, authority_info)?;
+        Create::close(config_info, authority_info)?;
+
+        msg!("Game closed after season {}", state.season);
         Ok(())
     }
 
-    fn check_draw_account(
+    fn process_recover_tokens(
         program_id: &Pubkey,
-        config_info: &AccountInfo,
-        draw_authority_info: &AccountInfo,
-        config: &BondConfig,
-    ) -> Result<(), ProgramError> {
+        accounts: &[AccountInfo],
+        seed: BondSeed,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        Check::signer_and_writable(authority_info)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
         Check::owner(config_info, program_id)?;
-        Check::signer(draw_authority_info)?;
-        Check::account(draw_authority_info, &config.draw_authority)?;
-        Check::valid(config, config_info)
-    }
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
 
-    fn check_draw_pda_account(
-        program_id: &Pubkey,
-        config_info: &AccountInfo,
-        share_info: &AccountInfo,
-        share: &impl BondProgramAccount,
-        share_ata_info: &AccountInfo,
-        share_ata: &Account,
-    ) -> Result<(), ProgramError> {
-        Check::account(config_info, &share.authority())?;
-        Check::owner(&share_info, program_id)?;
-        Check::valid(share, share_info)?;
-        Check::pubkey(&share_ata.owner, &share_info.key)
+        let owner_info = next_account_info(account_info_iter)?;
+        let bump = Self::unpack_share_bump(program_id, config_info, owner_info, &seed)?;
+
+        let stray_info = next_account_info(account_info_iter)?;
+        let mint_info = next_account_info(account_info_iter)?;
+        let fee_info = next_account_info(account_info_iter)?;
+        Self::unpack_share_bump(program_id, config_info, fee_info, &BondSeed::Fee)?;
+        let fee_ata_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_program(token_program_info)?;
+        Check::owner(stray_info, token_program_info.key)?;
+        Check::owner(mint_info, token_program_info.key)?;
+        Check::ata_with_program(fee_info, mint_info.key, token_program_info.key, fee_ata_info)?;
+        let associated_token_program_info = next_account_info(account_info_iter)?;
+        Check::account(associated_token_program_info, &spl_associated_token_account::id())?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+
+        let stray = Account::unpack_from_slice(&stray_info.data.borrow())?;
+        Check::pubkey(&stray.owner, owner_info.key)?;
+        Check::pubkey(&stray.mint, mint_info.key)?;
+
+        // The share's token account and the jackpot's prizes are in use.
+        let share_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+            owner_info.key, 
+            &config.token_mint, 
+            token_program_info.key,
+        );
+        let mut is_in_use = *stray_info.key == share_ata || stray_info.key == fee_ata_info.key;
+        if seed == BondSeed::Jackpot {
+            if let Some(jackpot_mints) = config.jackpot_mints {
+                let jackpot_mints_info = next_account_info(account_info_iter)?;
+                Check::account(jackpot_mints_info, &jackpot_mints)?;
+                let jackpot_mints = try_from_slice_unchecked::<BondJackpotMints>(
+                    &jackpot_mints_info.data.borrow(),
+                )?;
+                is_in_use |= jackpot_mints.mints.contains(&stray.mint);
+            }
+        }
+        if is_in_use {
+            return Err(BondError::TokenAccountInUse.into());
+        }
+
+        // Fee ATA Account
+        Create::ata_account(
+            authority_info, 
+            fee_ata_info, 
+            fee_info, 
+            mint_info, 
+            token_program_info, 
+            associated_token_program_info, 
+            system_program_info,
+        )?;
+
+        // Stray -> Fee
+        Self::close_share_ata(
+            config_info, 
+            token_program_info, 
+            mint_info, 
+            stray_info, 
+            fee_ata_info, 
+            authority_info, 
+            owner_info, 
+            seed.clone(), 
+            bump,
+        )?;
+
+        msg!("Recovered {} tokens of {} from {}", stray.amount, stray.mint, seed.as_ref());
+        Ok(())
     }
 
-    fn process_split_shares(
+    fn process_withdraw_equity(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        amount: Option<u64>,
+        amount: u64,
     ) -> ProgramResult {
 
+        // Unpack accounts...
         let account_info_iter = &mut accounts.iter();
-        let draw_authority_info = next_account_info(account_info_iter)?;
+        let equity_authority_info = next_account_info(account_info_iter)?;
+        Check::signer(equity_authority_info)?;
         let config_info = next_account_info(account_info_iter)?;
-        let config = try_from_slice_unchecked::<BondConfig>(&config_info.data.borrow())?;
-        Self::check_draw_account(program_id, config_info, draw_authority_info, &config)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        match config.equity_authority {
+            Some(equity_authority) => Check::account(equity_authority_info, &equity_authority)?,
+            None => Check::authority(&config, equity_authority_info)?,
+        }
+        Check::active(&config)?;
 
-        let fee_info = next_account_info(account_info_iter)?;
-        let fee = try_from_slice_unchecked::<BondFee>(&fee_info.data.borrow())?;
-        let fee_ata_info = next_account_info(account_info_iter)?;
-        let fee_ata = Account::unpack_from_slice(&fee_ata_info.data.borrow())?;
-        Self::check_draw_pda_account(
-            program_id, 
-            config_info, 
-            fee_info, 
-            &fee, 
-            fee_ata_info,
-            &fee_ata,
-        )?;
-        
         let equity_info = next_account_info(account_info_iter)?;
-        let equity = try_from_slice_unchecked::<BondShare>(&equity_info.data.borrow())?;
+        let equity = BondShare::unpack_from_slice(&equity_info.data.borrow())?;
         let equity_ata_info = next_account_info(account_info_iter)?;
         let equity_ata = Account::unpack_from_slice(&equity_ata_info.data.borrow())?;
         Self::check_draw_pda_account(
@@ -645,9 +7124,64 @@ impl Processor {
             equity_ata_info,
             &equity_ata,
         )?;
+        Check::pda(program_id, config_info, equity_info, BondSeed::Equity, equity.bump)?;
+
+        let destination_info = next_account_info(account_info_iter)?;
+        let destination = Account::unpack_from_slice(&destination_info.data.borrow())?;
+        Check::pubkey(&destination.mint, &config.token_mint)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        Check::ata_with_program(
+            equity_info, 
+            token_mint_info.key, 
+            token_program_info.key, 
+            equity_ata_info,
+        )?;
+
+        if amount > equity_ata.amount {
+            msg!("Equity balance {} is below {}", equity_ata.amount, amount);
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Equity -> Destination
+        Create::token_transfer_checked(
+            config_info, 
+            token_program_info, 
+            token_mint_info, 
+            equity_ata_info, 
+            destination_info, 
+            equity_info, 
+            BondSeed::Equity, 
+            equity.bump, 
+            amount, 
+            config.decimals,
+        )?;
+
+        msg!("Equity withdrawal: {} to {}", amount, destination_info.key);
+        Ok(())
+    }
+
+    fn process_spend_treasury(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        memo: String,
+    ) -> ProgramResult {
+
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::authority(&config, authority_info)?;
+        Check::active(&config)?;
 
         let treasury_info = next_account_info(account_info_iter)?;
-        let treasury = try_from_slice_unchecked::<BondShare>(&treasury_info.data.borrow())?;
+        let treasury = BondShare::unpack_from_slice(&treasury_info.data.borrow())?;
         let treasury_ata_info = next_account_info(account_info_iter)?;
         let treasury_ata = Account::unpack_from_slice(&treasury_ata_info.data.borrow())?;
         Self::check_draw_pda_account(
@@ -658,192 +7192,180 @@ impl Processor {
             treasury_ata_info,
             &treasury_ata,
         )?;
+        Check::pda(program_id, config_info, treasury_info, BondSeed::Treasury, treasury.bump)?;
 
-        let jackpot_info = next_account_info(account_info_iter)?;
-        let jackpot = try_from_slice_unchecked::<BondShare>(&jackpot_info.data.borrow())?;
-        let jackpot_ata_info = next_account_info(account_info_iter)?;
-        let jackpot_ata = Account::unpack_from_slice(&jackpot_ata_info.data.borrow())?;
-        Self::check_draw_pda_account(
-            program_id, 
-            config_info, 
-            jackpot_info, 
-            &jackpot, 
-            jackpot_ata_info,
-            &jackpot_ata,
-        )?;
-
-        let stake_info = next_account_info(account_info_iter)?;       
-        let stake = try_from_slice_unchecked::<BondShare>(&stake_info.data.borrow())?; 
-        let stake_ata_info = next_account_info(account_info_iter)?;
-        let stake_ata = Account::unpack_from_slice(&stake_ata_info.data.borrow())?;
-        Self::check_draw_pda_account(
-            program_id, 
-            config_info, 
-            stake_info, 
-            &stake, 
-            stake_ata_info,
-            &stake_ata,
-        )?;
-
+        let destination_info = next_account_info(account_info_iter)?;
+        let destination = Account::unpack_from_slice(&destination_info.data.borrow())?;
+        Check::pubkey(&destination.mint, &config.token_mint)?;
         let token_mint_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;  
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        Check::ata_with_program(
+            treasury_info, 
+            token_mint_info.key, 
+            token_program_info.key, 
+            treasury_ata_info,
+        )?;
+        let memo_program_info = next_account_info(account_info_iter)?;
+        Check::account(memo_program_info, &MEMO_PROGRAM_ID)?;
 
-        let amount: u64 = amount.unwrap_or(fee_ata.amount);
+        if memo.len() > MAX_MEMO_LEN {
+            return Err(BondError::MemoTooLong.into());
+        }
+        if amount > treasury_ata.amount {
+            msg!("Treasury balance {} is below {}", treasury_ata.amount, amount);
+            return Err(ProgramError::InsufficientFunds);
+        }
 
-        // Fee -> Equity
+        // Treasury -> Destination
         Create::token_transfer_checked(
-            draw_authority_info,
-            config_info,
-            token_program_info,
-            token_mint_info,
-            fee_ata_info,
-            equity_ata_info,
-            fee_info,
-            BondSeed::Fee,
-            fee.bump,
-            equity.share(amount),
+            config_info, 
+            token_program_info, 
+            token_mint_info, 
+            treasury_ata_info, 
+            destination_info, 
+            treasury_info, 
+            BondSeed::Treasury, 
+            treasury.bump, 
+            amount, 
+            config.decimals,
         )?;
 
-        // Fee -> Treasury
-        Create::token_transfer_checked(
-            draw_authority_info,
-            config_info,
-            token_program_info,
-            token_mint_info,
-            fee_ata_info,
-            treasury_ata_info,
-            fee_info,
-            BondSeed::Fee,
-            fee.bump,
-            treasury.share(amount),
+        let treasury_bump = [treasury.bump];
+        Create::memo(
+            memo_program_info, 
+            treasury_info, 
+            &memo, 
+            &BondSeed::Treasury.signer_seeds(config_info.key, &treasury_bump),
         )?;
 
-        // Fee -> Jackpot
-        Create::token_transfer_checked(
-            draw_authority_info,
-            config_info,
-            token_program_info,
-            token_mint_info,
-            fee_ata_info,
-            jackpot_ata_info,
-            fee_info,
-            BondSeed::Fee,
-            fee.bump,
-            jackpot.share(amount),
-        )?; 
-
-        // Fee -> Stake
-        Create::token_transfer_checked(
-            draw_authority_info,
-            config_info,
-            token_program_info,
-            token_mint_info,
-            fee_ata_info,
-            stake_ata_info,
-            fee_info,
-            BondSeed::Fee,
-            fee.bump,
-            stake.share(amount),
-        )
+        msg!("Treasury spend: {} to {}", amount, destination_info.key);
+        Ok(())
     }
 
-    fn process_draw(
+    fn process_compound_stake(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        receiver_seed: u64,
-        draw_seed: u64,
+        vault_bump: u8,
     ) -> ProgramResult {
 
         // Unpack accounts...
         let account_info_iter = &mut accounts.iter();
-        let draw_authority_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
-        let config = try_from_slice_unchecked::<BondConfig>(&config_info.data.borrow())?;
-        Self::check_draw_account(program_id, config_info, draw_authority_info, &config)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::active(&config)?;
 
-        let state_info = next_account_info(account_info_iter)?;
-        let mut state = try_from_slice_unchecked::<BondState>(&state_info.data.borrow_mut())?;
-        Check::owner(state_info, program_id)?;
-        Check::valid(&state, state_info)?;
-        Check::account(config_info, &state.authority)?;
-        
-        let jackpot_info = next_account_info(account_info_iter)?;
-        let jackpot = try_from_slice_unchecked::<BondShare>(&jackpot_info.data.borrow())?;
-        let jackpot_ata_info = next_account_info(account_info_iter)?;
-        let jackpot_ata = Account::unpack_from_slice(&jackpot_ata_info.data.borrow())?;
+        let stake_info = next_account_info(account_info_iter)?;
+        let stake = BondShare::unpack_from_slice(&stake_info.data.borrow())?;
+        Check::writable(stake_info)?;
+        let stake_ata_info = next_account_info(account_info_iter)?;
+        let stake_ata = Account::unpack_from_slice(&stake_ata_info.data.borrow())?;
         Self::check_draw_pda_account(
             program_id, 
             config_info, 
-            jackpot_info, 
-            &jackpot, 
-            jackpot_ata_info,
-            &jackpot_ata,
+            stake_info, 
+            &stake, 
+            stake_ata_info,
+            &stake_ata,
         )?;
+        Check::pda(program_id, config_info, stake_info, BondSeed::Stake, stake.bump)?;
+        Check::ata(stake_info, &config.token_mint, stake_ata_info)?;
 
-        let receiver_info = next_account_info(account_info_iter)?;
-        let receiver_ata_info = next_account_info(account_info_iter)?;
-        let receiver_ata = Account::unpack_from_slice(&receiver_ata_info.data.borrow())?;
-        Check::pubkey(&receiver_ata.owner, receiver_info.key)?;
-
-        let draw_info = next_account_info(account_info_iter)?;
-        let draw = try_from_slice_unchecked::<BondDraw>(&draw_info.data.borrow())?;
-        Check::uninitialized(&draw, draw_info)?;
-        Check::owner(draw_info, program_id)?;
+        let stake_vault_info = next_account_info(account_info_iter)?;
+        Check::writable(stake_vault_info)?;
+        Check::owner(stake_vault_info, &system_program::id())?;
+        Check::pda(program_id, config_info, stake_vault_info, BondSeed::StakeVault, vault_bump)?;
 
+        let pool_program_info = next_account_info(account_info_iter)?;
+        let pool_info = next_account_info(account_info_iter)?;
         let token_mint_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?; 
+        Check::account(token_mint_info, &config.token_mint)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        Check::token_mint(token_mint_info, token_program_info)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        Check::account(system_program_info, &system_program::id())?;
+        let adapter_infos = account_info_iter.as_slice();
 
-        let amount = jackpot_ata.amount;
-        if amount == 0 {
-            return Ok(())
+        // Vault -> Pool -> Stake (the vault stays rent exempt)
+        let rent = Rent::get()?;
+        let vault_rent = rent.minimum_balance(0);
+        let amount = stake_vault_info.lamports().saturating_sub(vault_rent);
+        let vault_bump = [vault_bump];
+        if amount > 0 {
+            Adapter::deposit_sol(
+                config.lst_adapter, 
+                pool_program_info, 
+                pool_info, 
+                stake_vault_info, 
+                stake_ata_info, 
+                token_mint_info, 
+                token_program_info, 
+                system_program_info, 
+                adapter_infos, 
+                amount, 
+                &BondSeed::StakeVault.signer_seeds(config_info.key, &vault_bump),
+            )?;
+            msg!("Compounded {} lamports into the stake", amount);
         }
 
-        let epoch = Clock::get()?;
-        let id = state.draw_id + 1;
-
-        if draw_seed != id {
-            return Err(ProgramError::InvalidSeeds);
+        // The stake account's lamports above rent exemption are swept into the vault once the 
+        // deposit (which must balance without the stake account) is done, for the next run.
+        let excess = stake_info.lamports()
+            .saturating_sub(rent.minimum_balance(stake_info.data_len()));
+        let vault_lamports = stake_vault_info.lamports()
+            .checked_add(excess)
+            .ok_or(BondError::NumericalOverflow)?;
+        if excess > 0 && vault_lamports >= vault_rent {
+            **stake_info.lamports.borrow_mut() -= excess;
+            **stake_vault_info.lamports.borrow_mut() = vault_lamports;
         }
+        Ok(())
+    }
 
-        let is_rollover = receiver_info.key.eq(jackpot_info.key);
-        let rollover = if is_rollover { state.rollover + 1 } else { 0 };
+    fn process_harvest_fees(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
 
-        BondDraw::new(
-            draw_authority_info.key.clone(),
-            id,
-            amount,
-            receiver_seed,
-            receiver_info.key.clone(),
-            rollover,
-            epoch.slot,
-            epoch.epoch_start_timestamp,
-            epoch.epoch,
-            epoch.unix_timestamp,
-        ).serialize(
-            &mut &mut draw_info.data.borrow_mut()[..],
-        )?;
+        // Unpack accounts...
+        let account_info_iter = &mut accounts.iter();
+        let config_info = next_account_info(account_info_iter)?;
+        let config = BondConfig::unpack_from_slice(&config_info.data.borrow())?;
+        Check::owner(config_info, program_id)?;
+        Check::valid(&config, config_info)?;
+        Check::active(&config)?;
 
-        state.draw_id = id;
-        state.rollover = rollover;
-        state.serialize(&mut &mut state_info.data.borrow_mut()[..])?;
+        let fee_info = next_account_info(account_info_iter)?;
+        let fee = BondFee::unpack_from_slice(&fee_info.data.borrow())?;
+        Check::owner(fee_info, program_id)?;
+        Check::valid(&fee, fee_info)?;
+        Check::account(config_info, &fee.authority)?;
+        Check::pda(program_id, config_info, fee_info, BondSeed::Fee, fee.bump)?;
+        let fee_ata_info = next_account_info(account_info_iter)?;
+        Check::ata(fee_info, &config.token_mint, fee_ata_info)?;
 
-        if !is_rollover {
-            // Jackpot -> Winner!
-            Create::token_transfer_checked(
-                draw_authority_info,
-                config_info,
-                token_program_info,
-                token_mint_info,
-                jackpot_ata_info,
-                receiver_ata_info,
-                jackpot_info,
-                BondSeed::Jackpot,
-                jackpot.bump,
-                amount,
-            )?;
+        // `token_mint` and `token_program` are checked by `UpdateStakePool`.
+        let pool_program_info = account_info_iter
+            .nth(2)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let pool_info = next_account_info(account_info_iter)?;
+
+        // A pool managed by the fee account pays its fee to the game.
+        let fee_bump = [fee.bump];
+        if Adapter::claim_fee_receiver(
+            config.lst_adapter, 
+            pool_program_info, 
+            pool_info, 
+            fee_info, 
+            fee_ata_info, 
+            &BondSeed::Fee.signer_seeds(config_info.key, &fee_bump),
+        )? {
+            msg!("Pool fee receiver set to {}", fee_ata_info.key);
         }
 
-        Ok(())
+        Self::process_update_stake_pool(program_id, accounts)
     }
-
-}
\ No newline at end of file
+}
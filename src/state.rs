@@ -5,9 +5,34 @@
 /// ------------------------------------------------------------------------------------------------
 
 use {
-    borsh::{BorshDeserialize, BorshSchema, BorshSerialize},
-    solana_program::pubkey::Pubkey,
-    strum_macros::AsRefStr,
+    crate::{
+        constants::*, 
+        error::BondError,
+        instruction::{
+            ConfigParams, 
+            DEFAULT_EPOCHS_PER_DRAW, 
+            DEFAULT_MAX_ROLLOVER, 
+            DEFAULT_ODDS_THRESHOLD,
+        },
+    },
+    borsh::{
+        maybestd::collections::HashMap,
+        schema::{Declaration, Definition},
+        BorshDeserialize, 
+        BorshSchema, 
+        BorshSerialize,
+    },
+    solana_program::{
+        keccak, 
+        program_error::ProgramError, 
+        program_pack::{self, Pack, Sealed}, 
+        pubkey, 
+        pubkey::{Pubkey, PubkeyError},
+    },
+    std::{
+        cmp::Reverse,
+        io::{Error, ErrorKind, Write},
+    },
 };
 
 
@@ -15,29 +40,70 @@ use {
 /// -------------------------------------------------------------------------------------------------
 
 /// The types of program derived addresses managed by the Bond program.
-#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+/// 
+/// Each account starts with its type's 8-byte discriminator (see [ACCOUNT_DISCRIMINATORS]) for 
+/// `memcmp` filters. The discriminants are stable and index the discriminators (they were stored 
+/// as each account's first byte before [DISCRIMINATOR_LAYOUT_VERSION]), so new types must only be 
+/// appended.
+#[derive(Clone, Debug, PartialEq)]
 pub enum BondAccountType {
 
     /// The type given to a new account that has not been initialized.
-    Uninitialized,
+    Uninitialized = 0,
     
     /// The game's configurations/settings.
-    Config,
+    Config = 1,
 
     /// The game's current state.
-    State,
+    State = 2,
     
     /// A share in the staking rewards (e.g. jackpot).
-    Share,
+    Share = 3,
 
     /// Stake pool's fee account.
-    Fee,
+    Fee = 4,
 
     /// A draw result.
-    Draw,
+    Draw = 5,
 
     /// Accounts excluded from winning the draw.
-    ExclusionList,
+    ExclusionList = 6,
+
+    /// A completed season's final state.
+    SeasonArchive = 7,
+
+    /// A wallet registered as eligible to win.
+    Participant = 8,
+
+    /// A compliance hold on a wallet's prizes.
+    Hold = 9,
+
+    /// The additional token mints held by the jackpot.
+    JackpotMints = 10,
+
+    /// A session key allowed to claim on a wallet's behalf.
+    Session = 11,
+
+    /// The top winners by cumulative prize amount.
+    Leaderboard = 12,
+
+    /// A treasury payout stream.
+    Stream = 13,
+
+    /// The rebate checkpoint of a claimed ticket.
+    RebateClaim = 14,
+
+    /// The progress of a receiver's validation against the exclusion list.
+    ExclusionValidation = 15,
+
+    /// The keeper's latest runs.
+    Heartbeat = 16,
+
+    /// The holder weights that a draw selects its winner from.
+    Snapshot = 17,
+
+    /// A chunk of a snapshot's holder weights.
+    ParticipantBuffer = 18,
 }
 
 impl Default for BondAccountType {
@@ -46,45 +112,294 @@ impl Default for BondAccountType {
     }
 }
 
+/// The 8-byte discriminator of each [BondAccountType] indexed by its discriminant, the first 8 
+/// bytes of `sha256("account:Bond<Type>")` (zeroed for [BondAccountType::Uninitialized]).
+pub const ACCOUNT_DISCRIMINATORS: [[u8; 8]; 19] = [
+    [0, 0, 0, 0, 0, 0, 0, 0],
+    [142, 20, 216, 1, 7, 142, 247, 125],
+    [251, 95, 76, 47, 191, 108, 163, 92],
+    [116, 142, 153, 46, 9, 165, 182, 67],
+    [137, 141, 177, 182, 117, 37, 172, 252],
+    [126, 228, 61, 232, 238, 203, 82, 38],
+    [140, 24, 180, 59, 161, 42, 129, 50],
+    [215, 1, 78, 131, 100, 44, 88, 80],
+    [186, 154, 217, 149, 51, 16, 39, 47],
+    [69, 3, 85, 15, 12, 173, 23, 217],
+    [225, 25, 146, 19, 156, 182, 42, 202],
+    [143, 169, 21, 253, 72, 2, 39, 134],
+    [250, 31, 209, 3, 164, 94, 164, 181],
+    [54, 103, 63, 150, 88, 173, 203, 114],
+    [28, 31, 73, 10, 186, 24, 48, 44],
+    [172, 231, 203, 203, 136, 148, 76, 44],
+    [177, 59, 4, 26, 223, 81, 156, 21],
+    [244, 198, 67, 147, 104, 234, 64, 231],
+    [233, 132, 56, 230, 83, 93, 163, 59],
+];
+
+/// The first layout version whose accounts start with an 8-byte discriminator rather than a 1-byte 
+/// `account_type`.
+pub const DISCRIMINATOR_LAYOUT_VERSION: u8 = 43;
+
+impl BondAccountType {
+
+    /// The account type's 8-byte discriminator.
+    pub fn discriminator(&self) -> [u8; 8] {
+        ACCOUNT_DISCRIMINATORS[self.clone() as usize]
+    }
+
+    /// The account type whose discriminator is `discriminator` (if any).
+    pub fn from_discriminator(discriminator: &[u8]) -> Option<Self> {
+        ACCOUNT_DISCRIMINATORS.iter()
+            .position(|account_discriminator| account_discriminator[..] == *discriminator)
+            .and_then(Self::from_index)
+    }
+
+    /// The account type whose discriminant is `index` (if any).
+    pub fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(BondAccountType::Uninitialized),
+            1 => Some(BondAccountType::Config),
+            2 => Some(BondAccountType::State),
+            3 => Some(BondAccountType::Share),
+            4 => Some(BondAccountType::Fee),
+            5 => Some(BondAccountType::Draw),
+            6 => Some(BondAccountType::ExclusionList),
+            7 => Some(BondAccountType::SeasonArchive),
+            8 => Some(BondAccountType::Participant),
+            9 => Some(BondAccountType::Hold),
+            10 => Some(BondAccountType::JackpotMints),
+            11 => Some(BondAccountType::Session),
+            12 => Some(BondAccountType::Leaderboard),
+            13 => Some(BondAccountType::Stream),
+            14 => Some(BondAccountType::RebateClaim),
+            15 => Some(BondAccountType::ExclusionValidation),
+            16 => Some(BondAccountType::Heartbeat),
+            17 => Some(BondAccountType::Snapshot),
+            18 => Some(BondAccountType::ParticipantBuffer),
+            _ => None,
+        }
+    }
+
+    /// The type of an initialized account written with a 1-byte `account_type`, i.e. before 
+    /// [DISCRIMINATOR_LAYOUT_VERSION] (none if `data` isn't such an account).
+    pub fn from_legacy(data: &[u8]) -> Option<Self> {
+        let is_current = data.get(..ACCOUNT_DISCRIMINATOR_SIZE)
+            .and_then(Self::from_discriminator)
+            .is_some();
+        match data {
+            [tag, version, ..] if !is_current && *version < DISCRIMINATOR_LAYOUT_VERSION => {
+                Self::from_index(usize::from(*tag))
+                    .filter(|account_type| *account_type != BondAccountType::Uninitialized)
+            },
+            _ => None,
+        }
+    }
+}
+
+impl BorshSerialize for BondAccountType {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&self.discriminator())
+    }
+}
+
+impl BorshDeserialize for BondAccountType {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, Error> {
+        let discriminator = buf.get(..ACCOUNT_DISCRIMINATOR_SIZE)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Unexpected length of input"))?;
+        let account_type = Self::from_discriminator(discriminator)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Unknown account discriminator"))?;
+        *buf = &buf[ACCOUNT_DISCRIMINATOR_SIZE..];
+        Ok(account_type)
+    }
+}
+
+impl BorshSchema for BondAccountType {
+    fn add_definitions_recursively(definitions: &mut HashMap<Declaration, Definition>) {
+        <[u8; ACCOUNT_DISCRIMINATOR_SIZE]>::add_definitions_recursively(definitions)
+    }
+
+    fn declaration() -> Declaration {
+        <[u8; ACCOUNT_DISCRIMINATOR_SIZE]>::declaration()
+    }
+}
+
+/// The version of the account layouts defined in this module, bumped whenever a layout changes. 
+/// Each account records the version it was written with after its `account_type`.
+/// 
+/// Fields must only be appended to existing layouts: `Migrate` reallocates accounts and reads the 
+/// new fields as zeroed defaults.
+pub const ACCOUNT_LAYOUT_VERSION: u8 = 44;
+
 
 /// Bond Seed
 /// -------------------------------------------------------------------------------------------------
 
 /// The seeds of program derived addresses managed by the Bond program.
-#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema, AsRefStr)]
+/// 
+/// The discriminants are stable and match the Borsh variant index used in instruction data, so 
+/// new seeds must only be appended.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum BondSeed {
 
+    /// The game's settings when derived from its authority and token mint.
+    Config = 0,
+
     /// The current state.
-    #[strum(serialize = "state")]
-    State,
+    State = 1,
 
     /// The Stake Pool's epoch fee.
-    #[strum(serialize = "fee")]
-    Fee,
+    Fee = 2,
 
     /// The latest draw result.
-    #[strum(serialize = "draw")]
-    Draw,
+    Draw = 3,
     
     /// Accounts excluded from winning the draw (e.g. program or partner accounts).
-    #[strum(serialize = "exclusionList")]
-    ExclusionList,
+    ExclusionList = 4,
 
     /// The creator's share.
-    #[strum(serialize = "equity")]
-    Equity,
+    Equity = 5,
 
     /// The treasury account.
-    #[strum(serialize = "treasury")]
-    Treasury,
+    Treasury = 6,
 
     /// The jackpot.
-    #[strum(serialize = "jackpot")]
-    Jackpot,
+    Jackpot = 7,
 
     /// The game's stake (locked).
-    #[strum(serialize = "stake")]
-    Stake,
+    Stake = 8,
+
+    /// A season's archive (also seeded by the season number).
+    Season = 9,
+
+    /// The game's Wormhole emitter.
+    Emitter = 10,
+
+    /// A draw's Wormhole message (also seeded by the draw id).
+    Message = 11,
+
+    /// A wallet's registration (also seeded by the wallet).
+    Participant = 12,
+
+    /// A compliance hold on a wallet (also seeded by the wallet).
+    Hold = 13,
+
+    /// The additional token mints held by the jackpot.
+    JackpotMints = 14,
+
+    /// A wallet's session key (also seeded by the wallet).
+    Session = 15,
+
+    /// The mint of the game's ticket receipt tokens.
+    Receipt = 16,
+
+    /// The winners leaderboard.
+    Leaderboard = 17,
+
+    /// A treasury payout stream (also seeded by the recipient).
+    Stream = 18,
+
+    /// A ticket's rebate claim (also seeded by the ticket's leaf hash).
+    Rebate = 19,
+
+    /// The progress of the next draw's receiver validation against the exclusion list.
+    ExclusionValidation = 20,
+
+    /// The keeper's heartbeat.
+    Heartbeat = 21,
+
+    /// A draw's holder snapshot (also seeded by the draw id).
+    Snapshot = 22,
+
+    /// A chunk of a draw's holder snapshot (also seeded by the draw id and the chunk index).
+    ParticipantBuffer = 23,
+
+    /// The system account collecting SOL to compound into the game's stake.
+    StakeVault = 24,
+}
+
+impl AsRef<str> for BondSeed {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl BondSeed {
+
+    /// The seed's string (one of the `SEED_*` [crate::constants]).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BondSeed::Config => SEED_CONFIG,
+            BondSeed::State => SEED_STATE,
+            BondSeed::Fee => SEED_FEE,
+            BondSeed::Draw => SEED_DRAW,
+            BondSeed::ExclusionList => SEED_EXCLUSION_LIST,
+            BondSeed::Equity => SEED_EQUITY,
+            BondSeed::Treasury => SEED_TREASURY,
+            BondSeed::Jackpot => SEED_JACKPOT,
+            BondSeed::Stake => SEED_STAKE,
+            BondSeed::Season => SEED_SEASON,
+            BondSeed::Emitter => SEED_EMITTER,
+            BondSeed::Message => SEED_MESSAGE,
+            BondSeed::Participant => SEED_PARTICIPANT,
+            BondSeed::Hold => SEED_HOLD,
+            BondSeed::JackpotMints => SEED_JACKPOT_MINTS,
+            BondSeed::Session => SEED_SESSION,
+            BondSeed::Receipt => SEED_RECEIPT,
+            BondSeed::Leaderboard => SEED_LEADERBOARD,
+            BondSeed::Stream => SEED_STREAM,
+            BondSeed::Rebate => SEED_REBATE,
+            BondSeed::ExclusionValidation => SEED_EXCLUSION_VALIDATION,
+            BondSeed::Heartbeat => SEED_HEARTBEAT,
+            BondSeed::Snapshot => SEED_SNAPSHOT,
+            BondSeed::ParticipantBuffer => SEED_PARTICIPANT_BUFFER,
+            BondSeed::StakeVault => SEED_STAKE_VAULT,
+        }
+    }
+
+    /// The seed's bytes.
+    pub fn as_bytes(&self) -> &'static [u8] {
+        self.as_str().as_bytes()
+    }
+
+    /// Finds the PDA derived from [`config`, seed] and its bump seed.
+    pub fn derive(&self, program_id: &Pubkey, config: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[config.as_ref(), self.as_bytes()], program_id)
+    }
+
+    /// Finds the PDA derived from [`config`, seed, `index`] and its bump seed.
+    pub fn derive_indexed(
+        &self, 
+        program_id: &Pubkey, 
+        config: &Pubkey, 
+        index: &[u8],
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[config.as_ref(), self.as_bytes(), index], program_id)
+    }
+
+    /// Creates the PDA derived from [`config`, seed] with a known `bump`.
+    pub fn address(
+        &self, 
+        program_id: &Pubkey, 
+        config: &Pubkey, 
+        bump: u8,
+    ) -> Result<Pubkey, PubkeyError> {
+        Pubkey::create_program_address(&[config.as_ref(), self.as_bytes(), &[bump]], program_id)
+    }
+
+    /// The seeds signing for the PDA derived from [`config`, seed], where `bump` is `[bump]`.
+    pub fn signer_seeds<'a>(&self, config: &'a Pubkey, bump: &'a [u8]) -> [&'a [u8]; 3] {
+        [config.as_ref(), self.as_bytes(), bump]
+    }
+
+    /// The seeds signing for the PDA derived from [`config`, seed, `index`].
+    pub fn indexed_signer_seeds<'a>(
+        &self, 
+        config: &'a Pubkey, 
+        index: &'a [u8], 
+        bump: &'a [u8],
+    ) -> [&'a [u8]; 4] {
+        [config.as_ref(), self.as_bytes(), index, bump]
+    }
 }
 
 
@@ -124,9 +439,182 @@ pub trait BondProgramDerivedAccount: BondProgramAccount {
 }
 
 
+/// Payout Mode
+/// ------------------------------------------------------------------------------------------------
+
+/// How a draw's jackpot is paid to the winner.
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum BondPayoutMode {
+
+    /// The jackpot is transferred to the winner's token account by the draw.
+    Transfer,
+
+    /// The jackpot is held in escrow until the winner submits a `ClaimPrize` instruction.
+    Claim,
+}
+
+impl Default for BondPayoutMode {
+    fn default() -> Self {
+        BondPayoutMode::Transfer
+    }
+}
+
+
+/// Payout Asset
+/// ------------------------------------------------------------------------------------------------
+
+/// The asset an escrowed prize is paid in, chosen by the winner.
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum BondPayoutAsset {
+
+    /// The prize is paid in the Stake Pool's tokens.
+    Token,
+
+    /// The prize's tokens are redeemed for SOL through the Stake Pool's `WithdrawSol`.
+    Sol,
+
+    /// The prize is paid in wrapped SOL and unwrapped into the winner's wallet by closing the 
+    /// receiving token account (only if the token mint is the native mint).
+    NativeSol,
+}
+
+impl Default for BondPayoutAsset {
+    fn default() -> Self {
+        BondPayoutAsset::Token
+    }
+}
+
+
+/// Skipped Share Policy
+/// ------------------------------------------------------------------------------------------------
+
+/// What happens to the portions of shares skipped by a `SplitShares` invocation.
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum BondSkippedSharePolicy {
+
+    /// The skipped portions stay in the fee account until the next split.
+    Retain,
+
+    /// The skipped portions are reallocated to the funded shares in proportion to their shares.
+    Reallocate,
+}
+
+impl Default for BondSkippedSharePolicy {
+    fn default() -> Self {
+        BondSkippedSharePolicy::Retain
+    }
+}
+
+
+/// Remainder Policy
+/// ------------------------------------------------------------------------------------------------
+
+/// What happens to the remainder of a `SplitShares` amount (its rounding, not the dust below a 
+/// share's `min_transfer`, which stays in the fee account until a later split).
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum BondRemainderPolicy {
+
+    /// The remainder stays in the fee account and is recorded in [BondState]`.split_remainder`.
+    Retain,
+
+    /// The remainder is moved to the jackpot (retained if the jackpot is skipped or has no share).
+    Jackpot,
+}
+
+impl Default for BondRemainderPolicy {
+    fn default() -> Self {
+        BondRemainderPolicy::Retain
+    }
+}
+
+
+/// LST Adapter
+/// ------------------------------------------------------------------------------------------------
+
+/// The liquid staking program that issues the game's `token_mint`.
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum BondLstAdapter {
+
+    /// An SPL stake pool.
+    SplStakePool,
+
+    /// Marinade's mSOL.
+    Marinade,
+
+    /// An SPL single-validator stake pool.
+    SinglePool,
+}
+
+impl Default for BondLstAdapter {
+    fn default() -> Self {
+        BondLstAdapter::SplStakePool
+    }
+}
+
+
+/// Squads Vault
+/// ------------------------------------------------------------------------------------------------
+
+/// The Squads v4 multisig program.
+pub const SQUADS_PROGRAM_ID: Pubkey = pubkey!("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+
+/// A Squads vault PDA used as an authority. The vault signs through the Squads program's CPI, so 
+/// no keypair is involved.
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondSquadsVault {
+
+    /// The Squads multisig account.
+    pub multisig: Pubkey,
+
+    /// The vault's index within the multisig.
+    pub index: u8,
+
+    /// The vault's PDA bump seed.
+    pub bump: u8,
+}
+
+impl BondSquadsVault {
+
+    /// The vault's address (PDA of [`multisig`, `vault`, `index`] under [SQUADS_PROGRAM_ID]).
+    pub fn address(&self) -> Result<Pubkey, PubkeyError> {
+        Pubkey::create_program_address(
+            &[
+                b"multisig",
+                self.multisig.as_ref(),
+                b"vault",
+                &[self.index],
+                &[self.bump],
+            ],
+            &SQUADS_PROGRAM_ID,
+        )
+    }
+}
+
+
 /// Bond Config
 /// ------------------------------------------------------------------------------------------------
 
+/// The denominator of values expressed in basis points.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// The maximum crank fee (1%) that can be paid to a permissionless caller.
+pub const MAX_CRANK_FEE_BPS: u16 = 100;
+
+/// The scale of [BondState]'s `rebate_per_weight` accumulator.
+pub const REBATE_PRECISION: u128 = 1_000_000_000_000;
+
+/// The maximum length of [BondConfig]'s `uri`.
+pub const MAX_URI_LEN: usize = 200;
+
+/// The maximum length of [BondConfig]'s `name`.
+pub const MAX_NAME_LEN: usize = 32;
+
+/// Returns `bps` basis points of `amount` (capped at 100%).
+fn bps_of(amount: u64, bps: u16) -> u64 {
+    let bps = u64::from(bps).min(BPS_DENOMINATOR);
+    (u128::from(amount) * u128::from(bps) / u128::from(BPS_DENOMINATOR)) as u64
+}
+
 /// The configurations and settings.
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -134,6 +622,9 @@ pub struct BondConfig {
 
     /// [BondAccountType::Config].
     pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
     
     /// Whether or not the game is active.
     pub is_active: bool,
@@ -152,11 +643,146 @@ pub struct BondConfig {
     /// `(odds_threshold_numerator/odds_threshold_denominator)*100`.
     pub odds_threshold_denominator: u32,
 
+    /// The minimum jackpot amount paid out by a draw. Smaller jackpots roll over without counting 
+    /// towards `max_rollover`.
+    pub min_draw_amount: u64,
+
+    /// The minimum weight (token balance, snapshot balance, or ticket weight with a ticket 
+    /// ledger) of an eligible winner. Lighter wallets are excluded from selection.
+    pub min_eligible_weight: u64,
+
+    /// The number of draws following a win during which the winner can't win again.
+    pub winner_cooldown: u64,
+
+    /// The minimum number of slots between two `SplitShares`.
+    pub min_split_interval: u64,
+
+    /// The fee paid to a permissionless caller of `SplitShares` or `Draw` in basis points, capped 
+    /// at [MAX_CRANK_FEE_BPS].
+    pub crank_fee_bps: u16,
+
+    /// Whether or not any account may run `SplitShares` (otherwise only `draw_authority`).
+    pub allow_permissionless_split: bool,
+
+    /// How the jackpot is paid to a draw's winner.
+    pub payout_mode: BondPayoutMode,
+
+    /// The portion of the jackpot moved to the treasury on each rollover in basis points.
+    pub rollover_retention_bps: u16,
+
+    /// The portion of each `SplitShares` amount rebated to ticket holders in basis points.
+    pub rebate_bps: u16,
+
+    /// What happens to the portions of shares skipped by `SplitShares`.
+    pub skipped_share_policy: BondSkippedSharePolicy,
+
+    /// The unix timestamp before which `SplitShares` and `Draw` are rejected (if set).
+    pub starts_at: Option<i64>,
+
+    /// The unix timestamp from which `SplitShares` and `Draw` are rejected (if set).
+    pub ends_at: Option<i64>,
+
+    /// Whether or not winners must have registered with `RegisterParticipant`.
+    pub require_registration: bool,
+
+    /// Whether or not draws must include an attestation by `attestation_signer`.
+    pub require_attestation: bool,
+
+    /// Whether or not draws must be top-level instructions of transactions that only hold compute 
+    /// budget and attestation instructions.
+    pub isolate_draws: bool,
+
+    /// Whether or not draws require the receiver to have been validated against the exclusion 
+    /// list with `ValidateReceiverChunk`.
+    pub require_exclusion_validation: bool,
+
+    /// The bump seed if the config is a PDA of [[BondSeed::Config], `authority`, `token_mint`], 
+    /// otherwise the config is a keypair account.
+    pub bump: Option<u8>,
+
+    /// The account authorized to manage the game.
+    pub authority: Pubkey,
+
     /// The account authorized to run a draw.
     pub draw_authority: Pubkey,
 
+    /// The Squads vault that `authority` is derived from (if any).
+    pub authority_vault: Option<BondSquadsVault>,
+
+    /// The Squads vault that `draw_authority` is derived from (if any).
+    pub draw_authority_vault: Option<BondSquadsVault>,
+
+    /// The account authorized to place compliance holds on wallets (if set).
+    pub compliance_authority: Option<Pubkey>,
+
+    /// The operator key attesting to the off-chain selection inputs of draws (if set).
+    pub attestation_signer: Option<Pubkey>,
+
     /// The Stake Pool token's mint address.
     pub token_mint: Pubkey,
+
+    /// The number of decimals of `token_mint`, cached at initialization.
+    pub decimals: u8,
+
+    /// The liquid staking program that issues `token_mint`.
+    pub lst_adapter: BondLstAdapter,
+
+    /// The Address Lookup Table of the game's accounts, created by `CreateLookupTable`.
+    pub lookup_table: Option<Pubkey>,
+
+    /// The concurrent merkle tree that draws are logged to instead of draw accounts, created by 
+    /// `CreateDrawTree`.
+    pub draw_tree: Option<Pubkey>,
+
+    /// The concurrent merkle tree of [BondTicket] leaves, created by `CreateTicketTree`. Claims 
+    /// must prove the winner's ticket when set.
+    pub ticket_tree: Option<Pubkey>,
+
+    /// The game's additional jackpot mints ([BondJackpotMints], if any were registered).
+    pub jackpot_mints: Option<Pubkey>,
+
+    /// The mint of the receipt tokens issued 1:1 with ticket weight and burned when tickets are 
+    /// redeemed (if created).
+    pub receipt_mint: Option<Pubkey>,
+
+    /// The game's winners leaderboard ([BondLeaderboard], if created).
+    pub leaderboard: Option<Pubkey>,
+
+    /// The game's display name (UTF-8, zero padded to [MAX_NAME_LEN] bytes).
+    pub name: [u8; MAX_NAME_LEN],
+
+    /// A URI pointing to the game's off-chain metadata (max [MAX_URI_LEN] bytes).
+    pub uri: String,
+
+    /// Whether or not draws must be run with `FulfillDraw` from a Switchboard VRF result 
+    /// requested by `RequestRandomness` (otherwise `Draw` is also accepted). VRF draws require 
+    /// `snapshot_draws`.
+    pub require_vrf: bool,
+
+    /// The oracle key whose Ed25519 signature of each draw's result is required (if set).
+    pub oracle: Option<Pubkey>,
+
+    /// Whether or not draws select their winner on chain from the draw's [BondSnapshot], only 
+    /// with `FulfillDraw`.
+    pub snapshot_draws: bool,
+
+    /// The number of seconds after a draw during which its escrowed prize may be claimed, after 
+    /// which `ExpireClaim` releases it back into the jackpot (never expires if none).
+    pub claim_window: Option<i64>,
+
+    /// The account authorized to withdraw from the equity account (`authority` if none).
+    pub equity_authority: Option<Pubkey>,
+
+    /// What happens to the remainder of each `SplitShares` amount.
+    pub remainder_policy: BondRemainderPolicy,
+
+    /// Whether or not any account may run `FulfillDraw` (otherwise only `draw_authority`), for 
+    /// games whose winners are selected on chain from a snapshot.
+    pub allow_permissionless_draw: bool,
+
+    /// Whether or not the draw authority is limited to one `SplitShares` per epoch, like 
+    /// permissionless callers.
+    pub split_once_per_epoch: bool,
 }
 
 impl BondAccount for BondConfig {
@@ -174,28 +800,104 @@ impl BondAccount for BondConfig {
 
 impl BondConfig {
 
-    /// Creates a new instance of [BondConfig] with an `account_type` of 
-    /// [BondAccountType::Config].
+    /// Creates a new, active instance of [BondConfig] with an `account_type` of 
+    /// [BondAccountType::Config] from the `params` of `Initialize`, applying the defaults of the 
+    /// settings left unset.
     pub fn new(
-        is_active: bool,
-        epochs_per_draw: u8,
-        max_rollover: u8,
-        odds_threshold_numerator: u32,
-        odds_threshold_denominator: u32,
+        authority: Pubkey,
         draw_authority: Pubkey,
         token_mint: Pubkey,
+        decimals: u8,
+        params: ConfigParams,
     ) -> Self {
+        let odds_threshold = params.odds_threshold.unwrap_or(DEFAULT_ODDS_THRESHOLD);
         Self { 
             account_type: BondAccountType::Config,
-            is_active,
-            epochs_per_draw,
-            max_rollover,
-            odds_threshold_numerator,
-            odds_threshold_denominator,
+            version: ACCOUNT_LAYOUT_VERSION,
+            is_active: true,
+            epochs_per_draw: params.epochs_per_draw.unwrap_or(DEFAULT_EPOCHS_PER_DRAW),
+            max_rollover: params.max_rollover.unwrap_or(DEFAULT_MAX_ROLLOVER),
+            odds_threshold_numerator: odds_threshold.numerator,
+            odds_threshold_denominator: odds_threshold.denominator,
+            min_draw_amount: params.min_draw_amount.unwrap_or_default(),
+            min_eligible_weight: params.min_eligible_weight.unwrap_or_default(),
+            winner_cooldown: params.winner_cooldown.unwrap_or_default(),
+            min_split_interval: params.min_split_interval.unwrap_or_default(),
+            crank_fee_bps: params.crank_fee_bps.unwrap_or_default(),
+            allow_permissionless_split: params.allow_permissionless_split.unwrap_or_default(),
+            payout_mode: params.payout_mode.unwrap_or_default(),
+            rollover_retention_bps: params.rollover_retention_bps.unwrap_or_default(),
+            rebate_bps: params.rebate_bps.unwrap_or_default(),
+            skipped_share_policy: params.skipped_share_policy.unwrap_or_default(),
+            starts_at: params.starts_at,
+            ends_at: params.ends_at,
+            require_registration: params.require_registration.unwrap_or_default(),
+            require_attestation: params.require_attestation.unwrap_or_default(),
+            isolate_draws: params.isolate_draws.unwrap_or_default(),
+            require_exclusion_validation: params.require_exclusion_validation.unwrap_or_default(),
+            bump: params.bump,
+            authority,
             draw_authority,
+            authority_vault: params.authority_vault,
+            draw_authority_vault: params.draw_authority_vault,
+            compliance_authority: params.compliance_authority,
+            attestation_signer: params.attestation_signer,
             token_mint,
+            decimals,
+            lst_adapter: params.lst_adapter.unwrap_or_default(),
+            lookup_table: None,
+            draw_tree: None,
+            ticket_tree: None,
+            jackpot_mints: None,
+            receipt_mint: None,
+            leaderboard: None,
+            name: [0; MAX_NAME_LEN],
+            uri: String::new(),
+            require_vrf: params.require_vrf.unwrap_or_default(),
+            oracle: params.oracle,
+            snapshot_draws: params.snapshot_draws.unwrap_or_default(),
+            claim_window: params.claim_window,
+            equity_authority: params.equity_authority,
+            remainder_policy: params.remainder_policy.unwrap_or_default(),
+            allow_permissionless_draw: params.allow_permissionless_draw.unwrap_or_default(),
+            split_once_per_epoch: params.split_once_per_epoch.unwrap_or_default(),
         }
     }
+
+    /// True if a wallet of `weight` may be selected by a draw (e.g. when building the selection 
+    /// index off chain).
+    pub fn is_eligible(&self, weight: u64) -> bool {
+        weight >= self.min_eligible_weight
+    }
+
+    /// True if a wallet of `weight` out of a total eligible `total_weight` has better odds than 
+    /// the odds threshold (no threshold with a zero denominator).
+    pub fn exceeds_odds_threshold(&self, weight: u64, total_weight: u64) -> bool {
+        self.odds_threshold_denominator != 0 
+            && u128::from(weight) * u128::from(self.odds_threshold_denominator) 
+                > u128::from(total_weight) * u128::from(self.odds_threshold_numerator)
+    }
+
+    /// True if any account may fulfill draws, whose VRF result then selects the winner on chain 
+    /// from the draw's snapshot.
+    pub fn is_permissionless_draw(&self) -> bool {
+        self.allow_permissionless_draw && self.snapshot_draws
+    }
+
+    /// The crank fee owed to a permissionless caller for processing `amount`.
+    pub fn crank_fee(&self, amount: u64) -> u64 {
+        bps_of(amount, self.crank_fee_bps.min(MAX_CRANK_FEE_BPS))
+    }
+
+    /// The portion of a rolled over jackpot `amount` moved to the treasury.
+    pub fn rollover_retention(&self, amount: u64) -> u64 {
+        bps_of(amount, self.rollover_retention_bps)
+    }
+
+    /// The portion of a split `amount` rebated to ticket holders.
+    pub fn rebate(&self, amount: u64) -> u64 {
+        bps_of(amount, self.rebate_bps)
+    }
 }
 
 
@@ -209,6 +911,9 @@ pub struct BondState {
 
     /// [BondAccountType::State].
     pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
     
     /// The account authorized to modify this account.
     pub authority: Pubkey,
@@ -221,6 +926,48 @@ pub struct BondState {
 
     /// The number of consecutive rollovers.
     pub rollover: u8,
+
+    /// The amount held in the jackpot for unclaimed prizes ([BondPayoutMode::Claim]).
+    pub reserved: u64,
+
+    /// The current season number.
+    pub season: u32,
+
+    /// The latest draw's winner (excluding rollovers).
+    pub last_winner: Pubkey,
+
+    /// The id of the draw won by `last_winner`.
+    pub last_win_draw_id: u64,
+
+    /// The total weight of the issued tickets.
+    pub ticket_weight: u64,
+
+    /// The rebates accrued per unit of ticket weight, scaled by [REBATE_PRECISION].
+    pub rebate_per_weight: u128,
+
+    /// The slot of the latest `SplitShares`.
+    pub last_split_slot: u64,
+
+    /// The epoch of the latest `SplitShares`.
+    pub last_split_epoch: u64,
+
+    /// The epoch of the latest draw.
+    pub last_draw_epoch: u64,
+
+    /// The Switchboard VRF account of the pending `RequestRandomness` (if any).
+    pub vrf: Pubkey,
+
+    /// The slot of the pending `RequestRandomness`, consumed by `FulfillDraw` (0 if none).
+    pub vrf_request_slot: u64,
+
+    /// The remainder of the latest `SplitShares` left in the fee account.
+    pub split_remainder: u64,
+
+    /// The number of treasury streams created and not yet revoked.
+    pub open_streams: u32,
+
+    /// The last draw id of the previous seasons, which the current season's draw ids follow.
+    pub season_start_draw_id: u64,
 }
 
 impl BondAccount for BondState {
@@ -257,14 +1004,73 @@ impl BondState {
         bump: u8,
         draw_id: u64,
         rollover: u8,
+        reserved: u64,
+        season: u32,
     ) -> Self {
         Self { 
             account_type: BondAccountType::State,
+            version: ACCOUNT_LAYOUT_VERSION,
             authority,
             bump,
             draw_id,
             rollover,
+            reserved,
+            season,
+            last_winner: Pubkey::default(),
+            last_win_draw_id: 0,
+            ticket_weight: 0,
+            rebate_per_weight: 0,
+            last_split_slot: 0,
+            last_split_epoch: 0,
+            last_draw_epoch: 0,
+            vrf: Pubkey::default(),
+            vrf_request_slot: 0,
+            split_remainder: 0,
+            open_streams: 0,
+            season_start_draw_id: draw_id,
+        }
+    }
+
+    /// The first epoch in which the next draw may run given `epochs_per_draw` (0 before the 
+    /// season's first draw).
+    pub fn next_draw_epoch(&self, epochs_per_draw: u8) -> u64 {
+        if self.draw_id == 0 {
+            return 0
+        }
+        self.last_draw_epoch.saturating_add(u64::from(epochs_per_draw))
+    }
+
+    /// The first slot at which `SplitShares` may run again given `min_split_interval` (0 before 
+    /// the first split).
+    pub fn next_split_slot(&self, min_split_interval: u64) -> u64 {
+        if self.last_split_slot == 0 {
+            return 0
+        }
+        self.last_split_slot.saturating_add(min_split_interval)
+    }
+
+    /// True if a `SplitShares` already ran in `epoch`.
+    pub fn is_split_in_epoch(&self, epoch: u64) -> bool {
+        self.last_split_slot != 0 && self.last_split_epoch == epoch
+    }
+
+    /// Records a `SplitShares` at `slot` in `epoch`.
+    pub fn record_split(&mut self, slot: u64, epoch: u64) {
+        self.last_split_slot = slot;
+        self.last_split_epoch = epoch;
+    }
+
+    /// Spreads a rebate `amount` over the issued ticket weight.
+    pub fn accrue_rebate(&mut self, amount: u64) -> Result<(), BondError> {
+        if self.ticket_weight == 0 {
+            return Ok(())
         }
+        self.rebate_per_weight = u128::from(amount)
+            .checked_mul(REBATE_PRECISION)
+            .map(|scaled| scaled / u128::from(self.ticket_weight))
+            .and_then(|accrued| self.rebate_per_weight.checked_add(accrued))
+            .ok_or(BondError::NumericalOverflow)?;
+        Ok(())
     }
 }
 
@@ -280,6 +1086,9 @@ pub struct BondShare {
     /// [BondAccountType::Share].
     pub account_type: BondAccountType,
 
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
     /// The account authorized to modify this account.
     pub authority: Pubkey,
 
@@ -291,6 +1100,10 @@ pub struct BondShare {
 
     /// The rewards share expressed by the percentage (0-100) `(numerator/denominator)*100`.
     pub denominator: u32,
+
+    /// The smallest portion transferred by `SplitShares`, smaller portions stay in the fee account 
+    /// until a later split.
+    pub min_transfer: u64,
 }
 
 impl BondAccount for BondShare {
@@ -329,16 +1142,32 @@ impl BondShare {
     ) -> Self {
         Self { 
             account_type: BondAccountType::Share, 
+            version: ACCOUNT_LAYOUT_VERSION,
             authority, 
             bump,
             numerator,
             denominator, 
+            min_transfer: 0,
         }
     }
 
+    /// The share of `amount` (0 if the share has no denominator), saturating if it exceeds 
+    /// `u64::MAX` (see [BondShare::try_share]).
     pub fn share(&self, amount: u64) -> u64 {
-        let denominator = u64::from(self.denominator);
-        if denominator == 0 { 0 } else { (amount * u64::from(self.numerator)) / denominator }
+        self.try_share(amount).unwrap_or(u64::MAX)
+    }
+
+    /// The share of `amount` (0 if the share has no denominator), computed with u128 
+    /// intermediates so that large amounts don't overflow.
+    pub fn try_share(&self, amount: u64) -> Result<u64, BondError> {
+        if self.denominator == 0 {
+            return Ok(0)
+        }
+        u128::from(amount)
+            .checked_mul(u128::from(self.numerator))
+            .map(|scaled| scaled / u128::from(self.denominator))
+            .and_then(|share| u64::try_from(share).ok())
+            .ok_or(BondError::NumericalOverflow)
     }
 }
 
@@ -354,9 +1183,15 @@ pub struct BondDraw {
     /// [BondAccountType::Draw].
     pub account_type: BondAccountType,
 
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
     /// The account authorized to modify this account.
     pub authority: Pubkey,
 
+    /// The game's config account.
+    pub config: Pubkey,
+
     /// Unique id / sequence number.
     pub id: u64,
 
@@ -383,6 +1218,38 @@ pub struct BondDraw {
 
     /// The timestamp at which the draw took place.
     pub unix_timestamp: i64,
+
+    /// True once the jackpot has been paid to `receiver` (or the draw rolled over).
+    pub is_claimed: bool,
+
+    /// The asset the winner chose to claim the prize in ([BondPayoutMode::Claim]).
+    pub payout_asset: BondPayoutAsset,
+
+    /// The keccak hash of the operator's signed attestation of the selection inputs (if any).
+    pub attestation: Option<[u8; 32]>,
+
+    /// The amount moved from the jackpot to the treasury on rollover.
+    pub retained: u64,
+
+    /// The balances of the additional jackpot mints paid to the winner.
+    pub extra_prizes: Vec<BondPrize>,
+
+    /// The winner's weight at selection (0 for rollovers).
+    pub receiver_weight: u64,
+
+    /// The total eligible weight at selection (0 for rollovers).
+    pub total_weight: u64,
+
+    /// The draw's randomness: the VRF result of `FulfillDraw`, which selected the winner, or the 
+    /// keccak hash of `Draw`'s `receiver_seed` mixed with the most recent slot hash at the draw.
+    pub entropy: [u8; 32],
+
+    /// True once the unclaimed prize was released back into the jackpot by `ExpireClaim`.
+    pub is_expired: bool,
+
+    /// The part of `amount` withheld by the token mint's Token-2022 transfer fee when the prize 
+    /// was paid, so that the winner received `amount - transfer_fee`.
+    pub transfer_fee: u64,
 }
 
 impl BondAccount for BondDraw {
@@ -409,6 +1276,7 @@ impl BondDraw {
     /// Creates a new instance of [BondDraw] with an `account_type` of [BondAccountType::Draw].
     pub fn new(
         authority: Pubkey,
+        config: Pubkey,
         id: u64,
         amount: u64,
         receiver_seed: u64,
@@ -418,10 +1286,15 @@ impl BondDraw {
         epoch_start_timestamp: i64,
         epoch: u64,
         unix_timestamp: i64,
+        is_claimed: bool,
+        retained: u64,
+        extra_prizes: Vec<BondPrize>,
     ) -> Self {
         Self { 
             account_type: BondAccountType::Draw, 
+            version: ACCOUNT_LAYOUT_VERSION,
             authority,
+            config,
             id,
             amount,
             receiver_seed,
@@ -431,9 +1304,39 @@ impl BondDraw {
             epoch_start_timestamp,
             epoch,
             unix_timestamp,
+            is_claimed,
+            payout_asset: BondPayoutAsset::default(),
+            attestation: None,
+            retained,
+            extra_prizes,
+            receiver_weight: 0,
+            total_weight: 0,
+            entropy: [0; 32],
+            is_expired: false,
+            transfer_fee: 0,
         }
     }
-}
+
+    /// True if the draw's escrowed prize can't be claimed anymore at `unix_timestamp` given the 
+    /// config's `claim_window`.
+    pub fn is_claim_expired(&self, claim_window: Option<i64>, unix_timestamp: i64) -> bool {
+        self.is_expired || claim_window.map_or(false, |claim_window| {
+            unix_timestamp >= self.unix_timestamp.saturating_add(claim_window)
+        })
+    }
+}
+
+
+/// A prize paid in one of the jackpot's additional mints.
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondPrize {
+
+    /// The prize's token mint.
+    pub mint: Pubkey,
+
+    /// The prize's amount.
+    pub amount: u64,
+}
 
 
 /// Bond Fee
@@ -447,11 +1350,17 @@ pub struct BondFee {
     /// [BondAccountType::Fee].
     pub account_type: BondAccountType,
 
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
     /// The account authorized to modify this account.
     pub authority: Pubkey,
 
     /// The derived account's bump seed.
     pub bump: u8,
+
+    /// The epoch in which the Stake Pool's balance was last updated through `UpdateStakePool`.
+    pub last_update_epoch: u64,
 }
 
 impl BondAccount for BondFee {
@@ -488,8 +1397,10 @@ impl BondFee {
     ) -> Self {
         Self { 
             account_type: BondAccountType::Fee,
+            version: ACCOUNT_LAYOUT_VERSION,
             authority, 
             bump,
+            last_update_epoch: 0,
         }
     }
 }
@@ -506,6 +1417,9 @@ pub struct BondExclusionList {
     /// [BondAccountType::ExclusionList].
     pub account_type: BondAccountType,
 
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
     /// The account authorized to modify this account.
     pub authority: Pubkey,
 
@@ -515,8 +1429,14 @@ pub struct BondExclusionList {
     /// The maximum number of accounts.
     pub capacity: u32,
 
+    /// The number of chunks appended with `AppendExclusionChunk`.
+    pub chunks: u32,
+
+    /// Incremented by every change of `accounts`, invalidating receiver validations in progress.
+    pub revision: u64,
+
     /// The excluded accounts.
-    pub accounts: Vec<Pubkey>,
+    pub accounts: Vec<BondExclusion>,
 }
 
 impl BondAccount for BondExclusionList {
@@ -546,6 +1466,11 @@ impl BondProgramDerivedAccount for BondExclusionList {
 
 impl BondExclusionList {
 
+    /// The allocation size of an exclusion list with room for `capacity` accounts.
+    pub fn space(capacity: u32) -> usize {
+        exclusion_list_size(capacity)
+    }
+
     /// Creates a new instance of [BondExclusionList] with an `account_type` of 
     /// [BondAccountType::ExclusionList].
     pub fn new(
@@ -556,10 +1481,2068 @@ impl BondExclusionList {
     ) -> Self {
         Self { 
             account_type: BondAccountType::ExclusionList,
+            version: ACCOUNT_LAYOUT_VERSION,
             authority, 
             bump,
             capacity,
-            accounts,
+            chunks: 0,
+            revision: 0,
+            accounts: accounts.into_iter().map(BondExclusion::permanent).collect(),
+        }
+    }
+
+    /// True if `account` has an entry in the list, expired or not.
+    pub fn contains(&self, account: &Pubkey) -> bool {
+        self.accounts.iter().any(|excluded| excluded.account == *account)
+    }
+
+    /// True if `account` is excluded in `epoch` (expired entries are eligible again).
+    pub fn is_excluded(&self, account: &Pubkey, epoch: u64) -> bool {
+        self.accounts
+            .iter()
+            .any(|excluded| excluded.account == *account && !excluded.is_expired(epoch))
+    }
+
+    /// Adds `account` to the list until `expires_at_epoch` (if any), failing if it's already 
+    /// listed or the list is full.
+    pub fn add(&mut self, account: Pubkey, expires_at_epoch: Option<u64>) -> Result<(), BondError> {
+        if self.contains(&account) {
+            Err(BondError::DuplicateExclusion)
+        } else if self.accounts.len() >= usize::try_from(self.capacity).unwrap() {
+            Err(BondError::ExclusionListFull)
+        } else {
+            self.accounts.push(BondExclusion { account, expires_at_epoch });
+            self.revision += 1;
+            Ok(())
+        }
+    }
+
+    /// Removes `account` from the list, returning true if it was listed.
+    pub fn remove(&mut self, account: &Pubkey) -> bool {
+        let len = self.accounts.len();
+        self.accounts.retain(|excluded| excluded.account != *account);
+        let is_removed = self.accounts.len() != len;
+        if is_removed {
+            self.revision += 1;
+        }
+        is_removed
+    }
+
+    /// Removes the entries expired in `epoch`, returning the number of removed entries.
+    pub fn prune_expired(&mut self, epoch: u64) -> usize {
+        let len = self.accounts.len();
+        self.accounts.retain(|excluded| !excluded.is_expired(epoch));
+        let pruned = len - self.accounts.len();
+        if pruned > 0 {
+            self.revision += 1;
+        }
+        pruned
+    }
+}
+
+
+/// An entry of a [BondExclusionList].
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondExclusion {
+
+    /// The excluded account.
+    pub account: Pubkey,
+
+    /// The epoch from which the account is eligible again (none for a permanent exclusion).
+    pub expires_at_epoch: Option<u64>,
+}
+
+impl BondExclusion {
+
+    /// A permanent exclusion of `account`.
+    pub fn permanent(account: Pubkey) -> Self {
+        Self { account, expires_at_epoch: None }
+    }
+
+    /// True if the exclusion has lapsed in `epoch`.
+    pub fn is_expired(&self, epoch: u64) -> bool {
+        self.expires_at_epoch.map_or(false, |expires_at_epoch| epoch >= expires_at_epoch)
+    }
+}
+
+
+/// Season Archive
+/// ------------------------------------------------------------------------------------------------
+
+/// The final state of a completed season.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondSeasonArchive {
+
+    /// [BondAccountType::SeasonArchive].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The archived season number.
+    pub season: u32,
+
+    /// The season's last draw id.
+    pub draw_id: u64,
+
+    /// The number of consecutive rollovers at the end of the season.
+    pub rollover: u8,
+
+    /// The season's start time (if set).
+    pub starts_at: Option<i64>,
+
+    /// The season's end time (if set).
+    pub ends_at: Option<i64>,
+
+    /// The timestamp at which the season was archived.
+    pub unix_timestamp: i64,
+
+    /// The season's first draw id (`draw_id + 1` if the season had no draws).
+    pub first_draw_id: u64,
+
+    /// The game's state and stats at the end of the season.
+    pub state: BondState,
+}
+
+impl BondAccount for BondSeasonArchive {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::SeasonArchive].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::SeasonArchive
+    }
+}
+
+impl BondProgramAccount for BondSeasonArchive {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondSeasonArchive {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondSeasonArchive {
+
+    /// Creates a new instance of [BondSeasonArchive] with an `account_type` of 
+    /// [BondAccountType::SeasonArchive].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+        state: &BondState,
+        starts_at: Option<i64>,
+        ends_at: Option<i64>,
+        unix_timestamp: i64,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::SeasonArchive,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            season: state.season,
+            draw_id: state.draw_id,
+            rollover: state.rollover,
+            starts_at,
+            ends_at,
+            unix_timestamp,
+            first_draw_id: state.season_start_draw_id + 1,
+            state: state.clone(),
+        }
+    }
+}
+
+
+/// Bond Participant
+/// ------------------------------------------------------------------------------------------------
+
+/// A wallet's one-time registration, required to win when [BondConfig]`.require_registration` is 
+/// set.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondParticipant {
+
+    /// [BondAccountType::Participant].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The registered wallet.
+    pub wallet: Pubkey,
+
+    /// The owner of the token accounts that receive the wallet's prizes paid by `Draw`.
+    pub payout: Pubkey,
+
+    /// The hash of the terms acknowledged by the wallet.
+    pub terms_hash: [u8; 32],
+
+    /// The timestamp at which the wallet registered.
+    pub unix_timestamp: i64,
+}
+
+impl BondAccount for BondParticipant {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::Participant].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::Participant
+    }
+}
+
+impl BondProgramAccount for BondParticipant {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondParticipant {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondParticipant {
+
+    /// Creates a new instance of [BondParticipant] with an `account_type` of 
+    /// [BondAccountType::Participant].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+        wallet: Pubkey,
+        payout: Pubkey,
+        terms_hash: [u8; 32],
+        unix_timestamp: i64,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::Participant,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            wallet,
+            payout,
+            terms_hash,
+            unix_timestamp,
+        }
+    }
+}
+
+
+/// Bond Hold
+/// ------------------------------------------------------------------------------------------------
+
+/// A compliance hold on a wallet. Prizes claimed by the wallet are escrowed in the jackpot until 
+/// the game's authority releases or reroutes them.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondHold {
+
+    /// [BondAccountType::Hold].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The held wallet.
+    pub wallet: Pubkey,
+
+    /// The prizes escrowed while the hold is in place.
+    pub amount: u64,
+
+    /// The timestamp at which the hold was placed.
+    pub unix_timestamp: i64,
+}
+
+impl BondAccount for BondHold {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::Hold].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::Hold
+    }
+}
+
+impl BondProgramAccount for BondHold {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondHold {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondHold {
+
+    /// Creates a new instance of [BondHold] with an `account_type` of [BondAccountType::Hold].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+        wallet: Pubkey,
+        unix_timestamp: i64,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::Hold,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            wallet,
+            amount: 0,
+            unix_timestamp,
         }
     }
-}
\ No newline at end of file
+}
+
+
+/// Bond Jackpot Mints
+/// ------------------------------------------------------------------------------------------------
+
+/// The maximum number of additional jackpot mints.
+pub const MAX_JACKPOT_MINTS: usize = 4;
+
+/// The additional token mints approved for the jackpot (e.g. sponsor contributions in USDC). Each 
+/// mint's balance in the jackpot's associated token account is paid to a draw's winner.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondJackpotMints {
+
+    /// [BondAccountType::JackpotMints].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The registered mints.
+    pub mints: Vec<Pubkey>,
+
+    /// The amounts of the registered mints held in the jackpot for unclaimed prizes 
+    /// ([BondPayoutMode::Claim]).
+    pub reserved: Vec<BondPrize>,
+}
+
+impl BondAccount for BondJackpotMints {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::JackpotMints].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::JackpotMints
+    }
+}
+
+impl BondProgramAccount for BondJackpotMints {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondJackpotMints {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondJackpotMints {
+
+    /// Creates a new instance of [BondJackpotMints] with an `account_type` of 
+    /// [BondAccountType::JackpotMints].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::JackpotMints,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            mints: Vec::new(),
+            reserved: Vec::new(),
+        }
+    }
+
+    /// The amount of `mint` held in the jackpot for unclaimed prizes.
+    pub fn reserved(&self, mint: &Pubkey) -> u64 {
+        self.reserved
+            .iter()
+            .find(|reserved| reserved.mint == *mint)
+            .map_or(0, |reserved| reserved.amount)
+    }
+
+    /// True if any prize is unclaimed.
+    pub fn is_reserved(&self) -> bool {
+        self.reserved.iter().any(|reserved| reserved.amount > 0)
+    }
+
+    /// Holds `prize` in the jackpot until it's claimed or expires.
+    pub fn reserve(&mut self, prize: &BondPrize) -> Result<(), BondError> {
+        match self.reserved.iter_mut().find(|reserved| reserved.mint == prize.mint) {
+            Some(reserved) => {
+                reserved.amount = reserved.amount
+                    .checked_add(prize.amount)
+                    .ok_or(BondError::NumericalOverflow)?;
+            },
+            None => self.reserved.push(prize.clone()),
+        }
+        Ok(())
+    }
+
+    /// Releases the reservation of a claimed or expired `prize`.
+    pub fn release(&mut self, prize: &BondPrize) -> Result<(), BondError> {
+        let reserved = self.reserved
+            .iter_mut()
+            .find(|reserved| reserved.mint == prize.mint)
+            .ok_or(BondError::NumericalOverflow)?;
+        reserved.amount = reserved.amount
+            .checked_sub(prize.amount)
+            .ok_or(BondError::NumericalOverflow)?;
+        Ok(())
+    }
+
+    /// Registers `mint`, failing if it's already registered or [MAX_JACKPOT_MINTS] is reached.
+    pub fn add(&mut self, mint: Pubkey) -> Result<(), BondError> {
+        if self.mints.contains(&mint) {
+            return Err(BondError::DuplicateJackpotMint);
+        }
+        if self.mints.len() >= MAX_JACKPOT_MINTS {
+            return Err(BondError::JackpotMintsFull);
+        }
+        self.mints.push(mint);
+        Ok(())
+    }
+}
+
+
+/// Bond Session
+/// ------------------------------------------------------------------------------------------------
+
+/// A session key registered by a wallet, whose signature authorizes claims on the wallet's behalf 
+/// (e.g. through a gasless relayer) until it expires.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondSession {
+
+    /// [BondAccountType::Session].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The wallet that registered the session.
+    pub wallet: Pubkey,
+
+    /// The key that may sign on behalf of `wallet`.
+    pub session_key: Pubkey,
+
+    /// The unix timestamp from which the session key is rejected.
+    pub expires_at: i64,
+}
+
+impl BondAccount for BondSession {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::Session].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::Session
+    }
+}
+
+impl BondProgramAccount for BondSession {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondSession {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondSession {
+
+    /// Creates a new instance of [BondSession] with an `account_type` of 
+    /// [BondAccountType::Session].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+        wallet: Pubkey,
+        session_key: Pubkey,
+        expires_at: i64,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::Session,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            wallet,
+            session_key,
+            expires_at,
+        }
+    }
+}
+
+
+/// Bond Leaderboard
+/// ------------------------------------------------------------------------------------------------
+
+/// The maximum number of winners kept by the leaderboard.
+pub const MAX_LEADERBOARD_ENTRIES: usize = 10;
+
+/// A winner's cumulative prizes.
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondLeaderboardEntry {
+
+    /// The winning wallet.
+    pub wallet: Pubkey,
+
+    /// The wallet's cumulative prize amount.
+    pub amount: u64,
+}
+
+/// The top [MAX_LEADERBOARD_ENTRIES] winners by cumulative prize amount, updated as prizes are 
+/// paid by `Draw` and `ClaimPrize`.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondLeaderboard {
+
+    /// [BondAccountType::Leaderboard].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The winners, sorted by descending `amount`.
+    pub entries: Vec<BondLeaderboardEntry>,
+}
+
+impl BondAccount for BondLeaderboard {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::Leaderboard].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::Leaderboard
+    }
+}
+
+impl BondProgramAccount for BondLeaderboard {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondLeaderboard {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondLeaderboard {
+
+    /// Creates a new instance of [BondLeaderboard] with an `account_type` of 
+    /// [BondAccountType::Leaderboard].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::Leaderboard,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds `amount` to `wallet`'s cumulative prizes, keeping the top [MAX_LEADERBOARD_ENTRIES].
+    pub fn record(&mut self, wallet: Pubkey, amount: u64) -> Result<(), BondError> {
+        match self.entries.iter_mut().find(|entry| entry.wallet == wallet) {
+            Some(entry) => {
+                entry.amount = entry.amount
+                    .checked_add(amount)
+                    .ok_or(BondError::NumericalOverflow)?;
+            },
+            None => self.entries.push(BondLeaderboardEntry { wallet, amount }),
+        }
+        self.entries.sort_by_key(|entry| Reverse(entry.amount));
+        self.entries.truncate(MAX_LEADERBOARD_ENTRIES);
+        Ok(())
+    }
+}
+
+
+/// Bond Stream
+/// ------------------------------------------------------------------------------------------------
+
+/// Treasury tokens locked in the stream's token account and vested linearly to the recipient, 
+/// until the game's authority revokes the stream.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondStream {
+
+    /// [BondAccountType::Stream].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The wallet the stream pays.
+    pub recipient: Pubkey,
+
+    /// The amount vested per second.
+    pub rate: u64,
+
+    /// The unix timestamp at which the stream started.
+    pub starts_at: i64,
+
+    /// The stream's duration in seconds.
+    pub duration: u64,
+
+    /// The amount already withdrawn by the recipient.
+    pub withdrawn: u64,
+}
+
+impl BondAccount for BondStream {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::Stream].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::Stream
+    }
+}
+
+impl BondProgramAccount for BondStream {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondStream {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondStream {
+
+    /// Creates a new instance of [BondStream] with an `account_type` of [BondAccountType::Stream].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+        recipient: Pubkey,
+        rate: u64,
+        starts_at: i64,
+        duration: u64,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::Stream,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            recipient,
+            rate,
+            starts_at,
+            duration,
+            withdrawn: 0,
+        }
+    }
+
+    /// The stream's total amount.
+    pub fn total(&self) -> Option<u64> {
+        self.rate.checked_mul(self.duration)
+    }
+
+    /// The amount vested and not yet withdrawn at `unix_timestamp`.
+    pub fn withdrawable(&self, unix_timestamp: i64) -> Option<u64> {
+        let elapsed = u64::try_from(unix_timestamp.saturating_sub(self.starts_at)).unwrap_or(0);
+        self.rate
+            .checked_mul(elapsed.min(self.duration))?
+            .checked_sub(self.withdrawn)
+    }
+}
+
+
+/// Bond Rebate Claim
+/// ------------------------------------------------------------------------------------------------
+
+/// The rebate checkpoint of a [BondTicket], created by its first `ClaimRebate`.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondRebateClaim {
+
+    /// [BondAccountType::RebateClaim].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// [BondState]'s `rebate_per_weight` at the ticket's latest claim.
+    pub rebate_per_weight: u128,
+}
+
+impl BondAccount for BondRebateClaim {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::RebateClaim].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::RebateClaim
+    }
+}
+
+impl BondProgramAccount for BondRebateClaim {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondRebateClaim {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondRebateClaim {
+
+    /// Creates a new instance of [BondRebateClaim] with an `account_type` of 
+    /// [BondAccountType::RebateClaim].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+        rebate_per_weight: u128,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::RebateClaim,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            rebate_per_weight,
+        }
+    }
+
+    /// The PDA index of a ticket's claim, the keccak hash of the ticket's `leaf` and its leaf 
+    /// `index` (u32 LE), so that identical tickets at different indexes have distinct claims.
+    pub fn seed_index(leaf: &[u8; 32], index: u32) -> [u8; 32] {
+        keccak::hashv(&[leaf, &index.to_le_bytes()]).to_bytes()
+    }
+}
+
+
+/// The leading fields of a serialized [BondExclusionList], read without deserializing its 
+/// accounts (for lists too large to deserialize in a single instruction).
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondExclusionListHeader {
+
+    /// [BondAccountType::ExclusionList].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify the list.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The maximum number of accounts.
+    pub capacity: u32,
+
+    /// The number of chunks appended with `AppendExclusionChunk`.
+    pub chunks: u32,
+
+    /// The list's revision.
+    pub revision: u64,
+
+    /// The number of accounts.
+    pub len: u32,
+}
+
+impl BondAccount for BondExclusionListHeader {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::ExclusionList].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::ExclusionList
+    }
+}
+
+
+/// Bond Exclusion Validation
+/// ------------------------------------------------------------------------------------------------
+
+/// The progress of a receiver's validation against the exclusion list, performed in chunks by 
+/// `ValidateReceiverChunk` and required by `Draw` when 
+/// [BondConfig]`.require_exclusion_validation` is set.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondExclusionValidation {
+
+    /// [BondAccountType::ExclusionValidation].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The receiver being validated.
+    pub receiver: Pubkey,
+
+    /// The id of the draw the receiver is validated for.
+    pub draw_id: u64,
+
+    /// The epoch in which the validation takes place (expiring entries are checked against it).
+    pub epoch: u64,
+
+    /// The exclusion list's `revision` when the validation started.
+    pub revision: u64,
+
+    /// The number of exclusion list entries checked so far.
+    pub index: u32,
+
+    /// The byte offset of the next entry to check in the exclusion list's data.
+    pub offset: u32,
+
+    /// True once every entry has been checked.
+    pub is_complete: bool,
+}
+
+impl BondAccount for BondExclusionValidation {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::ExclusionValidation].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::ExclusionValidation
+    }
+}
+
+impl BondProgramAccount for BondExclusionValidation {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondExclusionValidation {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondExclusionValidation {
+
+    /// Creates a new instance of [BondExclusionValidation] with an `account_type` of 
+    /// [BondAccountType::ExclusionValidation], starting at the exclusion list's first entry.
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+        receiver: Pubkey,
+        draw_id: u64,
+        epoch: u64,
+        revision: u64,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::ExclusionValidation,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            receiver,
+            draw_id,
+            epoch,
+            revision,
+            index: 0,
+            offset: EXCLUSION_LIST_SIZE as u32,
+            is_complete: false,
+        }
+    }
+
+    /// True if the validation is of `receiver` in draw `draw_id` during `epoch` against the 
+    /// exclusion list's current `revision`.
+    pub fn is_for(&self, receiver: &Pubkey, draw_id: u64, epoch: u64, revision: u64) -> bool {
+        self.is_initialized()
+            && self.receiver == *receiver 
+            && self.draw_id == draw_id 
+            && self.epoch == epoch 
+            && self.revision == revision
+    }
+}
+
+
+/// Bond Heartbeat
+/// ------------------------------------------------------------------------------------------------
+
+/// The slots of the keeper's latest runs, updated with `Heartbeat` so that monitoring can detect a 
+/// stalled keeper from chain state and fall back to permissionless cranks.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondHeartbeat {
+
+    /// [BondAccountType::Heartbeat].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The slot of the keeper's latest Stake Pool update.
+    pub last_harvest_slot: u64,
+
+    /// The slot of the keeper's latest `SplitShares`.
+    pub last_split_slot: u64,
+
+    /// The slot of the keeper's latest `Draw`.
+    pub last_draw_slot: u64,
+}
+
+impl BondAccount for BondHeartbeat {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::Heartbeat].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::Heartbeat
+    }
+}
+
+impl BondProgramAccount for BondHeartbeat {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondHeartbeat {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondHeartbeat {
+
+    /// Creates a new instance of [BondHeartbeat] with an `account_type` of 
+    /// [BondAccountType::Heartbeat].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::Heartbeat,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            last_harvest_slot: 0,
+            last_split_slot: 0,
+            last_draw_slot: 0,
+        }
+    }
+
+    /// The slot of the keeper's latest run of any kind.
+    pub fn last_slot(&self) -> u64 {
+        self.last_harvest_slot.max(self.last_split_slot).max(self.last_draw_slot)
+    }
+
+    /// True if the keeper hasn't run for more than `max_idle_slots` at `slot`.
+    pub fn is_stalled(&self, slot: u64, max_idle_slots: u64) -> bool {
+        slot.saturating_sub(self.last_slot()) > max_idle_slots
+    }
+}
+
+
+
+/// Bond Snapshot
+/// ------------------------------------------------------------------------------------------------
+
+/// The holder weights written by the draw authority ahead of a draw, from which the draw selects 
+/// its winner on chain when [BondConfig]`.snapshot_draws` is set.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondSnapshot {
+
+    /// [BondAccountType::Snapshot].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The id of the draw that the snapshot was taken for.
+    pub draw_id: u64,
+
+    /// The start of the draw window that the weights are time-weighted over (the timestamp of 
+    /// the first slot of the epoch in which the snapshot was created).
+    pub window_start: i64,
+
+    /// The end of the draw window (the timestamp at which the snapshot was created).
+    pub window_end: i64,
+
+    /// The minimum balance of an eligible holder ([BondConfig]`.min_eligible_weight` when the 
+    /// snapshot was created).
+    pub min_balance: u64,
+
+    /// The sum of the entries' weights.
+    pub total_weight: u64,
+
+    /// The holders and their weights, in selection order.
+    pub entries: Vec<BondSnapshotEntry>,
+
+    /// The number of [BondParticipantBuffer] chunks written with `WriteParticipants` (entries 
+    /// are either held by the snapshot or by its buffers).
+    pub buffers: u32,
+
+    /// The keccak hash chain of all entries in selection order (see [BondSnapshot::append]).
+    pub hash: [u8; 32],
+
+    /// True once `FinalizeSnapshot` froze the snapshot for its draw.
+    pub is_sealed: bool,
+
+    /// The merkle root of the [BondSnapshotLeaf] leaves posted with `PostSnapshotRoot` instead 
+    /// of entries (if any).
+    pub root: Option<[u8; 32]>,
+
+    /// The number of leaves under `root`.
+    pub leaf_count: u32,
+}
+
+impl BondAccount for BondSnapshot {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::Snapshot].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::Snapshot
+    }
+}
+
+impl BondProgramAccount for BondSnapshot {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondSnapshot {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondSnapshot {
+
+    /// Creates a new instance of [BondSnapshot] with an `account_type` of 
+    /// [BondAccountType::Snapshot].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+        draw_id: u64,
+        window_start: i64,
+        window_end: i64,
+        min_balance: u64,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::Snapshot,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            draw_id,
+            window_start,
+            window_end,
+            min_balance,
+            total_weight: 0,
+            entries: Vec::new(),
+            buffers: 0,
+            hash: [0; 32],
+            is_sealed: false,
+            root: None,
+            leaf_count: 0,
+        }
+    }
+
+    /// Adds `entries` to the snapshot's total weight and hash chain, where each entry's hash is 
+    /// `keccak(hash, wallet, weight (u64 LE))`, failing if the total weight overflows.
+    pub fn record(&mut self, entries: &[BondSnapshotEntry]) -> Result<(), BondError> {
+        for entry in entries {
+            self.total_weight = self.total_weight
+                .checked_add(entry.weight)
+                .ok_or(BondError::InvalidWeight)?;
+            self.hash = keccak::hashv(&[
+                &self.hash, 
+                entry.wallet.as_ref(), 
+                &entry.weight.to_le_bytes(),
+            ]).to_bytes();
+        }
+        Ok(())
+    }
+
+    /// True if a holder of `balance` may be selected from the snapshot.
+    pub fn is_eligible(&self, balance: u64) -> bool {
+        balance >= self.min_balance
+    }
+
+    /// The time-weighted weight of `balance` held since `held_since` over the snapshot's draw 
+    /// window (see [time_weighted]).
+    pub fn time_weighted(&self, balance: u64, held_since: i64) -> u64 {
+        time_weighted(balance, held_since, self.window_start, self.window_end)
+    }
+
+    /// Replaces the balance of each of `entries` with its time-weighted weight over the 
+    /// snapshot's draw window.
+    pub fn time_weight(&self, mut entries: Vec<BondSnapshotEntry>) -> Vec<BondSnapshotEntry> {
+        for entry in entries.iter_mut() {
+            entry.weight = self.time_weighted(entry.weight, entry.held_since);
+        }
+        entries
+    }
+
+    /// Appends `entries` to the snapshot, failing if the total weight overflows.
+    pub fn append(&mut self, entries: Vec<BondSnapshotEntry>) -> Result<(), BondError> {
+        self.record(&entries)?;
+        self.entries.extend(entries);
+        Ok(())
+    }
+
+    /// The point selected by a draw's `entropy` (the VRF result, or the slot hash mixed seed of a 
+    /// `PreviewDraw`, see [BondDraw]`.entropy`), its first 8 bytes (u64 LE) modulo 
+    /// `total_weight` (none if the snapshot has no weight).
+    pub fn point(&self, entropy: &[u8; 32]) -> Option<u64> {
+        let mut seed = [0; 8];
+        seed.copy_from_slice(&entropy[..8]);
+        u64::from_le_bytes(seed).checked_rem(self.total_weight)
+    }
+
+    /// The entry whose cumulative weight range holds the point of `entropy` (none if the snapshot 
+    /// has no weight or its entries are held by buffers).
+    pub fn select(&self, entropy: &[u8; 32]) -> Option<&BondSnapshotEntry> {
+        select_entry(&self.entries, self.point(entropy)?)
+    }
+
+    /// True if `leaf` is at `index` of the merkle tree of `root`, given the sibling hashes of its 
+    /// path from the leaf up (`keccak(left, right)` nodes).
+    pub fn verify(&self, leaf: &BondSnapshotLeaf, index: u32, proof: &[[u8; 32]]) -> bool {
+        let root = match self.root {
+            Some(root) if index < self.leaf_count => root,
+            _ => return false,
+        };
+        let mut node = match leaf.hash() {
+            Ok(hash) => hash,
+            Err(_) => return false,
+        };
+        let mut path = index;
+        for sibling in proof {
+            node = if path & 1 == 0 {
+                keccak::hashv(&[&node, sibling]).to_bytes()
+            } else {
+                keccak::hashv(&[sibling, &node]).to_bytes()
+            };
+            path >>= 1;
+        }
+        path == 0 && node == root
+    }
+}
+
+
+/// The time-weighted `balance` held since `held_since` over the window [`starts_at`, `ends_at`), 
+/// i.e. `balance` prorated by the portion of the window during which it was held, so that 
+/// balances acquired just before a draw barely count.
+pub fn time_weighted(balance: u64, held_since: i64, starts_at: i64, ends_at: i64) -> u64 {
+    if ends_at <= starts_at || held_since >= ends_at {
+        return 0;
+    }
+    let held = ends_at - held_since.max(starts_at);
+    let window = ends_at - starts_at;
+    (u128::from(balance) * held as u128 / window as u128) as u64
+}
+
+
+/// The entry of `entries` whose cumulative weight range holds `point`.
+fn select_entry(entries: &[BondSnapshotEntry], mut point: u64) -> Option<&BondSnapshotEntry> {
+    for entry in entries.iter() {
+        if point < entry.weight {
+            return Some(entry);
+        }
+        point -= entry.weight;
+    }
+    None
+}
+
+
+/// An entry of a [BondSnapshot].
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondSnapshotEntry {
+
+    /// The holder's wallet.
+    pub wallet: Pubkey,
+
+    /// The holder's weight: its balance when written, replaced by its time-weighted weight over 
+    /// the snapshot's draw window by `WriteSnapshot` and `WriteParticipants`.
+    pub weight: u64,
+
+    /// The timestamp since which the holder has held its balance.
+    pub held_since: i64,
+}
+
+
+/// A leaf of a [BondSnapshot]'s merkle `root`, holding the snapshot's weight range 
+/// `offset..offset + weight`.
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondSnapshotLeaf {
+
+    /// The holder's wallet.
+    pub wallet: Pubkey,
+
+    /// The holder's balance.
+    pub balance: u64,
+
+    /// The timestamp since which the holder has held `balance`.
+    pub held_since: i64,
+
+    /// The holder's weight, the time-weighted `balance` over the snapshot's draw window (see 
+    /// [BondSnapshot::time_weighted]).
+    pub weight: u64,
+
+    /// The total weight of the preceding leaves.
+    pub offset: u64,
+}
+
+impl BondSnapshotLeaf {
+
+    /// The leaf's hash (the keccak hash of its borsh serialization).
+    pub fn hash(&self) -> Result<[u8; 32], std::io::Error> {
+        Ok(keccak::hash(&self.try_to_vec()?).to_bytes())
+    }
+
+    /// True if the snapshot's `point` falls within the leaf's weight range.
+    pub fn holds(&self, point: u64) -> bool {
+        point >= self.offset && point - self.offset < self.weight
+    }
+}
+
+
+
+/// Bond Participant Buffer
+/// ------------------------------------------------------------------------------------------------
+
+/// The maximum number of entries of a [BondParticipantBuffer].
+pub const MAX_PARTICIPANT_BUFFER_ENTRIES: usize = 1000;
+
+/// A chunk of a [BondSnapshot]'s entries written with `WriteParticipants`, holding the weight 
+/// range `weight_offset..weight_offset + total_weight` of the snapshot.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondParticipantBuffer {
+
+    /// [BondAccountType::ParticipantBuffer].
+    pub account_type: BondAccountType,
+
+    /// The [ACCOUNT_LAYOUT_VERSION] the account was written with.
+    pub version: u8,
+
+    /// The account authorized to modify this account.
+    pub authority: Pubkey,
+
+    /// The derived account's bump seed.
+    pub bump: u8,
+
+    /// The id of the draw that the snapshot was taken for.
+    pub draw_id: u64,
+
+    /// The buffer's index in the snapshot.
+    pub index: u32,
+
+    /// The total weight of the snapshot's previous buffers.
+    pub weight_offset: u64,
+
+    /// The sum of the entries' weights.
+    pub total_weight: u64,
+
+    /// The holders and their weights, in selection order.
+    pub entries: Vec<BondSnapshotEntry>,
+}
+
+impl BondAccount for BondParticipantBuffer {
+    
+    /// True if `account_type` is not [BondAccountType::Uninitialized].
+    fn is_initialized(&self) -> bool {
+        self.account_type != BondAccountType::Uninitialized
+    }
+
+    /// True if `account_type` is [BondAccountType::ParticipantBuffer].
+    fn is_valid(&self) -> bool {
+        self.account_type == BondAccountType::ParticipantBuffer
+    }
+}
+
+impl BondProgramAccount for BondParticipantBuffer {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+}
+
+impl BondProgramDerivedAccount for BondParticipantBuffer {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl BondParticipantBuffer {
+
+    /// Creates a new instance of [BondParticipantBuffer] with an `account_type` of 
+    /// [BondAccountType::ParticipantBuffer].
+    pub fn new(
+        authority: Pubkey,
+        bump: u8,
+        draw_id: u64,
+        index: u32,
+        weight_offset: u64,
+    ) -> Self {
+        Self { 
+            account_type: BondAccountType::ParticipantBuffer,
+            version: ACCOUNT_LAYOUT_VERSION,
+            authority,
+            bump,
+            draw_id,
+            index,
+            weight_offset,
+            total_weight: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// The PDA index of the buffer (the draw id and the buffer index, LE).
+    pub fn seed_index(draw_id: u64, index: u32) -> [u8; 12] {
+        let mut seed_index = [0; 12];
+        seed_index[..8].copy_from_slice(&draw_id.to_le_bytes());
+        seed_index[8..].copy_from_slice(&index.to_le_bytes());
+        seed_index
+    }
+
+    /// Appends `entries` to the buffer, failing if it's full or the total weight overflows.
+    pub fn append(&mut self, entries: Vec<BondSnapshotEntry>) -> Result<(), BondError> {
+        if self.entries.len() + entries.len() > MAX_PARTICIPANT_BUFFER_ENTRIES {
+            return Err(BondError::ParticipantBufferFull);
+        }
+        for entry in entries {
+            self.total_weight = self.total_weight
+                .checked_add(entry.weight)
+                .ok_or(BondError::InvalidWeight)?;
+            self.entries.push(entry);
+        }
+        Ok(())
+    }
+
+    /// True if the snapshot's `point` falls within the buffer's weight range.
+    pub fn holds(&self, point: u64) -> bool {
+        point >= self.weight_offset && point - self.weight_offset < self.total_weight
+    }
+
+    /// The entry whose cumulative weight range holds the snapshot's `point` (none if the buffer 
+    /// doesn't hold it).
+    pub fn select(&self, point: u64) -> Option<&BondSnapshotEntry> {
+        if !self.holds(point) {
+            return None;
+        }
+        select_entry(&self.entries, point - self.weight_offset)
+    }
+}
+
+
+/// Bond Ticket
+/// ------------------------------------------------------------------------------------------------
+
+/// A ticket stored as a compressed leaf of [BondConfig]`.ticket_tree`.
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondTicket {
+
+    /// The ticket holder.
+    pub wallet: Pubkey,
+
+    /// The ticket's weight in the draw.
+    pub weight: u64,
+
+    /// The unix timestamp at which the ticket was issued (set by `IssueTickets`).
+    pub issued_at: i64,
+
+    /// [BondState]'s `rebate_per_weight` when the ticket was issued (set by `IssueTickets`).
+    pub rebate_per_weight: u128,
+}
+
+impl BondTicket {
+
+    /// The ticket's leaf hash (the keccak hash of its borsh serialization).
+    pub fn leaf(&self) -> Result<[u8; 32], std::io::Error> {
+        Ok(keccak::hash(&self.try_to_vec()?).to_bytes())
+    }
+
+    /// The ticket's time-weighted weight over the draw window [`starts_at`, `ends_at`) (see 
+    /// [time_weighted]).
+    pub fn time_weighted_weight(&self, starts_at: i64, ends_at: i64) -> u64 {
+        time_weighted(self.weight, self.issued_at, starts_at, ends_at)
+    }
+
+    /// The rebate accrued by the ticket's weight since the `checkpoint` accumulator value.
+    pub fn rebate(&self, rebate_per_weight: u128, checkpoint: u128) -> Option<u64> {
+        let accrued = rebate_per_weight.checked_sub(checkpoint)?;
+        u64::try_from(u128::from(self.weight).checked_mul(accrued)? / REBATE_PRECISION).ok()
+    }
+}
+
+
+/// Bond Draw Message
+/// ------------------------------------------------------------------------------------------------
+
+/// The payload of a draw result published through Wormhole by `PublishDrawMessage`.
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BondDrawMessage {
+
+    /// The game's config.
+    pub config: Pubkey,
+
+    /// The draw's id.
+    pub id: u64,
+
+    /// The winning account (or the jackpot for a rollover).
+    pub receiver: Pubkey,
+
+    /// The draw's jackpot amount.
+    pub amount: u64,
+
+    /// The draw's unix timestamp.
+    pub unix_timestamp: i64,
+}
+
+impl From<&BondDraw> for BondDrawMessage {
+    fn from(draw: &BondDraw) -> Self {
+        Self {
+            config: draw.config,
+            id: draw.id,
+            receiver: draw.receiver,
+            amount: draw.amount,
+            unix_timestamp: draw.unix_timestamp,
+        }
+    }
+}
+
+
+/// Pack
+/// ------------------------------------------------------------------------------------------------
+
+/// Packs `account` at the start of `dst`, zero-filling the rest of it, or fails with 
+/// `AccountDataTooSmall` if its Borsh layout doesn't fit.
+pub fn pack_account(account: &impl BorshSerialize, dst: &mut [u8]) -> Result<(), ProgramError> {
+    let len = dst.len();
+    let mut cursor = &mut dst[..];
+    if account.serialize(&mut cursor).is_err() {
+        dst.fill(0);
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let end = len - cursor.len();
+    dst[end..].fill(0);
+    Ok(())
+}
+
+/// Packs `account` into `dst` for [Pack::pack_into_slice], which can't return an error. An 
+/// account that doesn't fit panics, failing the transaction instead of erasing its data.
+fn pack_padded(account: &impl BorshSerialize, dst: &mut [u8]) {
+    if let Err(error) = pack_account(account, dst) {
+        panic!("Account doesn't fit in {} bytes: {}", dst.len(), error);
+    }
+}
+
+/// Unpacks an account from the Borsh layout at the start of `src`, ignoring the padding.
+fn unpack_padded<T: BorshDeserialize>(src: &[u8]) -> Result<T, ProgramError> {
+    T::deserialize(&mut &src[..]).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+impl Sealed for BondConfig {}
+
+impl program_pack::IsInitialized for BondConfig {
+    fn is_initialized(&self) -> bool {
+        BondAccount::is_initialized(self)
+    }
+}
+
+impl Pack for BondConfig {
+    const LEN: usize = CONFIG_SIZE;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        pack_padded(self, dst)
+    }
+
+    fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        pack_account(&src, dst)
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        unpack_padded(src)
+    }
+}
+
+impl Sealed for BondState {}
+
+impl program_pack::IsInitialized for BondState {
+    fn is_initialized(&self) -> bool {
+        BondAccount::is_initialized(self)
+    }
+}
+
+impl Pack for BondState {
+    const LEN: usize = STATE_SIZE;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        pack_padded(self, dst)
+    }
+
+    fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        pack_account(&src, dst)
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        unpack_padded(src)
+    }
+}
+
+impl Sealed for BondShare {}
+
+impl program_pack::IsInitialized for BondShare {
+    fn is_initialized(&self) -> bool {
+        BondAccount::is_initialized(self)
+    }
+}
+
+impl Pack for BondShare {
+    const LEN: usize = SHARE_SIZE;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        pack_padded(self, dst)
+    }
+
+    fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        pack_account(&src, dst)
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        unpack_padded(src)
+    }
+}
+
+impl Sealed for BondFee {}
+
+impl program_pack::IsInitialized for BondFee {
+    fn is_initialized(&self) -> bool {
+        BondAccount::is_initialized(self)
+    }
+}
+
+impl Pack for BondFee {
+    const LEN: usize = FEE_SIZE;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        pack_padded(self, dst)
+    }
+
+    fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        pack_account(&src, dst)
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        unpack_padded(src)
+    }
+}
+
+impl Sealed for BondDraw {}
+
+impl program_pack::IsInitialized for BondDraw {
+    fn is_initialized(&self) -> bool {
+        BondAccount::is_initialized(self)
+    }
+}
+
+impl Pack for BondDraw {
+    const LEN: usize = MAX_DRAW_SIZE;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        pack_padded(self, dst)
+    }
+
+    fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        pack_account(&src, dst)
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        unpack_padded(src)
+    }
+}
+
+
+/// Tests
+/// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A config with every optional field set and a `uri` of [MAX_URI_LEN] bytes.
+    fn max_config() -> BondConfig {
+        let vault = Some(BondSquadsVault { multisig: Pubkey::new_unique(), index: 0, bump: 0 });
+        let key = Some(Pubkey::new_unique());
+        BondConfig {
+            account_type: BondAccountType::Config,
+            starts_at: Some(i64::MAX),
+            ends_at: Some(i64::MAX),
+            bump: Some(u8::MAX),
+            authority_vault: vault,
+            draw_authority_vault: vault,
+            compliance_authority: key,
+            attestation_signer: key,
+            lookup_table: key,
+            draw_tree: key,
+            ticket_tree: key,
+            jackpot_mints: key,
+            receipt_mint: key,
+            leaderboard: key,
+            uri: "u".repeat(MAX_URI_LEN),
+            oracle: key,
+            claim_window: Some(i64::MAX),
+            equity_authority: key,
+            ..BondConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_config_size() {
+        assert_eq!(max_config().try_to_vec().unwrap().len(), CONFIG_SIZE);
+        assert_eq!(BondConfig::LEN, CONFIG_SIZE);
+    }
+
+    #[test]
+    fn test_state_size() {
+        let state = BondState { account_type: BondAccountType::State, ..BondState::default() };
+        assert_eq!(state.try_to_vec().unwrap().len(), STATE_SIZE);
+    }
+
+    #[test]
+    fn test_season_archive_size() {
+        let state = BondState { account_type: BondAccountType::State, ..BondState::default() };
+        let archive = BondSeasonArchive::new(Pubkey::default(), 0, &state, Some(0), Some(0), 0);
+        assert_eq!(archive.try_to_vec().unwrap().len(), SEASON_ARCHIVE_SIZE);
+    }
+
+    #[test]
+    fn test_draw_size() {
+        let mut draw = BondDraw { account_type: BondAccountType::Draw, ..BondDraw::default() };
+        assert_eq!(draw.try_to_vec().unwrap().len(), DRAW_SIZE - 32);
+        draw.attestation = Some([0; 32]);
+        assert_eq!(draw.try_to_vec().unwrap().len(), DRAW_SIZE);
+        draw.extra_prizes = vec![BondPrize::default(); MAX_JACKPOT_MINTS];
+        assert_eq!(draw.try_to_vec().unwrap().len(), MAX_DRAW_SIZE);
+    }
+
+    #[test]
+    fn test_pack_into_slice() {
+        let config = max_config();
+        let mut dst = vec![1; CONFIG_SIZE + 8];
+        config.pack_into_slice(&mut dst);
+        assert_eq!(BondConfig::unpack_from_slice(&dst).unwrap(), config);
+        assert_eq!(&dst[CONFIG_SIZE..], &[0; 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account doesn't fit")]
+    fn test_pack_into_slice_too_small() {
+        let mut dst = vec![1; CONFIG_SIZE - 1];
+        max_config().pack_into_slice(&mut dst);
+    }
+
+    #[test]
+    fn test_account_type_discriminants() {
+        for index in 0..ACCOUNT_DISCRIMINATORS.len() {
+            assert_eq!(BondAccountType::from_index(index).unwrap() as usize, index);
+        }
+        assert_eq!(BondAccountType::from_index(ACCOUNT_DISCRIMINATORS.len()), None);
+    }
+
+    #[test]
+    fn test_account_discriminators() {
+        let discriminator = solana_program::hash::hash(b"account:BondConfig").to_bytes();
+        assert_eq!(BondAccountType::Config.discriminator()[..], discriminator[..8]);
+        let data = BondAccountType::Draw.try_to_vec().unwrap();
+        assert_eq!(data, BondAccountType::Draw.discriminator());
+        assert_eq!(BondAccountType::try_from_slice(&data).unwrap(), BondAccountType::Draw);
+        assert!(BondAccountType::try_from_slice(&[1; ACCOUNT_DISCRIMINATOR_SIZE]).is_err());
+
+        // Accounts written with a 1-byte `account_type` are recognized by their version.
+        let legacy = [2, DISCRIMINATOR_LAYOUT_VERSION - 1, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(BondAccountType::from_legacy(&legacy), Some(BondAccountType::State));
+        assert_eq!(BondAccountType::from_legacy(&[0, 0]), None);
+        assert_eq!(BondAccountType::from_legacy(&[2, DISCRIMINATOR_LAYOUT_VERSION]), None);
+        assert_eq!(BondAccountType::from_legacy(&data), None);
+    }
+
+    #[test]
+    fn test_seed_discriminants() {
+        for index in 0..=BondSeed::StakeVault as u8 {
+            assert_eq!(BondSeed::try_from_slice(&[index]).unwrap() as u8, index);
+        }
+        assert!(BondSeed::try_from_slice(&[BondSeed::StakeVault as u8 + 1]).is_err());
+    }
+
+    #[test]
+    fn test_seed_derive() {
+        let (program_id, config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (state, bump) = BondSeed::State.derive(&program_id, &config);
+        assert_eq!(BondSeed::State.address(&program_id, &config, bump), Ok(state));
+        let bump = [bump];
+        let seeds = BondSeed::State.signer_seeds(&config, &bump);
+        assert_eq!(Pubkey::create_program_address(&seeds, &program_id), Ok(state));
+        assert_ne!(BondSeed::Treasury.derive(&program_id, &config).0, state);
+
+        // Indexed PDAs are derived from the index too.
+        let index = 7u64.to_le_bytes();
+        let (message, bump) = BondSeed::Message.derive_indexed(&program_id, &config, &index);
+        let bump = [bump];
+        let seeds = BondSeed::Message.indexed_signer_seeds(&config, &index, &bump);
+        assert_eq!(Pubkey::create_program_address(&seeds, &program_id), Ok(message));
+        assert_ne!(BondSeed::Message.derive(&program_id, &config).0, message);
+    }
+
+    #[test]
+    fn test_exclusion_list_add() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut exclusion_list = BondExclusionList::new(Pubkey::default(), 0, 1, vec![]);
+        exclusion_list.add(first, None).unwrap();
+        assert_eq!(exclusion_list.add(first, None), Err(BondError::DuplicateExclusion));
+        assert_eq!(exclusion_list.add(second, None), Err(BondError::ExclusionListFull));
+        assert_eq!(exclusion_list.accounts, vec![BondExclusion::permanent(first)]);
+        assert_eq!(exclusion_list.revision, 1);
+    }
+
+    #[test]
+    fn test_time_weighted() {
+        // Balances held during the whole window count fully, later ones prorated.
+        assert_eq!(time_weighted(1_000, 0, 100, 200), 1_000);
+        assert_eq!(time_weighted(1_000, 150, 100, 200), 500);
+        assert_eq!(time_weighted(1_000, 199, 100, 200), 10);
+        assert_eq!(time_weighted(1_000, 200, 100, 200), 0);
+        assert_eq!(time_weighted(1_000, 0, 200, 200), 0);
+        assert_eq!(time_weighted(u64::MAX, 0, 0, i64::MAX), u64::MAX);
+
+        let ticket = BondTicket { weight: 1_000, issued_at: 175, ..BondTicket::default() };
+        assert_eq!(ticket.time_weighted_weight(100, 200), 250);
+    }
+
+    #[test]
+    fn test_exclusion_list_expiry() {
+        let (temporary, permanent) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut exclusion_list = BondExclusionList::new(Pubkey::default(), 0, 2, vec![]);
+        exclusion_list.add(temporary, Some(5)).unwrap();
+        exclusion_list.add(permanent, None).unwrap();
+        assert!(exclusion_list.is_excluded(&temporary, 4));
+        assert!(!exclusion_list.is_excluded(&temporary, 5));
+        assert!(exclusion_list.is_excluded(&permanent, u64::MAX));
+
+        // Lapsed entries stay listed until pruned.
+        assert!(exclusion_list.contains(&temporary));
+        assert_eq!(exclusion_list.prune_expired(4), 0);
+        assert_eq!(exclusion_list.revision, 2);
+        assert_eq!(exclusion_list.prune_expired(5), 1);
+        assert_eq!(exclusion_list.accounts, vec![BondExclusion::permanent(permanent)]);
+        assert_eq!(exclusion_list.revision, 3);
+    }
+
+    #[test]
+    fn test_accrue_rebate() {
+        let mut state = BondState::default();
+        state.accrue_rebate(100).unwrap();
+        assert_eq!(state.rebate_per_weight, 0);
+
+        // Rebates accrue per unit of issued weight, from each ticket's checkpoint.
+        state.ticket_weight = 400;
+        state.accrue_rebate(100).unwrap();
+        state.accrue_rebate(300).unwrap();
+        let ticket = BondTicket { weight: 100, ..BondTicket::default() };
+        assert_eq!(ticket.rebate(state.rebate_per_weight, 0), Some(100));
+        assert_eq!(ticket.rebate(state.rebate_per_weight, REBATE_PRECISION / 4), Some(75));
+        assert_eq!(ticket.rebate(0, 1), None);
+    }
+
+    #[test]
+    fn test_stream_withdrawable() {
+        let mut stream = BondStream::new(Pubkey::default(), 0, Pubkey::default(), 10, 1_000, 100);
+        assert_eq!(stream.total(), Some(1_000));
+        assert_eq!(stream.withdrawable(900), Some(0));
+        assert_eq!(stream.withdrawable(1_030), Some(300));
+
+        // Nothing vests after the stream's duration, and withdrawals are deducted.
+        stream.withdrawn = 300;
+        assert_eq!(stream.withdrawable(1_030), Some(0));
+        assert_eq!(stream.withdrawable(5_000), Some(700));
+    }
+
+    #[test]
+    fn test_leaderboard_record() {
+        let wallets: Vec<Pubkey> = (0..=MAX_LEADERBOARD_ENTRIES)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let mut leaderboard = BondLeaderboard::new(Pubkey::default(), 0);
+        for (index, wallet) in wallets.iter().enumerate() {
+            leaderboard.record(*wallet, index as u64 + 1).unwrap();
+        }
+
+        // Only the top entries are kept, sorted by cumulative amount.
+        assert_eq!(leaderboard.entries.len(), MAX_LEADERBOARD_ENTRIES);
+        assert_eq!(leaderboard.entries[0].wallet, wallets[MAX_LEADERBOARD_ENTRIES]);
+        assert!(!leaderboard.entries.iter().any(|entry| entry.wallet == wallets[0]));
+
+        // Prizes of a listed wallet add up.
+        leaderboard.record(wallets[1], 100).unwrap();
+        let entry = BondLeaderboardEntry { wallet: wallets[1], amount: 102 };
+        assert_eq!(leaderboard.entries[0], entry);
+        assert_eq!(leaderboard.record(wallets[1], u64::MAX), Err(BondError::NumericalOverflow));
+    }
+
+    #[test]
+    fn test_share_try_share() {
+        let share = BondShare::new(Pubkey::default(), 0, 8, 10);
+        assert_eq!(share.try_share(1_000), Ok(800));
+        assert_eq!(share.try_share(999), Ok(799));
+        assert_eq!(BondShare::new(Pubkey::default(), 0, 8, 0).try_share(1_000), Ok(0));
+
+        // Large amounts are scaled without overflowing, unless the share itself does.
+        assert_eq!(share.try_share(u64::MAX), Ok(u64::MAX / 10 * 8 + 4));
+        let share = BondShare::new(Pubkey::default(), 0, 2, 1);
+        assert_eq!(share.try_share(u64::MAX), Err(BondError::NumericalOverflow));
+        assert_eq!(share.share(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_draw_claim_expired() {
+        let mut draw = BondDraw { unix_timestamp: 100, ..BondDraw::default() };
+        assert!(!draw.is_claim_expired(None, i64::MAX));
+        assert!(!draw.is_claim_expired(Some(50), 149));
+        assert!(draw.is_claim_expired(Some(50), 150));
+        assert!(!draw.is_claim_expired(Some(i64::MAX), i64::MAX - 1));
+
+        // Released prizes stay expired.
+        draw.is_expired = true;
+        assert!(draw.is_claim_expired(None, 0));
+    }
+
+    #[test]
+    fn test_next_draw_epoch() {
+        let mut state = BondState { last_draw_epoch: 5, ..BondState::default() };
+        assert_eq!(state.next_draw_epoch(2), 0);
+
+        // Draws are spaced by `epochs_per_draw` once the first one ran.
+        state.draw_id = 1;
+        assert_eq!(state.next_draw_epoch(0), 5);
+        assert_eq!(state.next_draw_epoch(2), 7);
+        state.last_draw_epoch = u64::MAX;
+        assert_eq!(state.next_draw_epoch(2), u64::MAX);
+    }
+
+    /// Snapshot entries of `weights`, each for a new wallet.
+    fn snapshot_entries(weights: &[u64]) -> Vec<BondSnapshotEntry> {
+        weights
+            .iter()
+            .map(|weight| BondSnapshotEntry {
+                wallet: Pubkey::new_unique(),
+                weight: *weight,
+                held_since: 0,
+            })
+            .collect()
+    }
+
+    /// Entropy whose first 8 bytes are `point` (u64 LE).
+    fn entropy(point: u64) -> [u8; 32] {
+        let mut entropy = [0xff; 32];
+        entropy[..8].copy_from_slice(&point.to_le_bytes());
+        entropy
+    }
+
+    #[test]
+    fn test_snapshot_select() {
+        let entries = snapshot_entries(&[10, 0, 30]);
+        let snapshot = BondSnapshot {
+            total_weight: 40,
+            entries: entries.clone(),
+            ..BondSnapshot::default()
+        };
+
+        // Each entry holds a range as wide as its weight, the point wrapping at the total weight.
+        for (point, index) in [(0, 0), (9, 0), (10, 2), (39, 2), (40, 0), (45, 0), (u64::MAX, 2)] {
+            assert_eq!(snapshot.select(&entropy(point)), Some(&entries[index]), "{}", point);
+        }
+
+        // Without weight or entries nothing is selected.
+        let empty = BondSnapshot { total_weight: 0, ..snapshot.clone() };
+        assert_eq!(empty.select(&entropy(0)), None);
+        let buffered = BondSnapshot { entries: vec![], buffers: 1, ..snapshot };
+        assert_eq!(buffered.select(&entropy(0)), None);
+    }
+
+    #[test]
+    fn test_snapshot_verify() {
+        let leaves: Vec<BondSnapshotLeaf> = snapshot_entries(&[10, 20, 30])
+            .into_iter()
+            .map(|entry| BondSnapshotLeaf {
+                wallet: entry.wallet,
+                balance: entry.weight,
+                weight: entry.weight,
+                ..BondSnapshotLeaf::default()
+            })
+            .collect();
+        let hashes: Vec<[u8; 32]> = leaves.iter().map(|leaf| leaf.hash().unwrap()).collect();
+        let left = keccak::hashv(&[&hashes[0], &hashes[1]]).to_bytes();
+        let right = keccak::hashv(&[&hashes[2], &[0; 32]]).to_bytes();
+        let snapshot = BondSnapshot {
+            root: Some(keccak::hashv(&[&left, &right]).to_bytes()),
+            leaf_count: 3,
+            ..BondSnapshot::default()
+        };
+
+        // Each leaf is proven by its siblings from the leaf up, at its own index.
+        assert!(snapshot.verify(&leaves[0], 0, &[hashes[1], right]));
+        assert!(snapshot.verify(&leaves[1], 1, &[hashes[0], right]));
+        assert!(snapshot.verify(&leaves[2], 2, &[[0; 32], left]));
+        assert!(!snapshot.verify(&leaves[0], 1, &[hashes[1], right]));
+        assert!(!snapshot.verify(&leaves[1], 1, &[hashes[0], left]));
+        assert!(!snapshot.verify(&leaves[0], 0, &[hashes[1]]));
+
+        // Altered leaves, leaves past the leaf count and snapshots without a root are rejected.
+        let altered = BondSnapshotLeaf { weight: 11, ..leaves[0].clone() };
+        assert!(!snapshot.verify(&altered, 0, &[hashes[1], right]));
+        let truncated = BondSnapshot { leaf_count: 2, ..snapshot.clone() };
+        assert!(!truncated.verify(&leaves[2], 2, &[[0; 32], left]));
+        let unrooted = BondSnapshot { root: None, ..snapshot };
+        assert!(!unrooted.verify(&leaves[0], 0, &[hashes[1], right]));
+    }
+
+    #[test]
+    fn test_participant_buffer() {
+        let mut buffer = BondParticipantBuffer::new(Pubkey::new_unique(), 255, 1, 0, 10);
+        buffer.append(snapshot_entries(&[10, 20])).unwrap();
+        assert_eq!(buffer.total_weight, 30);
+
+        // The buffer holds the snapshot's points from its offset, selecting its own entries.
+        assert!(!buffer.holds(9) && !buffer.holds(40));
+        assert_eq!(buffer.select(9), None);
+        assert_eq!(buffer.select(10), Some(&buffer.entries[0]));
+        assert_eq!(buffer.select(25), Some(&buffer.entries[1]));
+        assert_eq!(buffer.select(39), Some(&buffer.entries[1]));
+
+        // Full buffers and overflowing weights are rejected without appending.
+        let full = snapshot_entries(&vec![1; MAX_PARTICIPANT_BUFFER_ENTRIES - 1]);
+        assert_eq!(buffer.append(full), Err(BondError::ParticipantBufferFull));
+        let overflow = snapshot_entries(&[u64::MAX]);
+        assert_eq!(buffer.append(overflow), Err(BondError::InvalidWeight));
+        assert_eq!(buffer.total_weight, 30);
+    }
+}
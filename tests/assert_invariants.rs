@@ -0,0 +1,61 @@
+//! Integration tests of `AssertInvariants`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{BondInstruction, ConfigParams},
+    },
+    solana_program::instruction::{AccountMeta, Instruction},
+    solana_program_test::*,
+};
+
+/// An `AssertInvariants` instruction of `game`, which hasn't drawn yet.
+fn assert_invariants(game: &Game) -> Instruction {
+    let addresses = &game.addresses;
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(addresses.state.0, false),
+            AccountMeta::new_readonly(addresses.equity.0, false),
+            AccountMeta::new_readonly(addresses.treasury.0, false),
+            AccountMeta::new_readonly(addresses.jackpot.0, false),
+            AccountMeta::new_readonly(addresses.jackpot_ata, false),
+            AccountMeta::new_readonly(addresses.stake.0, false),
+            AccountMeta::new_readonly(addresses.fee.0, false),
+            AccountMeta::new_readonly(addresses.fee_ata, false),
+        ],
+        data: BondInstruction::AssertInvariants.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_assert_invariants() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    process(&mut context, vec![assert_invariants(&game)], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_assert_invariants_unfunded_reserve() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, 100).await;
+    let mut bond_state = state(&mut context, &game).await;
+    bond_state.reserved = 101;
+    set_state(&mut context, &game, &bond_state).await;
+    let result = process(&mut context, vec![assert_invariants(&game)], &[]).await;
+    assert_bond_error(result, 0, BondError::InvariantViolated);
+}
+
+#[tokio::test]
+async fn test_assert_invariants_unfunded_remainder() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let mut bond_state = state(&mut context, &game).await;
+    bond_state.split_remainder = 1;
+    set_state(&mut context, &game, &bond_state).await;
+    let result = process(&mut context, vec![assert_invariants(&game)], &[]).await;
+    assert_bond_error(result, 0, BondError::InvariantViolated);
+}
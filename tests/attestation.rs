@@ -0,0 +1,124 @@
+//! Integration tests of draws attested by the game's operator.
+
+mod common;
+
+use {
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, ConfigParams},
+        state::BondDraw,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        keccak,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        sysvar,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// The jackpot paid by the draw.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner.
+const BALANCE: u64 = 1_000;
+
+/// The seed of the draw.
+const DRAW_SEED: u64 = 1;
+
+/// A game requiring draws attested by an operator, with a funded jackpot and a holder.
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    operator: Keypair,
+    winner: Pubkey,
+    winner_ata: Pubkey,
+    draw: Pubkey,
+}
+
+/// Starts a game requiring attestations by a new operator.
+async fn start_attested() -> Setup {
+    let operator = Keypair::new();
+    let (mut context, game) = start(ConfigParams {
+        attestation_signer: Some(operator.pubkey()),
+        require_attestation: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let winner = Pubkey::new_unique();
+    let winner_ata = create_ata(&mut context, &game, &winner).await;
+    mint_to(&mut context, &game, &winner_ata, BALANCE).await;
+    let draw = create_draw_account(&mut context).await;
+    Setup { context, game, operator, winner, winner_ata, draw }
+}
+
+/// The attested message of the draw with `draw_seed`, followed by the operator's selection inputs.
+fn message(setup: &Setup, draw_seed: u64) -> Vec<u8> {
+    let mut message = setup.game.config().to_bytes().to_vec();
+    message.extend_from_slice(&draw_seed.to_le_bytes());
+    message.extend_from_slice(b"inputs");
+    message
+}
+
+/// A `Draw` instruction of the setup's holder with [DRAW_SEED].
+fn draw(setup: &Setup) -> Instruction {
+    let game = &setup.game;
+    instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &setup.winner,
+        &setup.winner_ata,
+        &setup.draw,
+        &[
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+        ],
+        42,
+        DRAW_SEED,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    )
+}
+
+#[tokio::test]
+async fn test_draw_attested() {
+    let mut setup = start_attested().await;
+    let message = message(&setup, DRAW_SEED);
+    let instructions = vec![ed25519_instruction(&setup.operator, &message), draw(&setup)];
+    process(&mut setup.context, instructions, &[&setup.game.draw_authority]).await.unwrap();
+
+    let data = account(&mut setup.context, &setup.draw).await.data;
+    let draw = BondDraw::unpack_from_slice(&data).unwrap();
+    assert_eq!(draw.attestation, Some(keccak::hash(&message).to_bytes()));
+    assert_eq!(balance(&mut setup.context, &setup.winner_ata).await, BALANCE + JACKPOT);
+}
+
+#[tokio::test]
+async fn test_draw_attestation_required() {
+    let mut setup = start_attested().await;
+    let instructions = vec![draw(&setup)];
+    let result = process(&mut setup.context, instructions, &[&setup.game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::AttestationRequired);
+
+    // Signatures of other keys aren't attestations.
+    let message = message(&setup, DRAW_SEED);
+    let instructions = vec![ed25519_instruction(&Keypair::new(), &message), draw(&setup)];
+    let result = process(&mut setup.context, instructions, &[&setup.game.draw_authority]).await;
+    assert_bond_error(result, 1, BondError::AttestationRequired);
+}
+
+#[tokio::test]
+async fn test_draw_attestation_of_another_draw() {
+    let mut setup = start_attested().await;
+    let message = message(&setup, DRAW_SEED + 1);
+    let instructions = vec![ed25519_instruction(&setup.operator, &message), draw(&setup)];
+    let result = process(&mut setup.context, instructions, &[&setup.game.draw_authority]).await;
+    assert_bond_error(result, 1, BondError::InvalidAttestation);
+}
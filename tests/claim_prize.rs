@@ -0,0 +1,205 @@
+//! Integration tests of the prizes escrowed by draws, pulled with `ClaimPrize` or released with 
+//! `ExpireClaim`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondDraw, BondPayoutMode},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        system_instruction,
+    },
+    spl_associated_token_account::get_associated_token_address,
+};
+
+/// The jackpot escrowed by the draw.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner.
+const BALANCE: u64 = 1_000;
+
+/// A game escrowing its prizes, whose first draw was won by a holder.
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    winner: Keypair,
+    draw: Pubkey,
+}
+
+/// Starts a game escrowing its prizes for `claim_window` and runs its first draw.
+async fn start_escrowed(claim_window: Option<i64>) -> Setup {
+    let (mut context, game) = start(ConfigParams {
+        payout_mode: Some(BondPayoutMode::Claim),
+        claim_window,
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let winner = Keypair::new();
+    let winner_ata = create_ata(&mut context, &game, &winner.pubkey()).await;
+    mint_to(&mut context, &game, &winner_ata, BALANCE).await;
+    let draw = create_draw_account(&mut context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &winner.pubkey(),
+        &winner_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    Setup { context, game, winner, draw }
+}
+
+/// Creates a token account of `owner` for the game's mint that isn't its associated one.
+async fn create_token_account(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    owner: &Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = vec![
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account3(
+            &spl_token::id(),
+            &account.pubkey(),
+            &game.token_mint,
+            owner,
+        ).unwrap(),
+    ];
+    process(context, instructions, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+/// An `ExpireClaim` instruction of the setup's draw.
+fn expire_claim(setup: &Setup) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(setup.game.config(), false),
+            AccountMeta::new(setup.game.state(), false),
+            AccountMeta::new(setup.draw, false),
+        ],
+        data: BondInstruction::ExpireClaim.try_to_vec().unwrap(),
+    }
+}
+
+/// Returns the setup's draw.
+async fn recorded_draw(setup: &mut Setup) -> BondDraw {
+    BondDraw::unpack_from_slice(&account(&mut setup.context, &setup.draw).await.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_claim_prize() {
+    let mut setup = start_escrowed(None).await;
+    assert_eq!(state(&mut setup.context, &setup.game).await.reserved, JACKPOT);
+
+    // The winner pulls the prize to any of its token accounts.
+    let game = &setup.game;
+    let winner = setup.winner.pubkey();
+    let destination = create_token_account(&mut setup.context, game, &winner).await;
+    let instruction = claim_prize(game, &setup.winner.pubkey(), &destination, &setup.draw, &[]);
+    process(&mut setup.context, vec![instruction], &[&setup.winner]).await.unwrap();
+
+    assert_eq!(balance(&mut setup.context, &destination).await, JACKPOT);
+    assert_eq!(balance(&mut setup.context, &setup.game.addresses.jackpot_ata).await, 0);
+    assert_eq!(state(&mut setup.context, &setup.game).await.reserved, 0);
+    assert!(recorded_draw(&mut setup).await.is_claimed);
+
+    // The prize is only paid once.
+    setup.context.get_new_latest_blockhash().await.unwrap();
+    let game = &setup.game;
+    let instruction = claim_prize(game, &setup.winner.pubkey(), &destination, &setup.draw, &[]);
+    let result = process(&mut setup.context, vec![instruction], &[&setup.winner]).await;
+    assert_bond_error(result, 0, BondError::PrizeAlreadyClaimed);
+}
+
+#[tokio::test]
+async fn test_claim_prize_of_another_receiver() {
+    let mut setup = start_escrowed(None).await;
+    let other = Keypair::new();
+    let other_ata = create_ata(&mut setup.context, &setup.game, &other.pubkey()).await;
+
+    let instruction = claim_prize(&setup.game, &other.pubkey(), &other_ata, &setup.draw, &[]);
+    let result = process(&mut setup.context, vec![instruction], &[&other]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+    assert!(!recorded_draw(&mut setup).await.is_claimed);
+}
+
+#[tokio::test]
+async fn test_claim_prize_to_another_owner() {
+    let mut setup = start_escrowed(None).await;
+    let other_ata = create_ata(&mut setup.context, &setup.game, &Pubkey::new_unique()).await;
+
+    // The winner signs, but the destination isn't its own.
+    let winner = setup.winner.pubkey();
+    let instruction = claim_prize(&setup.game, &winner, &other_ata, &setup.draw, &[]);
+    let result = process(&mut setup.context, vec![instruction], &[&setup.winner]).await;
+    assert_error(result, 0, InstructionError::IncorrectProgramId);
+    assert_eq!(balance(&mut setup.context, &other_ata).await, 0);
+}
+
+#[tokio::test]
+async fn test_expire_claim() {
+    let mut setup = start_escrowed(Some(0)).await;
+
+    // Anyone may release the expired prize, which stays in the jackpot for the next draw.
+    let instruction = expire_claim(&setup);
+    process(&mut setup.context, vec![instruction], &[]).await.unwrap();
+    assert!(recorded_draw(&mut setup).await.is_expired);
+    assert_eq!(state(&mut setup.context, &setup.game).await.reserved, 0);
+    let jackpot_ata = setup.game.addresses.jackpot_ata;
+    assert_eq!(balance(&mut setup.context, &jackpot_ata).await, JACKPOT);
+
+    // The expired prize can't be claimed nor released again.
+    let winner = setup.winner.pubkey();
+    let winner_ata = get_associated_token_address(&winner, &setup.game.token_mint);
+    let instruction = claim_prize(&setup.game, &winner, &winner_ata, &setup.draw, &[]);
+    let result = process(&mut setup.context, vec![instruction], &[&setup.winner]).await;
+    assert_bond_error(result, 0, BondError::ClaimExpired);
+    setup.context.get_new_latest_blockhash().await.unwrap();
+    let instruction = expire_claim(&setup);
+    let result = process(&mut setup.context, vec![instruction], &[]).await;
+    assert_bond_error(result, 0, BondError::PrizeAlreadyClaimed);
+}
+
+#[tokio::test]
+async fn test_expire_claim_not_expired() {
+    let mut setup = start_escrowed(Some(3_600)).await;
+    let instruction = expire_claim(&setup);
+    let result = process(&mut setup.context, vec![instruction], &[]).await;
+    assert_bond_error(result, 0, BondError::ClaimNotExpired);
+    assert_eq!(state(&mut setup.context, &setup.game).await.reserved, JACKPOT);
+
+    // Without a claim window, prizes never expire.
+    let mut setup = start_escrowed(None).await;
+    let instruction = expire_claim(&setup);
+    let result = process(&mut setup.context, vec![instruction], &[]).await;
+    assert_bond_error(result, 0, BondError::ClaimNotExpired);
+}
@@ -0,0 +1,168 @@
+//! Integration tests of the game's decommissioning: `CloseLottoShare` and `CloseLotto`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        constants::STREAM_SIZE,
+        error::BondError,
+        instruction::{BondInstruction, ConfigParams},
+        state::BondSeed,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+};
+
+/// The accounts closed by `CloseLottoShare`, in the order `CloseLotto` expects them.
+const SHARES: [BondSeed; 5] = [
+    BondSeed::Fee,
+    BondSeed::Equity,
+    BondSeed::Treasury,
+    BondSeed::Jackpot,
+    BondSeed::Stake,
+];
+
+/// A `CloseLottoShare` instruction draining the `seed` account to `destination`.
+fn close_lotto_share(game: &Game, seed: BondSeed, destination: &Pubkey) -> Instruction {
+    let (share, _) = seed.derive(&defi_bond::id(), &game.config());
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new(share, false),
+            AccountMeta::new(spl_associated_token_account::get_associated_token_address(
+                &share,
+                &game.token_mint,
+            ), false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: BondInstruction::CloseLottoShare { seed }.try_to_vec().unwrap(),
+    }
+}
+
+/// A `CloseLotto` instruction draining the state's token account to `destination`.
+fn close_lotto(game: &Game, destination: &Pubkey) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(game.authority.pubkey(), true),
+        AccountMeta::new(game.config(), false),
+        AccountMeta::new(game.state(), false),
+        AccountMeta::new(game.addresses.exclusion_list.0, false),
+    ];
+    accounts.extend(SHARES.iter().map(|seed| {
+        AccountMeta::new_readonly(seed.derive(&defi_bond::id(), &game.config()).0, false)
+    }));
+    accounts.extend([
+        AccountMeta::new(spl_associated_token_account::get_associated_token_address(
+            &game.state(),
+            &game.token_mint,
+        ), false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(game.token_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(BondSeed::Heartbeat.derive(&defi_bond::id(), &game.config()).0, false),
+    ]);
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts,
+        data: BondInstruction::CloseLotto.try_to_vec().unwrap(),
+    }
+}
+
+/// A `CreateStream` instruction locking `amount` treasury tokens, vested over 100 seconds.
+fn create_stream(context: &ProgramTestContext, game: &Game, amount: u64) -> Instruction {
+    let recipient = Pubkey::new_unique();
+    let (stream, bump) = BondSeed::Stream.derive_indexed(
+        &defi_bond::id(),
+        &game.config(),
+        recipient.as_ref(),
+    );
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.state(), false),
+            AccountMeta::new_readonly(game.addresses.treasury.0, false),
+            AccountMeta::new(game.addresses.treasury_ata, false),
+            AccountMeta::new(stream, false),
+            AccountMeta::new(spl_associated_token_account::get_associated_token_address(
+                &stream,
+                &game.token_mint,
+            ), false),
+            AccountMeta::new_readonly(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::CreateStream {
+            bump,
+            space: STREAM_SIZE as u32,
+            recipient,
+            rate: amount / 100,
+            duration: 100,
+        }.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_close_lotto() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    fund(&mut context, &game.authority.pubkey(), 1_000_000_000).await;
+    let destination = create_ata(&mut context, &game, &Pubkey::new_unique()).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, 500).await;
+    let state_ata = create_ata(&mut context, &game, &game.state()).await;
+    mint_to(&mut context, &game, &state_ata, 700).await;
+    pause(&mut context, &game).await;
+
+    for seed in SHARES {
+        let instruction = close_lotto_share(&game, seed, &destination);
+        process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    }
+    let instruction = close_lotto(&game, &destination);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    // The jackpot and the ticket holders' unclaimed rebates are drained before closing.
+    assert_eq!(balance(&mut context, &destination).await, 500 + 700);
+    for address in [game.config(), game.state(), state_ata] {
+        assert!(context.banks_client.get_account(address).await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_close_lotto_shares_first() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let destination = create_ata(&mut context, &game, &Pubkey::new_unique()).await;
+    pause(&mut context, &game).await;
+
+    let instruction = close_lotto(&game, &destination);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::AccountsNotClosed);
+}
+
+#[tokio::test]
+async fn test_close_lotto_open_stream() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let destination = create_ata(&mut context, &game, &Pubkey::new_unique()).await;
+    mint_to(&mut context, &game, &game.addresses.treasury_ata, 1_000).await;
+    let instruction = create_stream(&context, &game, 1_000);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    assert_eq!(state(&mut context, &game).await.open_streams, 1);
+    pause(&mut context, &game).await;
+
+    // The stream's unvested tokens return to the treasury when it's revoked.
+    let instruction = close_lotto_share(&game, BondSeed::Treasury, &destination);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::AccountsNotClosed);
+}
@@ -0,0 +1,610 @@
+//! Shared setup for the integration tests: a game created and initialized for a new token mint.
+
+#![allow(dead_code)]
+
+
+// Imports
+// -------------------------------------------------------------------------------------------------
+
+use {
+    borsh::BorshSerialize,
+    defi_bond::{
+        entrypoint,
+        error::BondError,
+        instruction::{
+            self,
+            BondAddresses,
+            BondInstruction,
+            ConfigParams,
+            InitializeParams,
+            ACCOUNT_COMPRESSION_PROGRAM_ID,
+            NOOP_PROGRAM_ID,
+            SWITCHBOARD_PROGRAM_ID,
+        },
+        state::{pack_account, BondDraw, BondSeed, BondSnapshotEntry, BondState},
+    },
+    solana_program::{
+        account_info::AccountInfo,
+        clock::Clock,
+        ed25519_program,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_instruction,
+        system_program,
+        sysvar,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        account::Account,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_associated_token_account::{
+        get_associated_token_address,
+        instruction::create_associated_token_account,
+    },
+};
+
+
+// Constants
+// -------------------------------------------------------------------------------------------------
+
+/// The decimals of the game's token mint.
+pub const DECIMALS: u8 = 9;
+
+/// The compute units of a transaction creating or initializing all the game's accounts.
+pub const COMPUTE_UNITS_CREATE: u32 = 1_000_000;
+
+/// The discriminator of Switchboard's `VrfAccountData`.
+pub const VRF_ACCOUNT_DISCRIMINATOR: [u8; 8] = [101, 35, 62, 239, 103, 151, 6, 18];
+
+/// The size of a Switchboard `VrfAccountData` account.
+pub const VRF_ACCOUNT_SIZE: usize = 29_058;
+
+/// The offsets of the fields of a `VrfAccountData` read by the program (from the end of the 
+/// account for the current round's).
+pub const VRF_STATUS_OFFSET: usize = 8;
+pub const VRF_AUTHORITY_OFFSET: usize = 25;
+pub const VRF_REQUEST_SLOT_END_OFFSET: usize = 8 + 8 + 32 + 4 + 256 + 1024;
+pub const VRF_RESULT_END_OFFSET: usize = 32 + 4 + 256 + 1024;
+
+/// The VRF status of a verified result (`StatusVerified`).
+pub const VRF_STATUS_VERIFIED: u8 = 3;
+
+/// The size of an account allocated for a merkle tree.
+pub const MERKLE_TREE_SIZE: usize = 1024;
+
+/// The VRF status of a request awaiting the oracle (`StatusRequesting`).
+pub const VRF_STATUS_REQUESTING: u8 = 1;
+
+
+// Game
+// -------------------------------------------------------------------------------------------------
+
+/// A game and its keys.
+pub struct Game {
+    pub config: Keypair,
+    pub authority: Keypair,
+    pub draw_authority: Keypair,
+    pub token_mint: Pubkey,
+    pub addresses: BondAddresses,
+}
+
+impl Game {
+
+    /// The game's config address.
+    pub fn config(&self) -> Pubkey {
+        self.config.pubkey()
+    }
+
+    /// The game's state address.
+    pub fn state(&self) -> Pubkey {
+        self.addresses.state.0
+    }
+
+    /// The game's jackpot address.
+    pub fn jackpot(&self) -> Pubkey {
+        self.addresses.jackpot.0
+    }
+}
+
+/// The program under test, run through its entrypoint, with a Switchboard program standing in for 
+/// the oracle, which writes its instruction data (a u32 LE offset followed by bytes) into the 
+/// first account if it owns it, a compression program standing in for SPL Account Compression, 
+/// which copies its instruction data to the start of the tree passed as its first account (if 
+/// writable, so that proofs always verify), and a noop program.
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "defi_bond",
+        defi_bond::id(),
+        processor!(entrypoint::process_instruction),
+    );
+    program_test.add_program("switchboard", SWITCHBOARD_PROGRAM_ID, processor!(switchboard));
+    program_test.add_program(
+        "compression",
+        ACCOUNT_COMPRESSION_PROGRAM_ID,
+        processor!(compression),
+    );
+    program_test.add_program("noop", NOOP_PROGRAM_ID, processor!(noop));
+    program_test
+}
+
+fn switchboard(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match (accounts.first(), data.get(..4)) {
+        (Some(account_info), Some(offset)) if account_info.owner == program_id => {
+            let offset = u32::from_le_bytes(offset.try_into().unwrap()) as usize;
+            let bytes = &data[4..];
+            account_info.data.borrow_mut()[offset..offset + bytes.len()].copy_from_slice(bytes);
+            Ok(())
+        },
+        _ => Ok(()),
+    }
+}
+
+fn compression(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if accounts[0].is_writable {
+        accounts[0].data.borrow_mut()[..data.len()].copy_from_slice(data);
+    }
+    Ok(())
+}
+
+fn noop(_program_id: &Pubkey, _accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    Ok(())
+}
+
+/// Starts a validator with a new token mint and a game created and initialized with `params`.
+pub async fn start(params: ConfigParams) -> (ProgramTestContext, Game) {
+    let mut context = program_test().start_with_context().await;
+    let game = create_game(&mut context, params).await;
+    (context, game)
+}
+
+/// Creates a token mint and a game for it, initialized with `params`.
+pub async fn create_game(context: &mut ProgramTestContext, params: ConfigParams) -> Game {
+    let token_mint = create_mint(context).await;
+    create_game_for_mint(context, token_mint, params).await
+}
+
+/// Creates a game for `token_mint`, initialized with `params`.
+pub async fn create_game_for_mint(
+    context: &mut ProgramTestContext,
+    token_mint: Pubkey,
+    params: ConfigParams,
+) -> Game {
+    create_game_for_token_program(context, token_mint, spl_token::id(), params).await
+}
+
+/// Creates a game for `token_mint`, owned by `token_program`, initialized with `params`.
+pub async fn create_game_for_token_program(
+    context: &mut ProgramTestContext,
+    token_mint: Pubkey,
+    token_program: Pubkey,
+    params: ConfigParams,
+) -> Game {
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let draw_authority = Keypair::new();
+    let addresses = BondAddresses::derive(
+        &defi_bond::id(),
+        &config.pubkey(),
+        &token_mint,
+        &token_program,
+    );
+    let payer = context.payer.pubkey();
+    let create = instruction::create(
+        &defi_bond::id(),
+        &payer,
+        &config.pubkey(),
+        &authority.pubkey(),
+        &token_mint,
+        &token_program,
+        None,
+        8,
+    );
+    let initialize = instruction::initialize(
+        &defi_bond::id(),
+        &payer,
+        &config.pubkey(),
+        &authority.pubkey(),
+        &draw_authority.pubkey(),
+        &token_mint,
+        &token_program,
+        InitializeParams {
+            exclusion_list_capacity: 8,
+            config: params,
+            ..InitializeParams::default()
+        },
+    );
+    for (instruction, signers) in [
+        (create, vec![&config, &authority]),
+        (initialize, vec![&config, &authority, &draw_authority]),
+    ] {
+        process(
+            context,
+            instruction::with_compute_budget(vec![instruction], &[COMPUTE_UNITS_CREATE], None),
+            &signers,
+        ).await.unwrap();
+    }
+    Game {
+        config,
+        authority,
+        draw_authority,
+        token_mint,
+        addresses,
+    }
+}
+
+/// Pauses the game.
+pub async fn pause(context: &mut ProgramTestContext, game: &Game) {
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new(game.config(), false),
+        ],
+        data: BondInstruction::Pause.try_to_vec().unwrap(),
+    };
+    process(context, vec![instruction], &[&game.authority]).await.unwrap();
+}
+
+
+// Transactions
+// -------------------------------------------------------------------------------------------------
+
+/// Processes `instructions` paid by the context's payer and signed by `signers`.
+pub async fn process(
+    context: &mut ProgramTestContext,
+    instructions: Vec<Instruction>,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    let mut keypairs = vec![&context.payer];
+    keypairs.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&context.payer.pubkey()),
+        &keypairs,
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Asserts that `result` failed in its instruction at `index` with `error`.
+pub fn assert_error(
+    result: Result<(), BanksClientError>,
+    index: u8,
+    error: InstructionError,
+) {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(i, e))) => {
+            assert_eq!((i, e), (index, error));
+        },
+        other => panic!("Expected {:?} at instruction {}, got {:?}", error, index, other),
+    }
+}
+
+/// Asserts that `result` failed in its instruction at `index` with the program's `error`.
+pub fn assert_bond_error(
+    result: Result<(), BanksClientError>,
+    index: u8,
+    error: BondError,
+) {
+    assert_error(result, index, InstructionError::Custom(error as u32));
+}
+
+/// Moves the validator to the first slot of `epoch`.
+pub async fn warp_to_epoch(context: &mut ProgramTestContext, epoch: u64) {
+    let slot = context.genesis_config().epoch_schedule.get_first_slot_in_epoch(epoch);
+    context.warp_to_slot(slot).unwrap();
+}
+
+/// An Ed25519 program instruction verifying `signer`'s signature of `message`, with the public key,
+/// signature and message in its own data.
+pub fn ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+    let public_key_offset: u16 = 16;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+    let mut data = vec![1, 0];
+    for offset in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signer.sign_message(message).as_ref());
+    data.extend_from_slice(message);
+    Instruction { program_id: ed25519_program::id(), accounts: vec![], data }
+}
+
+
+// Accounts
+// -------------------------------------------------------------------------------------------------
+
+/// Returns the account at `address`.
+pub async fn account(context: &mut ProgramTestContext, address: &Pubkey) -> Account {
+    context.banks_client.get_account(*address).await.unwrap().unwrap()
+}
+
+/// Returns the game's state.
+pub async fn state(context: &mut ProgramTestContext, game: &Game) -> BondState {
+    BondState::unpack_from_slice(&account(context, &game.state()).await.data).unwrap()
+}
+
+/// Overwrites the game's state.
+pub async fn set_state(context: &mut ProgramTestContext, game: &Game, state: &BondState) {
+    let mut account = account(context, &game.state()).await;
+    pack_account(state, &mut account.data).unwrap();
+    context.set_account(&game.state(), &account.into());
+}
+
+/// Creates an uninitialized account owned by the program for a draw's [BondDraw].
+pub async fn create_draw_account(context: &mut ProgramTestContext) -> Pubkey {
+    let draw = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instruction = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &draw.pubkey(),
+        rent.minimum_balance(BondDraw::LEN),
+        BondDraw::LEN as u64,
+        &defi_bond::id(),
+    );
+    process(context, vec![instruction], &[&draw]).await.unwrap();
+    draw.pubkey()
+}
+
+/// A `ClaimPrize` instruction signed by `receiver` paying the prize of `draw` to `receiver_ata`, 
+/// followed by the `remaining_accounts` the game's settings require (e.g. `hold`).
+pub fn claim_prize(
+    game: &Game,
+    receiver: &Pubkey,
+    receiver_ata: &Pubkey,
+    draw: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*receiver, true),
+        AccountMeta::new_readonly(game.config(), false),
+        AccountMeta::new(game.state(), false),
+        AccountMeta::new_readonly(game.jackpot(), false),
+        AccountMeta::new(game.addresses.jackpot_ata, false),
+        AccountMeta::new(*draw, false),
+        AccountMeta::new(*receiver_ata, false),
+        AccountMeta::new_readonly(game.token_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend_from_slice(remaining_accounts);
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts,
+        data: BondInstruction::ClaimPrize.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an account owned by `owner` for a merkle tree.
+pub async fn create_tree(context: &mut ProgramTestContext, owner: &Pubkey) -> Pubkey {
+    let tree = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instruction = system_instruction::create_account(
+        &context.payer.pubkey(),
+        &tree.pubkey(),
+        rent.minimum_balance(MERKLE_TREE_SIZE),
+        MERKLE_TREE_SIZE as u64,
+        owner,
+    );
+    process(context, vec![instruction], &[&tree]).await.unwrap();
+    tree.pubkey()
+}
+
+/// Transfers `lamports` from the context's payer to `to`.
+pub async fn fund(context: &mut ProgramTestContext, to: &Pubkey, lamports: u64) {
+    let instruction = system_instruction::transfer(&context.payer.pubkey(), to, lamports);
+    process(context, vec![instruction], &[]).await.unwrap();
+}
+
+/// Starts the current epoch's draw window `seconds` before the current time.
+pub async fn set_draw_window(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.epoch_start_timestamp = clock.unix_timestamp - seconds;
+    context.set_sysvar(&clock);
+}
+
+/// Returns the address of the snapshot of the draw `draw_id` and its bump.
+pub fn snapshot_address(game: &Game, draw_id: u64) -> (Pubkey, u8) {
+    BondSeed::Snapshot.derive_indexed(&defi_bond::id(), &game.config(), &draw_id.to_le_bytes())
+}
+
+/// A `WriteSnapshot` instruction appending `holders` of `weight` each, held since before the draw 
+/// window, to the snapshot of the draw `draw_id`.
+pub fn write_snapshot(game: &Game, draw_id: u64, holders: &[Pubkey], weight: u64) -> Instruction {
+    let (snapshot, bump) = snapshot_address(game, draw_id);
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(game.draw_authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new(snapshot, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::WriteSnapshot {
+            bump,
+            entries: holders.iter().map(|holder| BondSnapshotEntry {
+                wallet: *holder,
+                weight,
+                held_since: 0,
+            }).collect(),
+        }.try_to_vec().unwrap(),
+    }
+}
+
+/// Writes and seals the next draw's snapshot of `holders` of `weight` each, held since before 
+/// the draw window, and returns its address.
+pub async fn create_snapshot(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    holders: &[Pubkey],
+    weight: u64,
+) -> Pubkey {
+    let draw_id = state(context, game).await.draw_id + 1;
+    let snapshot = snapshot_address(game, draw_id).0;
+    let write_snapshot = write_snapshot(game, draw_id, holders, weight);
+    let finalize_snapshot = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.draw_authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new(snapshot, false),
+        ],
+        data: BondInstruction::FinalizeSnapshot.try_to_vec().unwrap(),
+    };
+    fund(context, &game.draw_authority.pubkey(), 1_000_000_000).await;
+    process(context, vec![write_snapshot, finalize_snapshot], &[&game.draw_authority])
+        .await
+        .unwrap();
+    snapshot
+}
+
+/// A `RequestRandomness` instruction for `vrf`, paid by the context's payer.
+#[allow(deprecated)]
+pub fn request_randomness(context: &ProgramTestContext, game: &Game, vrf: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.draw_authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.state(), false),
+            AccountMeta::new(*vrf, false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(sysvar::recent_blockhashes::id(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(SWITCHBOARD_PROGRAM_ID, false),
+        ],
+        data: BondInstruction::RequestRandomness {
+            permission_bump: 255,
+            program_state_bump: 255,
+        }.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates a Switchboard VRF account of `authority`.
+pub async fn create_vrf(context: &mut ProgramTestContext, authority: &Pubkey) -> Pubkey {
+    let vrf = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = vec![
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &vrf.pubkey(),
+            rent.minimum_balance(VRF_ACCOUNT_SIZE),
+            VRF_ACCOUNT_SIZE as u64,
+            &SWITCHBOARD_PROGRAM_ID,
+        ),
+        write_vrf(&vrf.pubkey(), 0, &VRF_ACCOUNT_DISCRIMINATOR),
+        write_vrf(&vrf.pubkey(), VRF_AUTHORITY_OFFSET, authority.as_ref()),
+    ];
+    process(context, instructions, &[&vrf]).await.unwrap();
+    vrf.pubkey()
+}
+
+/// Sets the `status`, the `result` and the slot of the current round's request of `vrf`.
+pub async fn set_vrf(
+    context: &mut ProgramTestContext,
+    vrf: &Pubkey,
+    status: u8,
+    request_slot: u64,
+    result: [u8; 32],
+) {
+    let instructions = vec![
+        write_vrf(vrf, VRF_STATUS_OFFSET, &[status]),
+        write_vrf(vrf, VRF_ACCOUNT_SIZE - VRF_REQUEST_SLOT_END_OFFSET, &request_slot.to_le_bytes()),
+        write_vrf(vrf, VRF_ACCOUNT_SIZE - VRF_RESULT_END_OFFSET, &result),
+    ];
+    process(context, instructions, &[]).await.unwrap();
+}
+
+fn write_vrf(vrf: &Pubkey, offset: usize, bytes: &[u8]) -> Instruction {
+    let mut data = (offset as u32).to_le_bytes().to_vec();
+    data.extend_from_slice(bytes);
+    Instruction::new_with_bytes(SWITCHBOARD_PROGRAM_ID, &data, vec![AccountMeta::new(*vrf, false)])
+}
+
+
+// Tokens
+// -------------------------------------------------------------------------------------------------
+
+/// Creates a token mint of [DECIMALS] whose authority is the context's payer.
+pub async fn create_mint(context: &mut ProgramTestContext) -> Pubkey {
+    create_mint_with_decimals(context, DECIMALS).await
+}
+
+/// Creates a token mint of `decimals` whose authority is the context's payer.
+pub async fn create_mint_with_decimals(context: &mut ProgramTestContext, decimals: u8) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = vec![
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &payer,
+            None,
+            decimals,
+        ).unwrap(),
+    ];
+    process(context, instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Creates the associated token account of `owner` for the game's mint.
+pub async fn create_ata(context: &mut ProgramTestContext, game: &Game, owner: &Pubkey) -> Pubkey {
+    let instruction = create_associated_token_account(
+        &context.payer.pubkey(),
+        owner,
+        &game.token_mint,
+        &spl_token::id(),
+    );
+    process(context, vec![instruction], &[]).await.unwrap();
+    get_associated_token_address(owner, &game.token_mint)
+}
+
+/// Mints `amount` of the game's token to the token account `ata`.
+pub async fn mint_to(context: &mut ProgramTestContext, game: &Game, ata: &Pubkey, amount: u64) {
+    let instruction = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &game.token_mint,
+        ata,
+        &context.payer.pubkey(),
+        &[],
+        amount,
+    ).unwrap();
+    process(context, vec![instruction], &[]).await.unwrap();
+}
+
+/// Returns the balance of the token account `ata` (of either token program, ignoring the 
+/// extensions of Token-2022 accounts).
+pub async fn balance(context: &mut ProgramTestContext, ata: &Pubkey) -> u64 {
+    let data = account(context, ata).await.data;
+    spl_token::state::Account::unpack_from_slice(&data).unwrap().amount
+}
@@ -0,0 +1,194 @@
+//! Integration tests of `CompoundStake`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        adapter::MARINADE_PROGRAM_ID,
+        instruction::{BondInstruction, ConfigParams},
+        state::{BondLstAdapter, BondSeed},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::{account::Account, signature::Signer},
+    spl_stake_pool::{
+        find_withdraw_authority_program_address,
+        state::{AccountType, StakePool},
+    },
+    spl_token::instruction::AuthorityType,
+};
+
+/// The lamports held by the stake vault above its rent exemption.
+const VAULT: u64 = 1_000_000_000;
+
+/// The lamports held by the stake account above rent exemption.
+const EXCESS: u64 = 5_000;
+
+/// A Stake Pool and the accounts passed along with it.
+struct Pool {
+    program: Pubkey,
+    address: Pubkey,
+    withdraw_authority: Pubkey,
+    reserve_stake: Pubkey,
+    manager_fee: Pubkey,
+}
+
+/// A `CompoundStake` instruction of `game` depositing its stake vault into `pool`.
+fn compound_stake(game: &Game, pool: &Pool) -> Instruction {
+    let (stake_vault, vault_bump) = BondSeed::StakeVault.derive(&defi_bond::id(), &game.config());
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.stake.0, false),
+            AccountMeta::new(game.addresses.stake_ata, false),
+            AccountMeta::new(stake_vault, false),
+            AccountMeta::new_readonly(pool.program, false),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(pool.withdraw_authority, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new(pool.manager_fee, false),
+        ],
+        data: BondInstruction::CompoundStake { vault_bump }.try_to_vec().unwrap(),
+    }
+}
+
+/// Sets the account at `address`, owned by `owner`, to `data`.
+fn set_account(context: &mut ProgramTestContext, address: &Pubkey, owner: &Pubkey, data: Vec<u8>) {
+    let account = Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(address, &account.into());
+}
+
+/// Starts a game of `lst_adapter` along with the SPL Stake Pool program.
+async fn start_compounding(lst_adapter: BondLstAdapter) -> (ProgramTestContext, Game) {
+    let mut program_test = program_test();
+    program_test.add_program(
+        "spl_stake_pool",
+        spl_stake_pool::id(),
+        processor!(spl_stake_pool::processor::Processor::process),
+    );
+    let mut context = program_test.start_with_context().await;
+    let game = create_game(&mut context, ConfigParams {
+        lst_adapter: Some(lst_adapter),
+        ..ConfigParams::default()
+    }).await;
+    (context, game)
+}
+
+/// Creates a Stake Pool minting the game's token, without fees, and hands it the mint authority.
+async fn create_pool(context: &mut ProgramTestContext, game: &Game) -> Pool {
+    let address = Pubkey::new_unique();
+    let (withdraw_authority, stake_withdraw_bump_seed) =
+        find_withdraw_authority_program_address(&spl_stake_pool::id(), &address);
+    let pool = Pool {
+        program: spl_stake_pool::id(),
+        address,
+        withdraw_authority,
+        reserve_stake: Pubkey::new_unique(),
+        manager_fee: create_ata(context, game, &Pubkey::new_unique()).await,
+    };
+    let stake_pool = StakePool {
+        account_type: AccountType::StakePool,
+        stake_withdraw_bump_seed,
+        reserve_stake: pool.reserve_stake,
+        pool_mint: game.token_mint,
+        manager_fee_account: pool.manager_fee,
+        token_program_id: spl_token::id(),
+        ..StakePool::default()
+    };
+    let mut data = stake_pool.try_to_vec().unwrap();
+    data.resize(1_000, 0);
+    set_account(context, &pool.address, &pool.program, data);
+    set_account(context, &pool.reserve_stake, &system_program::id(), vec![]);
+
+    let instruction = spl_token::instruction::set_authority(
+        &spl_token::id(),
+        &game.token_mint,
+        Some(&pool.withdraw_authority),
+        AuthorityType::MintTokens,
+        &context.payer.pubkey(),
+        &[],
+    ).unwrap();
+    process(context, vec![instruction], &[]).await.unwrap();
+    pool
+}
+
+#[tokio::test]
+async fn test_compound_stake() {
+    let (mut context, game) = start_compounding(BondLstAdapter::SplStakePool).await;
+    let pool = create_pool(&mut context, &game).await;
+    let vault_rent = context.banks_client.get_rent().await.unwrap().minimum_balance(0);
+    let (stake_vault, _) = BondSeed::StakeVault.derive(&defi_bond::id(), &game.config());
+    fund(&mut context, &stake_vault, vault_rent + VAULT).await;
+    fund(&mut context, &game.addresses.stake.0, EXCESS).await;
+    let stake_lamports = account(&mut context, &game.addresses.stake.0).await.lamports;
+
+    // The vault is deposited into the pool, minting to the stake, then the stake's excess 
+    // lamports are swept into the vault.
+    let instruction = compound_stake(&game, &pool);
+    process(&mut context, vec![instruction], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &game.addresses.stake_ata).await, VAULT);
+    let reserve_stake = account(&mut context, &pool.reserve_stake).await;
+    assert_eq!(reserve_stake.lamports, 1_000_000_000 + VAULT);
+    assert_eq!(account(&mut context, &stake_vault).await.lamports, vault_rent + EXCESS);
+    let stake_account = account(&mut context, &game.addresses.stake.0).await;
+    assert_eq!(stake_account.lamports, stake_lamports - EXCESS);
+}
+
+#[tokio::test]
+async fn test_compound_stake_empty() {
+    let (mut context, game) = start_compounding(BondLstAdapter::SplStakePool).await;
+    fund(&mut context, &game.addresses.stake.0, EXCESS).await;
+
+    // Without lamports to deposit, the pool isn't read, and the stake's excess lamports stay put
+    // as the vault couldn't be rent exempt.
+    let pool = Pool {
+        program: spl_stake_pool::id(),
+        address: Pubkey::new_unique(),
+        withdraw_authority: Pubkey::new_unique(),
+        reserve_stake: Pubkey::new_unique(),
+        manager_fee: Pubkey::new_unique(),
+    };
+    let stake_lamports = account(&mut context, &game.addresses.stake.0).await.lamports;
+    let instruction = compound_stake(&game, &pool);
+    process(&mut context, vec![instruction], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &game.addresses.stake_ata).await, 0);
+    let stake_account = account(&mut context, &game.addresses.stake.0).await;
+    assert_eq!(stake_account.lamports, stake_lamports);
+}
+
+#[tokio::test]
+async fn test_compound_stake_other_adapter() {
+    let (mut context, game) = start_compounding(BondLstAdapter::Marinade).await;
+    let pool = Pool {
+        program: MARINADE_PROGRAM_ID,
+        address: Pubkey::new_unique(),
+        withdraw_authority: Pubkey::new_unique(),
+        reserve_stake: Pubkey::new_unique(),
+        manager_fee: Pubkey::new_unique(),
+    };
+    set_account(&mut context, &pool.address, &pool.program, vec![0; 136]);
+    let (stake_vault, _) = BondSeed::StakeVault.derive(&defi_bond::id(), &game.config());
+    fund(&mut context, &stake_vault, VAULT).await;
+
+    // Only SPL stake pools take SOL deposits.
+    let instruction = compound_stake(&game, &pool);
+    let result = process(&mut context, vec![instruction], &[]).await;
+    assert_error(result, 0, InstructionError::InvalidArgument);
+    assert_eq!(account(&mut context, &stake_vault).await.lamports, VAULT);
+}
@@ -0,0 +1,144 @@
+//! Integration tests of `CrankTick`.
+
+mod common;
+
+use {
+    common::*,
+    defi_bond::{
+        adapter::MARINADE_PROGRAM_ID,
+        instruction::{self, ConfigParams},
+        state::BondLstAdapter,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// The epoch fee minted to the fee account before a tick.
+const EPOCH_FEE: u64 = 1_000;
+
+/// A Marinade game whose draws are fulfilled from VRF results.
+async fn start_vrf(allow_permissionless_draw: bool) -> (ProgramTestContext, Game) {
+    start_with(ConfigParams {
+        allow_permissionless_draw: Some(allow_permissionless_draw),
+        ..ConfigParams::default()
+    }).await
+}
+
+/// A Marinade game whose draws are fulfilled from VRF results, with the other `params`.
+async fn start_with(params: ConfigParams) -> (ProgramTestContext, Game) {
+    start(ConfigParams {
+        lst_adapter: Some(BondLstAdapter::Marinade),
+        require_vrf: Some(true),
+        snapshot_draws: Some(true),
+        ..params
+    }).await
+}
+
+/// A `CrankTick` instruction of `cranker` requesting the randomness of the draw of `snapshot` 
+/// from `vrf`.
+fn crank_tick(
+    context: &ProgramTestContext,
+    game: &Game,
+    cranker: &Pubkey,
+    snapshot: &Pubkey,
+    vrf: &Pubkey,
+) -> Instruction {
+    let mut randomness_accounts = vec![AccountMeta::new_readonly(*snapshot, false)];
+    randomness_accounts.extend_from_slice(&request_randomness(context, game, vrf).accounts[3..]);
+    instruction::crank_tick(
+        &defi_bond::id(),
+        cranker,
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &MARINADE_PROGRAM_ID,
+        &Pubkey::new_unique(),
+        &[],
+        &randomness_accounts,
+        255,
+        255,
+    )
+}
+
+#[tokio::test]
+async fn test_crank_tick_requests_randomness() {
+    let (mut context, game) = start_vrf(true).await;
+    let snapshot = create_snapshot(&mut context, &game, &[Pubkey::new_unique()], 1_000).await;
+    let vrf = create_vrf(&mut context, &game.state()).await;
+    let cranker = Keypair::new();
+
+    let instruction = crank_tick(&context, &game, &cranker.pubkey(), &snapshot, &vrf);
+    process(&mut context, vec![instruction], &[&cranker]).await.unwrap();
+    let requested = state(&mut context, &game).await;
+    assert_eq!(requested.vrf, vrf);
+    assert_ne!(requested.vrf_request_slot, 0);
+
+    // The pending request is left to `FulfillDraw`.
+    context.get_new_latest_blockhash().await.unwrap();
+    let instruction = crank_tick(&context, &game, &cranker.pubkey(), &snapshot, &vrf);
+    process(&mut context, vec![instruction], &[&cranker]).await.unwrap();
+    assert_eq!(state(&mut context, &game).await, requested);
+}
+
+#[tokio::test]
+async fn test_crank_tick_requires_snapshot() {
+    let (mut context, game) = start_vrf(true).await;
+    let vrf = create_vrf(&mut context, &game.state()).await;
+    let cranker = Keypair::new();
+
+    // The tick waits for the snapshot to be written and sealed.
+    let snapshot = snapshot_address(&game, 1).0;
+    let instruction = crank_tick(&context, &game, &cranker.pubkey(), &snapshot, &vrf);
+    process(&mut context, vec![instruction], &[&cranker]).await.unwrap();
+    assert_eq!(state(&mut context, &game).await.vrf_request_slot, 0);
+
+    create_snapshot(&mut context, &game, &[Pubkey::new_unique()], 1_000).await;
+    let instruction = crank_tick(&context, &game, &cranker.pubkey(), &snapshot, &vrf);
+    process(&mut context, vec![instruction], &[&cranker]).await.unwrap();
+    assert_eq!(state(&mut context, &game).await.vrf, vrf);
+}
+
+#[tokio::test]
+async fn test_crank_tick_permissioned_draw() {
+    let (mut context, game) = start_vrf(false).await;
+    let snapshot = create_snapshot(&mut context, &game, &[Pubkey::new_unique()], 1_000).await;
+    let vrf = create_vrf(&mut context, &game.state()).await;
+
+    // Only the draw authority may request the randomness.
+    let cranker = Keypair::new();
+    let instruction = crank_tick(&context, &game, &cranker.pubkey(), &snapshot, &vrf);
+    process(&mut context, vec![instruction], &[&cranker]).await.unwrap();
+    assert_eq!(state(&mut context, &game).await.vrf_request_slot, 0);
+
+    let cranker = &game.draw_authority;
+    let instruction = crank_tick(&context, &game, &cranker.pubkey(), &snapshot, &vrf);
+    process(&mut context, vec![instruction], &[cranker]).await.unwrap();
+    assert_eq!(state(&mut context, &game).await.vrf, vrf);
+}
+
+#[tokio::test]
+async fn test_crank_tick_skips_undue_split() {
+    let (mut context, game) = start_with(ConfigParams {
+        split_once_per_epoch: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    let snapshot = snapshot_address(&game, 1).0;
+    let vrf = create_vrf(&mut context, &game.state()).await;
+    let cranker = &game.draw_authority;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+    let instruction = crank_tick(&context, &game, &cranker.pubkey(), &snapshot, &vrf);
+    process(&mut context, vec![instruction], &[cranker]).await.unwrap();
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, 800);
+
+    // A split already ran in this epoch, so the next tick leaves the fee account as is.
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+    context.get_new_latest_blockhash().await.unwrap();
+    let instruction = crank_tick(&context, &game, &cranker.pubkey(), &snapshot, &vrf);
+    process(&mut context, vec![instruction], &[cranker]).await.unwrap();
+    assert_eq!(balance(&mut context, &game.addresses.fee_ata).await, EPOCH_FEE);
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, 800);
+}
@@ -0,0 +1,509 @@
+//! Integration tests of `Create`, `Initialize` and the per-account `Create*` instructions.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{
+            self,
+            BondAddresses,
+            BondInstruction,
+            ConfigParams,
+            DEFAULT_EPOCHS_PER_DRAW,
+            DEFAULT_ODDS_THRESHOLD,
+            InitializeParams,
+        },
+        state::{BondConfig, BondExclusionList, BondFee, BondSeed, BondShare, BondState},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_instruction,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        account::Account,
+        signature::{Keypair, Signer},
+    },
+};
+
+/// A `Create` instruction for a new game and its addresses.
+fn create(
+    context: &ProgramTestContext,
+    config: &Keypair,
+    authority: &Keypair,
+    token_mint: &Pubkey,
+) -> (Vec<Instruction>, BondAddresses) {
+    let instruction = instruction::create(
+        &defi_bond::id(),
+        &context.payer.pubkey(),
+        &config.pubkey(),
+        &authority.pubkey(),
+        token_mint,
+        &spl_token::id(),
+        None,
+        8,
+    );
+    let addresses = BondAddresses::derive(
+        &defi_bond::id(),
+        &config.pubkey(),
+        token_mint,
+        &spl_token::id(),
+    );
+    let instructions = instruction::with_compute_budget(
+        vec![instruction],
+        &[COMPUTE_UNITS_CREATE],
+        None,
+    );
+    (instructions, addresses)
+}
+
+#[tokio::test]
+async fn test_create_and_initialize() {
+    let (mut context, game) = start(ConfigParams {
+        max_rollover: Some(5),
+        ..ConfigParams::default()
+    }).await;
+
+    // The settings left unset take their defaults.
+    let config = BondConfig::unpack_from_slice(&account(&mut context, &game.config()).await.data)
+        .unwrap();
+    assert!(config.is_active);
+    assert_eq!(config.authority, game.authority.pubkey());
+    assert_eq!(config.draw_authority, game.draw_authority.pubkey());
+    assert_eq!(config.token_mint, game.token_mint);
+    assert_eq!(config.decimals, DECIMALS);
+    assert_eq!(config.max_rollover, 5);
+    assert_eq!(config.epochs_per_draw, DEFAULT_EPOCHS_PER_DRAW);
+    assert_eq!(config.odds_threshold_numerator, DEFAULT_ODDS_THRESHOLD.numerator);
+    assert_eq!(config.odds_threshold_denominator, DEFAULT_ODDS_THRESHOLD.denominator);
+
+    let state = state(&mut context, &game).await;
+    assert_eq!(state.draw_id, 0);
+    assert_eq!(state.bump, game.addresses.state.1);
+
+    let fee = account(&mut context, &game.addresses.fee.0).await;
+    assert_eq!(fee.owner, defi_bond::id());
+    assert_eq!(fee.data.len(), BondFee::LEN);
+    for (share, ata) in [
+        (game.addresses.equity.0, game.addresses.equity_ata),
+        (game.addresses.treasury.0, game.addresses.treasury_ata),
+        (game.addresses.jackpot.0, game.addresses.jackpot_ata),
+        (game.addresses.stake.0, game.addresses.stake_ata),
+    ] {
+        assert_eq!(account(&mut context, &share).await.data.len(), BondShare::LEN);
+        let ata = spl_token::state::Account::unpack(&account(&mut context, &ata).await.data)
+            .unwrap();
+        assert_eq!(ata.owner, share);
+        assert_eq!(ata.mint, game.token_mint);
+    }
+}
+
+#[tokio::test]
+async fn test_create_account_sizes() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let (instructions, addresses) = create(&context, &config, &authority, &token_mint);
+    process(&mut context, instructions, &[&config, &authority]).await.unwrap();
+
+    // The accounts are allocated to their layouts' sizes, the exclusion list to its capacity.
+    let sizes = [
+        (config.pubkey(), BondConfig::LEN),
+        (addresses.state.0, BondState::LEN),
+        (addresses.exclusion_list.0, BondExclusionList::space(8)),
+    ];
+    for (address, size) in sizes {
+        assert_eq!(account(&mut context, &address).await.data.len(), size);
+    }
+}
+
+#[tokio::test]
+async fn test_create_is_idempotent() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let (instructions, addresses) = create(&context, &config, &authority, &token_mint);
+    process(&mut context, instructions.clone(), &[&config, &authority]).await.unwrap();
+
+    // A retry skips the existing accounts.
+    context.get_new_latest_blockhash().await.unwrap();
+    process(&mut context, instructions, &[&config, &authority]).await.unwrap();
+    let state = account(&mut context, &addresses.state.0).await;
+    assert_eq!(state.owner, defi_bond::id());
+    assert_eq!(state.data.len(), BondState::LEN);
+}
+
+#[tokio::test]
+async fn test_create_prefunded_account() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let (instructions, addresses) = create(&context, &config, &authority, &token_mint);
+
+    // Lamports sent to the state PDA ahead of its creation don't block it.
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let transfer = system_instruction::transfer(
+        &context.payer.pubkey(),
+        &addresses.state.0,
+        rent.minimum_balance(0),
+    );
+    process(&mut context, vec![transfer], &[]).await.unwrap();
+    process(&mut context, instructions, &[&config, &authority]).await.unwrap();
+
+    let state = account(&mut context, &addresses.state.0).await;
+    assert_eq!(state.owner, defi_bond::id());
+    assert_eq!(state.data.len(), BondState::LEN);
+    assert_eq!(state.lamports, rent.minimum_balance(BondState::LEN));
+}
+
+#[tokio::test]
+async fn test_create_conflicting_account() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let (instructions, addresses) = create(&context, &config, &authority, &token_mint);
+
+    // An account of another size at the state PDA is rejected.
+    let conflict = Account {
+        lamports: 1_000_000_000,
+        data: vec![0; 8],
+        owner: defi_bond::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&addresses.state.0, &conflict.into());
+    let result = process(&mut context, instructions, &[&config, &authority]).await;
+    assert_bond_error(result, 1, BondError::AccountConflict);
+}
+
+#[tokio::test]
+async fn test_create_and_initialize_per_account() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let draw_authority = Keypair::new();
+    let addresses = BondAddresses::derive(
+        &defi_bond::id(),
+        &config.pubkey(),
+        &token_mint,
+        &spl_token::id(),
+    );
+    let payer = context.payer.pubkey();
+    let instruction = |accounts: Vec<AccountMeta>, data: BondInstruction| {
+        let mut metas = vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new_readonly(config.pubkey(), false),
+        ];
+        metas.extend(accounts);
+        Instruction {
+            program_id: defi_bond::id(),
+            accounts: metas,
+            data: data.try_to_vec().unwrap(),
+        }
+    };
+    let token_accounts = |share: Pubkey, ata: Pubkey| vec![
+        AccountMeta::new(share, false),
+        AccountMeta::new(ata, false),
+        AccountMeta::new_readonly(token_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    // Each account is created and initialized by its own instruction.
+    let mut instructions = vec![
+        Instruction {
+            program_id: defi_bond::id(),
+            accounts: vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(config.pubkey(), true),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(draw_authority.pubkey(), true),
+                AccountMeta::new_readonly(token_mint, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            data: BondInstruction::CreateAndInitializeConfig {
+                space: BondConfig::LEN as u32,
+                params: ConfigParams::default(),
+            }.try_to_vec().unwrap(),
+        },
+        instruction(
+            vec![
+                AccountMeta::new(addresses.state.0, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            BondInstruction::CreateAndInitializeState {
+                bump: addresses.state.1,
+                space: BondState::LEN as u32,
+            },
+        ),
+        instruction(
+            token_accounts(addresses.fee.0, addresses.fee_ata),
+            BondInstruction::CreateAndInitializeFee {
+                bump: addresses.fee.1,
+                space: BondFee::LEN as u32,
+            },
+        ),
+        instruction(
+            vec![
+                AccountMeta::new(addresses.exclusion_list.0, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            BondInstruction::CreateAndInitializeExclusionList {
+                bump: addresses.exclusion_list.1,
+                space: BondExclusionList::space(8) as u32,
+                capacity: 8,
+                accounts: vec![],
+            },
+        ),
+    ];
+    for (seed, (share, bump), ata) in [
+        (BondSeed::Equity, addresses.equity, addresses.equity_ata),
+        (BondSeed::Treasury, addresses.treasury, addresses.treasury_ata),
+        (BondSeed::Jackpot, addresses.jackpot, addresses.jackpot_ata),
+        (BondSeed::Stake, addresses.stake, addresses.stake_ata),
+    ] {
+        instructions.push(instruction(
+            token_accounts(share, ata),
+            BondInstruction::CreateAndInitializeShare {
+                seed,
+                bump,
+                space: BondShare::LEN as u32,
+                share: None,
+            },
+        ));
+    }
+    let create_config = instructions.remove(0);
+    process(&mut context, vec![create_config], &[&config, &authority, &draw_authority])
+        .await
+        .unwrap();
+    for instruction in instructions {
+        process(&mut context, vec![instruction], &[&authority]).await.unwrap();
+    }
+
+    let state = account(&mut context, &addresses.state.0).await;
+    assert_eq!(BondState::unpack_from_slice(&state.data).unwrap().bump, addresses.state.1);
+    let jackpot = account(&mut context, &addresses.jackpot.0).await;
+    assert_eq!(BondShare::unpack_from_slice(&jackpot.data).unwrap().bump, addresses.jackpot.1);
+    let stake_ata = account(&mut context, &addresses.stake_ata).await;
+    let stake_ata = spl_token::state::Account::unpack(&stake_ata.data).unwrap();
+    assert_eq!(stake_ata.owner, addresses.stake.0);
+}
+
+#[tokio::test]
+async fn test_create_config_pda() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let authority = Keypair::new();
+    let draw_authority = Keypair::new();
+    let (config, bump) = Pubkey::find_program_address(
+        &[BondSeed::Config.as_bytes(), authority.pubkey().as_ref(), token_mint.as_ref()],
+        &defi_bond::id(),
+    );
+    let payer = context.payer.pubkey();
+
+    // The config PDA is signed for by the program, so only the authorities sign.
+    let create = instruction::create(
+        &defi_bond::id(),
+        &payer,
+        &config,
+        &authority.pubkey(),
+        &token_mint,
+        &spl_token::id(),
+        Some(bump),
+        8,
+    );
+    let initialize = instruction::initialize(
+        &defi_bond::id(),
+        &payer,
+        &config,
+        &authority.pubkey(),
+        &draw_authority.pubkey(),
+        &token_mint,
+        &spl_token::id(),
+        InitializeParams {
+            exclusion_list_capacity: 8,
+            config: ConfigParams { bump: Some(bump), ..ConfigParams::default() },
+            ..InitializeParams::default()
+        },
+    );
+    for (instruction, signers) in [
+        (create, vec![&authority]),
+        (initialize, vec![&authority, &draw_authority]),
+    ] {
+        process(
+            &mut context,
+            instruction::with_compute_budget(vec![instruction], &[COMPUTE_UNITS_CREATE], None),
+            &signers,
+        ).await.unwrap();
+    }
+
+    let config = account(&mut context, &config).await;
+    assert_eq!(config.owner, defi_bond::id());
+    let config = BondConfig::unpack_from_slice(&config.data).unwrap();
+    assert_eq!(config.bump, Some(bump));
+    assert!(config.is_active);
+}
+
+#[tokio::test]
+async fn test_create_config_pda_of_another_authority() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let authority = Keypair::new();
+    let config = Pubkey::find_program_address(
+        &[BondSeed::Config.as_bytes(), Pubkey::new_unique().as_ref(), token_mint.as_ref()],
+        &defi_bond::id(),
+    ).0;
+
+    // The bump is valid for the authority's own config, which isn't `config`.
+    let bump = Pubkey::find_program_address(
+        &[BondSeed::Config.as_bytes(), authority.pubkey().as_ref(), token_mint.as_ref()],
+        &defi_bond::id(),
+    ).1;
+    let instruction = instruction::create(
+        &defi_bond::id(),
+        &context.payer.pubkey(),
+        &config,
+        &authority.pubkey(),
+        &token_mint,
+        &spl_token::id(),
+        Some(bump),
+        8,
+    );
+    let instructions =
+        instruction::with_compute_budget(vec![instruction], &[COMPUTE_UNITS_CREATE], None);
+    let result = process(&mut context, instructions, &[&authority]).await;
+    assert_error(result, 1, InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn test_create_paid_by_payer() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+
+    // The authorities only sign, the rent is paid by the context's payer.
+    for authority in [game.authority.pubkey(), game.draw_authority.pubkey()] {
+        assert_eq!(context.banks_client.get_account(authority).await.unwrap(), None);
+    }
+    assert_eq!(account(&mut context, &game.config()).await.owner, defi_bond::id());
+}
+
+#[tokio::test]
+async fn test_create_per_account() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let (instructions, addresses) = create(&context, &config, &authority, &token_mint);
+    let payer = context.payer.pubkey();
+    let create_accounts = |accounts: Vec<AccountMeta>| {
+        let mut metas = vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config.pubkey(), true),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+        ];
+        metas.extend(accounts);
+        metas
+    };
+
+    // The state, the fee and shares, and the exclusion list are created one by one.
+    let mut split = vec![Instruction {
+        program_id: defi_bond::id(),
+        accounts: create_accounts(vec![
+            AccountMeta::new(addresses.state.0, false),
+            AccountMeta::new_readonly(token_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]),
+        data: BondInstruction::CreateState {
+            config_bump: None,
+            bump: addresses.state.1,
+        }.try_to_vec().unwrap(),
+    }];
+    for (seed, (share, bump), ata) in [
+        (BondSeed::Fee, addresses.fee, addresses.fee_ata),
+        (BondSeed::Equity, addresses.equity, addresses.equity_ata),
+        (BondSeed::Treasury, addresses.treasury, addresses.treasury_ata),
+        (BondSeed::Jackpot, addresses.jackpot, addresses.jackpot_ata),
+        (BondSeed::Stake, addresses.stake, addresses.stake_ata),
+    ] {
+        split.push(Instruction {
+            program_id: defi_bond::id(),
+            accounts: create_accounts(vec![
+                AccountMeta::new(share, false),
+                AccountMeta::new(ata, false),
+                AccountMeta::new_readonly(token_mint, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ]),
+            data: BondInstruction::CreateShare {
+                config_bump: None,
+                seed,
+                bump,
+            }.try_to_vec().unwrap(),
+        });
+    }
+    split.push(Instruction {
+        program_id: defi_bond::id(),
+        accounts: create_accounts(vec![
+            AccountMeta::new(addresses.exclusion_list.0, false),
+            AccountMeta::new_readonly(token_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]),
+        data: BondInstruction::CreateExclusionList {
+            config_bump: None,
+            bump: addresses.exclusion_list.1,
+            capacity: 8,
+        }.try_to_vec().unwrap(),
+    });
+    for instruction in split {
+        process(&mut context, vec![instruction], &[&config, &authority]).await.unwrap();
+    }
+    assert_eq!(account(&mut context, &addresses.fee.0).await.data.len(), BondFee::LEN);
+    assert_eq!(account(&mut context, &addresses.stake.0).await.data.len(), BondShare::LEN);
+
+    // `Create` then only creates the config.
+    process(&mut context, instructions, &[&config, &authority]).await.unwrap();
+    assert_eq!(account(&mut context, &config.pubkey()).await.data.len(), BondConfig::LEN);
+}
+
+#[tokio::test]
+async fn test_create_share_rejects_other_seeds() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let (state, bump) = BondSeed::State.derive(&defi_bond::id(), &config.pubkey());
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(config.pubkey(), true),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new(state, false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::CreateShare {
+            config_bump: None,
+            seed: BondSeed::State,
+            bump,
+        }.try_to_vec().unwrap(),
+    };
+    let result = process(&mut context, vec![instruction], &[&config, &authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidSeeds);
+}
@@ -0,0 +1,492 @@
+//! Integration tests of `Draw`.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, BondInstruction, ConfigParams, COMPUTE_UNITS_DRAW},
+        state::{BondAccount, BondDraw, BondPayoutMode},
+    },
+    solana_program::{
+        clock::Clock,
+        instruction::{AccountMeta, Instruction},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+
+/// The jackpot paid by the draws.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of each holder.
+const BALANCE: u64 = 1_000;
+
+/// The total weight of the draws, at the default odds threshold (10%) of a holder.
+const TOTAL_WEIGHT: u64 = 10 * BALANCE;
+
+/// A game whose jackpot holds [JACKPOT].
+async fn start_jackpot(params: ConfigParams) -> (ProgramTestContext, Game) {
+    let (mut context, game) = start(params).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    (context, game)
+}
+
+/// A holder of [BALANCE] and its token account.
+async fn holder(context: &mut ProgramTestContext, game: &Game) -> (Pubkey, Pubkey) {
+    let holder = Pubkey::new_unique();
+    let holder_ata = create_ata(context, game, &holder).await;
+    mint_to(context, game, &holder_ata, BALANCE).await;
+    (holder, holder_ata)
+}
+
+/// A `Draw` instruction of the draw authority paying `receiver_ata`, checked against the game's
+/// exclusion list.
+fn draw(
+    game: &Game,
+    receiver: &Pubkey,
+    receiver_ata: &Pubkey,
+    draw: &Pubkey,
+    draw_seed: u64,
+    receiver_weight: u64,
+    total_weight: u64,
+) -> Instruction {
+    instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        receiver,
+        receiver_ata,
+        draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        draw_seed,
+        receiver_weight,
+        total_weight,
+        None,
+    )
+}
+
+/// Returns the draw recorded in `draw`.
+async fn recorded_draw(context: &mut ProgramTestContext, draw: &Pubkey) -> BondDraw {
+    BondDraw::unpack_from_slice(&account(context, draw).await.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_draw_below_min_amount() {
+    let (mut context, game) = start_jackpot(ConfigParams {
+        min_draw_amount: Some(JACKPOT + 1),
+        ..ConfigParams::default()
+    }).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    // A jackpot below the minimum rolls over without counting towards the rollover limit.
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    assert_eq!(balance(&mut context, &holder_ata).await, BALANCE);
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, JACKPOT);
+    let state = state(&mut context, &game).await;
+    assert_eq!(state.draw_id, 1);
+    assert_eq!(state.rollover, 0);
+}
+
+#[tokio::test]
+async fn test_draw_payout_mode_claim() {
+    let (mut context, game) = start_jackpot(ConfigParams {
+        payout_mode: Some(BondPayoutMode::Claim),
+        ..ConfigParams::default()
+    }).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    // The jackpot stays in escrow until the winner claims it.
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    assert_eq!(balance(&mut context, &holder_ata).await, BALANCE);
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, JACKPOT);
+    let draw = recorded_draw(&mut context, &draw_account).await;
+    assert_eq!((draw.receiver, draw.amount), (holder, JACKPOT));
+    assert!(!draw.is_claimed);
+}
+
+#[tokio::test]
+async fn test_draw_rollover_retention() {
+    let (mut context, game) = start_jackpot(ConfigParams {
+        rollover_retention_bps: Some(1_000),
+        ..ConfigParams::default()
+    }).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    // A draw won by the jackpot keeps it for the next draw, less the retention.
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &game.jackpot(),
+        &game.addresses.jackpot_ata,
+        &draw_account,
+        &[],
+        42,
+        1,
+        0,
+        0,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, JACKPOT - 500);
+    assert_eq!(balance(&mut context, &game.addresses.treasury_ata).await, 500);
+    assert_eq!(state(&mut context, &game).await.rollover, 1);
+    let draw = recorded_draw(&mut context, &draw_account).await;
+    assert_eq!(draw.receiver, game.jackpot());
+    assert_eq!(draw.retained, 500);
+}
+
+#[tokio::test]
+async fn test_draw_season_not_started() {
+    let (mut context, game) = start_jackpot(ConfigParams {
+        starts_at: Some(i64::MAX),
+        ..ConfigParams::default()
+    }).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::SeasonNotStarted);
+}
+
+#[tokio::test]
+async fn test_draw_below_min_eligible_weight() {
+    let (mut context, game) = start_jackpot(ConfigParams {
+        min_eligible_weight: Some(BALANCE + 1),
+        ..ConfigParams::default()
+    }).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::InsufficientWeight);
+    assert_eq!(balance(&mut context, &holder_ata).await, BALANCE);
+}
+
+#[tokio::test]
+async fn test_draw_records_last_winner() {
+    let (mut context, game) = start_jackpot(ConfigParams::default()).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    let state = state(&mut context, &game).await;
+    assert_eq!((state.last_winner, state.last_win_draw_id), (holder, 1));
+}
+
+#[tokio::test]
+async fn test_draw_winner_cooldown() {
+    let (mut context, game) = start_jackpot(ConfigParams {
+        winner_cooldown: Some(1),
+        ..ConfigParams::default()
+    }).await;
+    let (other, other_ata) = holder(&mut context, &game).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let mut bond_state = state(&mut context, &game).await;
+    bond_state.last_winner = holder;
+    set_state(&mut context, &game, &bond_state).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    // The winner of the previous draw sits out the next one.
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::WinnerCooldown);
+
+    let instruction = draw(&game, &other, &other_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &other_ata).await, BALANCE + JACKPOT);
+}
+
+#[tokio::test]
+async fn test_preview_draw() {
+    let (mut context, game) = start_jackpot(ConfigParams::default()).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+    let mut instruction =
+        draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    instruction.data = BondInstruction::PreviewDraw {
+        receiver_seed: 42,
+        draw_seed: 1,
+        receiver_weight: BALANCE,
+        total_weight: TOTAL_WEIGHT,
+        proof: None,
+    }.try_to_vec().unwrap();
+
+    // The would-be draw is returned to the simulating client.
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction.clone()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &game.draw_authority],
+        context.last_blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    let preview = BondDraw::deserialize(&mut return_data.data.as_slice()).unwrap();
+    assert_eq!((preview.id, preview.receiver, preview.amount), (1, holder, JACKPOT));
+
+    // Nothing is paid or recorded.
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &holder_ata).await, BALANCE);
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, JACKPOT);
+    assert_eq!(state(&mut context, &game).await.draw_id, 0);
+    assert!(!recorded_draw(&mut context, &draw_account).await.is_initialized());
+}
+
+#[tokio::test]
+async fn test_draw_isolated() {
+    let (mut context, game) = start_jackpot(ConfigParams {
+        isolate_draws: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+    let mut instruction =
+        draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    // The instructions sysvar precedes the exclusion list.
+    let exclusion_list = instruction.accounts.pop().unwrap();
+    instruction.accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+    instruction.accounts.push(exclusion_list);
+
+    // Only compute budget and Ed25519 instructions may accompany the draw.
+    let transfer = system_instruction::transfer(&context.payer.pubkey(), &holder, 1);
+    let instructions = vec![transfer, instruction.clone()];
+    let result = process(&mut context, instructions, &[&game.draw_authority]).await;
+    assert_bond_error(result, 1, BondError::TransactionNotIsolated);
+
+    // At most MAX_DRAW_TRANSACTION_INSTRUCTIONS.
+    let signer = Keypair::new();
+    let instructions = vec![
+        instruction::set_compute_unit_limit(COMPUTE_UNITS_DRAW),
+        instruction::set_compute_unit_price(1),
+        ed25519_instruction(&signer, b"first"),
+        ed25519_instruction(&signer, b"second"),
+        instruction.clone(),
+    ];
+    let result = process(&mut context, instructions, &[&game.draw_authority]).await;
+    assert_bond_error(result, 4, BondError::TransactionNotIsolated);
+
+    let instructions = vec![instruction::set_compute_unit_limit(COMPUTE_UNITS_DRAW), instruction];
+    process(&mut context, instructions, &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &holder_ata).await, BALANCE + JACKPOT);
+}
+
+#[tokio::test]
+async fn test_draw_excluded_receiver() {
+    let (mut context, game) = start_jackpot(ConfigParams::default()).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+    let exclude = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+        ],
+        data: BondInstruction::AddExcludedAccounts {
+            accounts: vec![holder],
+            expires_at_epoch: None,
+        }.try_to_vec().unwrap(),
+    };
+    process(&mut context, vec![exclude], &[&game.authority]).await.unwrap();
+
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::ExcludedReceiver);
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, JACKPOT);
+}
+
+#[tokio::test]
+async fn test_draw_too_soon() {
+    let (mut context, game) = start_jackpot(ConfigParams {
+        epochs_per_draw: Some(1),
+        ..ConfigParams::default()
+    }).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    // Draws are spaced by the configured number of epochs.
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let draw_account = create_draw_account(&mut context).await;
+    let weight = BALANCE + JACKPOT;
+    let total_weight = 10 * weight;
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 2, weight, total_weight);
+    let result = process(&mut context, vec![instruction.clone()], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::DrawTooSoon);
+
+    let epoch = context.banks_client.get_sysvar::<Clock>().await.unwrap().epoch;
+    warp_to_epoch(&mut context, epoch + 1).await;
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(state(&mut context, &game).await.draw_id, 2);
+}
+
+#[tokio::test]
+async fn test_draw() {
+    let (mut context, game) = start_jackpot(ConfigParams::default()).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    assert_eq!(balance(&mut context, &holder_ata).await, BALANCE + JACKPOT);
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, 0);
+    let state = state(&mut context, &game).await;
+    assert_eq!(state.draw_id, 1);
+    assert_eq!(state.last_winner, holder);
+    let draw = recorded_draw(&mut context, &draw_account).await;
+    assert_eq!(draw.id, 1);
+    assert_eq!(draw.receiver, holder);
+    assert_eq!(draw.amount, JACKPOT);
+    assert_eq!((draw.receiver_weight, draw.total_weight), (BALANCE, TOTAL_WEIGHT));
+}
+
+#[tokio::test]
+async fn test_draw_decimals() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint_with_decimals(&mut context, 6).await;
+    let game = create_game_for_mint(&mut context, token_mint, ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    // The jackpot's transfer is checked against the mint's own decimals.
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, TOTAL_WEIGHT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &holder_ata).await, BALANCE + JACKPOT);
+}
+
+/// Creates a token account of `owner` for `token_mint` at a keypair address, holding [BALANCE] if
+/// it's the game's mint.
+async fn create_token_account(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    token_mint: &Pubkey,
+    owner: &Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = vec![
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account3(
+            &spl_token::id(),
+            &account.pubkey(),
+            token_mint,
+            owner,
+        ).unwrap(),
+    ];
+    process(context, instructions, &[&account]).await.unwrap();
+    if token_mint == &game.token_mint {
+        mint_to(context, game, &account.pubkey(), BALANCE).await;
+    }
+    account.pubkey()
+}
+
+#[tokio::test]
+async fn test_draw_receiver_not_ata() {
+    let (mut context, game) = start_jackpot(ConfigParams::default()).await;
+    let holder = Pubkey::new_unique();
+    let other_mint = create_mint(&mut context).await;
+
+    // The prize is only paid to the receiver's associated token account for the game's mint.
+    let token_account = create_token_account(&mut context, &game, &game.token_mint, &holder).await;
+    let other_account = create_token_account(&mut context, &game, &other_mint, &holder).await;
+    for receiver_ata in [token_account, other_account] {
+        let draw_account = create_draw_account(&mut context).await;
+        let instruction = draw(&game, &holder, &receiver_ata, &draw_account, 1, 0, TOTAL_WEIGHT);
+        let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+        assert_bond_error(result, 0, BondError::InvalidAta);
+    }
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, JACKPOT);
+}
+
+#[tokio::test]
+async fn test_draw_game_account_receiver() {
+    let (mut context, game) = start_jackpot(ConfigParams::default()).await;
+
+    // Only the jackpot among the game's accounts may receive a draw, as a rollover.
+    let receivers = [
+        (Pubkey::default(), BondError::DefaultReceiver),
+        (game.config(), BondError::GameAccountReceiver),
+        (game.addresses.fee.0, BondError::GameAccountReceiver),
+        (game.addresses.treasury.0, BondError::GameAccountReceiver),
+    ];
+    for (receiver, error) in receivers {
+        let draw_account = create_draw_account(&mut context).await;
+        let receiver_ata = spl_associated_token_account::get_associated_token_address(
+            &receiver,
+            &game.token_mint,
+        );
+        let instruction = draw(&game, &receiver, &receiver_ata, &draw_account, 1, 0, TOTAL_WEIGHT);
+        let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+        assert_bond_error(result, 0, error);
+    }
+}
+
+#[tokio::test]
+async fn test_draw_invalid_weight() {
+    let (mut context, game) = start_jackpot(ConfigParams::default()).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    // The receiver's weight is its balance.
+    let weight = BALANCE - 1;
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, weight, TOTAL_WEIGHT);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidWeight);
+}
+
+#[tokio::test]
+async fn test_draw_odds_threshold_exceeded() {
+    let (mut context, game) = start_jackpot(ConfigParams::default()).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    let total_weight = TOTAL_WEIGHT - 1;
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 1, BALANCE, total_weight);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::OddsThresholdExceeded);
+}
+
+#[tokio::test]
+async fn test_draw_invalid_seed() {
+    let (mut context, game) = start_jackpot(ConfigParams::default()).await;
+    let (holder, holder_ata) = holder(&mut context, &game).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    let instruction = draw(&game, &holder, &holder_ata, &draw_account, 2, BALANCE, TOTAL_WEIGHT);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidSeeds);
+}
@@ -0,0 +1,90 @@
+//! Integration tests of `CreateDrawTree`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{
+            BondInstruction, ConfigParams, ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID,
+        },
+        state::{BondConfig, BondPayoutMode},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+};
+
+/// A `CreateDrawTree` instruction of `game` for `tree`.
+fn create_draw_tree(game: &Game, tree: &Pubkey, max_depth: u32) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new(*tree, false),
+            AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+        ],
+        data: BondInstruction::CreateDrawTree { max_depth, max_buffer_size: 8 }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Returns the game's settings.
+async fn config(context: &mut ProgramTestContext, game: &Game) -> BondConfig {
+    BondConfig::unpack_from_slice(&account(context, &game.config()).await.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_create_draw_tree() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let tree = create_tree(&mut context, &ACCOUNT_COMPRESSION_PROGRAM_ID).await;
+    let instruction = create_draw_tree(&game, &tree, 14);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    assert_eq!(config(&mut context, &game).await.draw_tree, Some(tree));
+
+    // The tree is initialized with the requested depth and buffer size.
+    let data = account(&mut context, &tree).await.data;
+    assert_eq!(data[8..12], 14u32.to_le_bytes());
+    assert_eq!(data[12..16], 8u32.to_le_bytes());
+
+    // The game's draw history can't be replaced.
+    let other_tree = create_tree(&mut context, &ACCOUNT_COMPRESSION_PROGRAM_ID).await;
+    let instruction = create_draw_tree(&game, &other_tree, 14);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::AccountAlreadyInitialized);
+    assert_eq!(config(&mut context, &game).await.draw_tree, Some(tree));
+}
+
+#[tokio::test]
+async fn test_create_draw_tree_not_owned_by_compression() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let tree = create_tree(&mut context, &defi_bond::id()).await;
+    let instruction = create_draw_tree(&game, &tree, 14);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidOwner);
+    assert_eq!(config(&mut context, &game).await.draw_tree, None);
+}
+
+#[tokio::test]
+async fn test_create_draw_tree_payout_mode_claim() {
+    let params = ConfigParams {
+        payout_mode: Some(BondPayoutMode::Claim),
+        ..ConfigParams::default()
+    };
+    let (mut context, game) = start(params).await;
+    let tree = create_tree(&mut context, &ACCOUNT_COMPRESSION_PROGRAM_ID).await;
+    let instruction = create_draw_tree(&game, &tree, 14);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::InvalidArgument);
+    assert_eq!(config(&mut context, &game).await.draw_tree, None);
+}
@@ -0,0 +1,44 @@
+//! Integration tests of the program's entrypoint.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::instruction::{BondInstruction, ConfigParams},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{signature::Signer, transaction::Transaction},
+};
+
+#[tokio::test]
+async fn test_print_error() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+        ],
+        data: BondInstruction::AddExcludedAccounts {
+            accounts: (0..9).map(|_| Pubkey::new_unique()).collect(),
+            expires_at_epoch: None,
+        }.try_to_vec().unwrap(),
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &game.authority],
+        context.last_blockhash,
+    );
+
+    // Custom errors are logged by their name.
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    let logs = simulation.simulation_details.unwrap().logs;
+    let log = "Program log: [Processor] Error: Exclusion list is full (ExclusionListFull)";
+    assert!(logs.iter().any(|line| line == log), "{:?}", logs);
+}
@@ -0,0 +1,381 @@
+//! Integration tests of the exclusion list's instructions.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        error::BondError,
+        constants::{exclusion_list_size, EXCLUSION_VALIDATION_SIZE},
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondExclusionList, BondExclusionValidation, BondSeed},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+};
+
+/// An `AppendExclusionChunk` instruction of `game`.
+fn append_exclusion_chunk(game: &Game, chunk_index: u32, accounts: Vec<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+        ],
+        data: BondInstruction::AppendExclusionChunk { chunk_index, accounts }.try_to_vec().unwrap(),
+    }
+}
+
+/// Returns the game's exclusion list.
+async fn exclusion_list(context: &mut ProgramTestContext, game: &Game) -> BondExclusionList {
+    let account = account(context, &game.addresses.exclusion_list.0).await;
+    BondExclusionList::deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// An `UpdateExclusionList` instruction of `game` adding `add` and removing `remove`.
+fn update_exclusion_list(game: &Game, add: Vec<Pubkey>, remove: Vec<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+        ],
+        data: BondInstruction::UpdateExclusionList {
+            add,
+            remove,
+            expires_at_epoch: None,
+        }.try_to_vec().unwrap(),
+    }
+}
+
+/// An `UpdateExclusionList` instruction of `game` excluding `account` until `expires_at_epoch`.
+fn exclude_until(game: &Game, account: Pubkey, expires_at_epoch: u64) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+        ],
+        data: BondInstruction::UpdateExclusionList {
+            add: vec![account],
+            remove: vec![],
+            expires_at_epoch: Some(expires_at_epoch),
+        }.try_to_vec().unwrap(),
+    }
+}
+
+/// An `AddExcludedAccounts` instruction of `game` permanently excluding `accounts`.
+fn add_excluded_accounts(game: &Game, accounts: Vec<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+        ],
+        data: BondInstruction::AddExcludedAccounts { accounts, expires_at_epoch: None }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// A `RemoveExcludedAccounts` instruction of `game` making `accounts` eligible again.
+fn remove_excluded_accounts(game: &Game, accounts: Vec<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+        ],
+        data: BondInstruction::RemoveExcludedAccounts { accounts }.try_to_vec().unwrap(),
+    }
+}
+
+/// A `GrowExclusionList` instruction of `game` paid by the context's payer.
+fn grow_exclusion_list(context: &ProgramTestContext, game: &Game, capacity: u32) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::GrowExclusionList { capacity }.try_to_vec().unwrap(),
+    }
+}
+
+/// A `ValidateReceiverChunk` instruction of `game` checking `receiver` against the next `count`
+/// exclusions.
+fn validate_receiver_chunk(game: &Game, receiver: Pubkey, count: u32) -> Instruction {
+    let (validation, bump) = BondSeed::ExclusionValidation.derive(&defi_bond::id(), &game.config());
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(game.draw_authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+            AccountMeta::new(validation, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::ValidateReceiverChunk {
+            bump,
+            space: EXCLUSION_VALIDATION_SIZE as u32,
+            receiver,
+            count,
+        }.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_update_exclusion_list() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let accounts: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+    let instruction = update_exclusion_list(&game, accounts.clone(), vec![]);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    // Removals apply first, making room for the additions.
+    let added = Pubkey::new_unique();
+    let instruction = update_exclusion_list(&game, vec![added], accounts[..2].to_vec());
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    let exclusion_list = exclusion_list(&mut context, &game).await;
+    assert_eq!(exclusion_list.accounts.len(), 7);
+    assert!(exclusion_list.contains(&added));
+    assert!(!exclusion_list.contains(&accounts[0]));
+    assert!(!exclusion_list.contains(&accounts[1]));
+    assert_eq!(exclusion_list.revision, 11);
+}
+
+#[tokio::test]
+async fn test_update_exclusion_list_duplicate() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let account = Pubkey::new_unique();
+    let instruction = update_exclusion_list(&game, vec![account], vec![]);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    let instruction = update_exclusion_list(&game, vec![account, Pubkey::new_unique()], vec![]);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::DuplicateExclusion);
+}
+
+#[tokio::test]
+async fn test_update_exclusion_list_full() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let accounts: Vec<Pubkey> = (0..9).map(|_| Pubkey::new_unique()).collect();
+
+    let instruction = update_exclusion_list(&game, accounts.clone(), vec![]);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::ExclusionListFull);
+
+    let instruction = update_exclusion_list(&game, accounts[..8].to_vec(), vec![]);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_add_and_remove_excluded_accounts() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let accounts: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let instruction = add_excluded_accounts(&game, vec![accounts[0], accounts[1], accounts[0]]);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    // Excluded accounts are skipped rather than rejected.
+    let instruction = add_excluded_accounts(&game, vec![accounts[1], accounts[2]]);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    assert_eq!(exclusion_list(&mut context, &game).await.accounts.len(), 3);
+
+    // So are accounts that aren't excluded.
+    let instruction = remove_excluded_accounts(&game, vec![accounts[0], accounts[3]]);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let exclusion_list = exclusion_list(&mut context, &game).await;
+    assert_eq!(exclusion_list.accounts.len(), 2);
+    assert!(!exclusion_list.contains(&accounts[0]));
+    assert!(exclusion_list.contains(&accounts[1]));
+    assert!(exclusion_list.contains(&accounts[2]));
+}
+
+#[tokio::test]
+async fn test_add_excluded_accounts_full() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let accounts: Vec<Pubkey> = (0..9).map(|_| Pubkey::new_unique()).collect();
+    let instruction = add_excluded_accounts(&game, accounts);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::ExclusionListFull);
+}
+
+#[tokio::test]
+async fn test_grow_exclusion_list() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let instruction = grow_exclusion_list(&context, &game, 16);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let account = account(&mut context, &game.addresses.exclusion_list.0).await;
+    assert_eq!(account.data.len(), exclusion_list_size(16));
+
+    // The grown list holds more than the initial 8 accounts.
+    let accounts: Vec<Pubkey> = (0..16).map(|_| Pubkey::new_unique()).collect();
+    let instruction = add_excluded_accounts(&game, accounts);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let exclusion_list = exclusion_list(&mut context, &game).await;
+    assert_eq!((exclusion_list.accounts.len(), exclusion_list.capacity), (16, 16));
+
+    // Lists only grow.
+    let instruction = grow_exclusion_list(&context, &game, 16);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_append_exclusion_chunk() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let accounts: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+    let first = append_exclusion_chunk(&game, 0, accounts[..3].to_vec());
+    process(&mut context, vec![first.clone()], &[&game.authority]).await.unwrap();
+
+    // Chunks are appended in order, and re-sent chunks are skipped.
+    let instruction = append_exclusion_chunk(&game, 2, accounts[3..].to_vec());
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidChunkIndex);
+    let second = append_exclusion_chunk(&game, 1, accounts[3..].to_vec());
+    context.get_new_latest_blockhash().await.unwrap();
+    process(&mut context, vec![first, second], &[&game.authority]).await.unwrap();
+
+    let exclusion_list = exclusion_list(&mut context, &game).await;
+    assert_eq!(exclusion_list.chunks, 2);
+    let excluded: Vec<Pubkey> = exclusion_list.accounts.iter().map(|entry| entry.account).collect();
+    assert_eq!(excluded, accounts);
+}
+
+#[tokio::test]
+async fn test_prune_expired() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let (temporary, permanent) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let instructions = vec![
+        exclude_until(&game, temporary, 1),
+        update_exclusion_list(&game, vec![permanent], vec![]),
+    ];
+    process(&mut context, instructions, &[&game.authority]).await.unwrap();
+
+    // Anyone may prune the exclusions that lapsed.
+    let prune_expired = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+        ],
+        data: BondInstruction::PruneExpired.try_to_vec().unwrap(),
+    };
+    process(&mut context, vec![prune_expired.clone()], &[]).await.unwrap();
+    assert_eq!(exclusion_list(&mut context, &game).await.accounts.len(), 2);
+
+    warp_to_epoch(&mut context, 1).await;
+    process(&mut context, vec![prune_expired], &[]).await.unwrap();
+    let exclusion_list = exclusion_list(&mut context, &game).await;
+    assert!(!exclusion_list.contains(&temporary));
+    assert!(exclusion_list.contains(&permanent));
+}
+
+#[tokio::test]
+async fn test_draw_expired_exclusion() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, 5_000).await;
+    let holder = Pubkey::new_unique();
+    let holder_ata = create_ata(&mut context, &game, &holder).await;
+    mint_to(&mut context, &game, &holder_ata, 1_000).await;
+    let instruction = exclude_until(&game, holder, 1);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    // A lapsed exclusion is eligible again, even before it's pruned.
+    warp_to_epoch(&mut context, 1).await;
+    let draw = create_draw_account(&mut context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &holder,
+        &holder_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        1_000,
+        10_000,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &holder_ata).await, 6_000);
+}
+
+#[tokio::test]
+async fn test_validate_receiver_chunk() {
+    let (mut context, game) = start(ConfigParams {
+        require_exclusion_validation: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    fund(&mut context, &game.draw_authority.pubkey(), 1_000_000_000).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, 5_000).await;
+    let holder = Pubkey::new_unique();
+    let holder_ata = create_ata(&mut context, &game, &holder).await;
+    mint_to(&mut context, &game, &holder_ata, 1_000).await;
+    let excluded: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let instruction = update_exclusion_list(&game, excluded.clone(), vec![]);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    // Excluded accounts fail their validation.
+    let instruction = validate_receiver_chunk(&game, excluded[1], 2);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::ExcludedReceiver);
+
+    // The draw requires a complete validation of its receiver.
+    let validation = BondSeed::ExclusionValidation.derive(&defi_bond::id(), &game.config()).0;
+    let draw = create_draw_account(&mut context).await;
+    let draw_instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &holder,
+        &holder_ata,
+        &draw,
+        &[
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+            AccountMeta::new_readonly(validation, false),
+        ],
+        42,
+        1,
+        1_000,
+        10_000,
+        None,
+    );
+    let instruction = validate_receiver_chunk(&game, holder, 3);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    let data = account(&mut context, &validation).await.data;
+    let progress = BondExclusionValidation::deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!((progress.receiver, progress.index, progress.is_complete), (holder, 3, false));
+    let instructions = vec![draw_instruction.clone()];
+    let result = process(&mut context, instructions, &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::ExclusionValidationRequired);
+
+    // The validation resumes where it stopped.
+    let instruction = validate_receiver_chunk(&game, holder, 3);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    let data = account(&mut context, &validation).await.data;
+    let progress = BondExclusionValidation::deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!((progress.index, progress.is_complete), (4, true));
+    process(&mut context, vec![draw_instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &holder_ata).await, 6_000);
+}
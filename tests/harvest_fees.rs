@@ -0,0 +1,167 @@
+//! Integration tests of `HarvestFees`.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        instruction::{BondInstruction, ConfigParams},
+        state::BondFee,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        stake::state::{Meta, StakeStateV2},
+    },
+    solana_program_test::*,
+    solana_sdk::{account::Account, signature::Signer},
+    spl_stake_pool::{
+        find_withdraw_authority_program_address,
+        state::{AccountType, StakePool, ValidatorList, ValidatorListHeader},
+    },
+    spl_token::instruction::AuthorityType,
+};
+
+/// The lamports held by each of the pool's accounts, its reserve stake included.
+const RESERVE: u64 = 1_000_000_000;
+
+/// A `HarvestFees` instruction of `game` for the SPL Stake Pool `pool`.
+fn harvest_fees(game: &Game, pool: &Pubkey, stake_pool: &StakePool) -> Instruction {
+    let (withdraw_authority, _) =
+        find_withdraw_authority_program_address(&spl_stake_pool::id(), pool);
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.fee.0, false),
+            AccountMeta::new(game.addresses.fee_ata, false),
+            AccountMeta::new(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_stake_pool::id(), false),
+            AccountMeta::new(*pool, false),
+            AccountMeta::new_readonly(withdraw_authority, false),
+            AccountMeta::new(stake_pool.validator_list, false),
+            AccountMeta::new_readonly(stake_pool.reserve_stake, false),
+        ],
+        data: BondInstruction::HarvestFees.try_to_vec().unwrap(),
+    }
+}
+
+/// Sets the account at `address`, owned by `owner`, to `data`.
+fn set_account(context: &mut ProgramTestContext, address: &Pubkey, owner: &Pubkey, data: Vec<u8>) {
+    let account = Account {
+        lamports: RESERVE,
+        data,
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(address, &account.into());
+}
+
+/// Starts a game along with the SPL Stake Pool program, in its second epoch.
+async fn start_harvesting() -> (ProgramTestContext, Game) {
+    let mut program_test = program_test();
+    program_test.add_program(
+        "spl_stake_pool",
+        spl_stake_pool::id(),
+        processor!(spl_stake_pool::processor::Processor::process),
+    );
+    let mut context = program_test.start_with_context().await;
+    let game = create_game(&mut context, ConfigParams::default()).await;
+    warp_to_epoch(&mut context, 1).await;
+    (context, game)
+}
+
+/// Creates an empty Stake Pool minting the game's token, managed by `manager` and paying its fee
+/// to `manager_fee_account`, hands it the mint authority, and returns its address.
+async fn create_pool(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    manager: &Pubkey,
+    manager_fee_account: &Pubkey,
+) -> (Pubkey, StakePool) {
+    let pool = Pubkey::new_unique();
+    let (withdraw_authority, stake_withdraw_bump_seed) =
+        find_withdraw_authority_program_address(&spl_stake_pool::id(), &pool);
+    let stake_pool = StakePool {
+        account_type: AccountType::StakePool,
+        manager: *manager,
+        stake_withdraw_bump_seed,
+        validator_list: Pubkey::new_unique(),
+        reserve_stake: Pubkey::new_unique(),
+        pool_mint: game.token_mint,
+        manager_fee_account: *manager_fee_account,
+        token_program_id: spl_token::id(),
+        ..StakePool::default()
+    };
+    let mut data = stake_pool.try_to_vec().unwrap();
+    data.resize(1_000, 0);
+    set_account(context, &pool, &spl_stake_pool::id(), data);
+    let validator_list = ValidatorList {
+        header: ValidatorListHeader {
+            account_type: AccountType::ValidatorList,
+            max_validators: 0,
+        },
+        validators: vec![],
+    };
+    let data = validator_list.try_to_vec().unwrap();
+    set_account(context, &stake_pool.validator_list, &spl_stake_pool::id(), data);
+    let data = StakeStateV2::Initialized(Meta::default()).try_to_vec().unwrap();
+    set_account(context, &stake_pool.reserve_stake, &solana_program::stake::program::id(), data);
+
+    let instruction = spl_token::instruction::set_authority(
+        &spl_token::id(),
+        &game.token_mint,
+        Some(&withdraw_authority),
+        AuthorityType::MintTokens,
+        &context.payer.pubkey(),
+        &[],
+    ).unwrap();
+    process(context, vec![instruction], &[]).await.unwrap();
+    (pool, stake_pool)
+}
+
+/// Returns the stake pool at `pool`.
+async fn stake_pool(context: &mut ProgramTestContext, pool: &Pubkey) -> StakePool {
+    StakePool::deserialize(&mut account(context, pool).await.data.as_slice()).unwrap()
+}
+
+/// Returns the game's fee account.
+async fn fee(context: &mut ProgramTestContext, game: &Game) -> BondFee {
+    BondFee::unpack_from_slice(&account(context, &game.addresses.fee.0).await.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_harvest_fees() {
+    let (mut context, game) = start_harvesting().await;
+    let fee_pda = game.addresses.fee.0;
+    let fee_ata = game.addresses.fee_ata;
+    let (pool, created_pool) = create_pool(&mut context, &game, &fee_pda, &fee_ata).await;
+
+    // The pool managed by the game's fee account already pays it, so it's only updated: an empty
+    // pool pays its whole reward (the reserve above its minimum) as its fee.
+    let instruction = harvest_fees(&game, &pool, &created_pool);
+    process(&mut context, vec![instruction], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &fee_ata).await, RESERVE - 1);
+    assert_eq!(fee(&mut context, &game).await.last_update_epoch, 1);
+}
+
+#[tokio::test]
+async fn test_harvest_fees_other_manager() {
+    let (mut context, game) = start_harvesting().await;
+    let manager = Pubkey::new_unique();
+    let manager_fee_account = create_ata(&mut context, &game, &manager).await;
+    let (pool, created_pool) = create_pool(&mut context, &game, &manager, &manager_fee_account)
+        .await;
+
+    // A pool managed elsewhere keeps paying its fee elsewhere, so it can't be cranked by the game.
+    let instruction = harvest_fees(&game, &pool, &created_pool);
+    let result = process(&mut context, vec![instruction], &[]).await;
+    assert_error(result, 0, InstructionError::IncorrectProgramId);
+    let unchanged_pool = stake_pool(&mut context, &pool).await;
+    assert_eq!(unchanged_pool.manager_fee_account, manager_fee_account);
+    assert_eq!(fee(&mut context, &game).await.last_update_epoch, 0);
+}
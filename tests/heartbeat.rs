@@ -0,0 +1,82 @@
+//! Integration tests of `Heartbeat`.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        constants::HEARTBEAT_SIZE,
+        error::BondError,
+        instruction::{
+            BondInstruction,
+            ConfigParams,
+            HEARTBEAT_DRAW,
+            HEARTBEAT_HARVEST,
+            HEARTBEAT_SPLIT,
+        },
+        state::{BondHeartbeat, BondSeed},
+    },
+    solana_program::{
+        clock::Clock,
+        instruction::{AccountMeta, Instruction},
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// A `Heartbeat` instruction of `draw_authority` recording `runs`.
+fn heartbeat(game: &Game, draw_authority: &Keypair, runs: u8) -> Instruction {
+    let (heartbeat, bump) = BondSeed::Heartbeat.derive(&defi_bond::id(), &game.config());
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(draw_authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(heartbeat, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::Heartbeat { bump, space: HEARTBEAT_SIZE as u32, runs }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Returns the game's heartbeat.
+async fn recorded_heartbeat(context: &mut ProgramTestContext, game: &Game) -> BondHeartbeat {
+    let heartbeat = BondSeed::Heartbeat.derive(&defi_bond::id(), &game.config()).0;
+    let data = account(context, &heartbeat).await.data;
+    BondHeartbeat::deserialize(&mut data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn test_heartbeat() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    fund(&mut context, &game.draw_authority.pubkey(), 1_000_000_000).await;
+    let instruction = heartbeat(&game, &game.draw_authority, HEARTBEAT_HARVEST | HEARTBEAT_DRAW);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    let slot = context.banks_client.get_sysvar::<Clock>().await.unwrap().slot;
+    let recorded = recorded_heartbeat(&mut context, &game).await;
+    assert_eq!(recorded.authority, game.config());
+    let slots = (recorded.last_harvest_slot, recorded.last_split_slot, recorded.last_draw_slot);
+    assert_eq!(slots, (slot, 0, slot));
+
+    // Only the completed runs are updated.
+    context.warp_to_slot(slot + 10).unwrap();
+    let instruction = heartbeat(&game, &game.draw_authority, HEARTBEAT_SPLIT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    let recorded = recorded_heartbeat(&mut context, &game).await;
+    let slots = (recorded.last_harvest_slot, recorded.last_split_slot, recorded.last_draw_slot);
+    assert_eq!(slots, (slot, slot + 10, slot));
+}
+
+#[tokio::test]
+async fn test_heartbeat_of_another_authority() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let keeper = Keypair::new();
+    fund(&mut context, &keeper.pubkey(), 1_000_000_000).await;
+    let instruction = heartbeat(&game, &keeper, HEARTBEAT_DRAW);
+    let result = process(&mut context, vec![instruction], &[&keeper]).await;
+    assert_bond_error(result, 0, BondError::InvalidAuthority);
+}
@@ -0,0 +1,184 @@
+//! Integration tests of `PlaceHold`, `ReleaseHold` and claims of held winners.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        constants::HOLD_SIZE,
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondHold, BondPayoutMode, BondSeed},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// The jackpot paid by the draw.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner.
+const BALANCE: u64 = 1_000;
+
+/// A game with a compliance authority whose jackpot of [JACKPOT] was won by a holder and escrowed
+/// for its claim.
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    compliance_authority: Keypair,
+    winner: Keypair,
+    winner_ata: Pubkey,
+    draw: Pubkey,
+}
+
+/// Starts a game with a compliance authority and runs a draw won by a new holder.
+async fn start_won() -> Setup {
+    let compliance_authority = Keypair::new();
+    let (mut context, game) = start(ConfigParams {
+        payout_mode: Some(BondPayoutMode::Claim),
+        compliance_authority: Some(compliance_authority.pubkey()),
+        ..ConfigParams::default()
+    }).await;
+    fund(&mut context, &compliance_authority.pubkey(), 1_000_000_000).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let winner = Keypair::new();
+    let winner_ata = create_ata(&mut context, &game, &winner.pubkey()).await;
+    mint_to(&mut context, &game, &winner_ata, BALANCE).await;
+    let draw = create_draw_account(&mut context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &winner.pubkey(),
+        &winner_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    Setup { context, game, compliance_authority, winner, winner_ata, draw }
+}
+
+/// Returns the address of the hold of `wallet` and its bump.
+fn hold_address(game: &Game, wallet: &Pubkey) -> (Pubkey, u8) {
+    BondSeed::Hold.derive_indexed(&defi_bond::id(), &game.config(), wallet.as_ref())
+}
+
+/// A `PlaceHold` instruction of `compliance_authority` on `wallet`.
+fn place_hold(game: &Game, compliance_authority: &Pubkey, wallet: &Pubkey) -> Instruction {
+    let (hold, bump) = hold_address(game, wallet);
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(*compliance_authority, true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(*wallet, false),
+            AccountMeta::new(hold, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::PlaceHold { bump, space: HOLD_SIZE as u32 }.try_to_vec().unwrap(),
+    }
+}
+
+/// A `ReleaseHold` instruction of the winner of `setup`'s draw paying `destination_ata`.
+fn release_hold(setup: &Setup, destination_ata: &Pubkey, reroute: bool) -> Instruction {
+    let game = &setup.game;
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.state(), false),
+            AccountMeta::new_readonly(game.jackpot(), false),
+            AccountMeta::new(game.addresses.jackpot_ata, false),
+            AccountMeta::new(hold_address(game, &setup.winner.pubkey()).0, false),
+            AccountMeta::new(*destination_ata, false),
+            AccountMeta::new_readonly(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: BondInstruction::ReleaseHold { reroute }.try_to_vec().unwrap(),
+    }
+}
+
+/// Places a hold on the winner and claims its prize.
+async fn hold_and_claim(setup: &mut Setup) {
+    let instruction = place_hold(
+        &setup.game,
+        &setup.compliance_authority.pubkey(),
+        &setup.winner.pubkey(),
+    );
+    let signers = [&setup.compliance_authority];
+    process(&mut setup.context, vec![instruction], &signers).await.unwrap();
+    let hold = hold_address(&setup.game, &setup.winner.pubkey()).0;
+    let instruction = claim_prize(
+        &setup.game,
+        &setup.winner.pubkey(),
+        &setup.winner_ata,
+        &setup.draw,
+        &[AccountMeta::new(hold, false)],
+    );
+    process(&mut setup.context, vec![instruction], &[&setup.winner]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_claim_prize_held() {
+    let mut setup = start_won().await;
+    hold_and_claim(&mut setup).await;
+
+    // The prize stays reserved in the jackpot until the hold is released.
+    assert_eq!(balance(&mut setup.context, &setup.winner_ata).await, BALANCE);
+    assert_eq!(balance(&mut setup.context, &setup.game.addresses.jackpot_ata).await, JACKPOT);
+    let hold = hold_address(&setup.game, &setup.winner.pubkey()).0;
+    let data = account(&mut setup.context, &hold).await.data;
+    let hold = BondHold::deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!((hold.wallet, hold.amount), (setup.winner.pubkey(), JACKPOT));
+    assert_eq!(state(&mut setup.context, &setup.game).await.reserved, JACKPOT);
+
+    let instruction = release_hold(&setup, &setup.winner_ata, false);
+    let signers = [&setup.game.authority];
+    process(&mut setup.context, vec![instruction], &signers).await.unwrap();
+    assert_eq!(balance(&mut setup.context, &setup.winner_ata).await, BALANCE + JACKPOT);
+    assert_eq!(state(&mut setup.context, &setup.game).await.reserved, 0);
+    let hold = hold_address(&setup.game, &setup.winner.pubkey()).0;
+    assert!(setup.context.banks_client.get_account(hold).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_release_hold_reroute() {
+    let mut setup = start_won().await;
+    hold_and_claim(&mut setup).await;
+    let other = Pubkey::new_unique();
+    let other_ata = create_ata(&mut setup.context, &setup.game, &other).await;
+
+    // Escrowed prizes are only paid to another wallet when rerouted.
+    let instruction = release_hold(&setup, &other_ata, false);
+    let signers = [&setup.game.authority];
+    let result = process(&mut setup.context, vec![instruction], &signers).await;
+    assert_error(result, 0, InstructionError::IncorrectProgramId);
+
+    let instruction = release_hold(&setup, &other_ata, true);
+    process(&mut setup.context, vec![instruction], &signers).await.unwrap();
+    assert_eq!(balance(&mut setup.context, &other_ata).await, JACKPOT);
+    assert_eq!(balance(&mut setup.context, &setup.winner_ata).await, BALANCE);
+}
+
+#[tokio::test]
+async fn test_place_hold_of_another_authority() {
+    let mut setup = start_won().await;
+    let other = Keypair::new();
+    fund(&mut setup.context, &other.pubkey(), 1_000_000_000).await;
+    let instruction = place_hold(&setup.game, &other.pubkey(), &setup.winner.pubkey());
+    let result = process(&mut setup.context, vec![instruction], &[&other]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+}
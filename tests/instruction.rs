@@ -0,0 +1,168 @@
+//! Tests of the instruction builders and data.
+
+use {
+    borsh::BorshSerialize,
+    defi_bond::instruction::{
+        self,
+        BondAddresses,
+        BondInstruction,
+        ConfigParams,
+        InitializeParams,
+        COMPUTE_UNITS_DRAW,
+        COMPUTE_UNITS_SPLIT_SHARES,
+        TOKEN_2022_PROGRAM_ID,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    solana_sdk::compute_budget::ComputeBudgetInstruction,
+    spl_associated_token_account::get_associated_token_address_with_program_id,
+};
+
+#[test]
+fn test_with_compute_budget() {
+    let draw = Instruction::new_with_bytes(defi_bond::id(), &[8], vec![]);
+    let split = Instruction::new_with_bytes(defi_bond::id(), &[7], vec![]);
+    let instructions = instruction::with_compute_budget(
+        vec![split.clone(), draw.clone()],
+        &[COMPUTE_UNITS_SPLIT_SHARES, COMPUTE_UNITS_DRAW],
+        Some(1_000),
+    );
+    assert_eq!(instructions, vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(
+            COMPUTE_UNITS_SPLIT_SHARES + COMPUTE_UNITS_DRAW,
+        ),
+        ComputeBudgetInstruction::set_compute_unit_price(1_000),
+        split,
+        draw.clone(),
+    ]);
+
+    // The price is optional.
+    let instructions =
+        instruction::with_compute_budget(vec![draw.clone()], &[COMPUTE_UNITS_DRAW], None);
+    assert_eq!(instructions, vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(COMPUTE_UNITS_DRAW),
+        draw,
+    ]);
+}
+
+#[test]
+fn test_bond_addresses_derive() {
+    let config = Pubkey::new_unique();
+    let token_mint = Pubkey::new_unique();
+    let addresses =
+        BondAddresses::derive(&defi_bond::id(), &config, &token_mint, &TOKEN_2022_PROGRAM_ID);
+    let pda = |seed: &str| {
+        Pubkey::find_program_address(&[config.as_ref(), seed.as_bytes()], &defi_bond::id())
+    };
+    let ata = |(owner, _): (Pubkey, u8)| {
+        get_associated_token_address_with_program_id(&owner, &token_mint, &TOKEN_2022_PROGRAM_ID)
+    };
+    assert_eq!(addresses, BondAddresses {
+        state: pda("state"),
+        fee: pda("fee"),
+        fee_ata: ata(pda("fee")),
+        exclusion_list: pda("exclusionList"),
+        equity: pda("equity"),
+        equity_ata: ata(pda("equity")),
+        treasury: pda("treasury"),
+        treasury_ata: ata(pda("treasury")),
+        jackpot: pda("jackpot"),
+        jackpot_ata: ata(pda("jackpot")),
+        stake: pda("stake"),
+        stake_ata: ata(pda("stake")),
+        emitter: pda("emitter"),
+    });
+}
+
+#[test]
+fn test_instruction_discriminants() {
+    let tag = |instruction: BondInstruction| instruction.try_to_vec().unwrap()[0];
+    assert_eq!(tag(BondInstruction::GetVersion), 9);
+    assert_eq!(tag(BondInstruction::AssertInvariants), 26);
+    assert_eq!(tag(BondInstruction::Pause), 46);
+}
+
+#[test]
+fn test_initialize_builder() {
+    let config = Pubkey::new_unique();
+    let token_mint = Pubkey::new_unique();
+    let addresses = BondAddresses::derive(&defi_bond::id(), &config, &token_mint, &spl_token::id());
+    let initialize = |params: InitializeParams| instruction::initialize(
+        &defi_bond::id(),
+        &Pubkey::new_unique(),
+        &config,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &token_mint,
+        &spl_token::id(),
+        params,
+    );
+
+    // The bump seeds are derived, and a keypair config signs.
+    let instruction = initialize(InitializeParams::default());
+    assert_eq!(instruction.accounts[1], AccountMeta::new(config, true));
+    assert_eq!(instruction.accounts[5], AccountMeta::new(addresses.state.0, false));
+    assert_eq!(instruction.accounts[11], AccountMeta::new(addresses.stake.0, false));
+    let params = InitializeParams {
+        state_bump: addresses.state.1,
+        fee_bump: addresses.fee.1,
+        exclusion_list_bump: addresses.exclusion_list.1,
+        equity_bump: addresses.equity.1,
+        treasury_bump: addresses.treasury.1,
+        jackpot_bump: addresses.jackpot.1,
+        stake_bump: addresses.stake.1,
+        ..InitializeParams::default()
+    };
+    assert_eq!(instruction.data, BondInstruction::Initialize(params).try_to_vec().unwrap());
+
+    // A PDA config doesn't.
+    let instruction = initialize(InitializeParams {
+        config: ConfigParams { bump: Some(255), ..ConfigParams::default() },
+        ..InitializeParams::default()
+    });
+    assert_eq!(instruction.accounts[1], AccountMeta::new(config, false));
+}
+
+#[test]
+fn test_split_shares_builder() {
+    let authority = Pubkey::new_unique();
+    let config = Pubkey::new_unique();
+    let token_mint = Pubkey::new_unique();
+    let addresses = BondAddresses::derive(&defi_bond::id(), &config, &token_mint, &spl_token::id());
+    let split_shares = |authority_ata: Option<&Pubkey>, with_rebate: bool| {
+        instruction::split_shares(
+            &defi_bond::id(),
+            &authority,
+            &config,
+            &token_mint,
+            &spl_token::id(),
+            authority_ata,
+            with_rebate,
+            Some(500),
+            None,
+        )
+    };
+
+    let instruction = split_shares(None, false);
+    assert_eq!(instruction.accounts.len(), 15);
+    assert_eq!(instruction.accounts[0], AccountMeta::new_readonly(authority, true));
+    assert_eq!(instruction.accounts[3], AccountMeta::new(addresses.fee_ata, false));
+    assert_eq!(instruction.accounts[14], AccountMeta::new(addresses.state.0, false));
+    let data = BondInstruction::SplitShares { amount: Some(500), destinations: None };
+    assert_eq!(instruction.data, data.try_to_vec().unwrap());
+
+    // The crank fee's and the rebate's token accounts are appended when required.
+    let authority_ata = Pubkey::new_unique();
+    let instruction = split_shares(Some(&authority_ata), true);
+    let state_ata = get_associated_token_address_with_program_id(
+        &addresses.state.0,
+        &token_mint,
+        &spl_token::id(),
+    );
+    assert_eq!(instruction.accounts[15..], [
+        AccountMeta::new(authority_ata, false),
+        AccountMeta::new(state_ata, false),
+    ]);
+}
@@ -0,0 +1,244 @@
+//! Integration tests of the jackpot's additional mints paid with the draws' prizes.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        constants::JACKPOT_MINTS_SIZE,
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondDraw, BondJackpotMints, BondPayoutMode, BondPrize, BondSeed},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+    spl_associated_token_account::{
+        get_associated_token_address,
+        instruction::create_associated_token_account,
+    },
+};
+
+/// The jackpot paid by the draws.
+const JACKPOT: u64 = 5_000;
+
+/// The jackpot's balance of the additional mint.
+const PRIZE: u64 = 300;
+
+/// The balance of the holder.
+const BALANCE: u64 = 1_000;
+
+/// A game escrowing its prizes, whose jackpot holds [JACKPOT] and [PRIZE] of an additional mint, 
+/// and a holder of [BALANCE].
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    mint: Pubkey,
+    holder: Keypair,
+    holder_ata: Pubkey,
+    holder_mint_ata: Pubkey,
+}
+
+impl Setup {
+
+    /// The jackpot mints' address.
+    fn jackpot_mints(&self) -> Pubkey {
+        BondSeed::JackpotMints.derive(&defi_bond::id(), &self.game.config()).0
+    }
+
+    /// The jackpot's token account for the additional mint.
+    fn jackpot_mint_ata(&self) -> Pubkey {
+        get_associated_token_address(&self.game.jackpot(), &self.mint)
+    }
+
+    /// The additional mint's accounts of the draw and the claim.
+    fn prize_accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.jackpot_mints(), false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(self.jackpot_mint_ata(), false),
+            AccountMeta::new(self.holder_mint_ata, false),
+        ]
+    }
+}
+
+async fn setup(claim_window: Option<i64>) -> Setup {
+    let (mut context, game) = start(ConfigParams {
+        payout_mode: Some(BondPayoutMode::Claim),
+        claim_window,
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let mint = create_mint(&mut context).await;
+    let (jackpot_mints, bump) = BondSeed::JackpotMints.derive(&defi_bond::id(), &game.config());
+    let register = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new(game.config(), false),
+            AccountMeta::new_readonly(game.jackpot(), false),
+            AccountMeta::new(jackpot_mints, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(get_associated_token_address(&game.jackpot(), &mint), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::RegisterJackpotMint {
+            bump,
+            space: JACKPOT_MINTS_SIZE as u32,
+        }.try_to_vec().unwrap(),
+    };
+    process(&mut context, vec![register], &[&game.authority]).await.unwrap();
+
+    let holder = Keypair::new();
+    let holder_ata = create_ata(&mut context, &game, &holder.pubkey()).await;
+    mint_to(&mut context, &game, &holder_ata, BALANCE).await;
+    let create_holder_mint_ata = create_associated_token_account(
+        &context.payer.pubkey(),
+        &holder.pubkey(),
+        &mint,
+        &spl_token::id(),
+    );
+    let fund_prize = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &mint,
+        &get_associated_token_address(&game.jackpot(), &mint),
+        &context.payer.pubkey(),
+        &[],
+        PRIZE,
+    ).unwrap();
+    process(&mut context, vec![create_holder_mint_ata, fund_prize], &[]).await.unwrap();
+    let holder_mint_ata = get_associated_token_address(&holder.pubkey(), &mint);
+    Setup { context, game, mint, holder, holder_ata, holder_mint_ata }
+}
+
+/// Runs the draw `draw_seed`, won by the holder, and returns its account.
+async fn draw(setup: &mut Setup, draw_seed: u64) -> Pubkey {
+    let draw = create_draw_account(&mut setup.context).await;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(setup.game.addresses.exclusion_list.0, false),
+    ];
+    accounts.extend(setup.prize_accounts());
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &setup.game.draw_authority.pubkey(),
+        &setup.game.config(),
+        &setup.game.token_mint,
+        &spl_token::id(),
+        &setup.holder.pubkey(),
+        &setup.holder_ata,
+        &draw,
+        &accounts,
+        42,
+        draw_seed,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    );
+    process(&mut setup.context, vec![instruction], &[&setup.game.draw_authority]).await.unwrap();
+    draw
+}
+
+/// Returns the jackpot mints' amount of the additional mint reserved for unclaimed prizes.
+async fn reserved(setup: &mut Setup) -> u64 {
+    let address = setup.jackpot_mints();
+    let data = account(&mut setup.context, &address).await.data;
+    let jackpot_mints = BondJackpotMints::deserialize(&mut data.as_slice()).unwrap();
+    jackpot_mints.reserved(&setup.mint)
+}
+
+#[tokio::test]
+async fn test_claim_prize_pays_extra_prizes() {
+    let mut setup = setup(None).await;
+    let draw = draw(&mut setup, 1).await;
+
+    // The additional mint's prize is reserved with the escrowed jackpot.
+    let recorded = BondDraw::unpack_from_slice(&account(&mut setup.context, &draw).await.data);
+    let prize = BondPrize { mint: setup.mint, amount: PRIZE };
+    assert_eq!(recorded.unwrap().extra_prizes, vec![prize]);
+    assert_eq!(reserved(&mut setup).await, PRIZE);
+    assert_eq!(balance(&mut setup.context, &setup.holder_mint_ata).await, 0);
+
+    let claim = claim_prize(&setup, &draw);
+    process(&mut setup.context, vec![claim], &[&setup.holder]).await.unwrap();
+
+    assert_eq!(balance(&mut setup.context, &setup.holder_ata).await, BALANCE + JACKPOT);
+    assert_eq!(balance(&mut setup.context, &setup.holder_mint_ata).await, PRIZE);
+    assert_eq!(reserved(&mut setup).await, 0);
+}
+
+#[tokio::test]
+async fn test_draw_excludes_reserved_extra_prizes() {
+    let mut setup = setup(None).await;
+    draw(&mut setup, 1).await;
+
+    // The next escrowed draw only reserves the additional mint's unreserved balance.
+    let mint_to = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &setup.mint,
+        &setup.jackpot_mint_ata(),
+        &setup.context.payer.pubkey(),
+        &[],
+        100,
+    ).unwrap();
+    process(&mut setup.context, vec![mint_to], &[]).await.unwrap();
+    let game = &setup.game;
+    common::mint_to(&mut setup.context, game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let draw = draw(&mut setup, 2).await;
+
+    let recorded = BondDraw::unpack_from_slice(&account(&mut setup.context, &draw).await.data);
+    let prize = BondPrize { mint: setup.mint, amount: 100 };
+    assert_eq!(recorded.unwrap().extra_prizes, vec![prize]);
+    assert_eq!(reserved(&mut setup).await, PRIZE + 100);
+}
+
+#[tokio::test]
+async fn test_expire_claim_releases_extra_prizes() {
+    let mut setup = setup(Some(0)).await;
+    let draw = draw(&mut setup, 1).await;
+
+    let expire = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(setup.game.config(), false),
+            AccountMeta::new(setup.game.state(), false),
+            AccountMeta::new(draw, false),
+            AccountMeta::new(setup.jackpot_mints(), false),
+        ],
+        data: BondInstruction::ExpireClaim.try_to_vec().unwrap(),
+    };
+    process(&mut setup.context, vec![expire], &[]).await.unwrap();
+
+    assert_eq!(reserved(&mut setup).await, 0);
+    let jackpot_mint_ata = setup.jackpot_mint_ata();
+    assert_eq!(balance(&mut setup.context, &jackpot_mint_ata).await, PRIZE);
+}
+
+/// A `ClaimPrize` instruction of the holder for `draw`.
+fn claim_prize(setup: &Setup, draw: &Pubkey) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(setup.holder.pubkey(), true),
+        AccountMeta::new_readonly(setup.game.config(), false),
+        AccountMeta::new(setup.game.state(), false),
+        AccountMeta::new_readonly(setup.game.jackpot(), false),
+        AccountMeta::new(setup.game.addresses.jackpot_ata, false),
+        AccountMeta::new(*draw, false),
+        AccountMeta::new(setup.holder_ata, false),
+        AccountMeta::new_readonly(setup.game.token_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend(setup.prize_accounts());
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts,
+        data: BondInstruction::ClaimPrize.try_to_vec().unwrap(),
+    }
+}
@@ -0,0 +1,108 @@
+//! Integration tests of `CreateLeaderboard` and the leaderboard updates of draws.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        constants::LEADERBOARD_SIZE,
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondConfig, BondLeaderboard, BondLeaderboardEntry, BondSeed},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+};
+
+/// The jackpot paid by the draw.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner.
+const BALANCE: u64 = 1_000;
+
+/// A `CreateLeaderboard` instruction of `game`.
+fn create_leaderboard(context: &ProgramTestContext, game: &Game) -> Instruction {
+    let (leaderboard, bump) = BondSeed::Leaderboard.derive(&defi_bond::id(), &game.config());
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new(game.config(), false),
+            AccountMeta::new(leaderboard, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::CreateLeaderboard { bump, space: LEADERBOARD_SIZE as u32 }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Returns the game's leaderboard.
+async fn leaderboard(context: &mut ProgramTestContext, game: &Game) -> BondLeaderboard {
+    let leaderboard = BondSeed::Leaderboard.derive(&defi_bond::id(), &game.config()).0;
+    let data = account(context, &leaderboard).await.data;
+    BondLeaderboard::deserialize(&mut data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn test_create_leaderboard() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let instruction = create_leaderboard(&context, &game);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    let address = BondSeed::Leaderboard.derive(&defi_bond::id(), &game.config()).0;
+    let data = account(&mut context, &game.config()).await.data;
+    assert_eq!(BondConfig::unpack_from_slice(&data).unwrap().leaderboard, Some(address));
+    let leaderboard = leaderboard(&mut context, &game).await;
+    assert_eq!(leaderboard.authority, game.config());
+    assert!(leaderboard.entries.is_empty());
+
+    // The game's leaderboard can't be replaced.
+    context.get_new_latest_blockhash().await.unwrap();
+    let instruction = create_leaderboard(&context, &game);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::AccountAlreadyInitialized);
+}
+
+#[tokio::test]
+async fn test_draw_records_winner() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let instruction = create_leaderboard(&context, &game);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let winner = Pubkey::new_unique();
+    let winner_ata = create_ata(&mut context, &game, &winner).await;
+    mint_to(&mut context, &game, &winner_ata, BALANCE).await;
+    let draw = create_draw_account(&mut context).await;
+
+    let address = BondSeed::Leaderboard.derive(&defi_bond::id(), &game.config()).0;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &winner,
+        &winner_ata,
+        &draw,
+        &[
+            AccountMeta::new(address, false),
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+        ],
+        42,
+        1,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    let entries = leaderboard(&mut context, &game).await.entries;
+    assert_eq!(entries, vec![BondLeaderboardEntry { wallet: winner, amount: JACKPOT }]);
+}
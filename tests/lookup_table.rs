@@ -0,0 +1,103 @@
+//! Integration tests of `CreateLookupTable` and `ExtendLookupTable`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        instruction::{BondInstruction, ConfigParams, LOOKUP_TABLE_PROGRAM_ID},
+        state::BondConfig,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        slot_hashes::SlotHashes,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+};
+
+/// The size of a lookup table's metadata, before its addresses.
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// The accounts shared by the lookup table instructions of `game`.
+fn lookup_table_accounts(
+    context: &ProgramTestContext,
+    game: &Game,
+    lookup_table: &Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(game.authority.pubkey(), true),
+        AccountMeta::new(context.payer.pubkey(), true),
+        AccountMeta::new(game.config(), false),
+        AccountMeta::new_readonly(game.state(), false),
+        AccountMeta::new(*lookup_table, false),
+        AccountMeta::new_readonly(LOOKUP_TABLE_PROGRAM_ID, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+/// Creates the game's lookup table and returns its address.
+async fn create_lookup_table(context: &mut ProgramTestContext, game: &Game) -> Pubkey {
+    let slot_hashes = context.banks_client.get_sysvar::<SlotHashes>().await.unwrap();
+    let recent_slot = slot_hashes.first().unwrap().0;
+    let (lookup_table, bump) = Pubkey::find_program_address(
+        &[game.state().as_ref(), &recent_slot.to_le_bytes()],
+        &LOOKUP_TABLE_PROGRAM_ID,
+    );
+    let mut accounts = lookup_table_accounts(context, game, &lookup_table);
+    accounts.push(AccountMeta::new_readonly(game.token_mint, false));
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts,
+        data: BondInstruction::CreateLookupTable { recent_slot, bump }.try_to_vec().unwrap(),
+    };
+    process(context, vec![instruction], &[&game.authority]).await.unwrap();
+    lookup_table
+}
+
+/// Returns the addresses in `lookup_table`.
+async fn addresses(context: &mut ProgramTestContext, lookup_table: &Pubkey) -> Vec<Pubkey> {
+    let lookup_table = account(context, lookup_table).await;
+    assert_eq!(lookup_table.owner, LOOKUP_TABLE_PROGRAM_ID);
+    lookup_table.data[LOOKUP_TABLE_META_SIZE..]
+        .chunks(32)
+        .map(|address| Pubkey::try_from(address).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_create_lookup_table() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let lookup_table = create_lookup_table(&mut context, &game).await;
+
+    // The program, config, mint, token program, PDAs and ATAs.
+    let addresses = addresses(&mut context, &lookup_table).await;
+    assert_eq!(addresses.len(), 16);
+    let game_addresses = [defi_bond::id(), game.config(), game.token_mint, spl_token::id()];
+    assert_eq!(&addresses[..4], &game_addresses);
+    assert!(addresses.contains(&game.addresses.jackpot_ata));
+    let config = BondConfig::unpack_from_slice(&account(&mut context, &game.config()).await.data)
+        .unwrap();
+    assert_eq!(config.lookup_table, Some(lookup_table));
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let lookup_table = create_lookup_table(&mut context, &game).await;
+
+    let pool = Pubkey::new_unique();
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: lookup_table_accounts(&context, &game, &lookup_table),
+        data: BondInstruction::ExtendLookupTable { addresses: vec![pool] }.try_to_vec().unwrap(),
+    };
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let addresses = addresses(&mut context, &lookup_table).await;
+    assert_eq!(addresses.len(), 17);
+    assert_eq!(addresses[16], pool);
+}
@@ -0,0 +1,65 @@
+//! Integration tests of `SetMetadata`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{BondInstruction, ConfigParams},
+        state::{BondConfig, MAX_NAME_LEN, MAX_URI_LEN},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_pack::Pack,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+};
+
+/// A `SetMetadata` instruction of `game` setting `name` (zero padded) and `uri`.
+fn set_metadata(game: &Game, name: &[u8], uri: String) -> Instruction {
+    let mut padded_name = [0; MAX_NAME_LEN];
+    padded_name[..name.len()].copy_from_slice(name);
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new(game.config(), false),
+        ],
+        data: BondInstruction::SetMetadata { name: padded_name, uri }.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_set_metadata() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let uri = "u".repeat(MAX_URI_LEN);
+    let instruction = set_metadata(&game, "Bond Lotto".as_bytes(), uri.clone());
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    let config = BondConfig::unpack_from_slice(&account(&mut context, &game.config()).await.data)
+        .unwrap();
+    assert_eq!(&config.name[..10], "Bond Lotto".as_bytes());
+    assert_eq!(&config.name[10..], &[0; MAX_NAME_LEN - 10]);
+    assert_eq!(config.uri, uri);
+}
+
+#[tokio::test]
+async fn test_set_metadata_uri_too_long() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let instruction = set_metadata(&game, &[], "u".repeat(MAX_URI_LEN + 1));
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::UriTooLong);
+}
+
+#[tokio::test]
+async fn test_set_metadata_invalid_name() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+
+    // Zero bytes may only pad the end of the name.
+    let instruction = set_metadata(&game, &[b'a', 0, b'b'], String::new());
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidName);
+}
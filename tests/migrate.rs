@@ -0,0 +1,119 @@
+//! Integration tests of `Migrate`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        constants::{ACCOUNT_DISCRIMINATOR_SIZE, ACCOUNT_VERSION_OFFSET, STATE_SIZE},
+        error::BondError,
+        instruction::{BondInstruction, ConfigParams},
+        state::{BondAccountType, ACCOUNT_LAYOUT_VERSION, DISCRIMINATOR_LAYOUT_VERSION},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// The number of bytes appended to the state since the previous layout (in this test).
+const APPENDED: usize = 16;
+
+/// A `Migrate` instruction of `authority` upgrading `address` to `space` bytes.
+fn migrate(game: &Game, authority: &Keypair, address: &Pubkey, space: usize) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(*address, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::Migrate { space: space as u32 }.try_to_vec().unwrap(),
+    }
+}
+
+/// Rewrites the game's state as written by the previous layout, without its appended fields.
+async fn set_previous_layout(context: &mut ProgramTestContext, game: &Game) {
+    let mut account = account(context, &game.state()).await;
+    account.data.truncate(STATE_SIZE - APPENDED);
+    account.data[ACCOUNT_VERSION_OFFSET] = ACCOUNT_LAYOUT_VERSION - 1;
+    context.set_account(&game.state(), &account.into());
+}
+
+#[tokio::test]
+async fn test_migrate() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    fund(&mut context, &game.authority.pubkey(), 1_000_000_000).await;
+    let current = state(&mut context, &game).await;
+    set_previous_layout(&mut context, &game).await;
+
+    // The account grows to the current layout, whose appended fields read as zeroed defaults.
+    let instruction = migrate(&game, &game.authority, &game.state(), STATE_SIZE);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let data = account(&mut context, &game.state()).await.data;
+    assert_eq!(data.len(), STATE_SIZE);
+    assert_eq!(data[ACCOUNT_VERSION_OFFSET], ACCOUNT_LAYOUT_VERSION);
+    assert_eq!(state(&mut context, &game).await, current);
+
+    // Up to date accounts are left unchanged.
+    context.get_new_latest_blockhash().await.unwrap();
+    let instruction = migrate(&game, &game.authority, &game.state(), STATE_SIZE + 8);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    assert_eq!(account(&mut context, &game.state()).await.data.len(), STATE_SIZE);
+}
+
+#[tokio::test]
+async fn test_migrate_legacy_discriminator() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    fund(&mut context, &game.authority.pubkey(), 1_000_000_000).await;
+    let current = state(&mut context, &game).await;
+
+    // The state as written with a 1-byte `account_type`, before discriminators.
+    let mut legacy = account(&mut context, &game.state()).await;
+    let mut data = vec![BondAccountType::State as u8, DISCRIMINATOR_LAYOUT_VERSION - 1];
+    data.extend_from_slice(&legacy.data[ACCOUNT_VERSION_OFFSET + 1..]);
+    legacy.data = data;
+    context.set_account(&game.state(), &legacy.into());
+
+    // The account must grow to fit its discriminator.
+    let instruction = migrate(&game, &game.authority, &game.state(), STATE_SIZE - 1);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::AccountDataTooSmall);
+
+    // Its fields are shifted behind the discriminator.
+    let instruction = migrate(&game, &game.authority, &game.state(), STATE_SIZE);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let data = account(&mut context, &game.state()).await.data;
+    assert_eq!(data[..ACCOUNT_DISCRIMINATOR_SIZE], BondAccountType::State.discriminator());
+    assert_eq!(data[ACCOUNT_VERSION_OFFSET], ACCOUNT_LAYOUT_VERSION);
+    assert_eq!(state(&mut context, &game).await, current);
+}
+
+#[tokio::test]
+async fn test_migrate_newer_version() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let mut account = account(&mut context, &game.state()).await;
+    account.data[ACCOUNT_VERSION_OFFSET] = ACCOUNT_LAYOUT_VERSION + 1;
+    context.set_account(&game.state(), &account.into());
+
+    let instruction = migrate(&game, &game.authority, &game.state(), STATE_SIZE);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidAccountVersion);
+}
+
+#[tokio::test]
+async fn test_migrate_of_another_authority() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    set_previous_layout(&mut context, &game).await;
+
+    let other = Keypair::new();
+    fund(&mut context, &other.pubkey(), 1_000_000_000).await;
+    let instruction = migrate(&game, &other, &game.state(), STATE_SIZE);
+    let result = process(&mut context, vec![instruction], &[&other]).await;
+    assert_bond_error(result, 0, BondError::InvalidAuthority);
+}
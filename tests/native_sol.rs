@@ -0,0 +1,151 @@
+//! Integration tests of the prizes of wrapped SOL games, claimed as native SOL.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondPayoutAsset, BondPayoutMode},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        system_instruction,
+    },
+    spl_token::native_mint,
+};
+
+/// The jackpot escrowed by the draw, in lamports.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner, in lamports.
+const BALANCE: u64 = 1_000;
+
+/// A wrapped SOL game escrowing its prizes, whose first draw was won by a holder.
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    winner: Keypair,
+    draw: Pubkey,
+}
+
+/// Wraps `lamports` into the wrapped SOL account `account`.
+async fn wrap(context: &mut ProgramTestContext, account: &Pubkey, lamports: u64) {
+    let instructions = vec![
+        system_instruction::transfer(&context.payer.pubkey(), account, lamports),
+        spl_token::instruction::sync_native(&spl_token::id(), account).unwrap(),
+    ];
+    process(context, instructions, &[]).await.unwrap();
+}
+
+/// Starts a wrapped SOL game escrowing its prizes and runs its first draw.
+async fn start_wrapped() -> Setup {
+    let mut context = program_test().start_with_context().await;
+    let game = create_game_for_mint(&mut context, native_mint::id(), ConfigParams {
+        payout_mode: Some(BondPayoutMode::Claim),
+        ..ConfigParams::default()
+    }).await;
+    wrap(&mut context, &game.addresses.jackpot_ata, JACKPOT).await;
+    let winner = Keypair::new();
+    let winner_ata = create_ata(&mut context, &game, &winner.pubkey()).await;
+    wrap(&mut context, &winner_ata, BALANCE).await;
+    let draw = create_draw_account(&mut context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &winner.pubkey(),
+        &winner_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    Setup { context, game, winner, draw }
+}
+
+/// Creates a temporary wrapped SOL account of `owner` and returns it with its lamports.
+async fn create_temporary_account(
+    context: &mut ProgramTestContext,
+    owner: &Pubkey,
+) -> (Pubkey, u64) {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let lamports = rent.minimum_balance(spl_token::state::Account::LEN);
+    let instructions = vec![
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &account.pubkey(),
+            lamports,
+            spl_token::state::Account::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account3(
+            &spl_token::id(),
+            &account.pubkey(),
+            &native_mint::id(),
+            owner,
+        ).unwrap(),
+    ];
+    process(context, instructions, &[&account]).await.unwrap();
+    (account.pubkey(), lamports)
+}
+
+/// A `SetPayoutAsset` instruction of the winner choosing native SOL.
+fn set_native_sol(setup: &Setup) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(setup.winner.pubkey(), true),
+            AccountMeta::new_readonly(setup.game.config(), false),
+            AccountMeta::new(setup.draw, false),
+        ],
+        data: BondInstruction::SetPayoutAsset { payout_asset: BondPayoutAsset::NativeSol }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_claim_prize_native_sol() {
+    let mut setup = start_wrapped().await;
+    let instruction = set_native_sol(&setup);
+    process(&mut setup.context, vec![instruction], &[&setup.winner]).await.unwrap();
+
+    // The prize is paid to a temporary account, closed into the winner's wallet.
+    let winner = setup.winner.pubkey();
+    let (temporary, rent) = create_temporary_account(&mut setup.context, &winner).await;
+    let mut instruction = claim_prize(&setup.game, &winner, &temporary, &setup.draw, &[]);
+    instruction.accounts[0].is_writable = true;
+    process(&mut setup.context, vec![instruction], &[&setup.winner]).await.unwrap();
+    assert!(setup.context.banks_client.get_account(temporary).await.unwrap().is_none());
+    assert_eq!(account(&mut setup.context, &winner).await.lamports, JACKPOT + rent);
+}
+
+#[tokio::test]
+async fn test_claim_prize_native_sol_readonly_receiver() {
+    let mut setup = start_wrapped().await;
+    let instruction = set_native_sol(&setup);
+    process(&mut setup.context, vec![instruction], &[&setup.winner]).await.unwrap();
+
+    // The winner's wallet receives the lamports.
+    let winner = setup.winner.pubkey();
+    let (temporary, _) = create_temporary_account(&mut setup.context, &winner).await;
+    let instruction = claim_prize(&setup.game, &winner, &temporary, &setup.draw, &[]);
+    let result = process(&mut setup.context, vec![instruction], &[&setup.winner]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+    assert_eq!(balance(&mut setup.context, &temporary).await, 0);
+}
@@ -0,0 +1,121 @@
+//! Integration tests of draws whose results are signed by an oracle.
+
+mod common;
+
+use {
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, ConfigParams},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// The jackpot paid by the draw.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner.
+const BALANCE: u64 = 1_000;
+
+/// The seed of the draw.
+const DRAW_SEED: u64 = 1;
+
+/// The seed of the draw's receiver.
+const RECEIVER_SEED: u64 = 42;
+
+/// A game whose draw results are signed by an oracle, with a funded jackpot and a holder.
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    oracle: Keypair,
+    winner: Pubkey,
+    winner_ata: Pubkey,
+    draw: Pubkey,
+}
+
+/// Starts a game requiring the signature of a new oracle.
+async fn start_signed() -> Setup {
+    let oracle = Keypair::new();
+    let (mut context, game) = start(ConfigParams {
+        oracle: Some(oracle.pubkey()),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let winner = Pubkey::new_unique();
+    let winner_ata = create_ata(&mut context, &game, &winner).await;
+    mint_to(&mut context, &game, &winner_ata, BALANCE).await;
+    let draw = create_draw_account(&mut context).await;
+    Setup { context, game, oracle, winner, winner_ata, draw }
+}
+
+/// The signed result of the draw selecting `receiver`.
+fn result_message(setup: &Setup, receiver: &Pubkey) -> Vec<u8> {
+    let mut message = setup.game.config().to_bytes().to_vec();
+    message.extend_from_slice(&DRAW_SEED.to_le_bytes());
+    message.extend_from_slice(receiver.as_ref());
+    message.extend_from_slice(&RECEIVER_SEED.to_le_bytes());
+    message
+}
+
+/// A `Draw` instruction of the setup's holder.
+fn draw(setup: &Setup) -> Instruction {
+    let game = &setup.game;
+    instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &setup.winner,
+        &setup.winner_ata,
+        &setup.draw,
+        &[
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+        ],
+        RECEIVER_SEED,
+        DRAW_SEED,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    )
+}
+
+#[tokio::test]
+async fn test_draw_signed_by_oracle() {
+    let mut setup = start_signed().await;
+    let message = result_message(&setup, &setup.winner);
+    let instructions = vec![ed25519_instruction(&setup.oracle, &message), draw(&setup)];
+    process(&mut setup.context, instructions, &[&setup.game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut setup.context, &setup.winner_ata).await, BALANCE + JACKPOT);
+}
+
+#[tokio::test]
+async fn test_draw_unsigned() {
+    let mut setup = start_signed().await;
+    let instructions = vec![draw(&setup)];
+    let result = process(&mut setup.context, instructions, &[&setup.game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidOracleSignature);
+
+    // Signatures of other keys are ignored.
+    let message = result_message(&setup, &setup.winner);
+    let instructions = vec![ed25519_instruction(&Keypair::new(), &message), draw(&setup)];
+    let result = process(&mut setup.context, instructions, &[&setup.game.draw_authority]).await;
+    assert_bond_error(result, 1, BondError::InvalidOracleSignature);
+}
+
+#[tokio::test]
+async fn test_draw_signed_for_another_receiver() {
+    let mut setup = start_signed().await;
+    let message = result_message(&setup, &Pubkey::new_unique());
+    let instructions = vec![ed25519_instruction(&setup.oracle, &message), draw(&setup)];
+    let result = process(&mut setup.context, instructions, &[&setup.game.draw_authority]).await;
+    assert_bond_error(result, 1, BondError::InvalidOracleSignature);
+    assert_eq!(balance(&mut setup.context, &setup.winner_ata).await, BALANCE);
+}
@@ -0,0 +1,125 @@
+//! Integration tests of snapshots written to participant buffers with `WriteParticipants`.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{BondInstruction, ConfigParams},
+        state::{BondParticipantBuffer, BondSeed, BondSnapshot, BondSnapshotEntry},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+};
+
+/// The weight of each holder of the snapshot.
+const WEIGHT: u64 = 1_000;
+
+/// Returns the address of the buffer `index` of the draw `draw_id`'s snapshot and its bump.
+fn buffer_address(game: &Game, draw_id: u64, index: u32) -> (Pubkey, u8) {
+    let seed_index = BondParticipantBuffer::seed_index(draw_id, index);
+    BondSeed::ParticipantBuffer.derive_indexed(&defi_bond::id(), &game.config(), &seed_index)
+}
+
+/// A `WriteParticipants` instruction appending `holders` to the buffer `index` of the first
+/// draw's snapshot.
+fn write_participants(game: &Game, index: u32, holders: &[Pubkey]) -> Instruction {
+    let (snapshot, snapshot_bump) = snapshot_address(game, 1);
+    let (buffer, bump) = buffer_address(game, 1, index);
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(game.draw_authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new(snapshot, false),
+            AccountMeta::new(buffer, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::WriteParticipants {
+            snapshot_bump,
+            bump,
+            index,
+            entries: holders.iter().map(|holder| BondSnapshotEntry {
+                wallet: *holder,
+                weight: WEIGHT,
+                held_since: 0,
+            }).collect(),
+        }.try_to_vec().unwrap(),
+    }
+}
+
+/// A `FinalizeSnapshot` instruction sealing the first draw's snapshot.
+fn finalize_snapshot(game: &Game) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.draw_authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new(snapshot_address(game, 1).0, false),
+        ],
+        data: BondInstruction::FinalizeSnapshot.try_to_vec().unwrap(),
+    }
+}
+
+/// Starts a game whose draw window opened before the snapshot's holders were written.
+async fn start_buffered() -> (ProgramTestContext, Game) {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    set_draw_window(&mut context, 1_000).await;
+    fund(&mut context, &game.draw_authority.pubkey(), 1_000_000_000).await;
+    (context, game)
+}
+
+#[tokio::test]
+async fn test_write_participants() {
+    let (mut context, game) = start_buffered().await;
+    let holders: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+
+    // The first buffer is appended to before the second is opened, then the snapshot is sealed.
+    let instructions = vec![
+        write_participants(&game, 0, &holders[..2]),
+        write_participants(&game, 0, &holders[2..3]),
+        write_participants(&game, 1, &holders[3..]),
+        finalize_snapshot(&game),
+    ];
+    process(&mut context, instructions, &[&game.draw_authority]).await.unwrap();
+
+    let data = account(&mut context, &snapshot_address(&game, 1).0).await.data;
+    let snapshot = BondSnapshot::deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!((snapshot.buffers, snapshot.total_weight), (2, 5 * WEIGHT));
+    assert!(snapshot.entries.is_empty() && snapshot.is_sealed);
+    for (index, offset, count) in [(0, 0, 3), (1, 3 * WEIGHT, 2)] {
+        let data = account(&mut context, &buffer_address(&game, 1, index).0).await.data;
+        let buffer = BondParticipantBuffer::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(buffer.weight_offset, offset);
+        assert_eq!(buffer.entries.len(), count);
+    }
+}
+
+#[tokio::test]
+async fn test_write_participants_out_of_order() {
+    let (mut context, game) = start_buffered().await;
+    let holder = Pubkey::new_unique();
+
+    // Buffers are opened in order.
+    let instruction = write_participants(&game, 1, &[holder]);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidSnapshot);
+
+    // Earlier buffers are closed once the next is opened.
+    let instructions = vec![
+        write_participants(&game, 0, &[holder]),
+        write_participants(&game, 1, &[holder]),
+        write_participants(&game, 0, &[holder]),
+    ];
+    let result = process(&mut context, instructions, &[&game.draw_authority]).await;
+    assert_bond_error(result, 2, BondError::InvalidSnapshot);
+}
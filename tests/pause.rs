@@ -0,0 +1,127 @@
+//! Integration tests of `Pause` and `Resume`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, BondInstruction, ConfigParams},
+        state::BondLstAdapter,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// A `PruneExpired` instruction of `game`.
+fn prune_expired(game: &Game) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.exclusion_list.0, false),
+        ],
+        data: BondInstruction::PruneExpired.try_to_vec().unwrap(),
+    }
+}
+
+/// A permissionless `SplitShares` instruction of `game`.
+fn split_shares(game: &Game) -> Instruction {
+    instruction::split_shares(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        None,
+        false,
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+async fn test_pause_rejects_draw() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, 5_000).await;
+    let holder = Pubkey::new_unique();
+    let holder_ata = create_ata(&mut context, &game, &holder).await;
+    mint_to(&mut context, &game, &holder_ata, 1_000).await;
+    let draw = create_draw_account(&mut context).await;
+    pause(&mut context, &game).await;
+
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &holder,
+        &holder_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        1_000,
+        10_000,
+        None,
+    );
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::GameInactive);
+    assert_eq!(balance(&mut context, &holder_ata).await, 1_000);
+}
+
+#[tokio::test]
+async fn test_pause_rejects_split_shares() {
+    let (mut context, game) = start(ConfigParams {
+        lst_adapter: Some(BondLstAdapter::Marinade),
+        ..ConfigParams::default()
+    }).await;
+    pause(&mut context, &game).await;
+
+    let result = process(&mut context, vec![split_shares(&game)], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::GameInactive);
+}
+
+#[tokio::test]
+async fn test_pause_rejects_prune_expired() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    process(&mut context, vec![prune_expired(&game)], &[]).await.unwrap();
+    pause(&mut context, &game).await;
+
+    let result = process(&mut context, vec![prune_expired(&game)], &[]).await;
+    assert_bond_error(result, 0, BondError::GameInactive);
+
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new(game.config(), false),
+        ],
+        data: BondInstruction::Resume.try_to_vec().unwrap(),
+    };
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+    process(&mut context, vec![prune_expired(&game)], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pause_of_another_authority() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let other = Keypair::new();
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(other.pubkey(), true),
+            AccountMeta::new(game.config(), false),
+        ],
+        data: BondInstruction::Pause.try_to_vec().unwrap(),
+    };
+    let result = process(&mut context, vec![instruction], &[&other]).await;
+    assert_bond_error(result, 0, BondError::InvalidAuthority);
+}
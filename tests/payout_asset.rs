@@ -0,0 +1,130 @@
+//! Integration tests of `SetPayoutAsset`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondDraw, BondLstAdapter, BondPayoutAsset, BondPayoutMode},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// The jackpot paid by the draw.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner.
+const BALANCE: u64 = 1_000;
+
+/// A game in claim mode whose jackpot of [JACKPOT] was won by a holder.
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    winner: Keypair,
+    winner_ata: Pubkey,
+    draw: Pubkey,
+}
+
+/// Starts a game in claim mode with `params` and runs a draw won by a new holder.
+async fn start_won(params: ConfigParams) -> Setup {
+    let (mut context, game) = start(ConfigParams {
+        payout_mode: Some(BondPayoutMode::Claim),
+        ..params
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let winner = Keypair::new();
+    let winner_ata = create_ata(&mut context, &game, &winner.pubkey()).await;
+    mint_to(&mut context, &game, &winner_ata, BALANCE).await;
+    let draw = create_draw_account(&mut context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &winner.pubkey(),
+        &winner_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    Setup { context, game, winner, winner_ata, draw }
+}
+
+/// A `SetPayoutAsset` instruction of `receiver` for the draw of `setup`.
+fn set_payout_asset(
+    setup: &Setup,
+    receiver: &Pubkey,
+    payout_asset: BondPayoutAsset,
+) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*receiver, true),
+            AccountMeta::new_readonly(setup.game.config(), false),
+            AccountMeta::new(setup.draw, false),
+        ],
+        data: BondInstruction::SetPayoutAsset { payout_asset }.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_set_payout_asset() {
+    let mut setup = start_won(ConfigParams::default()).await;
+    let instruction = set_payout_asset(&setup, &setup.winner.pubkey(), BondPayoutAsset::Sol);
+    process(&mut setup.context, vec![instruction], &[&setup.winner]).await.unwrap();
+    let data = account(&mut setup.context, &setup.draw).await.data;
+    assert_eq!(BondDraw::unpack_from_slice(&data).unwrap().payout_asset, BondPayoutAsset::Sol);
+
+    // Only the winner chooses.
+    let other = Keypair::new();
+    let instruction = set_payout_asset(&setup, &other.pubkey(), BondPayoutAsset::Token);
+    let result = process(&mut setup.context, vec![instruction], &[&other]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn test_set_payout_asset_unsupported() {
+    let mut setup = start_won(ConfigParams {
+        lst_adapter: Some(BondLstAdapter::Marinade),
+        ..ConfigParams::default()
+    }).await;
+
+    // SOL is only redeemed from SPL stake pools, and only wrapped SOL is unwrapped.
+    for payout_asset in [BondPayoutAsset::Sol, BondPayoutAsset::NativeSol] {
+        let instruction = set_payout_asset(&setup, &setup.winner.pubkey(), payout_asset);
+        let result = process(&mut setup.context, vec![instruction], &[&setup.winner]).await;
+        assert_error(result, 0, InstructionError::InvalidArgument);
+    }
+}
+
+#[tokio::test]
+async fn test_set_payout_asset_claimed() {
+    let mut setup = start_won(ConfigParams::default()).await;
+    let instruction = claim_prize(
+        &setup.game,
+        &setup.winner.pubkey(),
+        &setup.winner_ata,
+        &setup.draw,
+        &[],
+    );
+    process(&mut setup.context, vec![instruction], &[&setup.winner]).await.unwrap();
+
+    let instruction = set_payout_asset(&setup, &setup.winner.pubkey(), BondPayoutAsset::Sol);
+    let result = process(&mut setup.context, vec![instruction], &[&setup.winner]).await;
+    assert_bond_error(result, 0, BondError::PrizeAlreadyClaimed);
+}
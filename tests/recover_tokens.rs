@@ -0,0 +1,113 @@
+//! Integration tests of `RecoverTokens`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{BondInstruction, ConfigParams},
+        state::BondSeed,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+    spl_associated_token_account::{
+        get_associated_token_address,
+        instruction::create_associated_token_account,
+    },
+};
+
+/// The stray balance sent to the jackpot.
+const STRAY: u64 = 300;
+
+/// A `RecoverTokens` instruction of `authority` sweeping the jackpot's `stray` account of `mint`.
+fn recover_tokens(game: &Game, authority: &Keypair, stray: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.jackpot(), false),
+            AccountMeta::new(*stray, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(game.addresses.fee.0, false),
+            AccountMeta::new(get_associated_token_address(&game.addresses.fee.0, mint), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::RecoverTokens { seed: BondSeed::Jackpot }.try_to_vec().unwrap(),
+    }
+}
+
+/// Sends [STRAY] tokens of a new mint to the jackpot's associated token account for it, and
+/// returns the mint and the account.
+async fn send_stray(context: &mut ProgramTestContext, game: &Game) -> (Pubkey, Pubkey) {
+    let mint = create_mint(context).await;
+    let stray = get_associated_token_address(&game.jackpot(), &mint);
+    let instructions = vec![
+        create_associated_token_account(
+            &context.payer.pubkey(),
+            &game.jackpot(),
+            &mint,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &mint,
+            &stray,
+            &context.payer.pubkey(),
+            &[],
+            STRAY,
+        ).unwrap(),
+    ];
+    process(context, instructions, &[]).await.unwrap();
+    (mint, stray)
+}
+
+#[tokio::test]
+async fn test_recover_tokens() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    fund(&mut context, &game.authority.pubkey(), 1_000_000_000).await;
+    let (mint, stray) = send_stray(&mut context, &game).await;
+
+    // The stray balance is swept into the fee's account for its mint, created by the sweep.
+    let instruction = recover_tokens(&game, &game.authority, &stray, &mint);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let fee_ata = get_associated_token_address(&game.addresses.fee.0, &mint);
+    assert_eq!(balance(&mut context, &fee_ata).await, STRAY);
+    assert!(context.banks_client.get_account(stray).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_recover_tokens_in_use() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    fund(&mut context, &game.authority.pubkey(), 1_000_000_000).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, STRAY).await;
+
+    // The jackpot's account for the game's mint isn't stray.
+    let jackpot_ata = game.addresses.jackpot_ata;
+    let instruction = recover_tokens(&game, &game.authority, &jackpot_ata, &game.token_mint);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::TokenAccountInUse);
+    assert_eq!(balance(&mut context, &jackpot_ata).await, STRAY);
+}
+
+#[tokio::test]
+async fn test_recover_tokens_of_another_authority() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let (mint, stray) = send_stray(&mut context, &game).await;
+
+    let other = Keypair::new();
+    fund(&mut context, &other.pubkey(), 1_000_000_000).await;
+    let instruction = recover_tokens(&game, &other, &stray, &mint);
+    let result = process(&mut context, vec![instruction], &[&other]).await;
+    assert_bond_error(result, 0, BondError::InvalidAuthority);
+    assert_eq!(balance(&mut context, &stray).await, STRAY);
+}
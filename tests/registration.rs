@@ -0,0 +1,157 @@
+//! Integration tests of `RegisterParticipant` and draws of games requiring registration.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        constants::PARTICIPANT_SIZE,
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondParticipant, BondSeed},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// The jackpot paid by the draws.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner's payout account.
+const BALANCE: u64 = 1_000;
+
+/// The hash of the acknowledged terms.
+const TERMS_HASH: [u8; 32] = [7; 32];
+
+/// A game requiring registration whose jackpot holds [JACKPOT].
+async fn start_registration() -> (ProgramTestContext, Game) {
+    let (mut context, game) = start(ConfigParams {
+        require_registration: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    (context, game)
+}
+
+/// Returns the address of the registration of `wallet`.
+fn participant(game: &Game, wallet: &Pubkey) -> Pubkey {
+    BondSeed::Participant.derive_indexed(&defi_bond::id(), &game.config(), wallet.as_ref()).0
+}
+
+/// Registers `wallet` with prizes paid to `payout`, paid by the context's payer.
+async fn register(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    wallet: &Keypair,
+    payout: &Pubkey,
+) {
+    let (participant, bump) = BondSeed::Participant.derive_indexed(
+        &defi_bond::id(),
+        &game.config(),
+        wallet.pubkey().as_ref(),
+    );
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(wallet.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(participant, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::RegisterParticipant {
+            bump,
+            space: PARTICIPANT_SIZE as u32,
+            payout: *payout,
+            terms_hash: TERMS_HASH,
+        }.try_to_vec().unwrap(),
+    };
+    process(context, vec![instruction], &[wallet]).await.unwrap();
+}
+
+/// A `Draw` instruction of the first draw, won by `receiver` and paid to `receiver_ata`.
+fn draw(game: &Game, receiver: &Pubkey, receiver_ata: &Pubkey, draw: &Pubkey) -> Instruction {
+    instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        receiver,
+        receiver_ata,
+        draw,
+        &[
+            AccountMeta::new_readonly(participant(game, receiver), false),
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+        ],
+        42,
+        1,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    )
+}
+
+#[tokio::test]
+async fn test_register_participant() {
+    let (mut context, game) = start_registration().await;
+    let wallet = Keypair::new();
+    let payout = Pubkey::new_unique();
+    register(&mut context, &game, &wallet, &payout).await;
+
+    let data = account(&mut context, &participant(&game, &wallet.pubkey())).await.data;
+    let participant = BondParticipant::deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(participant.authority, game.config());
+    assert_eq!(participant.wallet, wallet.pubkey());
+    assert_eq!(participant.payout, payout);
+    assert_eq!(participant.terms_hash, TERMS_HASH);
+}
+
+#[tokio::test]
+async fn test_draw_pays_registered_payout() {
+    let (mut context, game) = start_registration().await;
+    let wallet = Keypair::new();
+    let payout = Pubkey::new_unique();
+    register(&mut context, &game, &wallet, &payout).await;
+    let payout_ata = create_ata(&mut context, &game, &payout).await;
+    mint_to(&mut context, &game, &payout_ata, BALANCE).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    let instruction = draw(&game, &wallet.pubkey(), &payout_ata, &draw_account);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &payout_ata).await, BALANCE + JACKPOT);
+}
+
+#[tokio::test]
+async fn test_draw_unregistered() {
+    let (mut context, game) = start_registration().await;
+    let wallet = Pubkey::new_unique();
+    let wallet_ata = create_ata(&mut context, &game, &wallet).await;
+    mint_to(&mut context, &game, &wallet_ata, BALANCE).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    let instruction = draw(&game, &wallet, &wallet_ata, &draw_account);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_error(result, 0, InstructionError::BorshIoError("Unknown".to_string()));
+    assert_eq!(balance(&mut context, &wallet_ata).await, BALANCE);
+}
+
+#[tokio::test]
+async fn test_draw_registered_to_another_payout() {
+    let (mut context, game) = start_registration().await;
+    let wallet = Keypair::new();
+    register(&mut context, &game, &wallet, &Pubkey::new_unique()).await;
+    let wallet_ata = create_ata(&mut context, &game, &wallet.pubkey()).await;
+    mint_to(&mut context, &game, &wallet_ata, BALANCE).await;
+    let draw_account = create_draw_account(&mut context).await;
+
+    // The prize can't be paid to the wallet's own token account.
+    let instruction = draw(&game, &wallet.pubkey(), &wallet_ata, &draw_account);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_error(result, 0, InstructionError::IncorrectProgramId);
+}
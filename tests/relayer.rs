@@ -0,0 +1,207 @@
+//! Integration tests of claims paid by relayers: `CreateSession` and `ClaimPrize` signed by the
+//! winner or its session key while another signer pays the fees.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        constants::SESSION_SIZE,
+        error::BondError,
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondPayoutMode, BondSeed},
+    },
+    solana_program::{
+        clock::Clock,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+
+/// The jackpot paid by the draw.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner.
+const BALANCE: u64 = 1_000;
+
+/// A game in claim mode whose jackpot of [JACKPOT] was won by a wallet without any SOL, and a
+/// relayer paying the fees of its transactions.
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    relayer: Keypair,
+    winner: Keypair,
+    winner_ata: Pubkey,
+    draw: Pubkey,
+}
+
+/// Starts a game in claim mode and runs a draw won by a new holder.
+async fn start_won() -> Setup {
+    let (mut context, game) = start(ConfigParams {
+        payout_mode: Some(BondPayoutMode::Claim),
+        ..ConfigParams::default()
+    }).await;
+    let relayer = Keypair::new();
+    fund(&mut context, &relayer.pubkey(), 1_000_000_000).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let winner = Keypair::new();
+    let winner_ata = create_ata(&mut context, &game, &winner.pubkey()).await;
+    mint_to(&mut context, &game, &winner_ata, BALANCE).await;
+    let draw = create_draw_account(&mut context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &winner.pubkey(),
+        &winner_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    Setup { context, game, relayer, winner, winner_ata, draw }
+}
+
+/// Processes `instructions` signed by `signers`, with the fees paid by `relayer`.
+async fn relay(
+    context: &mut ProgramTestContext,
+    relayer: &Keypair,
+    instructions: Vec<Instruction>,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all_signers = vec![relayer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&relayer.pubkey()),
+        &all_signers,
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Returns the address of the winner's session.
+fn session_address(setup: &Setup) -> (Pubkey, u8) {
+    BondSeed::Session.derive_indexed(
+        &defi_bond::id(),
+        &setup.game.config(),
+        setup.winner.pubkey().as_ref(),
+    )
+}
+
+/// Creates a session of the winner for `session_key` expiring at `expires_at`.
+async fn create_session(setup: &mut Setup, session_key: &Pubkey, expires_at: i64) {
+    let (session, bump) = session_address(setup);
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(setup.relayer.pubkey(), true),
+            AccountMeta::new_readonly(setup.winner.pubkey(), true),
+            AccountMeta::new_readonly(setup.game.config(), false),
+            AccountMeta::new(session, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::CreateSession {
+            bump,
+            space: SESSION_SIZE as u32,
+            session_key: *session_key,
+            expires_at,
+        }.try_to_vec().unwrap(),
+    };
+    let signers = [&setup.winner];
+    relay(&mut setup.context, &setup.relayer, vec![instruction], &signers).await.unwrap();
+}
+
+/// A `ClaimPrize` instruction of the winner signed by `session_key` instead.
+fn claim_prize_with_session(setup: &Setup, session_key: &Pubkey) -> Instruction {
+    let mut instruction = claim_prize(
+        &setup.game,
+        &setup.winner.pubkey(),
+        &setup.winner_ata,
+        &setup.draw,
+        &[
+            AccountMeta::new_readonly(*session_key, true),
+            AccountMeta::new_readonly(session_address(setup).0, false),
+        ],
+    );
+    instruction.accounts[0].is_signer = false;
+    instruction
+}
+
+/// Returns the current unix timestamp.
+async fn now(context: &mut ProgramTestContext) -> i64 {
+    context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+}
+
+#[tokio::test]
+async fn test_claim_prize_paid_by_relayer() {
+    let mut setup = start_won().await;
+    let instruction = claim_prize(
+        &setup.game,
+        &setup.winner.pubkey(),
+        &setup.winner_ata,
+        &setup.draw,
+        &[],
+    );
+    let signers = [&setup.winner];
+    relay(&mut setup.context, &setup.relayer, vec![instruction], &signers).await.unwrap();
+
+    // The winner is paid without holding any SOL.
+    assert_eq!(balance(&mut setup.context, &setup.winner_ata).await, BALANCE + JACKPOT);
+    let winner = setup.context.banks_client.get_account(setup.winner.pubkey()).await.unwrap();
+    assert!(winner.is_none());
+}
+
+#[tokio::test]
+async fn test_claim_prize_with_session() {
+    let mut setup = start_won().await;
+    let session_key = Keypair::new();
+    let expires_at = now(&mut setup.context).await + 3_600;
+    create_session(&mut setup, &session_key.pubkey(), expires_at).await;
+
+    let instruction = claim_prize_with_session(&setup, &session_key.pubkey());
+    let signers = [&session_key];
+    relay(&mut setup.context, &setup.relayer, vec![instruction], &signers).await.unwrap();
+    assert_eq!(balance(&mut setup.context, &setup.winner_ata).await, BALANCE + JACKPOT);
+}
+
+#[tokio::test]
+async fn test_claim_prize_with_expired_session() {
+    let mut setup = start_won().await;
+    let session_key = Keypair::new();
+    let expires_at = now(&mut setup.context).await;
+    create_session(&mut setup, &session_key.pubkey(), expires_at).await;
+
+    let instruction = claim_prize_with_session(&setup, &session_key.pubkey());
+    let signers = [&session_key];
+    let result = relay(&mut setup.context, &setup.relayer, vec![instruction], &signers).await;
+    assert_bond_error(result, 0, BondError::SessionExpired);
+}
+
+#[tokio::test]
+async fn test_claim_prize_with_another_session_key() {
+    let mut setup = start_won().await;
+    let session_key = Keypair::new();
+    let expires_at = now(&mut setup.context).await + 3_600;
+    create_session(&mut setup, &session_key.pubkey(), expires_at).await;
+
+    let other_key = Keypair::new();
+    let instruction = claim_prize_with_session(&setup, &other_key.pubkey());
+    let signers = [&other_key];
+    let result = relay(&mut setup.context, &setup.relayer, vec![instruction], &signers).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+    assert_eq!(balance(&mut setup.context, &setup.winner_ata).await, BALANCE);
+}
@@ -0,0 +1,169 @@
+//! Integration tests of `CloseSeason` and `RolloverSeason`.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, BondInstruction, ConfigParams},
+        state::{BondConfig, BondSeasonArchive, BondSeed},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+};
+
+/// A `CloseSeason` instruction after the latest draw `draw` (if any).
+fn close_season(game: &Game, draw: Option<&Pubkey>) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(game.authority.pubkey(), true),
+        AccountMeta::new(game.config(), false),
+        AccountMeta::new_readonly(game.state(), false),
+    ];
+    if let Some(draw) = draw {
+        accounts.push(AccountMeta::new_readonly(*draw, false));
+    }
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts,
+        data: BondInstruction::CloseSeason.try_to_vec().unwrap(),
+    }
+}
+
+/// The address of the archive of `season` and its bump.
+fn season_archive_address(game: &Game, season: u32) -> (Pubkey, u8) {
+    BondSeed::Season.derive_indexed(&defi_bond::id(), &game.config(), &season.to_le_bytes())
+}
+
+/// A `RolloverSeason` instruction archiving `season`, paid by the context's payer.
+fn rollover_season(context: &ProgramTestContext, game: &Game, season: u32) -> Instruction {
+    let (season_archive, bump) = season_archive_address(game, season);
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new(game.config(), false),
+            AccountMeta::new(game.state(), false),
+            AccountMeta::new(season_archive, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::RolloverSeason {
+            bump,
+            starts_at: None,
+            ends_at: None,
+        }.try_to_vec().unwrap(),
+    }
+}
+
+/// Processes the rolled over draw `draw_id` of `game` into a new draw account.
+async fn rollover_draw(context: &mut ProgramTestContext, game: &Game, draw_id: u64) -> Pubkey {
+    let draw = create_draw_account(context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &game.jackpot(),
+        &game.addresses.jackpot_ata,
+        &draw,
+        &[],
+        42,
+        draw_id,
+        0,
+        0,
+        None,
+    );
+    process(context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    draw
+}
+
+/// Returns the archive of `season`.
+async fn season_archive(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    season: u32,
+) -> BondSeasonArchive {
+    let address = season_archive_address(game, season).0;
+    BondSeasonArchive::deserialize(&mut account(context, &address).await.data.as_slice()).unwrap()
+}
+
+/// Returns the game's settings.
+async fn config(context: &mut ProgramTestContext, game: &Game) -> BondConfig {
+    BondConfig::unpack_from_slice(&account(context, &game.config()).await.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_rollover_season_archive() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, 1_000).await;
+
+    // The first season has a single (rolled over) draw.
+    let draw = rollover_draw(&mut context, &game, 1).await;
+    let season = state(&mut context, &game).await.season;
+    let instruction = close_season(&game, Some(&draw));
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let instruction = rollover_season(&context, &game, season);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    let archive = season_archive(&mut context, &game, season).await;
+    assert_eq!((archive.first_draw_id, archive.draw_id), (1, 1));
+    assert_eq!(archive.state.season, season);
+    assert_eq!(archive.state.rollover, 1);
+
+    // The next season's draws follow the first season's.
+    warp_to_epoch(&mut context, 2).await;
+    let draw = rollover_draw(&mut context, &game, 2).await;
+    let state = state(&mut context, &game).await;
+    let instruction = close_season(&game, Some(&draw));
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let instruction = rollover_season(&context, &game, season + 1);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    let archive = season_archive(&mut context, &game, season + 1).await;
+    assert_eq!((archive.first_draw_id, archive.draw_id), (2, 2));
+    assert_eq!(archive.state, state);
+    assert!(config(&mut context, &game).await.is_active);
+}
+
+#[tokio::test]
+async fn test_close_season() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+
+    // A season without draws ends when it's closed.
+    let instruction = close_season(&game, None);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let config = config(&mut context, &game).await;
+    assert!(!config.is_active);
+    assert!(config.ends_at.is_some());
+}
+
+#[tokio::test]
+async fn test_rollover_season_not_closed() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let season = state(&mut context, &game).await.season;
+
+    let instruction = rollover_season(&context, &game, season);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::SeasonNotClosed);
+}
+
+#[tokio::test]
+async fn test_rollover_season_paused() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let season = state(&mut context, &game).await.season;
+    pause(&mut context, &game).await;
+
+    // A paused game isn't closed.
+    let instruction = rollover_season(&context, &game, season);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::SeasonNotClosed);
+}
@@ -0,0 +1,106 @@
+//! Integration tests of `RotateShareAta`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        instruction::{BondInstruction, ConfigParams},
+        state::{pack_account, BondConfig, BondSeed},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+    spl_associated_token_account::get_associated_token_address,
+};
+
+/// The jackpot's balance before the migration.
+const JACKPOT: u64 = 5_000;
+
+/// Replaces the game's token mint with `token_mint`, as a mint migration would.
+async fn set_token_mint(context: &mut ProgramTestContext, game: &Game, token_mint: &Pubkey) {
+    let mut account = account(context, &game.config()).await;
+    let mut config = BondConfig::unpack_from_slice(&account.data).unwrap();
+    config.token_mint = *token_mint;
+    pack_account(&config, &mut account.data).unwrap();
+    context.set_account(&game.config(), &account.into());
+}
+
+/// A `RotateShareAta` instruction of the jackpot moving its `old_ata` of `old_mint` to the game's
+/// `new_mint` and sweeping the old balance into `destination`.
+fn rotate_jackpot_ata(
+    game: &Game,
+    old_ata: &Pubkey,
+    old_mint: &Pubkey,
+    destination: &Pubkey,
+    new_mint: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.jackpot(), false),
+            AccountMeta::new(*old_ata, false),
+            AccountMeta::new_readonly(*old_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new(get_associated_token_address(&game.jackpot(), new_mint), false),
+            AccountMeta::new_readonly(*new_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::RotateShareAta { seed: BondSeed::Jackpot }.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_rotate_share_ata() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    fund(&mut context, &game.authority.pubkey(), 1_000_000_000).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    let destination = create_ata(&mut context, &game, &Pubkey::new_unique()).await;
+    let new_mint = create_mint(&mut context).await;
+    set_token_mint(&mut context, &game, &new_mint).await;
+
+    // The old balance is swept and the share's new account created for the new mint.
+    let instruction = rotate_jackpot_ata(
+        &game,
+        &game.addresses.jackpot_ata,
+        &game.token_mint,
+        &destination,
+        &new_mint,
+    );
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &destination).await, JACKPOT);
+    let old_ata = context.banks_client.get_account(game.addresses.jackpot_ata).await.unwrap();
+    assert!(old_ata.is_none());
+    let new_ata = get_associated_token_address(&game.jackpot(), &new_mint);
+    let data = account(&mut context, &new_ata).await.data;
+    let new_ata = spl_token::state::Account::unpack_from_slice(&data).unwrap();
+    assert_eq!((new_ata.mint, new_ata.owner, new_ata.amount), (new_mint, game.jackpot(), 0));
+}
+
+#[tokio::test]
+async fn test_rotate_share_ata_current() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let destination = create_ata(&mut context, &game, &Pubkey::new_unique()).await;
+
+    // The share's account already matches the game's mint and token program.
+    let instruction = rotate_jackpot_ata(
+        &game,
+        &game.addresses.jackpot_ata,
+        &game.token_mint,
+        &destination,
+        &game.token_mint,
+    );
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::InvalidArgument);
+}
@@ -0,0 +1,76 @@
+//! Integration tests of the draws selecting their winner on chain from a snapshot.
+
+mod common;
+
+use {
+    borsh::BorshDeserialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, ConfigParams},
+        state::BondSnapshot,
+    },
+    solana_program::{instruction::AccountMeta, pubkey::Pubkey},
+    solana_program_test::*,
+    solana_sdk::signature::Signer,
+};
+
+/// The weight of each holder of the snapshots.
+const WEIGHT: u64 = 1_000;
+
+#[tokio::test]
+async fn test_snapshot_draw_requires_vrf() {
+    let (mut context, game) = start(ConfigParams {
+        snapshot_draws: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    set_draw_window(&mut context, 1_000).await;
+    let holder = Pubkey::new_unique();
+    let snapshot = create_snapshot(&mut context, &game, &[holder], WEIGHT).await;
+    let holder_ata = create_ata(&mut context, &game, &holder).await;
+    let draw = create_draw_account(&mut context).await;
+
+    // The sole holder would be selected by any seed, but the slot hash can be ground by the draw 
+    // authority, so only a VRF result selects a snapshot's winner.
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &holder,
+        &holder_ata,
+        &draw,
+        &[
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+            AccountMeta::new_readonly(snapshot, false),
+        ],
+        42,
+        1,
+        WEIGHT,
+        WEIGHT,
+        None,
+    );
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::VrfRequired);
+}
+
+#[tokio::test]
+async fn test_write_snapshot() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    set_draw_window(&mut context, 1_000).await;
+    fund(&mut context, &game.draw_authority.pubkey(), 1_000_000_000).await;
+    let holders = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+    // Entries are appended across writes, the snapshot growing with them.
+    let instructions = vec![
+        write_snapshot(&game, 1, &holders[..1], WEIGHT),
+        write_snapshot(&game, 1, &holders[1..], WEIGHT),
+    ];
+    process(&mut context, instructions, &[&game.draw_authority]).await.unwrap();
+    let data = account(&mut context, &snapshot_address(&game, 1).0).await.data;
+    let snapshot = BondSnapshot::deserialize(&mut data.as_slice()).unwrap();
+    let wallets: Vec<Pubkey> = snapshot.entries.iter().map(|entry| entry.wallet).collect();
+    assert_eq!(wallets, holders);
+    assert_eq!(snapshot.total_weight, 3 * WEIGHT);
+}
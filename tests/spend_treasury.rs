@@ -0,0 +1,106 @@
+//! Integration tests of `SpendTreasury`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{BondInstruction, ConfigParams, MAX_MEMO_LEN, MEMO_PROGRAM_ID},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+
+/// The treasury's balance.
+const TREASURY: u64 = 1_000;
+
+/// A `SpendTreasury` instruction of `authority` spending `amount` to `destination` for `memo`.
+fn spend_treasury(
+    game: &Game,
+    authority: &Keypair,
+    destination: &Pubkey,
+    amount: u64,
+    memo: &str,
+) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.addresses.treasury.0, false),
+            AccountMeta::new(game.addresses.treasury_ata, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+        ],
+        data: BondInstruction::SpendTreasury { amount, memo: memo.to_string() }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Starts a game whose treasury holds [TREASURY] and returns a destination account.
+async fn start_treasury() -> (ProgramTestContext, Game, Pubkey) {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.treasury_ata, TREASURY).await;
+    let destination = create_ata(&mut context, &game, &Pubkey::new_unique()).await;
+    (context, game, destination)
+}
+
+#[tokio::test]
+async fn test_spend_treasury() {
+    let (mut context, game, destination) = start_treasury().await;
+    let instruction = spend_treasury(&game, &game.authority, &destination, 300, "Audit");
+
+    // The memo is recorded by the Memo program.
+    let transaction = Transaction::new_signed_with_payer(
+        std::slice::from_ref(&instruction),
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &game.authority],
+        context.last_blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    let logs = simulation.simulation_details.unwrap().logs;
+    assert!(logs.iter().any(|line| line.contains("Memo") && line.contains("Audit")), "{:?}", logs);
+
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &destination).await, 300);
+    assert_eq!(balance(&mut context, &game.addresses.treasury_ata).await, TREASURY - 300);
+}
+
+#[tokio::test]
+async fn test_spend_treasury_memo_too_long() {
+    let (mut context, game, destination) = start_treasury().await;
+    let memo = "m".repeat(MAX_MEMO_LEN + 1);
+    let instruction = spend_treasury(&game, &game.authority, &destination, 300, &memo);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::MemoTooLong);
+}
+
+#[tokio::test]
+async fn test_spend_treasury_insufficient_funds() {
+    let (mut context, game, destination) = start_treasury().await;
+    let instruction = spend_treasury(&game, &game.authority, &destination, TREASURY + 1, "Audit");
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn test_spend_treasury_of_another_authority() {
+    let (mut context, game, destination) = start_treasury().await;
+    let other = Keypair::new();
+    let instruction = spend_treasury(&game, &other, &destination, 300, "Audit");
+    let result = process(&mut context, vec![instruction], &[&other]).await;
+    assert_bond_error(result, 0, BondError::InvalidAuthority);
+    assert_eq!(balance(&mut context, &destination).await, 0);
+}
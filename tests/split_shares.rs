@@ -0,0 +1,463 @@
+//! Integration tests of `SplitShares` and `SetShares`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, BondInstruction, ConfigParams, Ratio, SPLIT_JACKPOT},
+        state::{
+            pack_account,
+            BondConfig,
+            BondLstAdapter,
+            BondRemainderPolicy,
+            BondShare,
+            BondSkippedSharePolicy,
+        },
+    },
+    solana_program::{
+        clock::Clock,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// The epoch fee minted to the fee account before a split.
+const EPOCH_FEE: u64 = 1_000;
+
+/// A game whose token mint doesn't need a harvest before its splits.
+async fn start_marinade(params: ConfigParams) -> (ProgramTestContext, Game) {
+    start(ConfigParams {
+        lst_adapter: Some(BondLstAdapter::Marinade),
+        ..params
+    }).await
+}
+
+/// A `SplitShares` instruction of `authority` to all the shares.
+fn split_shares(game: &Game, authority: &Keypair, amount: Option<u64>) -> Instruction {
+    instruction::split_shares(
+        &defi_bond::id(),
+        &authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        None,
+        false,
+        amount,
+        None,
+    )
+}
+
+/// A `SplitShares` instruction of `authority` to the shares selected by `destinations`.
+fn split_to(game: &Game, authority: &Keypair, destinations: u8) -> Instruction {
+    instruction::split_shares(
+        &defi_bond::id(),
+        &authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        None,
+        false,
+        None,
+        Some(destinations),
+    )
+}
+
+/// A `SetShares` instruction of `game` with the equity, treasury, jackpot and stake `shares`.
+fn set_shares(game: &Game, shares: [(u32, u32); 4]) -> Instruction {
+    let [equity, treasury, jackpot, stake] =
+        shares.map(|(numerator, denominator)| Ratio { numerator, denominator });
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.addresses.equity.0, false),
+            AccountMeta::new(game.addresses.treasury.0, false),
+            AccountMeta::new(game.addresses.jackpot.0, false),
+            AccountMeta::new(game.addresses.stake.0, false),
+        ],
+        data: BondInstruction::SetShares { equity, treasury, jackpot, stake }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// The balances of the fee, equity, treasury, jackpot and stake accounts.
+async fn balances(context: &mut ProgramTestContext, game: &Game) -> [u64; 5] {
+    [
+        balance(context, &game.addresses.fee_ata).await,
+        balance(context, &game.addresses.equity_ata).await,
+        balance(context, &game.addresses.treasury_ata).await,
+        balance(context, &game.addresses.jackpot_ata).await,
+        balance(context, &game.addresses.stake_ata).await,
+    ]
+}
+
+/// Sets the `min_transfer` of `share`.
+async fn set_min_transfer(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    share: &Pubkey,
+    min_transfer: u64,
+) {
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(*share, false),
+        ],
+        data: BondInstruction::SetMinTransfer { min_transfer }.try_to_vec().unwrap(),
+    };
+    process(context, vec![instruction], &[&game.authority]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_split_shares() {
+    let (mut context, game) = start_marinade(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    // 10% equity, 0% treasury, 80% jackpot and 10% stake: the treasury's ATA isn't read, so the
+    // treasury itself may be passed in its place.
+    let mut instruction = split_shares(&game, &game.draw_authority, None);
+    instruction.accounts[7] = AccountMeta::new_readonly(game.addresses.treasury.0, false);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [0, 100, 0, 800, 100]);
+    let state = state(&mut context, &game).await;
+    assert_ne!(state.last_split_slot, 0);
+    assert_eq!(state.split_remainder, 0);
+}
+
+#[tokio::test]
+async fn test_split_shares_remainder() {
+    let (mut context, game) = start_marinade(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE + 3).await;
+
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    // The rounding stays in the fee account and is recorded.
+    assert_eq!(balances(&mut context, &game).await, [1, 100, 0, 802, 100]);
+    assert_eq!(state(&mut context, &game).await.split_remainder, 1);
+}
+
+#[tokio::test]
+async fn test_split_shares_remainder_to_jackpot() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        remainder_policy: Some(BondRemainderPolicy::Jackpot),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE + 3).await;
+
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [0, 100, 0, 803, 100]);
+    assert_eq!(state(&mut context, &game).await.split_remainder, 0);
+}
+
+#[tokio::test]
+async fn test_split_shares_exceed_amount() {
+    let (mut context, game) = start_marinade(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+    let stake = game.addresses.stake.0;
+    let mut account = account(&mut context, &stake).await;
+    let mut share = BondShare::unpack_from_slice(&account.data).unwrap();
+    share.numerator = 20;
+    pack_account(&share, &mut account.data).unwrap();
+    context.set_account(&stake, &account.into());
+
+    // Shares summing up to 120% are rejected before any transfer.
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::SharesExceedAmount);
+    assert_eq!(balances(&mut context, &game).await, [EPOCH_FEE, 0, 0, 0, 0]);
+}
+
+#[tokio::test]
+async fn test_split_shares_min_transfer() {
+    let (mut context, game) = start_marinade(ConfigParams::default()).await;
+    set_min_transfer(&mut context, &game, &game.addresses.equity.0, 200).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    // The equity's 100 stay in the fee account until a later split.
+    assert_eq!(balances(&mut context, &game).await, [100, 0, 0, 800, 100]);
+    assert_eq!(state(&mut context, &game).await.split_remainder, 0);
+}
+
+#[tokio::test]
+async fn test_split_shares_min_transfer_not_routed() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        remainder_policy: Some(BondRemainderPolicy::Jackpot),
+        ..ConfigParams::default()
+    }).await;
+    set_min_transfer(&mut context, &game, &game.addresses.equity.0, 200).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE + 3).await;
+
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    // Only the rounding is routed to the jackpot, the equity's dust stays in the fee account.
+    assert_eq!(balances(&mut context, &game).await, [100, 0, 0, 803, 100]);
+}
+
+#[tokio::test]
+async fn test_split_shares_crank_fee() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        allow_permissionless_split: Some(true),
+        crank_fee_bps: Some(500),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+    let keeper = Keypair::new();
+    let keeper_ata = create_ata(&mut context, &game, &keeper.pubkey()).await;
+
+    // The fee is capped at 1% and paid before the split.
+    let instruction = instruction::split_shares(
+        &defi_bond::id(),
+        &keeper.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        Some(&keeper_ata),
+        false,
+        None,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&keeper]).await.unwrap();
+    assert_eq!(balance(&mut context, &keeper_ata).await, 10);
+    assert_eq!(balances(&mut context, &game).await, [0, 99, 0, 792, 99]);
+}
+
+#[tokio::test]
+async fn test_split_shares_decimals() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint_with_decimals(&mut context, 6).await;
+    let game = create_game_for_mint(&mut context, token_mint, ConfigParams {
+        lst_adapter: Some(BondLstAdapter::Marinade),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    // The transfers are checked against the decimals cached at initialization.
+    let config = BondConfig::unpack_from_slice(&account(&mut context, &game.config()).await.data)
+        .unwrap();
+    assert_eq!(config.decimals, 6);
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [0, 100, 0, 800, 100]);
+}
+
+#[tokio::test]
+async fn test_split_shares_season_ended() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        ends_at: Some(1),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::SeasonEnded);
+}
+
+#[tokio::test]
+async fn test_split_shares_of_another_authority() {
+    let (mut context, game) = start_marinade(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    let keeper = Keypair::new();
+    let instruction = split_shares(&game, &keeper, None);
+    let result = process(&mut context, vec![instruction], &[&keeper]).await;
+    assert_bond_error(result, 0, BondError::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn test_split_shares_permissionless() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        allow_permissionless_split: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    let keeper = Keypair::new();
+    let instruction = split_shares(&game, &keeper, None);
+    process(&mut context, vec![instruction], &[&keeper]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [0, 100, 0, 800, 100]);
+}
+
+#[tokio::test]
+async fn test_split_shares_once_per_epoch() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        split_once_per_epoch: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+    let instruction = split_shares(&game, &game.draw_authority, Some(500));
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    // The draw authority is limited like permissionless keepers.
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    let result = process(&mut context, vec![instruction.clone()], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::SplitTooSoon);
+    let epoch = context.banks_client.get_sysvar::<Clock>().await.unwrap().epoch;
+    warp_to_epoch(&mut context, epoch + 1).await;
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [0, 100, 0, 800, 100]);
+}
+
+#[tokio::test]
+async fn test_split_shares_amount() {
+    let (mut context, game) = start_marinade(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    // The draw authority may split part of the fee account.
+    let instruction = split_shares(&game, &game.draw_authority, Some(500));
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [500, 50, 0, 400, 50]);
+}
+
+#[tokio::test]
+async fn test_split_shares_permissionless_ignores_amount() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        allow_permissionless_split: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    // Permissionless keepers split the fee account's full balance.
+    let keeper = Keypair::new();
+    let instruction = split_shares(&game, &keeper, Some(1));
+    process(&mut context, vec![instruction], &[&keeper]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [0, 100, 0, 800, 100]);
+}
+
+#[tokio::test]
+async fn test_split_shares_permissionless_once_per_epoch() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        allow_permissionless_split: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+    let keeper = Keypair::new();
+    let instruction = split_shares(&game, &keeper, None);
+    process(&mut context, vec![instruction.clone()], &[&keeper]).await.unwrap();
+
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+    let result = process(&mut context, vec![instruction.clone()], &[&keeper]).await;
+    assert_bond_error(result, 0, BondError::SplitTooSoon);
+
+    // The next epoch allows another split.
+    let epoch = context.banks_client.get_sysvar::<Clock>().await.unwrap().epoch;
+    warp_to_epoch(&mut context, epoch + 1).await;
+    process(&mut context, vec![instruction], &[&keeper]).await.unwrap();
+    assert_eq!(balance(&mut context, &game.addresses.jackpot_ata).await, 1_600);
+}
+
+#[tokio::test]
+async fn test_split_shares_requires_harvest() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    // SPL stake pools must be updated through `UpdateStakePool` in each epoch first.
+    warp_to_epoch(&mut context, 1).await;
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::StaleStakePool);
+}
+
+#[tokio::test]
+async fn test_split_shares_destinations_retain() {
+    let (mut context, game) = start_marinade(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    // The skipped equity and stake portions stay in the fee account.
+    let instruction = split_to(&game, &game.draw_authority, SPLIT_JACKPOT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [200, 0, 0, 800, 0]);
+    assert_eq!(state(&mut context, &game).await.split_remainder, 0);
+}
+
+#[tokio::test]
+async fn test_split_shares_destinations_reallocate() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        skipped_share_policy: Some(BondSkippedSharePolicy::Reallocate),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    let instruction = split_to(&game, &game.draw_authority, SPLIT_JACKPOT);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [0, 0, 0, 1000, 0]);
+}
+
+#[tokio::test]
+async fn test_split_shares_permissionless_destinations() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        allow_permissionless_split: Some(true),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+
+    // Only the draw authority may skip shares.
+    let keeper = Keypair::new();
+    let instruction = split_to(&game, &keeper, SPLIT_JACKPOT);
+    let result = process(&mut context, vec![instruction], &[&keeper]).await;
+    assert_error(result, 0, InstructionError::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_split_shares_too_soon() {
+    let (mut context, game) = start_marinade(ConfigParams {
+        min_split_interval: Some(1_000),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+    let instruction = split_shares(&game, &game.draw_authority, Some(500));
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let state = state(&mut context, &game).await;
+    assert_eq!((state.last_split_slot, state.last_split_epoch), (clock.slot, clock.epoch));
+
+    // Splits are spaced by the configured interval, even for the draw authority.
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    let result = process(&mut context, vec![instruction.clone()], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::SplitTooSoon);
+
+    context.warp_to_slot(clock.slot + 1_000).unwrap();
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [0, 100, 0, 800, 100]);
+}
+
+#[tokio::test]
+async fn test_set_shares() {
+    let (mut context, game) = start_marinade(ConfigParams::default()).await;
+    let instruction = set_shares(&game, [(1, 10), (2, 10), (1, 2), (0, 1)]);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+
+    // The unallocated 20% stays in the fee account.
+    mint_to(&mut context, &game, &game.addresses.fee_ata, EPOCH_FEE).await;
+    let instruction = split_shares(&game, &game.draw_authority, None);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balances(&mut context, &game).await, [200, 100, 200, 500, 0]);
+}
+
+#[tokio::test]
+async fn test_set_shares_invalid() {
+    let (mut context, game) = start_marinade(ConfigParams::default()).await;
+
+    // Shares may not exceed 100% or divide by zero.
+    for shares in [[(1, 10), (2, 10), (7, 10), (1, 100)], [(1, 10), (0, 0), (1, 2), (0, 1)]] {
+        let instruction = set_shares(&game, shares);
+        let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+        assert_bond_error(result, 0, BondError::InvalidShares);
+    }
+}
@@ -0,0 +1,156 @@
+//! Integration tests of games whose authority is a Squads vault.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, BondInstruction, ConfigParams, InitializeParams},
+        state::{pack_account, BondConfig, BondSquadsVault, MAX_NAME_LEN, SQUADS_PROGRAM_ID},
+    },
+    solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// A multisig standing in for Squads. Its data is a vault's index and bump followed by the data 
+/// of an instruction of the program passed as its second account, which it runs on the remaining 
+/// accounts, signed by that vault of the multisig passed as its first account.
+fn squads(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (multisig_info, target_program_info) = (&accounts[0], &accounts[1]);
+    let vault_seeds: &[&[u8]] = &[
+        b"multisig",
+        multisig_info.key.as_ref(),
+        b"vault",
+        &data[..1],
+        &data[1..2],
+    ];
+    let vault = Pubkey::create_program_address(vault_seeds, program_id).unwrap();
+    let ix = Instruction {
+        program_id: *target_program_info.key,
+        accounts: accounts[2..].iter().map(|info| AccountMeta {
+            pubkey: *info.key,
+            is_signer: info.is_signer || info.key == &vault,
+            is_writable: info.is_writable,
+        }).collect(),
+        data: data[2..].to_vec(),
+    };
+    invoke_signed(&ix, &accounts[1..], &[vault_seeds])
+}
+
+/// A Squads vault of a new multisig.
+fn vault() -> BondSquadsVault {
+    let multisig = Pubkey::new_unique();
+    let (_, bump) = Pubkey::find_program_address(
+        &[b"multisig", multisig.as_ref(), b"vault", &[0]],
+        &SQUADS_PROGRAM_ID,
+    );
+    BondSquadsVault { multisig, index: 0, bump }
+}
+
+/// A `SetMetadata` instruction of `authority` naming the game "Squads".
+fn set_metadata(game: &Game, authority: &Pubkey) -> Instruction {
+    let mut name = [0; MAX_NAME_LEN];
+    name[..6].copy_from_slice(b"Squads");
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(game.config(), false),
+        ],
+        data: BondInstruction::SetMetadata { name, uri: String::new() }.try_to_vec().unwrap(),
+    }
+}
+
+/// Returns the game's settings.
+async fn config(context: &mut ProgramTestContext, game: &Game) -> BondConfig {
+    BondConfig::unpack_from_slice(&account(context, &game.config()).await.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_initialize_authority_vault_mismatch() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let draw_authority = Keypair::new();
+    let payer = context.payer.pubkey();
+    let create = instruction::create(
+        &defi_bond::id(),
+        &payer,
+        &config.pubkey(),
+        &authority.pubkey(),
+        &token_mint,
+        &spl_token::id(),
+        None,
+        8,
+    );
+    let instructions =
+        instruction::with_compute_budget(vec![create], &[COMPUTE_UNITS_CREATE], None);
+    process(&mut context, instructions, &[&config, &authority]).await.unwrap();
+
+    // A keypair authority can't stand in for the vault.
+    let initialize = instruction::initialize(
+        &defi_bond::id(),
+        &payer,
+        &config.pubkey(),
+        &authority.pubkey(),
+        &draw_authority.pubkey(),
+        &token_mint,
+        &spl_token::id(),
+        InitializeParams {
+            exclusion_list_capacity: 8,
+            config: ConfigParams { authority_vault: Some(vault()), ..ConfigParams::default() },
+            ..InitializeParams::default()
+        },
+    );
+    let instructions =
+        instruction::with_compute_budget(vec![initialize], &[COMPUTE_UNITS_CREATE], None);
+    let signers = [&config, &authority, &draw_authority];
+    let result = process(&mut context, instructions, &signers).await;
+    assert_bond_error(result, 1, BondError::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn test_authority_vault_signs_through_squads() {
+    let mut program_test = program_test();
+    program_test.add_program("squads", SQUADS_PROGRAM_ID, processor!(squads));
+    let mut context = program_test.start_with_context().await;
+    let game = create_game(&mut context, ConfigParams::default()).await;
+
+    // Hand the game over to a vault.
+    let vault = vault();
+    let vault_address = vault.address().unwrap();
+    let mut vault_config = config(&mut context, &game).await;
+    vault_config.authority = vault_address;
+    vault_config.authority_vault = Some(vault);
+    let mut config_account = account(&mut context, &game.config()).await;
+    pack_account(&vault_config, &mut config_account.data).unwrap();
+    context.set_account(&game.config(), &config_account.into());
+
+    let instruction = set_metadata(&game, &game.authority.pubkey());
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidAuthority);
+
+    let inner = set_metadata(&game, &vault_address);
+    let mut data = vec![vault.index, vault.bump];
+    data.extend(inner.data);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(vault.multisig, false),
+        AccountMeta::new_readonly(defi_bond::id(), false),
+        AccountMeta::new_readonly(vault_address, false),
+    ];
+    accounts.extend(inner.accounts.into_iter().skip(1));
+    let instruction = Instruction { program_id: SQUADS_PROGRAM_ID, accounts, data };
+    process(&mut context, vec![instruction], &[]).await.unwrap();
+    assert_eq!(&config(&mut context, &game).await.name[..6], b"Squads");
+}
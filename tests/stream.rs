@@ -0,0 +1,174 @@
+//! Integration tests of `CreateStream`, `WithdrawStream` and `RevokeStream`.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        constants::STREAM_SIZE,
+        instruction::{BondInstruction, ConfigParams},
+        state::{BondSeed, BondStream},
+    },
+    solana_program::{
+        clock::Clock,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+/// The treasury's balance before the stream.
+const TREASURY: u64 = 1_500;
+
+/// The amount vested per second.
+const RATE: u64 = 10;
+
+/// The stream's duration in seconds.
+const DURATION: u64 = 100;
+
+/// A game streaming treasury tokens to a recipient.
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    recipient: Keypair,
+    recipient_ata: Pubkey,
+    stream: Pubkey,
+    stream_ata: Pubkey,
+}
+
+/// Starts a game with a funded treasury and creates a stream of [RATE] for [DURATION] to a new
+/// recipient.
+async fn start_stream() -> Setup {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.treasury_ata, TREASURY).await;
+    let recipient = Keypair::new();
+    let recipient_ata = create_ata(&mut context, &game, &recipient.pubkey()).await;
+    let (stream, bump) = BondSeed::Stream.derive_indexed(
+        &defi_bond::id(),
+        &game.config(),
+        recipient.pubkey().as_ref(),
+    );
+    let stream_ata = get_associated_token_address(&stream, &game.token_mint);
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.state(), false),
+            AccountMeta::new_readonly(game.addresses.treasury.0, false),
+            AccountMeta::new(game.addresses.treasury_ata, false),
+            AccountMeta::new(stream, false),
+            AccountMeta::new(stream_ata, false),
+            AccountMeta::new_readonly(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::CreateStream {
+            bump,
+            space: STREAM_SIZE as u32,
+            recipient: recipient.pubkey(),
+            rate: RATE,
+            duration: DURATION,
+        }.try_to_vec().unwrap(),
+    };
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    Setup { context, game, recipient, recipient_ata, stream, stream_ata }
+}
+
+/// Moves the clock `seconds` forward.
+async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+/// A `WithdrawStream` instruction of `recipient` paying `recipient_ata`.
+fn withdraw_stream(setup: &Setup, recipient: &Pubkey, recipient_ata: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*recipient, true),
+            AccountMeta::new_readonly(setup.game.config(), false),
+            AccountMeta::new(setup.stream, false),
+            AccountMeta::new(setup.stream_ata, false),
+            AccountMeta::new(*recipient_ata, false),
+            AccountMeta::new_readonly(setup.game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: BondInstruction::WithdrawStream.try_to_vec().unwrap(),
+    }
+}
+
+/// A `RevokeStream` instruction of the game's authority.
+fn revoke_stream(setup: &Setup) -> Instruction {
+    let game = &setup.game;
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.state(), false),
+            AccountMeta::new(setup.stream, false),
+            AccountMeta::new(setup.stream_ata, false),
+            AccountMeta::new(setup.recipient_ata, false),
+            AccountMeta::new_readonly(game.addresses.treasury.0, false),
+            AccountMeta::new(game.addresses.treasury_ata, false),
+            AccountMeta::new_readonly(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: BondInstruction::RevokeStream.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_create_stream() {
+    let mut setup = start_stream().await;
+    let total = RATE * DURATION;
+    assert_eq!(balance(&mut setup.context, &setup.stream_ata).await, total);
+    let treasury_ata = setup.game.addresses.treasury_ata;
+    assert_eq!(balance(&mut setup.context, &treasury_ata).await, TREASURY - total);
+    assert_eq!(state(&mut setup.context, &setup.game).await.open_streams, 1);
+
+    let data = account(&mut setup.context, &setup.stream).await.data;
+    let stream = BondStream::deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(stream.recipient, setup.recipient.pubkey());
+    assert_eq!((stream.rate, stream.duration, stream.withdrawn), (RATE, DURATION, 0));
+}
+
+#[tokio::test]
+async fn test_withdraw_stream() {
+    let mut setup = start_stream().await;
+    advance_clock(&mut setup.context, 30).await;
+    let instruction = withdraw_stream(&setup, &setup.recipient.pubkey(), &setup.recipient_ata);
+    process(&mut setup.context, vec![instruction], &[&setup.recipient]).await.unwrap();
+    assert_eq!(balance(&mut setup.context, &setup.recipient_ata).await, 30 * RATE);
+
+    // Only the recipient may withdraw.
+    let other = Keypair::new();
+    let other_ata = create_ata(&mut setup.context, &setup.game, &other.pubkey()).await;
+    let instruction = withdraw_stream(&setup, &other.pubkey(), &other_ata);
+    let result = process(&mut setup.context, vec![instruction], &[&other]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn test_revoke_stream() {
+    let mut setup = start_stream().await;
+    advance_clock(&mut setup.context, 40).await;
+    let instruction = revoke_stream(&setup);
+    process(&mut setup.context, vec![instruction], &[&setup.game.authority]).await.unwrap();
+
+    // The vested tokens are paid and the rest returned to the treasury.
+    assert_eq!(balance(&mut setup.context, &setup.recipient_ata).await, 40 * RATE);
+    let treasury_ata = setup.game.addresses.treasury_ata;
+    assert_eq!(balance(&mut setup.context, &treasury_ata).await, TREASURY - 40 * RATE);
+    assert_eq!(state(&mut setup.context, &setup.game).await.open_streams, 0);
+    assert!(setup.context.banks_client.get_account(setup.stream).await.unwrap().is_none());
+    assert!(setup.context.banks_client.get_account(setup.stream_ata).await.unwrap().is_none());
+}
@@ -0,0 +1,159 @@
+//! Integration tests of `SwapTreasury`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{BondInstruction, ConfigParams, JUPITER_PROGRAM_ID},
+    },
+    solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program::invoke,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+    spl_associated_token_account::{
+        get_associated_token_address,
+        instruction::create_associated_token_account,
+    },
+};
+
+/// The treasury's balance of the game's token.
+const TREASURY: u64 = 1_000;
+
+/// A swap route standing in for Jupiter, which transfers the u64 LE amount at the start of its 
+/// data from `[source, owner, destination]` and the one after it from `[vault, vault_owner, 
+/// output]`, followed by the Token Program.
+fn jupiter(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let token_program_info = &accounts[6];
+    for (index, infos) in accounts[..6].chunks(3).enumerate() {
+        let amount = u64::from_le_bytes(data[index * 8..(index + 1) * 8].try_into().unwrap());
+        let ix = spl_token::instruction::transfer(
+            token_program_info.key,
+            infos[0].key,
+            infos[2].key,
+            infos[1].key,
+            &[],
+            amount,
+        )?;
+        invoke(
+            &ix,
+            &[infos[0].clone(), infos[2].clone(), infos[1].clone(), token_program_info.clone()],
+        )?;
+    }
+    Ok(())
+}
+
+/// A game whose treasury holds [TREASURY], with its token account for an output mint, and the 
+/// route's vault of that mint.
+struct Setup {
+    context: ProgramTestContext,
+    game: Game,
+    output_ata: Pubkey,
+    vault_owner: Keypair,
+    vault_ata: Pubkey,
+    destination_ata: Pubkey,
+}
+
+async fn setup() -> Setup {
+    let mut program_test = program_test();
+    program_test.add_program("jupiter", JUPITER_PROGRAM_ID, processor!(jupiter));
+    let mut context = program_test.start_with_context().await;
+    let game = create_game(&mut context, ConfigParams::default()).await;
+    mint_to(&mut context, &game, &game.addresses.treasury_ata, TREASURY).await;
+
+    let mint = create_mint(&mut context).await;
+    let vault_owner = Keypair::new();
+    let payer = context.payer.pubkey();
+    let instructions = [game.addresses.treasury.0, vault_owner.pubkey()]
+        .iter()
+        .map(|owner| create_associated_token_account(&payer, owner, &mint, &spl_token::id()))
+        .collect();
+    process(&mut context, instructions, &[]).await.unwrap();
+    let output_ata = get_associated_token_address(&game.addresses.treasury.0, &mint);
+    let vault_ata = get_associated_token_address(&vault_owner.pubkey(), &mint);
+    let fund_vault = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &mint,
+        &vault_ata,
+        &payer,
+        &[],
+        TREASURY,
+    ).unwrap();
+    process(&mut context, vec![fund_vault], &[]).await.unwrap();
+    let destination_ata = create_ata(&mut context, &game, &vault_owner.pubkey()).await;
+    Setup { context, game, output_ata, vault_owner, vault_ata, destination_ata }
+}
+
+/// A `SwapTreasury` instruction through `swap_program`, routed to trade `amount_in` for 
+/// `amount_out`.
+fn swap_treasury(
+    setup: &Setup,
+    swap_program: &Pubkey,
+    amount_in: u64,
+    amount_out: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    let game = &setup.game;
+    let mut route_data = amount_in.to_le_bytes().to_vec();
+    route_data.extend_from_slice(&amount_out.to_le_bytes());
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.addresses.treasury.0, false),
+            AccountMeta::new(game.addresses.treasury_ata, false),
+            AccountMeta::new(setup.output_ata, false),
+            AccountMeta::new_readonly(*swap_program, false),
+            AccountMeta::new(game.addresses.treasury_ata, false),
+            AccountMeta::new_readonly(game.addresses.treasury.0, false),
+            AccountMeta::new(setup.destination_ata, false),
+            AccountMeta::new(setup.vault_ata, false),
+            AccountMeta::new_readonly(setup.vault_owner.pubkey(), true),
+            AccountMeta::new(setup.output_ata, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: BondInstruction::SwapTreasury {
+            amount_in,
+            min_amount_out,
+            route_data,
+        }.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_swap_treasury() {
+    let mut setup = setup().await;
+    let instruction = swap_treasury(&setup, &JUPITER_PROGRAM_ID, 400, 100, 100);
+    let signers = [&setup.game.authority, &setup.vault_owner];
+    process(&mut setup.context, vec![instruction], &signers).await.unwrap();
+
+    let treasury_ata = setup.game.addresses.treasury_ata;
+    assert_eq!(balance(&mut setup.context, &treasury_ata).await, TREASURY - 400);
+    assert_eq!(balance(&mut setup.context, &setup.output_ata).await, 100);
+}
+
+#[tokio::test]
+async fn test_swap_treasury_slippage_exceeded() {
+    let mut setup = setup().await;
+    let instruction = swap_treasury(&setup, &JUPITER_PROGRAM_ID, 400, 100, 101);
+    let signers = [&setup.game.authority, &setup.vault_owner];
+    let result = process(&mut setup.context, vec![instruction], &signers).await;
+    assert_bond_error(result, 0, BondError::SlippageExceeded);
+}
+
+#[tokio::test]
+async fn test_swap_treasury_unsupported_program() {
+    let mut setup = setup().await;
+    let instruction = swap_treasury(&setup, &Pubkey::new_unique(), 400, 100, 100);
+    let signers = [&setup.game.authority, &setup.vault_owner];
+    let result = process(&mut setup.context, vec![instruction], &signers).await;
+    assert_error(result, 0, InstructionError::IncorrectProgramId);
+}
@@ -0,0 +1,407 @@
+//! Integration tests of `CreateTicketTree`, `IssueTickets`, `ClaimPrizeWithTicket`, `ClaimRebate`
+//! and the receipt tokens minted by `CreateReceiptMint` and burned by `RedeemTicket`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        constants::REBATE_CLAIM_SIZE,
+        error::BondError,
+        instruction::{
+            self, BondInstruction, ConfigParams, TicketProof, ACCOUNT_COMPRESSION_PROGRAM_ID,
+            NOOP_PROGRAM_ID,
+        },
+        state::{
+            BondConfig, BondLstAdapter, BondPayoutMode, BondRebateClaim, BondSeed, BondTicket,
+        },
+    },
+    solana_program::{
+        clock::Clock,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+    spl_associated_token_account::{
+        get_associated_token_address,
+        instruction::create_associated_token_account,
+    },
+};
+
+/// The jackpot won by ticket holders.
+const JACKPOT: u64 = 5_000;
+
+/// The accounts shared by the ticket tree instructions of `game`.
+fn ticket_tree_accounts(game: &Game, tree: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(game.authority.pubkey(), true),
+        AccountMeta::new(game.config(), false),
+        AccountMeta::new(game.state(), false),
+        AccountMeta::new(*tree, false),
+        AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+        AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+    ]
+}
+
+/// Creates the game's ticket ledger and returns its address.
+async fn create_ticket_tree(context: &mut ProgramTestContext, game: &Game) -> Pubkey {
+    let tree = create_tree(context, &ACCOUNT_COMPRESSION_PROGRAM_ID).await;
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: ticket_tree_accounts(game, &tree),
+        data: BondInstruction::CreateTicketTree { max_depth: 14, max_buffer_size: 8 }
+            .try_to_vec()
+            .unwrap(),
+    };
+    process(context, vec![instruction], &[&game.authority]).await.unwrap();
+    tree
+}
+
+/// An `IssueTickets` instruction of `game` appending `tickets` to `tree`.
+fn issue_tickets(game: &Game, tree: &Pubkey, tickets: Vec<BondTicket>) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: ticket_tree_accounts(game, tree),
+        data: BondInstruction::IssueTickets { tickets }.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates the game's receipt mint and returns its address.
+async fn create_receipt_mint(context: &mut ProgramTestContext, game: &Game) -> Pubkey {
+    let (receipt_mint, bump) = BondSeed::Receipt.derive(&defi_bond::id(), &game.config());
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(game.authority.pubkey(), true),
+            AccountMeta::new(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new(receipt_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::CreateReceiptMint { bump }.try_to_vec().unwrap(),
+    };
+    process(context, vec![instruction], &[&game.authority]).await.unwrap();
+    receipt_mint
+}
+
+/// Creates the receipt token account of `wallet` and returns its address.
+async fn create_receipt_ata(
+    context: &mut ProgramTestContext,
+    receipt_mint: &Pubkey,
+    wallet: &Pubkey,
+) -> Pubkey {
+    let instruction = create_associated_token_account(
+        &context.payer.pubkey(),
+        wallet,
+        receipt_mint,
+        &spl_token::id(),
+    );
+    process(context, vec![instruction], &[]).await.unwrap();
+    get_associated_token_address(wallet, receipt_mint)
+}
+
+/// A ticket of a new wallet with `weight`.
+fn ticket(weight: u64) -> BondTicket {
+    BondTicket { wallet: Pubkey::new_unique(), weight, ..BondTicket::default() }
+}
+
+/// Starts a game in claim mode with `params` and a ticket ledger holding the ticket of `weight`
+/// of `winner`, and returns the ledger and the ticket as issued.
+async fn start_ticket(
+    params: ConfigParams,
+    winner: &Pubkey,
+    weight: u64,
+) -> (ProgramTestContext, Game, Pubkey, BondTicket) {
+    let (mut context, game) = start(ConfigParams {
+        payout_mode: Some(BondPayoutMode::Claim),
+        ..params
+    }).await;
+    let tree = create_ticket_tree(&mut context, &game).await;
+    let ticket = BondTicket { wallet: *winner, weight, ..BondTicket::default() };
+    let instruction = issue_tickets(&game, &tree, vec![ticket.clone()]);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    (context, game, tree, BondTicket { issued_at: clock.unix_timestamp, ..ticket })
+}
+
+/// Runs a draw won by the holder of `ticket` (of `receiver_ata`) `seconds` after the current time.
+async fn draw_ticket(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    ticket: &BondTicket,
+    receiver_ata: &Pubkey,
+    seconds: i64,
+) -> Pubkey {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+    let draw = create_draw_account(context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &ticket.wallet,
+        receiver_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        ticket.weight,
+        10 * ticket.weight,
+        None,
+    );
+    process(context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    draw
+}
+
+/// A `ClaimPrizeWithTicket` instruction of the holder of the first `ticket` of `tree`.
+fn claim_prize_with_ticket(
+    game: &Game,
+    tree: &Pubkey,
+    ticket: &BondTicket,
+    receiver_ata: &Pubkey,
+    draw: &Pubkey,
+) -> Instruction {
+    let mut instruction = claim_prize(
+        game,
+        &ticket.wallet,
+        receiver_ata,
+        draw,
+        &[
+            AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*tree, false),
+        ],
+    );
+    let ticket_proof = TicketProof {
+        root: [0; 32],
+        weight: ticket.weight,
+        issued_at: ticket.issued_at,
+        rebate_per_weight: ticket.rebate_per_weight,
+        index: 0,
+    };
+    instruction.data = BondInstruction::ClaimPrizeWithTicket(ticket_proof).try_to_vec().unwrap();
+    instruction
+}
+
+#[tokio::test]
+async fn test_create_ticket_tree() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let tree = create_ticket_tree(&mut context, &game).await;
+    let data = account(&mut context, &game.config()).await.data;
+    assert_eq!(BondConfig::unpack_from_slice(&data).unwrap().ticket_tree, Some(tree));
+
+    // The game's ticket ledger can't be replaced.
+    let other_tree = create_tree(&mut context, &ACCOUNT_COMPRESSION_PROGRAM_ID).await;
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: ticket_tree_accounts(&game, &other_tree),
+        data: BondInstruction::CreateTicketTree { max_depth: 14, max_buffer_size: 8 }
+            .try_to_vec()
+            .unwrap(),
+    };
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::AccountAlreadyInitialized);
+}
+
+#[tokio::test]
+async fn test_issue_tickets() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let tree = create_ticket_tree(&mut context, &game).await;
+    let tickets = vec![ticket(100), ticket(250)];
+    let instruction = issue_tickets(&game, &tree, tickets.clone());
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    assert_eq!(state(&mut context, &game).await.ticket_weight, 350);
+
+    // The last leaf appended is the hash of the ticket stamped with its issue time.
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let last = BondTicket { issued_at: clock.unix_timestamp, ..tickets[1].clone() };
+    let data = account(&mut context, &tree).await.data;
+    assert_eq!(data[8..40], last.leaf().unwrap());
+}
+
+#[tokio::test]
+async fn test_issue_tickets_other_tree() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    create_ticket_tree(&mut context, &game).await;
+    let other_tree = create_tree(&mut context, &ACCOUNT_COMPRESSION_PROGRAM_ID).await;
+    let instruction = issue_tickets(&game, &other_tree, vec![ticket(100)]);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+    assert_eq!(state(&mut context, &game).await.ticket_weight, 0);
+}
+
+#[tokio::test]
+async fn test_issue_and_redeem_tickets_with_receipts() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let tree = create_ticket_tree(&mut context, &game).await;
+    let receipt_mint = create_receipt_mint(&mut context, &game).await;
+    let data = account(&mut context, &game.config()).await.data;
+    assert_eq!(BondConfig::unpack_from_slice(&data).unwrap().receipt_mint, Some(receipt_mint));
+
+    // Receipts are minted 1:1 with the weight of each ticket.
+    let wallet = Keypair::new();
+    let tickets = vec![ticket(100), BondTicket { wallet: wallet.pubkey(), ..ticket(250) }];
+    let mut instruction = issue_tickets(&game, &tree, tickets.clone());
+    instruction.accounts.push(AccountMeta::new(receipt_mint, false));
+    instruction.accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+    let mut receipt_atas = vec![];
+    for ticket in tickets.iter() {
+        let receipt_ata = create_receipt_ata(&mut context, &receipt_mint, &ticket.wallet).await;
+        instruction.accounts.push(AccountMeta::new(receipt_ata, false));
+        receipt_atas.push(receipt_ata);
+    }
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &receipt_atas[0]).await, 100);
+    assert_eq!(balance(&mut context, &receipt_atas[1]).await, 250);
+
+    // Redeeming a ticket burns its receipts.
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let ticket_proof = TicketProof {
+        root: [0; 32],
+        weight: 250,
+        issued_at: clock.unix_timestamp,
+        rebate_per_weight: 0,
+        index: 1,
+    };
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(wallet.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new(game.state(), false),
+            AccountMeta::new(tree, false),
+            AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+            AccountMeta::new(receipt_mint, false),
+            AccountMeta::new(receipt_atas[1], false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: BondInstruction::RedeemTicket(ticket_proof).try_to_vec().unwrap(),
+    };
+    process(&mut context, vec![instruction], &[&wallet]).await.unwrap();
+    assert_eq!(balance(&mut context, &receipt_atas[1]).await, 0);
+    assert_eq!(state(&mut context, &game).await.ticket_weight, 100);
+}
+
+#[tokio::test]
+async fn test_claim_prize_with_ticket() {
+    let winner = Keypair::new();
+    let (mut context, game, tree, ticket) =
+        start_ticket(ConfigParams::default(), &winner.pubkey(), 1_000).await;
+    let winner_ata = create_ata(&mut context, &game, &winner.pubkey()).await;
+    let draw = draw_ticket(&mut context, &game, &ticket, &winner_ata, 100).await;
+
+    let instruction = claim_prize_with_ticket(&game, &tree, &ticket, &winner_ata, &draw);
+    process(&mut context, vec![instruction], &[&winner]).await.unwrap();
+    assert_eq!(balance(&mut context, &winner_ata).await, JACKPOT);
+}
+
+#[tokio::test]
+async fn test_claim_prize_with_ticket_issued_at_draw() {
+    let winner = Keypair::new();
+    let (mut context, game, tree, ticket) =
+        start_ticket(ConfigParams::default(), &winner.pubkey(), 1_000).await;
+    let winner_ata = create_ata(&mut context, &game, &winner.pubkey()).await;
+    let draw = draw_ticket(&mut context, &game, &ticket, &winner_ata, 0).await;
+
+    // A ticket issued when the draw's window closes isn't weighted.
+    let instruction = claim_prize_with_ticket(&game, &tree, &ticket, &winner_ata, &draw);
+    let result = process(&mut context, vec![instruction], &[&winner]).await;
+    assert_bond_error(result, 0, BondError::IneligibleTicket);
+}
+
+#[tokio::test]
+async fn test_claim_prize_with_ticket_below_min_eligible_weight() {
+    let winner = Keypair::new();
+    let params = ConfigParams { min_eligible_weight: Some(1_001), ..ConfigParams::default() };
+    let (mut context, game, tree, ticket) = start_ticket(params, &winner.pubkey(), 1_000).await;
+    let winner_ata = create_ata(&mut context, &game, &winner.pubkey()).await;
+    let draw = draw_ticket(&mut context, &game, &ticket, &winner_ata, 100).await;
+
+    let instruction = claim_prize_with_ticket(&game, &tree, &ticket, &winner_ata, &draw);
+    let result = process(&mut context, vec![instruction], &[&winner]).await;
+    assert_bond_error(result, 0, BondError::InsufficientWeight);
+}
+
+#[tokio::test]
+async fn test_claim_rebate() {
+    let (mut context, game) = start(ConfigParams {
+        lst_adapter: Some(BondLstAdapter::Marinade),
+        rebate_bps: Some(1_000),
+        ..ConfigParams::default()
+    }).await;
+    let tree = create_ticket_tree(&mut context, &game).await;
+    let state_ata = create_ata(&mut context, &game, &game.state()).await;
+    let wallet = Keypair::new();
+    fund(&mut context, &wallet.pubkey(), 1_000_000_000).await;
+    let wallet_ata = create_ata(&mut context, &game, &wallet.pubkey()).await;
+    let tickets = vec![BondTicket { wallet: wallet.pubkey(), ..ticket(100) }, ticket(300)];
+    let instruction = issue_tickets(&game, &tree, tickets.clone());
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+
+    // 10% of the split is rebated to the 400 issued weight.
+    mint_to(&mut context, &game, &game.addresses.fee_ata, 1_000).await;
+    let instruction = instruction::split_shares(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        None,
+        true,
+        None,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &state_ata).await, 100);
+
+    let ticket = BondTicket { issued_at: clock.unix_timestamp, ..tickets[0].clone() };
+    let seed_index = BondRebateClaim::seed_index(&ticket.leaf().unwrap(), 0);
+    let (rebate_claim, bump) =
+        BondSeed::Rebate.derive_indexed(&defi_bond::id(), &game.config(), &seed_index);
+    let ticket_proof = TicketProof {
+        root: [0; 32],
+        weight: ticket.weight,
+        issued_at: ticket.issued_at,
+        rebate_per_weight: ticket.rebate_per_weight,
+        index: 0,
+    };
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(wallet.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new(state_ata, false),
+            AccountMeta::new(wallet_ata, false),
+            AccountMeta::new(rebate_claim, false),
+            AccountMeta::new_readonly(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+            AccountMeta::new_readonly(tree, false),
+        ],
+        data: BondInstruction::ClaimRebate {
+            bump,
+            space: REBATE_CLAIM_SIZE as u32,
+            ticket_proof,
+        }.try_to_vec().unwrap(),
+    };
+    process(&mut context, vec![instruction.clone()], &[&wallet]).await.unwrap();
+    assert_eq!(balance(&mut context, &wallet_ata).await, 25);
+
+    // The claim's checkpoint only pays the rebates accrued since.
+    context.get_new_latest_blockhash().await.unwrap();
+    process(&mut context, vec![instruction], &[&wallet]).await.unwrap();
+    assert_eq!(balance(&mut context, &wallet_ata).await, 25);
+}
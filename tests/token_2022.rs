@@ -0,0 +1,231 @@
+//! Integration tests of games of Token-2022 mints.
+
+mod common;
+
+use {
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, ConfigParams, TOKEN_2022_PROGRAM_ID},
+        state::BondDraw,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_option::COption,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_instruction,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+    spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        instruction::create_associated_token_account,
+    },
+    spl_token::instruction::TokenInstruction,
+};
+
+/// The jackpot paid by the draw.
+const JACKPOT: u64 = 5_000;
+
+/// The balance of the winner.
+const BALANCE: u64 = 1_000;
+
+/// The transfer fee of the mint, in basis points.
+const TRANSFER_FEE_BASIS_POINTS: u16 = 100;
+
+/// The size of a Token-2022 mint with the `TransferFeeConfig` extension (the base mint padded to
+/// the size of a token account, the account type, and the extension's type, length and config).
+const TRANSFER_FEE_MINT_LEN: usize = spl_token::state::Account::LEN + 1 + 2 + 2 + 108;
+
+/// The `InitializeTransferFeeConfig` instruction of `mint` (without authorities), whose data
+/// isn't packed by `spl_token`.
+fn initialize_transfer_fee_config(
+    mint: &Pubkey,
+    basis_points: u16,
+    maximum_fee: u64,
+) -> Instruction {
+    let mut data = vec![26, 0, 0, 0];
+    data.extend_from_slice(&basis_points.to_le_bytes());
+    data.extend_from_slice(&maximum_fee.to_le_bytes());
+    Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(*mint, false)],
+        data,
+    }
+}
+
+/// Creates a Token-2022 mint whose authority is the context's payer, with the `TransferFeeConfig`
+/// extension if `transfer_fee` (basis points and maximum fee) is set.
+async fn create_mint_2022(
+    context: &mut ProgramTestContext,
+    transfer_fee: Option<(u16, u64)>,
+) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let len = match transfer_fee {
+        Some(_) => TRANSFER_FEE_MINT_LEN,
+        None => spl_token::state::Mint::LEN,
+    };
+    let mut instructions = vec![system_instruction::create_account(
+        &payer,
+        &mint.pubkey(),
+        rent.minimum_balance(len),
+        len as u64,
+        &TOKEN_2022_PROGRAM_ID,
+    )];
+    if let Some((basis_points, maximum_fee)) = transfer_fee {
+        let instruction = initialize_transfer_fee_config(&mint.pubkey(), basis_points, maximum_fee);
+        instructions.push(instruction);
+    }
+    instructions.push(Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(mint.pubkey(), false)],
+        data: TokenInstruction::InitializeMint2 {
+            decimals: DECIMALS,
+            mint_authority: payer,
+            freeze_authority: COption::None,
+        }.pack(),
+    });
+    process(context, instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Creates the Token-2022 associated token account of `owner` for the game's mint (unless it's
+/// the jackpot's, created with the game), holding `amount`.
+async fn create_ata_2022(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let ata = get_associated_token_address_with_program_id(
+        owner,
+        &game.token_mint,
+        &TOKEN_2022_PROGRAM_ID,
+    );
+    let mut instructions = vec![];
+    if owner != &game.jackpot() {
+        instructions.push(create_associated_token_account(
+            &context.payer.pubkey(),
+            owner,
+            &game.token_mint,
+            &TOKEN_2022_PROGRAM_ID,
+        ));
+    }
+    instructions.push(Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(game.token_mint, false),
+            AccountMeta::new(ata, false),
+            AccountMeta::new_readonly(context.payer.pubkey(), true),
+        ],
+        data: TokenInstruction::MintTo { amount }.pack(),
+    });
+    process(context, instructions, &[]).await.unwrap();
+    ata
+}
+
+#[tokio::test]
+async fn test_draw_token_2022() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint_2022(&mut context, None).await;
+    let game = create_game_for_token_program(
+        &mut context,
+        token_mint,
+        TOKEN_2022_PROGRAM_ID,
+        ConfigParams::default(),
+    ).await;
+    let jackpot = game.jackpot();
+    let jackpot_ata = create_ata_2022(&mut context, &game, &jackpot, JACKPOT).await;
+    assert_eq!(jackpot_ata, game.addresses.jackpot_ata);
+    let winner = Pubkey::new_unique();
+    let winner_ata = create_ata_2022(&mut context, &game, &winner, BALANCE).await;
+
+    // The jackpot is paid through the Token-2022 program.
+    let draw = create_draw_account(&mut context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &TOKEN_2022_PROGRAM_ID,
+        &winner,
+        &winner_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &winner_ata).await, BALANCE + JACKPOT);
+    assert_eq!(balance(&mut context, &jackpot_ata).await, 0);
+}
+
+#[tokio::test]
+async fn test_draw_transfer_fee() {
+    let mut context = program_test().start_with_context().await;
+    let transfer_fee = Some((TRANSFER_FEE_BASIS_POINTS, JACKPOT));
+    let token_mint = create_mint_2022(&mut context, transfer_fee).await;
+    let game = create_game_for_token_program(
+        &mut context,
+        token_mint,
+        TOKEN_2022_PROGRAM_ID,
+        ConfigParams::default(),
+    ).await;
+    let jackpot = game.jackpot();
+    create_ata_2022(&mut context, &game, &jackpot, JACKPOT).await;
+    let winner = Pubkey::new_unique();
+    let winner_ata = create_ata_2022(&mut context, &game, &winner, BALANCE).await;
+
+    // The fee withheld from the prize is recorded with the draw.
+    let draw = create_draw_account(&mut context).await;
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &TOKEN_2022_PROGRAM_ID,
+        &winner,
+        &winner_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        BALANCE,
+        10 * BALANCE,
+        None,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    let fee = JACKPOT * u64::from(TRANSFER_FEE_BASIS_POINTS) / 10_000;
+    assert_eq!(balance(&mut context, &winner_ata).await, BALANCE + JACKPOT - fee);
+    let recorded = BondDraw::unpack_from_slice(&account(&mut context, &draw).await.data).unwrap();
+    assert_eq!(recorded.amount, JACKPOT);
+    assert_eq!(recorded.transfer_fee, fee);
+}
+
+#[tokio::test]
+async fn test_create_other_token_program() {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = create_mint(&mut context).await;
+
+    // The token program must own the mint.
+    let config = Keypair::new();
+    let authority = Keypair::new();
+    let instruction = instruction::create(
+        &defi_bond::id(),
+        &context.payer.pubkey(),
+        &config.pubkey(),
+        &authority.pubkey(),
+        &token_mint,
+        &TOKEN_2022_PROGRAM_ID,
+        None,
+        8,
+    );
+    let result = process(&mut context, vec![instruction], &[&config, &authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidOwner);
+}
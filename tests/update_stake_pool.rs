@@ -0,0 +1,136 @@
+//! Integration tests of `UpdateStakePool`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        adapter::MARINADE_PROGRAM_ID,
+        instruction::{BondInstruction, ConfigParams},
+        state::{BondFee, BondLstAdapter},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::account::Account,
+    spl_stake_pool::state::StakePool,
+};
+
+/// An `UpdateStakePool` instruction of `game` for the `pool` of `pool_program`.
+fn update_stake_pool(
+    game: &Game,
+    pool_program: &Pubkey,
+    pool: &Pubkey,
+    adapter_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(game.config(), false),
+        AccountMeta::new(game.addresses.fee.0, false),
+        AccountMeta::new(game.addresses.fee_ata, false),
+        AccountMeta::new(game.token_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(*pool_program, false),
+        AccountMeta::new(*pool, false),
+    ];
+    accounts.extend(adapter_accounts);
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts,
+        data: BondInstruction::UpdateStakePool.try_to_vec().unwrap(),
+    }
+}
+
+/// Sets the account at `address`, owned by `owner`, to `data`.
+fn set_pool(context: &mut ProgramTestContext, address: &Pubkey, owner: &Pubkey, data: Vec<u8>) {
+    let account = Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(address, &account.into());
+}
+
+/// Returns the game's fee account.
+async fn fee(context: &mut ProgramTestContext, game: &Game) -> BondFee {
+    BondFee::unpack_from_slice(&account(context, &game.addresses.fee.0).await.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_update_stake_pool_other_fee_receiver() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+
+    // A stake pool paying its epoch fee elsewhere can't be cranked by the game.
+    let pool = Pubkey::new_unique();
+    let stake_pool = StakePool {
+        manager_fee_account: Pubkey::new_unique(),
+        pool_mint: game.token_mint,
+        ..StakePool::default()
+    };
+    set_pool(&mut context, &pool, &spl_stake_pool::id(), stake_pool.try_to_vec().unwrap());
+    let adapter_accounts = (0..3).map(|_| AccountMeta::new(Pubkey::new_unique(), false)).collect();
+    let instruction = update_stake_pool(&game, &spl_stake_pool::id(), &pool, adapter_accounts);
+    let result = process(&mut context, vec![instruction], &[]).await;
+    assert_error(result, 0, InstructionError::IncorrectProgramId);
+    assert_eq!(fee(&mut context, &game).await.last_update_epoch, 0);
+}
+
+/// A Marinade state account paying its fee in `treasury` for `msol_mint`.
+fn marinade_state(msol_mint: &Pubkey, treasury: &Pubkey) -> Vec<u8> {
+    let mut data = vec![0; 136];
+    data[8..40].copy_from_slice(msol_mint.as_ref());
+    data[104..136].copy_from_slice(treasury.as_ref());
+    data
+}
+
+#[tokio::test]
+async fn test_update_stake_pool_marinade() {
+    let (mut context, game) = start(ConfigParams {
+        lst_adapter: Some(BondLstAdapter::Marinade),
+        ..ConfigParams::default()
+    }).await;
+    warp_to_epoch(&mut context, 1).await;
+    let pool = Pubkey::new_unique();
+    let data = marinade_state(&game.token_mint, &game.addresses.fee_ata);
+    set_pool(&mut context, &pool, &MARINADE_PROGRAM_ID, data);
+
+    let instruction = update_stake_pool(&game, &MARINADE_PROGRAM_ID, &pool, vec![]);
+    process(&mut context, vec![instruction], &[]).await.unwrap();
+    assert_eq!(fee(&mut context, &game).await.last_update_epoch, 1);
+}
+
+#[tokio::test]
+async fn test_update_stake_pool_marinade_other_mint() {
+    let (mut context, game) = start(ConfigParams {
+        lst_adapter: Some(BondLstAdapter::Marinade),
+        ..ConfigParams::default()
+    }).await;
+    let pool = Pubkey::new_unique();
+    let data = marinade_state(&Pubkey::new_unique(), &game.addresses.fee_ata);
+    set_pool(&mut context, &pool, &MARINADE_PROGRAM_ID, data);
+
+    let instruction = update_stake_pool(&game, &MARINADE_PROGRAM_ID, &pool, vec![]);
+    let result = process(&mut context, vec![instruction], &[]).await;
+    assert_error(result, 0, InstructionError::IncorrectProgramId);
+}
+
+#[tokio::test]
+async fn test_update_stake_pool_other_adapter() {
+    let (mut context, game) = start(ConfigParams {
+        lst_adapter: Some(BondLstAdapter::SinglePool),
+        ..ConfigParams::default()
+    }).await;
+    let pool = Pubkey::new_unique();
+    let data = marinade_state(&game.token_mint, &game.addresses.fee_ata);
+    set_pool(&mut context, &pool, &MARINADE_PROGRAM_ID, data);
+
+    // The pool program must be the configured adapter's.
+    let instruction = update_stake_pool(&game, &MARINADE_PROGRAM_ID, &pool, vec![]);
+    let result = process(&mut context, vec![instruction], &[]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+}
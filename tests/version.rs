@@ -0,0 +1,39 @@
+//! Integration tests of `GetVersion`.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        instruction::{BondInstruction, BondVersion},
+        state::ACCOUNT_LAYOUT_VERSION,
+    },
+    solana_program::instruction::Instruction,
+    solana_program_test::*,
+    solana_sdk::{signature::Signer, transaction::Transaction},
+};
+
+#[tokio::test]
+async fn test_get_version() {
+    let mut context = program_test().start_with_context().await;
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![],
+        data: BondInstruction::GetVersion.try_to_vec().unwrap(),
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    // The version is returned to the simulating client.
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, defi_bond::id());
+    let version = BondVersion::deserialize(&mut return_data.data.as_slice()).unwrap();
+    assert_eq!(version.program_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(version.layout_version, ACCOUNT_LAYOUT_VERSION);
+}
@@ -0,0 +1,539 @@
+//! Integration tests of the VRF flow: `RequestRandomness` and `FulfillDraw`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::{
+        error::BondError,
+        instruction::{self, BondInstruction, ConfigParams, Ratio, SnapshotProof},
+        state::{BondDraw, BondSnapshotLeaf},
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        keccak,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+/// The slots after which a request left unverified by the oracle may be replaced.
+const VRF_REQUEST_EXPIRY_SLOTS: u64 = 9_000;
+
+/// The jackpot paid by the draws.
+const JACKPOT: u64 = 5_000;
+
+/// The weight of each holder of the snapshots.
+const WEIGHT: u64 = 1_000;
+
+/// A game whose draws are fulfilled from VRF results.
+async fn start_vrf() -> (ProgramTestContext, Game) {
+    start(ConfigParams {
+        require_vrf: Some(true),
+        snapshot_draws: Some(true),
+        ..ConfigParams::default()
+    }).await
+}
+
+/// A `FulfillDraw` instruction for the next draw from `vrf`, paying the jackpot back to itself.
+fn fulfill_draw(game: &Game, vrf: &Pubkey, draw: &Pubkey) -> Instruction {
+    let mut instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &game.jackpot(),
+        &game.addresses.jackpot_ata,
+        draw,
+        &[],
+        0,
+        1,
+        0,
+        0,
+        None,
+    );
+    instruction.accounts.insert(0, AccountMeta::new_readonly(*vrf, false));
+    instruction.data = BondInstruction::FulfillDraw {
+        draw_seed: 1,
+        receiver_weight: 0,
+        total_weight: 0,
+        proof: None,
+    }.try_to_vec().unwrap();
+    instruction
+}
+
+/// Requests randomness from a new VRF account of the game and returns it.
+async fn request(context: &mut ProgramTestContext, game: &Game) -> Pubkey {
+    let vrf = create_vrf(context, &game.state()).await;
+    let instruction = request_randomness(context, game, &vrf);
+    process(context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    vrf
+}
+
+#[tokio::test]
+async fn test_request_randomness() {
+    let (mut context, game) = start_vrf().await;
+    let vrf = request(&mut context, &game).await;
+
+    let state = state(&mut context, &game).await;
+    assert_eq!(state.vrf, vrf);
+    assert_ne!(state.vrf_request_slot, 0);
+}
+
+#[tokio::test]
+async fn test_request_randomness_requires_snapshot_draws() {
+    let (mut context, game) = start(ConfigParams::default()).await;
+    let vrf = create_vrf(&mut context, &game.state()).await;
+
+    let instruction = request_randomness(&context, &game, &vrf);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::SnapshotRequired);
+}
+
+#[tokio::test]
+async fn test_request_randomness_of_another_authority() {
+    let (mut context, game) = start_vrf().await;
+    let vrf = create_vrf(&mut context, &Pubkey::new_unique()).await;
+
+    let instruction = request_randomness(&context, &game, &vrf);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_request_randomness_pending() {
+    let (mut context, game) = start_vrf().await;
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+
+    // The unverified request is pending until it expires.
+    set_vrf(&mut context, &vrf, VRF_STATUS_REQUESTING, request_slot, [0; 32]).await;
+    context.warp_to_slot(request_slot + VRF_REQUEST_EXPIRY_SLOTS - 1).unwrap();
+    let instruction = request_randomness(&context, &game, &vrf);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::VrfRequestPending);
+}
+
+#[tokio::test]
+async fn test_request_randomness_verified_never_expires() {
+    let (mut context, game) = start_vrf().await;
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+
+    // A verified result must be consumed by `FulfillDraw`, however old.
+    set_vrf(&mut context, &vrf, VRF_STATUS_VERIFIED, request_slot, [7; 32]).await;
+    context.warp_to_slot(request_slot + 2 * VRF_REQUEST_EXPIRY_SLOTS).unwrap();
+    let instruction = request_randomness(&context, &game, &vrf);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::VrfRequestPending);
+}
+
+#[tokio::test]
+async fn test_request_randomness_replaces_stale_request() {
+    let (mut context, game) = start_vrf().await;
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+
+    // A request left unverified past its expiry is replaced.
+    set_vrf(&mut context, &vrf, VRF_STATUS_REQUESTING, request_slot, [0; 32]).await;
+    context.warp_to_slot(request_slot + VRF_REQUEST_EXPIRY_SLOTS).unwrap();
+    let instruction = request_randomness(&context, &game, &vrf);
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    let state = state(&mut context, &game).await;
+    assert_eq!(state.vrf, vrf);
+    assert!(state.vrf_request_slot >= request_slot + VRF_REQUEST_EXPIRY_SLOTS);
+}
+
+#[tokio::test]
+async fn test_request_randomness_pending_with_another_vrf() {
+    let (mut context, game) = start_vrf().await;
+    request(&mut context, &game).await;
+
+    // The pending request can't be replaced through another VRF account.
+    let other = create_vrf(&mut context, &game.state()).await;
+    let instruction = request_randomness(&context, &game, &other);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn test_fulfill_draw_with_another_vrf() {
+    let (mut context, game) = start_vrf().await;
+    request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+    let draw = create_draw_account(&mut context).await;
+
+    // Only the requested VRF account's result is consumed.
+    let other = create_vrf(&mut context, &game.state()).await;
+    set_vrf(&mut context, &other, VRF_STATUS_VERIFIED, request_slot, [7; 32]).await;
+    let instruction = fulfill_draw(&game, &other, &draw);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn test_fulfill_draw_unverified() {
+    let (mut context, game) = start_vrf().await;
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+    let draw = create_draw_account(&mut context).await;
+
+    set_vrf(&mut context, &vrf, VRF_STATUS_REQUESTING, request_slot, [7; 32]).await;
+    let instruction = fulfill_draw(&game, &vrf, &draw);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidVrf);
+}
+
+#[tokio::test]
+async fn test_fulfill_draw_stale_round() {
+    let (mut context, game) = start_vrf().await;
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+    let draw = create_draw_account(&mut context).await;
+
+    // A result verified for an earlier round doesn't fulfill the pending request.
+    set_vrf(&mut context, &vrf, VRF_STATUS_VERIFIED, request_slot - 1, [7; 32]).await;
+    let instruction = fulfill_draw(&game, &vrf, &draw);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidVrf);
+}
+
+#[tokio::test]
+async fn test_fulfill_draw_without_request() {
+    let (mut context, game) = start_vrf().await;
+    let vrf = request(&mut context, &game).await;
+    let mut state = state(&mut context, &game).await;
+    let request_slot = state.vrf_request_slot;
+    state.vrf_request_slot = 0;
+    set_state(&mut context, &game, &state).await;
+    let draw = create_draw_account(&mut context).await;
+
+    // A result can't be consumed twice.
+    set_vrf(&mut context, &vrf, VRF_STATUS_VERIFIED, request_slot, [7; 32]).await;
+    let instruction = fulfill_draw(&game, &vrf, &draw);
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidVrf);
+}
+
+#[tokio::test]
+async fn test_draw_requires_vrf() {
+    let (mut context, game) = start_vrf().await;
+    let receiver = Pubkey::new_unique();
+    let receiver_ata = create_ata(&mut context, &game, &receiver).await;
+    mint_to(&mut context, &game, &receiver_ata, WEIGHT).await;
+    let draw = create_draw_account(&mut context).await;
+
+    // The winners of VRF games are only selected by `FulfillDraw`.
+    let instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &receiver,
+        &receiver_ata,
+        &draw,
+        &[AccountMeta::new_readonly(game.addresses.exclusion_list.0, false)],
+        42,
+        1,
+        WEIGHT,
+        10 * WEIGHT,
+        None,
+    );
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::VrfRequired);
+}
+
+#[tokio::test]
+async fn test_fulfill_draw() {
+    let (mut context, game) = start_vrf().await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+
+    // Ten holders of equal weight, held over the whole draw window.
+    set_draw_window(&mut context, 1_000).await;
+    let holders: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+    let snapshot = create_snapshot(&mut context, &game, &holders, WEIGHT).await;
+    let total_weight = 10 * WEIGHT;
+
+    // The verified VRF result selects the winner from the sealed snapshot.
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+    let result = [7; 32];
+    set_vrf(&mut context, &vrf, VRF_STATUS_VERIFIED, request_slot, result).await;
+    let point = u64::from_le_bytes(result[..8].try_into().unwrap()) % total_weight;
+    let winner = holders[(point / WEIGHT) as usize];
+    let winner_ata = create_ata(&mut context, &game, &winner).await;
+    let draw = create_draw_account(&mut context).await;
+
+    let mut instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &winner,
+        &winner_ata,
+        &draw,
+        &[
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+            AccountMeta::new_readonly(snapshot, false),
+        ],
+        0,
+        1,
+        WEIGHT,
+        total_weight,
+        None,
+    );
+    instruction.accounts.insert(0, AccountMeta::new_readonly(vrf, false));
+    instruction.data = BondInstruction::FulfillDraw {
+        draw_seed: 1,
+        receiver_weight: WEIGHT,
+        total_weight,
+        proof: None,
+    }.try_to_vec().unwrap();
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+
+    assert_eq!(balance(&mut context, &winner_ata).await, JACKPOT);
+    let state = state(&mut context, &game).await;
+    assert_eq!(state.draw_id, 1);
+    assert_eq!(state.vrf_request_slot, 0);
+    let draw = BondDraw::unpack_from_slice(&account(&mut context, &draw).await.data).unwrap();
+    assert_eq!(draw.receiver, winner);
+    assert_eq!(draw.entropy, result);
+}
+
+#[tokio::test]
+async fn test_fulfill_draw_permissionless() {
+    let (mut context, game) = start(ConfigParams {
+        require_vrf: Some(true),
+        snapshot_draws: Some(true),
+        allow_permissionless_draw: Some(true),
+        crank_fee_bps: Some(100),
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    set_draw_window(&mut context, 1_000).await;
+    let holders: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+    let snapshot = create_snapshot(&mut context, &game, &holders, WEIGHT).await;
+    let total_weight = 10 * WEIGHT;
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+    let result = [7; 32];
+    set_vrf(&mut context, &vrf, VRF_STATUS_VERIFIED, request_slot, result).await;
+    let point = u64::from_le_bytes(result[..8].try_into().unwrap()) % total_weight;
+    let winner = holders[(point / WEIGHT) as usize];
+    let winner_ata = create_ata(&mut context, &game, &winner).await;
+    let draw = create_draw_account(&mut context).await;
+
+    // Any keeper may fulfill the draw, and is paid 1% of the jackpot.
+    let keeper = Keypair::new();
+    let keeper_ata = create_ata(&mut context, &game, &keeper.pubkey()).await;
+    let mut instruction = instruction::draw(
+        &defi_bond::id(),
+        &keeper.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &winner,
+        &winner_ata,
+        &draw,
+        &[
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+            AccountMeta::new_readonly(snapshot, false),
+        ],
+        0,
+        1,
+        WEIGHT,
+        total_weight,
+        None,
+    );
+    instruction.accounts.insert(0, AccountMeta::new_readonly(vrf, false));
+    instruction.accounts.push(AccountMeta::new(keeper_ata, false));
+    instruction.data = BondInstruction::FulfillDraw {
+        draw_seed: 1,
+        receiver_weight: WEIGHT,
+        total_weight,
+        proof: None,
+    }.try_to_vec().unwrap();
+    process(&mut context, vec![instruction], &[&keeper]).await.unwrap();
+    assert_eq!(balance(&mut context, &keeper_ata).await, JACKPOT / 100);
+    assert_eq!(balance(&mut context, &winner_ata).await, JACKPOT - JACKPOT / 100);
+}
+
+#[tokio::test]
+async fn test_fulfill_draw_of_another_authority() {
+    let (mut context, game) = start_vrf().await;
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+    set_vrf(&mut context, &vrf, VRF_STATUS_VERIFIED, request_slot, [7; 32]).await;
+    let draw = create_draw_account(&mut context).await;
+
+    // Only games allowing permissionless draws accept other callers.
+    let keeper = Keypair::new();
+    let mut instruction = fulfill_draw(&game, &vrf, &draw);
+    instruction.accounts[1].pubkey = keeper.pubkey();
+    let result = process(&mut context, vec![instruction], &[&keeper]).await;
+    assert_bond_error(result, 0, BondError::InvalidAuthority);
+}
+
+/// A game whose draws are fulfilled from VRF results, with odds of up to one half so that two 
+/// holders may win.
+async fn start_halved() -> (ProgramTestContext, Game) {
+    start(ConfigParams {
+        require_vrf: Some(true),
+        snapshot_draws: Some(true),
+        odds_threshold: Some(Ratio { numerator: 1, denominator: 2 }),
+        ..ConfigParams::default()
+    }).await
+}
+
+/// Posts the merkle root of two leaves of `holders` of [WEIGHT] each as the next draw's snapshot, 
+/// held since before the draw window, and returns the snapshot and the leaves.
+async fn post_snapshot_root(
+    context: &mut ProgramTestContext,
+    game: &Game,
+    holders: [Pubkey; 2],
+) -> (Pubkey, Vec<BondSnapshotLeaf>) {
+    let leaves: Vec<BondSnapshotLeaf> = holders.iter().enumerate().map(|(index, holder)| {
+        BondSnapshotLeaf {
+            wallet: *holder,
+            balance: WEIGHT,
+            held_since: 0,
+            weight: WEIGHT,
+            offset: index as u64 * WEIGHT,
+        }
+    }).collect();
+    let root = keccak::hashv(&[&leaves[0].hash().unwrap(), &leaves[1].hash().unwrap()]);
+    let (snapshot, bump) = snapshot_address(game, 1);
+    let instruction = Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(game.draw_authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.state(), false),
+            AccountMeta::new(snapshot, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BondInstruction::PostSnapshotRoot {
+            bump,
+            root: root.to_bytes(),
+            leaf_count: 2,
+            total_weight: 2 * WEIGHT,
+        }.try_to_vec().unwrap(),
+    };
+    fund(context, &game.draw_authority.pubkey(), 1_000_000_000).await;
+    process(context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    (snapshot, leaves)
+}
+
+/// A `FulfillDraw` instruction of the first draw from `vrf`, paying `leaf`'s holder proven by 
+/// `proof` at `index` of the `snapshot`'s root.
+fn fulfill_proven_draw(
+    game: &Game,
+    vrf: &Pubkey,
+    snapshot: &Pubkey,
+    draw: &Pubkey,
+    leaf: &BondSnapshotLeaf,
+    index: u32,
+    proof: [u8; 32],
+) -> Instruction {
+    let receiver_ata = get_associated_token_address(&leaf.wallet, &game.token_mint);
+    let mut instruction = instruction::draw(
+        &defi_bond::id(),
+        &game.draw_authority.pubkey(),
+        &game.config(),
+        &game.token_mint,
+        &spl_token::id(),
+        &leaf.wallet,
+        &receiver_ata,
+        draw,
+        &[
+            AccountMeta::new_readonly(game.addresses.exclusion_list.0, false),
+            AccountMeta::new_readonly(*snapshot, false),
+        ],
+        0,
+        1,
+        WEIGHT,
+        2 * WEIGHT,
+        None,
+    );
+    instruction.accounts.insert(0, AccountMeta::new_readonly(*vrf, false));
+    instruction.data = BondInstruction::FulfillDraw {
+        draw_seed: 1,
+        receiver_weight: WEIGHT,
+        total_weight: 2 * WEIGHT,
+        proof: Some(SnapshotProof {
+            index,
+            balance: leaf.balance,
+            held_since: leaf.held_since,
+            offset: leaf.offset,
+            proof: vec![proof],
+        }),
+    }.try_to_vec().unwrap();
+    instruction
+}
+
+#[tokio::test]
+async fn test_fulfill_draw_proven() {
+    let (mut context, game) = start_halved().await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    set_draw_window(&mut context, 1_000).await;
+    let holders = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let (snapshot, leaves) = post_snapshot_root(&mut context, &game, holders).await;
+
+    // The verified VRF result selects the leaf proven by its sibling.
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+    let result = [7; 32];
+    set_vrf(&mut context, &vrf, VRF_STATUS_VERIFIED, request_slot, result).await;
+    let point = u64::from_le_bytes(result[..8].try_into().unwrap()) % (2 * WEIGHT);
+    let index = (point / WEIGHT) as usize;
+    let winner_ata = create_ata(&mut context, &game, &holders[index]).await;
+    let draw = create_draw_account(&mut context).await;
+
+    let sibling = leaves[1 - index].hash().unwrap();
+    let instruction = fulfill_proven_draw(
+        &game,
+        &vrf,
+        &snapshot,
+        &draw,
+        &leaves[index],
+        index as u32,
+        sibling,
+    );
+    process(&mut context, vec![instruction], &[&game.draw_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &winner_ata).await, JACKPOT);
+}
+
+#[tokio::test]
+async fn test_fulfill_draw_invalid_proof() {
+    let (mut context, game) = start_halved().await;
+    mint_to(&mut context, &game, &game.addresses.jackpot_ata, JACKPOT).await;
+    set_draw_window(&mut context, 1_000).await;
+    let holders = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let (snapshot, leaves) = post_snapshot_root(&mut context, &game, holders).await;
+    let vrf = request(&mut context, &game).await;
+    let request_slot = state(&mut context, &game).await.vrf_request_slot;
+    set_vrf(&mut context, &vrf, VRF_STATUS_VERIFIED, request_slot, [7; 32]).await;
+    create_ata(&mut context, &game, &holders[0]).await;
+    let draw = create_draw_account(&mut context).await;
+
+    // The leaf isn't proven by its own hash.
+    let instruction = fulfill_proven_draw(
+        &game,
+        &vrf,
+        &snapshot,
+        &draw,
+        &leaves[0],
+        0,
+        leaves[0].hash().unwrap(),
+    );
+    let result = process(&mut context, vec![instruction], &[&game.draw_authority]).await;
+    assert_bond_error(result, 0, BondError::InvalidSnapshotProof);
+}
@@ -0,0 +1,86 @@
+//! Integration tests of `WithdrawEquity`.
+
+mod common;
+
+use {
+    borsh::BorshSerialize,
+    common::*,
+    defi_bond::instruction::{BondInstruction, ConfigParams},
+    solana_program::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::signature::{Keypair, Signer},
+};
+
+/// The equity's balance.
+const EQUITY: u64 = 1_000;
+
+/// A `WithdrawEquity` instruction of `equity_authority` withdrawing `amount` to `destination`.
+fn withdraw_equity(
+    game: &Game,
+    equity_authority: &Keypair,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(equity_authority.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(game.addresses.equity.0, false),
+            AccountMeta::new(game.addresses.equity_ata, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(game.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: BondInstruction::WithdrawEquity { amount }.try_to_vec().unwrap(),
+    }
+}
+
+/// Starts a game whose equity holds [EQUITY], withdrawn by `equity_authority` (if any), and
+/// returns a destination account.
+async fn start_equity(equity_authority: Option<Pubkey>) -> (ProgramTestContext, Game, Pubkey) {
+    let (mut context, game) = start(ConfigParams {
+        equity_authority,
+        ..ConfigParams::default()
+    }).await;
+    mint_to(&mut context, &game, &game.addresses.equity_ata, EQUITY).await;
+    let destination = create_ata(&mut context, &game, &Pubkey::new_unique()).await;
+    (context, game, destination)
+}
+
+#[tokio::test]
+async fn test_withdraw_equity() {
+    let equity_authority = Keypair::new();
+    let (mut context, game, destination) = start_equity(Some(equity_authority.pubkey())).await;
+
+    let instruction = withdraw_equity(&game, &equity_authority, &destination, 300);
+    process(&mut context, vec![instruction], &[&equity_authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &destination).await, 300);
+    assert_eq!(balance(&mut context, &game.addresses.equity_ata).await, EQUITY - 300);
+
+    // The equity authority replaces the game's main authority.
+    let instruction = withdraw_equity(&game, &game.authority, &destination, 300);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn test_withdraw_equity_by_authority() {
+    let (mut context, game, destination) = start_equity(None).await;
+
+    // Without an equity authority, the game's main authority withdraws the equity.
+    let instruction = withdraw_equity(&game, &game.authority, &destination, EQUITY);
+    process(&mut context, vec![instruction], &[&game.authority]).await.unwrap();
+    assert_eq!(balance(&mut context, &destination).await, EQUITY);
+}
+
+#[tokio::test]
+async fn test_withdraw_equity_insufficient_funds() {
+    let (mut context, game, destination) = start_equity(None).await;
+    let instruction = withdraw_equity(&game, &game.authority, &destination, EQUITY + 1);
+    let result = process(&mut context, vec![instruction], &[&game.authority]).await;
+    assert_error(result, 0, InstructionError::InsufficientFunds);
+}
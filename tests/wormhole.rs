@@ -0,0 +1,158 @@
+//! Integration tests of `PublishDrawMessage`.
+
+mod common;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    common::*,
+    defi_bond::{
+        instruction::{BondInstruction, ConfigParams, WORMHOLE_PROGRAM_ID},
+        state::{pack_account, BondDraw, BondDrawMessage, BondSeed},
+    },
+    solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+        sysvar,
+    },
+    solana_program_test::*,
+    solana_sdk::{account::Account, signature::Signer},
+};
+
+/// The Wormhole message fee.
+const MESSAGE_FEE: u64 = 100;
+
+/// The fee collector's balance before any message.
+const FEE_COLLECTOR_BALANCE: u64 = 1_000_000_000;
+
+/// The offset of the message fee in the bridge config.
+const BRIDGE_FEE_OFFSET: usize = 16;
+
+/// A core bridge standing in for Wormhole, which copies the payload of a posted message (after
+/// the instruction's tag, nonce and payload length) to the sequence account.
+fn wormhole(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let len = u32::from_le_bytes(data[5..9].try_into().unwrap()) as usize;
+    accounts[3].data.borrow_mut()[..len].copy_from_slice(&data[9..9 + len]);
+    Ok(())
+}
+
+/// The accounts of the bridge.
+struct Bridge {
+    bridge: Pubkey,
+    sequence: Pubkey,
+    fee_collector: Pubkey,
+}
+
+/// Starts a validator with the Wormhole bridge and a game with a recorded draw `draw_id`.
+async fn start_with_draw(draw_id: u64) -> (ProgramTestContext, Game, Bridge, BondDraw, Pubkey) {
+    let mut program_test = program_test();
+    program_test.add_program("wormhole", WORMHOLE_PROGRAM_ID, processor!(wormhole));
+    let mut context = program_test.start_with_context().await;
+    let game = create_game(&mut context, ConfigParams::default()).await;
+
+    let mut bridge_data = vec![0; 32];
+    let fee = MESSAGE_FEE.to_le_bytes();
+    bridge_data[BRIDGE_FEE_OFFSET..BRIDGE_FEE_OFFSET + 8].copy_from_slice(&fee);
+    let bridge = Bridge {
+        bridge: Pubkey::new_unique(),
+        sequence: Pubkey::new_unique(),
+        fee_collector: Pubkey::new_unique(),
+    };
+    context.set_account(&bridge.bridge, &wormhole_account(bridge_data).into());
+    context.set_account(&bridge.sequence, &wormhole_account(vec![0; 256]).into());
+    fund(&mut context, &bridge.fee_collector, FEE_COLLECTOR_BALANCE).await;
+
+    let draw = BondDraw::new(
+        game.draw_authority.pubkey(),
+        game.config(),
+        draw_id,
+        5000,
+        7,
+        Pubkey::new_unique(),
+        0,
+        42,
+        1_700_000_000,
+        3,
+        1_700_000_100,
+        false,
+        0,
+        vec![],
+    );
+    let draw_address = Pubkey::new_unique();
+    let mut data = vec![0; BondDraw::LEN];
+    pack_account(&draw, &mut data).unwrap();
+    let draw_account = Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: defi_bond::id(),
+        ..Account::default()
+    };
+    context.set_account(&draw_address, &draw_account.into());
+    (context, game, bridge, draw, draw_address)
+}
+
+/// An account of the bridge holding `data`.
+fn wormhole_account(data: Vec<u8>) -> Account {
+    Account { lamports: 1_000_000_000, data, owner: WORMHOLE_PROGRAM_ID, ..Account::default() }
+}
+
+/// A `PublishDrawMessage` instruction for the draw `draw_id` at `draw`.
+fn publish_draw_message(
+    context: &ProgramTestContext,
+    game: &Game,
+    bridge: &Bridge,
+    draw: &Pubkey,
+    draw_id: u64,
+) -> Instruction {
+    let (emitter, emitter_bump) = BondSeed::Emitter.derive(&defi_bond::id(), &game.config());
+    let (message, message_bump) = Pubkey::find_program_address(
+        &[game.config().as_ref(), BondSeed::Message.as_bytes(), &draw_id.to_le_bytes()],
+        &defi_bond::id(),
+    );
+    Instruction {
+        program_id: defi_bond::id(),
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new_readonly(game.config(), false),
+            AccountMeta::new_readonly(*draw, false),
+            AccountMeta::new_readonly(emitter, false),
+            AccountMeta::new(message, false),
+            AccountMeta::new(bridge.bridge, false),
+            AccountMeta::new(bridge.sequence, false),
+            AccountMeta::new(bridge.fee_collector, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(WORMHOLE_PROGRAM_ID, false),
+        ],
+        data: BondInstruction::PublishDrawMessage { nonce: 0, emitter_bump, message_bump }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_publish_draw_message() {
+    let (mut context, game, bridge, draw, draw_address) = start_with_draw(3).await;
+    let instruction = publish_draw_message(&context, &game, &bridge, &draw_address, 3);
+    process(&mut context, vec![instruction], &[]).await.unwrap();
+
+    // The payer pays the message fee and the message holds the draw's result.
+    let lamports = account(&mut context, &bridge.fee_collector).await.lamports;
+    assert_eq!(lamports, FEE_COLLECTOR_BALANCE + MESSAGE_FEE);
+    let data = account(&mut context, &bridge.sequence).await.data;
+    let message = BondDrawMessage::deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(message, BondDrawMessage::from(&draw));
+}
+
+#[tokio::test]
+async fn test_publish_draw_message_invalid_message() {
+    let (mut context, game, bridge, _, draw_address) = start_with_draw(3).await;
+    let mut instruction = publish_draw_message(&context, &game, &bridge, &draw_address, 3);
+    instruction.accounts[4].pubkey = Pubkey::new_unique();
+    let result = process(&mut context, vec![instruction], &[]).await;
+    assert_error(result, 0, InstructionError::InvalidAccountData);
+}